@@ -3,7 +3,11 @@ use fuzzy_matcher::FuzzyMatcher;
 use uuid::Uuid;
 use zellij_tile::prelude::*;
 
+mod registry;
+use registry::RegistryScreen;
+
 use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
 pub struct SearchResult {
     plugin_id: u32,
@@ -493,6 +497,7 @@ struct State {
     plugin_id_to_tab_position: HashMap<u32, usize>,
     search_term: String,
     new_plugin_screen: Option<NewPluginScreen>,
+    registry_screen: Option<RegistryScreen>,
     colors: Styling,
 }
 
@@ -507,6 +512,11 @@ impl SwarmPlugin for State {
             EventType::TabUpdate,
             EventType::Key,
             EventType::SessionUpdate,
+            EventType::PermissionRequestResult,
+            EventType::WebRequestResult,
+            EventType::RunCommandResult,
+            EventType::HostFolderChanged,
+            EventType::FailedToChangeHostFolder,
         ]);
         let own_plugin_id = get_plugin_ids().plugin_id;
         rename_plugin_pane(own_plugin_id, "Plugin Manager");
@@ -574,6 +584,33 @@ impl SwarmPlugin for State {
                     }
                 }
             },
+            Event::PermissionRequestResult(PermissionStatus::Granted) => {
+                if let Some(registry_screen) = self.registry_screen.as_mut() {
+                    registry_screen.permissions_granted();
+                    should_render = true;
+                }
+            },
+            Event::RunCommandResult(..)
+            | Event::WebRequestResult(..)
+            | Event::HostFolderChanged(..)
+            | Event::FailedToChangeHostFolder(..) => {
+                if let Some(registry_screen) = self.registry_screen.as_mut() {
+                    should_render = registry_screen.handle_event(&event);
+                }
+            },
+            Event::Key(key) if self.registry_screen.is_some() => {
+                let (should_render_registry_screen, should_close_registry_screen) = self
+                    .registry_screen
+                    .as_mut()
+                    .map(|r| r.handle_key(key))
+                    .unwrap_or_default();
+                if should_close_registry_screen {
+                    self.registry_screen = None;
+                    should_render = true;
+                } else {
+                    should_render = should_render_registry_screen;
+                }
+            },
             Event::Key(key) => match self.new_plugin_screen.as_mut() {
                 Some(new_plugin_screen) => {
                     let (should_render_new_plugin_screen, should_close_new_plugin_screen) =
@@ -593,6 +630,10 @@ impl SwarmPlugin for State {
     }
 
     fn render(&mut self, rows: usize, cols: usize) {
+        if let Some(registry_screen) = &self.registry_screen {
+            registry_screen.render(rows, cols);
+            return;
+        }
         match &self.new_plugin_screen {
             Some(new_plugin_screen) => {
                 new_plugin_screen.render(rows, cols);
@@ -866,11 +907,11 @@ impl State {
         tab_line
     }
     pub fn render_help(&self, y: usize, cols: usize) {
-        let full_text = "Help: <←↓↑→> - Navigate/Expand, <ENTER> - focus, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <ESC> - Exit";
+        let full_text = "Help: <←↓↑→> - Navigate/Expand, <ENTER> - focus, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <Ctrl r> - Registry, <ESC> - Exit";
         let middle_text =
-            "Help: <←↓↑→/ENTER> - Navigate, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <ESC> - Exit";
+            "Help: <←↓↑→/ENTER> - Navigate, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <Ctrl r> - Registry, <ESC> - Exit";
         let short_text =
-            "<←↓↑→/ENTER/TAB/Del> - Navigate/Expand/Reload/Close, <Ctrl a> - New, <ESC> - Exit";
+            "<←↓↑→/ENTER/TAB/Del> - Navigate/Expand/Reload/Close, <Ctrl a> - New, <Ctrl r> - Registry, <ESC> - Exit";
         if cols >= full_text.chars().count() {
             let text = Text::new(full_text)
                 .color_range(3, 5..=11)
@@ -878,7 +919,8 @@ impl State {
                 .color_range(3, 49..=53)
                 .color_range(3, 65..=69)
                 .color_range(3, 80..=87)
-                .color_range(3, 96..=100);
+                .color_range(3, 96..=103)
+                .color_range(3, 117..=121);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         } else if cols >= middle_text.chars().count() {
             let text = Text::new(middle_text)
@@ -886,13 +928,15 @@ impl State {
                 .color_range(3, 31..=35)
                 .color_range(3, 47..=51)
                 .color_range(3, 62..=69)
-                .color_range(3, 78..=82);
+                .color_range(3, 78..=85)
+                .color_range(3, 99..=103);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         } else {
             let text = Text::new(short_text)
                 .color_range(3, ..=21)
                 .color_range(3, 53..=60)
-                .color_range(3, 69..=73);
+                .color_range(3, 69..=76)
+                .color_range(3, 90..=94);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         }
     }
@@ -1022,6 +1066,17 @@ impl State {
                 self.new_plugin_screen = Some(NewPluginScreen::new(self.colors));
                 should_render = true;
             },
+            BareKey::Char('r') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                let registry_screen = RegistryScreen::new(
+                    self.userspace_configuration.get("registry").cloned(),
+                    self.userspace_configuration
+                        .get("plugin_dir")
+                        .map(PathBuf::from),
+                );
+                registry_screen.request_permissions();
+                self.registry_screen = Some(registry_screen);
+                should_render = true;
+            },
             BareKey::Delete if key.has_no_modifiers() => {
                 self.close_selected();
             },
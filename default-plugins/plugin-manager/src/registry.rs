@@ -0,0 +1,783 @@
+use kdl::KdlDocument;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zellij_tile::prelude::*;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::truncate_string_start;
+
+// the plugin dir is mounted as our host folder once it has been prepared
+const HOST_FOLDER: &str = "/host";
+const REGISTRY_LOCK_FILE_NAME: &str = "registry.json";
+const REGISTRY_ACTION: &str = "registry_action";
+const REGISTRY_PLUGIN_NAME: &str = "registry_plugin_name";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RegistryIndex {
+    #[serde(default)]
+    pub plugins: Vec<RegistryEntry>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RegistryEntry {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub version: String,
+    pub url: String,
+    pub sha256: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+impl RegistryIndex {
+    /// Parses a JSON or a KDL index, refusing it if any of its plugins has a name we cannot use
+    /// as a file name in the plugin dir
+    pub fn from_bytes(raw_index: &[u8]) -> Result<Self, String> {
+        let raw_index = std::str::from_utf8(raw_index).map_err(|e| e.to_string())?;
+        let index = if raw_index.trim_start().starts_with('{') {
+            serde_json::from_str::<RegistryIndex>(raw_index).map_err(|e| e.to_string())?
+        } else {
+            RegistryIndex::from_kdl(raw_index)?
+        };
+        for entry in &index.plugins {
+            validate_plugin_name(&entry.name)?;
+        }
+        Ok(index)
+    }
+    // eg.
+    // plugin "my-plugin" {
+    //     description "Does things"
+    //     version "1.0.0"
+    //     url "https://example.com/my-plugin.wasm"
+    //     sha256 "..."
+    //     permissions "ReadApplicationState" "RunCommands"
+    // }
+    fn from_kdl(raw_index: &str) -> Result<Self, String> {
+        let document: KdlDocument = raw_index
+            .parse()
+            .map_err(|e: kdl::KdlError| e.to_string())?;
+        let mut plugins = vec![];
+        for node in document.nodes() {
+            if node.name().value() != "plugin" {
+                return Err(format!("Unknown node in index: {}", node.name().value()));
+            }
+            let name = node
+                .entries()
+                .iter()
+                .find(|entry| entry.name().is_none())
+                .and_then(|entry| entry.value().as_string())
+                .ok_or("Every plugin in the index needs a name")?
+                .to_owned();
+            let children = node.children();
+            let get_string = |field: &str| {
+                children
+                    .and_then(|children| children.get_arg(field))
+                    .and_then(|value| value.as_string())
+                    .map(|value| value.to_owned())
+            };
+            let url = get_string("url").ok_or_else(|| format!("{} has no url", name))?;
+            let sha256 = get_string("sha256").ok_or_else(|| format!("{} has no sha256", name))?;
+            let permissions = children
+                .map(|children| {
+                    children
+                        .get_args("permissions")
+                        .iter()
+                        .filter_map(|value| value.as_string())
+                        .map(|value| value.to_owned())
+                        .collect()
+                })
+                .unwrap_or_default();
+            plugins.push(RegistryEntry {
+                description: get_string("description").unwrap_or_default(),
+                version: get_string("version").unwrap_or_default(),
+                name,
+                url,
+                sha256,
+                permissions,
+            });
+        }
+        Ok(RegistryIndex { plugins })
+    }
+}
+
+// the name becomes the file name of the plugin in the plugin dir, and the index is not ours
+fn validate_plugin_name(name: &str) -> Result<(), String> {
+    let is_plain_file_name = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains(&['/', '\\'][..])
+        && !name.contains("..")
+        && !name.chars().any(|c| c.is_control());
+    if is_plain_file_name {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid plugin name in index: {:?}, names must be plain file names",
+            name
+        ))
+    }
+}
+
+impl RegistryEntry {
+    fn wasm_file_name(&self) -> String {
+        format!("{}.wasm", self.name)
+    }
+    fn permission_names(&self) -> Vec<String> {
+        self.permissions
+            .iter()
+            .map(|p| match PermissionType::from_str(p) {
+                Ok(permission_type) => permission_type.display_name(),
+                Err(_) => format!("{} (unknown)", p),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstalledPlugin {
+    pub version: String,
+    pub sha256: String,
+    pub url: String,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EntryStatus {
+    NotInstalled,
+    UpToDate,
+    UpdateAvailable,
+    Pinned,
+}
+
+#[derive(Default)]
+pub struct RegistryScreen {
+    registry_location: Option<String>,
+    plugin_dir: Option<PathBuf>,
+    index: Vec<RegistryEntry>,
+    installed: BTreeMap<String, InstalledPlugin>, // plugin name -> installed plugin
+    selected_index: Option<usize>,
+    plugin_dir_ready: bool,
+    pending_installs: Vec<String>,
+    status_message: Option<(String, bool)>, // message, is_error
+}
+
+impl RegistryScreen {
+    pub fn new(registry_location: Option<String>, plugin_dir: Option<PathBuf>) -> Self {
+        RegistryScreen {
+            registry_location,
+            plugin_dir,
+            ..Default::default()
+        }
+    }
+    pub fn request_permissions(&self) {
+        request_permission(&[
+            PermissionType::WebAccess,
+            PermissionType::RunCommands,
+            PermissionType::FullHdAccess,
+            PermissionType::Reconfigure,
+        ]);
+    }
+    pub fn permissions_granted(&mut self) {
+        self.prepare_plugin_dir();
+        self.fetch_index();
+    }
+    pub fn fetch_index(&mut self) {
+        let Some(registry_location) = self.registry_location.clone() else {
+            self.set_error("No registry configured, set the \"registry\" plugin configuration");
+            return;
+        };
+        let context = registry_context("fetch_index", None);
+        if registry_location.starts_with("http://") || registry_location.starts_with("https://") {
+            web_request(
+                registry_location,
+                HttpVerb::Get,
+                BTreeMap::new(),
+                vec![],
+                context,
+            );
+        } else {
+            run_command(&["cat", &registry_location], context);
+        }
+        self.set_info("Fetching registry index...");
+    }
+    fn prepare_plugin_dir(&mut self) {
+        match &self.plugin_dir {
+            Some(plugin_dir) => {
+                let plugin_dir = plugin_dir.display().to_string();
+                run_command(
+                    &["mkdir", "-p", &plugin_dir],
+                    registry_context("prepare_plugin_dir", None),
+                );
+            },
+            None => {
+                self.set_error("No plugin dir configured, set the \"plugin_dir\" plugin configuration to install plugins");
+            },
+        }
+    }
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::RunCommandResult(exit_code, stdout, stderr, context) => {
+                match context.get(REGISTRY_ACTION).map(|a| a.as_str()) {
+                    Some("fetch_index") if *exit_code == Some(0) => self.parse_index(stdout),
+                    Some("fetch_index") => {
+                        self.set_error(&format!(
+                            "Failed to read registry index: {}",
+                            String::from_utf8_lossy(stderr).trim()
+                        ));
+                    },
+                    Some("prepare_plugin_dir") if *exit_code == Some(0) => {
+                        if let Some(plugin_dir) = &self.plugin_dir {
+                            change_host_folder(plugin_dir.clone());
+                        }
+                    },
+                    Some("prepare_plugin_dir") => {
+                        self.set_error(&format!(
+                            "Failed to create plugin dir: {}",
+                            String::from_utf8_lossy(stderr).trim()
+                        ));
+                    },
+                    _ => return false,
+                }
+                true
+            },
+            Event::WebRequestResult(status, _headers, body, context) => {
+                let is_success = *status >= 200 && *status < 300;
+                match context.get(REGISTRY_ACTION).map(|a| a.as_str()) {
+                    Some("fetch_index") if is_success => self.parse_index(body),
+                    Some("fetch_index") => {
+                        self.set_error(&format!("Failed to fetch registry index: {}", status));
+                    },
+                    Some("install") => {
+                        let Some(name) = context.get(REGISTRY_PLUGIN_NAME) else {
+                            return false;
+                        };
+                        self.pending_installs.retain(|p| p != name);
+                        if is_success {
+                            self.finish_install(name, body);
+                        } else {
+                            self.set_error(&format!("Failed to download {}: {}", name, status));
+                        }
+                    },
+                    _ => return false,
+                }
+                true
+            },
+            Event::HostFolderChanged(_new_host_folder) => {
+                self.plugin_dir_ready = true;
+                self.read_lock_file();
+                true
+            },
+            Event::FailedToChangeHostFolder(error) => {
+                self.set_error(&format!(
+                    "Failed to access plugin dir: {}",
+                    error.clone().unwrap_or_default()
+                ));
+                true
+            },
+            _ => false,
+        }
+    }
+    fn parse_index(&mut self, raw_index: &[u8]) {
+        match RegistryIndex::from_bytes(raw_index) {
+            Ok(index) => {
+                let update_count = index
+                    .plugins
+                    .iter()
+                    .filter(|e| self.entry_status(e) == EntryStatus::UpdateAvailable)
+                    .count();
+                self.index = index.plugins;
+                self.selected_index = None;
+                if update_count > 0 {
+                    self.set_info(&format!("{} update(s) available", update_count));
+                } else {
+                    self.status_message = None;
+                }
+            },
+            Err(e) => self.set_error(&format!("Failed to parse registry index: {}", e)),
+        }
+    }
+    fn read_lock_file(&mut self) {
+        let lock_file = PathBuf::from(HOST_FOLDER).join(REGISTRY_LOCK_FILE_NAME);
+        match std::fs::read(&lock_file) {
+            Ok(raw_lock_file) => match serde_json::from_slice(&raw_lock_file) {
+                Ok(installed) => self.installed = installed,
+                Err(e) => self.set_error(&format!("Failed to parse registry lock file: {}", e)),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+            Err(e) => self.set_error(&format!("Failed to read registry lock file: {}", e)),
+        }
+    }
+    fn write_lock_file(&mut self) {
+        let lock_file = PathBuf::from(HOST_FOLDER).join(REGISTRY_LOCK_FILE_NAME);
+        let write_result = serde_json::to_vec_pretty(&self.installed)
+            .map_err(|e| e.to_string())
+            .and_then(|serialized| {
+                std::fs::write(&lock_file, serialized).map_err(|e| e.to_string())
+            });
+        if let Err(e) = write_result {
+            self.set_error(&format!("Failed to write registry lock file: {}", e));
+        }
+    }
+    fn entry_status(&self, entry: &RegistryEntry) -> EntryStatus {
+        match self.installed.get(&entry.name) {
+            None => EntryStatus::NotInstalled,
+            Some(installed) if installed.sha256.eq_ignore_ascii_case(&entry.sha256) => {
+                EntryStatus::UpToDate
+            },
+            Some(installed) if installed.pinned => EntryStatus::Pinned,
+            Some(_) => EntryStatus::UpdateAvailable,
+        }
+    }
+    fn selected_entry(&self) -> Option<&RegistryEntry> {
+        self.selected_index.and_then(|i| self.index.get(i))
+    }
+    fn install_selected(&mut self) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            return;
+        };
+        if !self.plugin_dir_ready {
+            self.set_error("Plugin dir is not ready, cannot install plugins");
+            return;
+        }
+        match self.entry_status(&entry) {
+            EntryStatus::UpToDate => {
+                self.set_info(&format!("{} is up to date", entry.name));
+            },
+            EntryStatus::Pinned => {
+                self.set_info(&format!(
+                    "{} is pinned, unpin it to update to {}",
+                    entry.name, entry.version
+                ));
+            },
+            EntryStatus::NotInstalled | EntryStatus::UpdateAvailable => {
+                if !self.pending_installs.contains(&entry.name) {
+                    self.pending_installs.push(entry.name.clone());
+                    web_request(
+                        &entry.url,
+                        HttpVerb::Get,
+                        BTreeMap::new(),
+                        vec![],
+                        registry_context("install", Some(&entry.name)),
+                    );
+                    self.set_info(&format!("Downloading {}...", entry.name));
+                }
+            },
+        }
+    }
+    fn finish_install(&mut self, name: &str, wasm_bytes: &[u8]) {
+        let Some(entry) = self.index.iter().find(|e| e.name == name).cloned() else {
+            return;
+        };
+        let sha256 = sha256_hex(wasm_bytes);
+        if !sha256.eq_ignore_ascii_case(&entry.sha256) {
+            self.set_error(&format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                name, entry.sha256, sha256
+            ));
+            return;
+        }
+        let wasm_file = PathBuf::from(HOST_FOLDER).join(entry.wasm_file_name());
+        if let Err(e) = std::fs::write(&wasm_file, wasm_bytes) {
+            self.set_error(&format!("Failed to write {}: {}", wasm_file.display(), e));
+            return;
+        }
+        let pinned = self
+            .installed
+            .get(name)
+            .map(|installed| installed.pinned)
+            .unwrap_or(false);
+        self.installed.insert(
+            entry.name.clone(),
+            InstalledPlugin {
+                version: entry.version.clone(),
+                sha256,
+                url: entry.url.clone(),
+                pinned,
+            },
+        );
+        self.write_lock_file();
+        self.add_alias(&entry);
+        self.set_info(&format!(
+            "Installed {} {}, available as the \"{}\" alias",
+            entry.name, entry.version, entry.name
+        ));
+    }
+    fn add_alias(&self, entry: &RegistryEntry) {
+        if let Some(plugin_dir) = &self.plugin_dir {
+            let location = plugin_dir.join(entry.wasm_file_name());
+            let new_config = format!(
+                "plugins {{\n    \"{}\" location=\"file:{}\"\n}}",
+                entry.name.replace('"', "\\\""),
+                location.display().to_string().replace('"', "\\\"")
+            );
+            let save_configuration_file = true;
+            reconfigure(new_config, save_configuration_file);
+        }
+    }
+    fn toggle_pin_selected(&mut self) {
+        let Some(name) = self.selected_entry().map(|e| e.name.clone()) else {
+            return;
+        };
+        match self.installed.get_mut(&name) {
+            Some(installed) => {
+                installed.pinned = !installed.pinned;
+                let message = if installed.pinned {
+                    format!("Pinned {} to {}", name, installed.version)
+                } else {
+                    format!("Unpinned {}", name)
+                };
+                self.write_lock_file();
+                self.set_info(&message);
+            },
+            None => self.set_info(&format!("{} is not installed", name)),
+        }
+    }
+    fn set_info(&mut self, message: &str) {
+        self.status_message = Some((message.to_owned(), false));
+    }
+    fn set_error(&mut self, message: &str) {
+        self.status_message = Some((message.to_owned(), true));
+    }
+    pub fn handle_key(&mut self, key: KeyWithModifier) -> (bool, bool) {
+        let (mut should_render, mut should_close) = (false, false);
+        match key.bare_key {
+            BareKey::Down if key.has_no_modifiers() => {
+                let max_len = self.index.len().saturating_sub(1);
+                if self.selected_index.is_none() && !self.index.is_empty() {
+                    self.selected_index = Some(0);
+                } else if self.selected_index == Some(max_len) {
+                    self.selected_index = None;
+                } else {
+                    self.selected_index = self.selected_index.map(|s| s + 1);
+                }
+                should_render = true;
+            },
+            BareKey::Up if key.has_no_modifiers() => {
+                let max_len = self.index.len().saturating_sub(1);
+                if self.selected_index.is_none() && !self.index.is_empty() {
+                    self.selected_index = Some(max_len);
+                } else if self.selected_index == Some(0) {
+                    self.selected_index = None;
+                } else {
+                    self.selected_index = self.selected_index.map(|s| s.saturating_sub(1));
+                }
+                should_render = true;
+            },
+            BareKey::Enter if key.has_no_modifiers() => {
+                self.install_selected();
+                should_render = true;
+            },
+            BareKey::Char('p') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.toggle_pin_selected();
+                should_render = true;
+            },
+            BareKey::Char('u') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.fetch_index();
+                should_render = true;
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                should_close = true;
+            },
+            _ => {},
+        }
+        (should_render, should_close)
+    }
+    pub fn render(&self, rows: usize, cols: usize) {
+        self.render_title(cols);
+        self.render_registry_location(cols);
+        let max_list_items = rows.saturating_sub(7); // 3 top, 4 bottom
+        self.render_entries(cols, max_list_items);
+        self.render_status_message(rows.saturating_sub(2), cols);
+        self.render_help(rows, cols);
+    }
+    fn render_title(&self, cols: usize) {
+        let title_text = "PLUGIN REGISTRY";
+        let title_text_len = title_text.chars().count();
+        print_text_with_coordinates(
+            Text::new(title_text),
+            (cols / 2).saturating_sub(title_text_len / 2),
+            0,
+            None,
+            None,
+        );
+    }
+    fn render_registry_location(&self, cols: usize) {
+        let location = self
+            .registry_location
+            .clone()
+            .unwrap_or_else(|| "<NOT CONFIGURED>".to_owned());
+        let location = truncate_string_start(&location, cols.saturating_sub(8)); // 7 the length of the prompt + 1 for padding
+        let text = format!("Index: {}", location);
+        print_text_with_coordinates(
+            Text::new(text).color_range(2, ..=5).color_range(0, 7..),
+            0,
+            1,
+            None,
+            None,
+        );
+    }
+    fn render_entries(&self, cols: usize, max_list_items: usize) {
+        let mut items = vec![];
+        let mut selected_index_in_list = None;
+        for (i, entry) in self.index.iter().enumerate() {
+            let is_selected = Some(i) == self.selected_index;
+            if is_selected {
+                selected_index_in_list = Some(items.len());
+            }
+            items.push(self.render_entry_line(entry, is_selected, cols));
+            if is_selected {
+                items.append(&mut self.render_entry_details(entry, cols));
+            }
+        }
+        if items.is_empty() {
+            items.push(NestedListItem::new("<NO PLUGINS>").color_range(0, ..));
+        }
+        let list_start = match selected_index_in_list {
+            Some(selected_index_in_list) if items.len() > max_list_items => {
+                selected_index_in_list.saturating_sub(max_list_items / 2)
+            },
+            _ => 0,
+        };
+        let items = items
+            .into_iter()
+            .skip(list_start)
+            .take(max_list_items)
+            .collect();
+        print_nested_list_with_coordinates(items, 0, 3, Some(cols), None);
+    }
+    fn render_entry_line(
+        &self,
+        entry: &RegistryEntry,
+        is_selected: bool,
+        cols: usize,
+    ) -> NestedListItem {
+        let status = if self.pending_installs.contains(&entry.name) {
+            "[INSTALLING]".to_owned()
+        } else {
+            match (self.entry_status(entry), self.installed.get(&entry.name)) {
+                (EntryStatus::UpToDate, Some(installed)) if installed.pinned => {
+                    "[INSTALLED, PINNED]".to_owned()
+                },
+                (EntryStatus::UpToDate, _) => "[INSTALLED]".to_owned(),
+                (EntryStatus::UpdateAvailable, Some(installed)) => {
+                    format!("[UPDATE FROM {}]", installed.version)
+                },
+                (EntryStatus::Pinned, Some(installed)) => {
+                    format!("[PINNED TO {}]", installed.version)
+                },
+                _ => String::new(),
+            }
+        };
+        let name_and_version = if entry.version.is_empty() {
+            entry.name.clone()
+        } else {
+            format!("{} {}", entry.name, entry.version)
+        };
+        let max_len = cols.saturating_sub(status.chars().count() + 4); // 3 for the bulletin + 1 space
+        let name_and_version = truncate_string_start(&name_and_version, max_len);
+        let name_and_version_len = name_and_version.chars().count();
+        let mut item = NestedListItem::new(format!("{} {}", name_and_version, status))
+            .color_range(0, ..name_and_version_len)
+            .color_range(2, name_and_version_len + 1..);
+        if is_selected {
+            item = item.selected();
+        }
+        item
+    }
+    fn render_entry_details(&self, entry: &RegistryEntry, cols: usize) -> Vec<NestedListItem> {
+        let max_len = cols.saturating_sub(5); // 5 for the indented bulletin
+        let mut details = vec![];
+        if !entry.description.is_empty() {
+            details.push(
+                NestedListItem::new(truncate_string_start(&entry.description, max_len)).indent(1),
+            );
+        }
+        let url_line = truncate_string_start(&format!("URL: {}", entry.url), max_len);
+        details.push(NestedListItem::new(url_line).color_range(2, ..=3).indent(1));
+        let permission_names = entry.permission_names();
+        if permission_names.is_empty() {
+            details.push(
+                NestedListItem::new("Permissions: <NONE>")
+                    .color_range(2, ..=11)
+                    .indent(1),
+            );
+        } else {
+            details.push(
+                NestedListItem::new("Permissions:")
+                    .color_range(2, ..=11)
+                    .indent(1),
+            );
+            for permission_name in permission_names {
+                details.push(
+                    NestedListItem::new(truncate_string_start(&permission_name, max_len)).indent(2),
+                );
+            }
+        }
+        details
+    }
+    fn render_status_message(&self, y: usize, cols: usize) {
+        if let Some((message, is_error)) = &self.status_message {
+            let message = truncate_string_start(message, cols);
+            let color = if *is_error { 3 } else { 2 };
+            print_text_with_coordinates(
+                Text::new(message).color_range(color, ..),
+                0,
+                y,
+                None,
+                None,
+            );
+        }
+    }
+    fn render_help(&self, y: usize, cols: usize) {
+        let full_text = "Help: <↓↑> - Navigate, <ENTER> - Install/Update, <Ctrl p> - Pin, <Ctrl u> - Check for Updates, <ESC> - Back";
+        let short_text = "<↓↑/ENTER> - Navigate/Install, <Ctrl p/u> - Pin/Update, <ESC> - Back";
+        if cols >= full_text.chars().count() {
+            let text = Text::new(full_text)
+                .color_range(3, 6..=9)
+                .color_range(3, 23..=29)
+                .color_range(3, 49..=56)
+                .color_range(3, 65..=72)
+                .color_range(3, 95..=99);
+            print_text_with_coordinates(text, 0, y, Some(cols), None);
+        } else {
+            let text = Text::new(short_text)
+                .color_range(3, ..=9)
+                .color_range(3, 31..=40)
+                .color_range(3, 56..=60);
+            print_text_with_coordinates(text, 0, y, Some(cols), None);
+        }
+    }
+}
+
+fn registry_context(action: &str, plugin_name: Option<&str>) -> BTreeMap<String, String> {
+    let mut context = BTreeMap::new();
+    context.insert(REGISTRY_ACTION.to_owned(), action.to_owned());
+    if let Some(plugin_name) = plugin_name {
+        context.insert(REGISTRY_PLUGIN_NAME.to_owned(), plugin_name.to_owned());
+    }
+    context
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_json_index() {
+        let raw_index = br#"{
+            "plugins": [
+                {
+                    "name": "my-plugin",
+                    "description": "Does things",
+                    "version": "1.0.0",
+                    "url": "https://example.com/my-plugin.wasm",
+                    "sha256": "abc123",
+                    "permissions": ["ReadApplicationState", "RunCommands"]
+                },
+                {
+                    "name": "minimal",
+                    "url": "https://example.com/minimal.wasm",
+                    "sha256": "def456"
+                }
+            ]
+        }"#;
+        let index = RegistryIndex::from_bytes(raw_index).unwrap();
+        assert_eq!(index.plugins.len(), 2);
+        assert_eq!(index.plugins[0].name, "my-plugin");
+        assert_eq!(index.plugins[0].version, "1.0.0");
+        assert_eq!(
+            index.plugins[0].permissions,
+            vec!["ReadApplicationState".to_owned(), "RunCommands".to_owned()]
+        );
+        assert_eq!(index.plugins[1].description, "");
+        assert!(index.plugins[1].permissions.is_empty());
+    }
+
+    #[test]
+    fn parses_a_kdl_index() {
+        let raw_index = br#"
+            plugin "my-plugin" {
+                description "Does things"
+                version "1.0.0"
+                url "https://example.com/my-plugin.wasm"
+                sha256 "abc123"
+                permissions "ReadApplicationState" "RunCommands"
+            }
+            plugin "minimal" {
+                url "https://example.com/minimal.wasm"
+                sha256 "def456"
+            }
+        "#;
+        let index = RegistryIndex::from_bytes(raw_index).unwrap();
+        assert_eq!(index.plugins.len(), 2);
+        assert_eq!(index.plugins[0].name, "my-plugin");
+        assert_eq!(index.plugins[0].description, "Does things");
+        assert_eq!(index.plugins[0].url, "https://example.com/my-plugin.wasm");
+        assert_eq!(index.plugins[0].sha256, "abc123");
+        assert_eq!(
+            index.plugins[0].permissions,
+            vec!["ReadApplicationState".to_owned(), "RunCommands".to_owned()]
+        );
+        assert_eq!(index.plugins[1].version, "");
+    }
+
+    #[test]
+    fn kdl_index_entries_need_a_url_and_a_checksum() {
+        let raw_index = br#"plugin "my-plugin" { url "https://example.com/my-plugin.wasm"; }"#;
+        assert!(RegistryIndex::from_bytes(raw_index).is_err());
+    }
+
+    #[test]
+    fn plain_plugin_names_are_valid() {
+        for name in ["my-plugin", "my_plugin", "plugin.v2", "zjstatus"] {
+            assert!(
+                validate_plugin_name(name).is_ok(),
+                "{} should be valid",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn plugin_names_that_are_not_plain_file_names_are_rejected() {
+        for name in [
+            "",
+            ".",
+            "..",
+            ".hidden",
+            "../../x",
+            "a/b",
+            "/abs/path",
+            "a\\b",
+            "..\\x",
+            "a..b",
+            "new\nline",
+        ] {
+            assert!(
+                validate_plugin_name(name).is_err(),
+                "{:?} should be rejected",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn an_index_with_a_traversal_name_is_rejected() {
+        let json_index = br#"{"plugins": [{"name": "../../x", "url": "u", "sha256": "s"}]}"#;
+        assert!(RegistryIndex::from_bytes(json_index).is_err());
+        let kdl_index = br#"plugin "/abs/path" { url "u"; sha256 "s"; }"#;
+        assert!(RegistryIndex::from_bytes(kdl_index).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 mod new_session_info;
 mod resurrectable_sessions;
 mod session_list;
+mod session_snapshots;
 mod ui;
 use std::collections::BTreeMap;
 use uuid::Uuid;
@@ -18,12 +19,14 @@ use ui::{
 
 use resurrectable_sessions::ResurrectableSessions;
 use session_list::SessionList;
+use session_snapshots::SessionSnapshots;
 
 #[derive(Clone, Debug, Copy)]
 enum ActiveScreen {
     NewSession,
     AttachToSession,
     ResurrectSession,
+    Snapshots,
 }
 
 impl Default for ActiveScreen {
@@ -37,6 +40,7 @@ struct State {
     session_name: Option<String>,
     sessions: SessionList,
     resurrectable_sessions: ResurrectableSessions,
+    session_snapshots: SessionSnapshots,
     search_term: String,
     new_session_info: NewSessionInfo,
     renaming_session_name: Option<String>,
@@ -114,6 +118,9 @@ impl SwarmPlugin for State {
                 self.resurrectable_sessions
                     .update(resurrectable_session_list);
                 self.update_session_infos(session_infos);
+                if let ActiveScreen::Snapshots = self.active_screen {
+                    self.refresh_session_snapshots();
+                }
                 should_render = true;
             },
             _ => (),
@@ -168,6 +175,9 @@ impl SwarmPlugin for State {
             ActiveScreen::ResurrectSession => {
                 self.resurrectable_sessions.render(height, width, x, y);
             },
+            ActiveScreen::Snapshots => {
+                self.session_snapshots.render(height, width, x, y);
+            },
         }
         if let Some(error) = self.error.as_ref() {
             render_error(&error, height, width, x, y);
@@ -194,6 +204,7 @@ impl State {
             ActiveScreen::NewSession => self.handle_new_session_key(key),
             ActiveScreen::AttachToSession => self.handle_attach_to_session(key),
             ActiveScreen::ResurrectSession => self.handle_resurrect_session_key(key),
+            ActiveScreen::Snapshots => self.handle_snapshots_key(key),
         }
     }
     fn handle_new_session_key(&mut self, key: KeyWithModifier) -> bool {
@@ -451,6 +462,47 @@ impl State {
         }
         should_render
     }
+    fn handle_snapshots_key(&mut self, key: KeyWithModifier) -> bool {
+        let mut should_render = false;
+        match key.bare_key {
+            BareKey::Down if key.has_no_modifiers() => {
+                self.session_snapshots.move_selection_down();
+                should_render = true;
+            },
+            BareKey::Up if key.has_no_modifiers() => {
+                self.session_snapshots.move_selection_up();
+                should_render = true;
+            },
+            BareKey::Enter if key.has_no_modifiers() => {
+                if !self.session_snapshots.is_showing_diff {
+                    self.handle_selection();
+                    should_render = true;
+                }
+            },
+            BareKey::Char(' ') if key.has_no_modifiers() => {
+                self.session_snapshots.toggle_mark_selected();
+                should_render = true;
+            },
+            BareKey::Char('w') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.active_screen = ActiveScreen::NewSession;
+                should_render = true;
+            },
+            BareKey::Tab if key.has_no_modifiers() => {
+                self.toggle_active_screen();
+                should_render = true;
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                if self.session_snapshots.is_showing_diff {
+                    self.session_snapshots.close_diff();
+                    should_render = true;
+                } else if !self.is_welcome_screen {
+                    hide_self();
+                }
+            },
+            _ => {},
+        }
+        should_render
+    }
     fn handle_selection(&mut self) {
         match self.active_screen {
             ActiveScreen::NewSession => {
@@ -471,7 +523,9 @@ impl State {
                     self.show_error("This session exists and web clients cannot attach to it.");
                     return;
                 }
-                self.new_session_info.handle_selection(&self.session_name);
+                if let Err(e) = self.new_session_info.handle_selection(&self.session_name) {
+                    self.show_error(&e);
+                }
             },
             ActiveScreen::AttachToSession => {
                 if let Some(renaming_session_name) = &self.renaming_session_name.take() {
@@ -543,14 +597,39 @@ impl State {
                     switch_session(Some(&session_name_to_resurrect));
                 }
             },
+            ActiveScreen::Snapshots => {
+                if let Some(snapshot) = self.session_snapshots.get_selected_snapshot() {
+                    if self.sessions.has_session(&snapshot.session_name) {
+                        self.show_error(
+                            "This session is running, kill it before restoring one of its snapshots.",
+                        );
+                        return;
+                    }
+                    switch_session_with_layout(
+                        Some(&snapshot.session_name),
+                        LayoutInfo::File(snapshot.layout_path.clone()),
+                        None,
+                    );
+                }
+            },
         }
     }
     fn toggle_active_screen(&mut self) {
         self.active_screen = match self.active_screen {
             ActiveScreen::NewSession => ActiveScreen::AttachToSession,
             ActiveScreen::AttachToSession => ActiveScreen::ResurrectSession,
-            ActiveScreen::ResurrectSession => ActiveScreen::NewSession,
+            ActiveScreen::ResurrectSession => ActiveScreen::Snapshots,
+            ActiveScreen::Snapshots => ActiveScreen::NewSession,
         };
+        if let ActiveScreen::Snapshots = self.active_screen {
+            self.refresh_session_snapshots();
+        }
+    }
+    fn refresh_session_snapshots(&mut self) {
+        match list_session_snapshots() {
+            Ok(snapshots) => self.session_snapshots.update(snapshots),
+            Err(e) => self.show_error(&format!("Failed to list snapshots: {}", e)),
+        }
     }
     fn show_error(&mut self, error_text: &str) {
         self.error = Some(error_text.to_owned());
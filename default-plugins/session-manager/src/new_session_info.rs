@@ -1,5 +1,6 @@
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use zellij_tile::prelude::*;
 
@@ -8,6 +9,7 @@ pub struct NewSessionInfo {
     name: String,
     layout_list: LayoutList,
     entering_new_session_info: EnteringState,
+    template_params: Option<TemplateParams>,
     pub new_session_folder: Option<PathBuf>,
 }
 
@@ -15,6 +17,79 @@ pub struct NewSessionInfo {
 enum EnteringState {
     EnteringName,
     EnteringLayoutSearch,
+    EnteringTemplateParams,
+}
+
+/// The values entered for the params of the selected session template
+struct TemplateParams {
+    template: SessionTemplate,
+    values: Vec<String>,
+    selected_index: usize,
+}
+
+impl TemplateParams {
+    pub fn new(template: SessionTemplate) -> Self {
+        let values = template
+            .params
+            .iter()
+            .map(|p| p.default.clone().unwrap_or_default())
+            .collect();
+        TemplateParams {
+            template,
+            values,
+            selected_index: 0,
+        }
+    }
+    pub fn args(&self) -> BTreeMap<String, String> {
+        self.template
+            .params
+            .iter()
+            .zip(self.values.iter())
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(param, value)| (param.name.clone(), value.clone()))
+            .collect()
+    }
+    fn is_last_selected(&self) -> bool {
+        self.selected_index + 1 >= self.values.len()
+    }
+    fn move_selection_up(&mut self) {
+        if self.selected_index > 0 {
+            self.selected_index -= 1;
+        } else {
+            self.selected_index = self.values.len().saturating_sub(1);
+        }
+    }
+    fn move_selection_down(&mut self) {
+        if !self.is_last_selected() {
+            self.selected_index += 1;
+        } else {
+            self.selected_index = 0;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum NewSessionLayout {
+    Layout(LayoutInfo),
+    Template(SessionTemplate),
+}
+
+impl NewSessionLayout {
+    pub fn name(&self) -> &str {
+        match self {
+            NewSessionLayout::Layout(layout_info) => layout_info.name(),
+            NewSessionLayout::Template(template) => &template.name,
+        }
+    }
+    pub fn is_builtin(&self) -> bool {
+        match self {
+            NewSessionLayout::Layout(layout_info) => layout_info.is_builtin(),
+            NewSessionLayout::Template(_) => false,
+        }
+    }
+    pub fn is_template(&self) -> bool {
+        matches!(self, NewSessionLayout::Template(_))
+    }
 }
 
 impl Default for EnteringState {
@@ -36,6 +111,27 @@ impl NewSessionInfo {
     pub fn entering_layout_search_term(&self) -> bool {
         self.entering_new_session_info == EnteringState::EnteringLayoutSearch
     }
+    pub fn entering_template_params(&self) -> bool {
+        self.entering_new_session_info == EnteringState::EnteringTemplateParams
+    }
+    pub fn template_params_to_render(
+        &self,
+    ) -> Option<(&SessionTemplate, Vec<(&SessionTemplateParam, &str, bool)>)> {
+        // (param, value, is_selected)
+        self.template_params.as_ref().map(|template_params| {
+            let params = template_params
+                .template
+                .params
+                .iter()
+                .zip(template_params.values.iter())
+                .enumerate()
+                .map(|(i, (param, value))| {
+                    (param, value.as_str(), i == template_params.selected_index)
+                })
+                .collect();
+            (&template_params.template, params)
+        })
+    }
     pub fn add_char(&mut self, character: char) {
         match self.entering_new_session_info {
             EnteringState::EnteringName => {
@@ -45,6 +141,11 @@ impl NewSessionInfo {
                 self.layout_list.layout_search_term.push(character);
                 self.update_layout_search_term();
             },
+            EnteringState::EnteringTemplateParams => {
+                if let Some(value) = self.selected_template_param_value() {
+                    value.push(character);
+                }
+            },
         }
     }
     pub fn handle_backspace(&mut self) {
@@ -56,6 +157,11 @@ impl NewSessionInfo {
                 self.layout_list.layout_search_term.pop();
                 self.update_layout_search_term();
             },
+            EnteringState::EnteringTemplateParams => {
+                if let Some(value) = self.selected_template_param_value() {
+                    value.pop();
+                }
+            },
         }
     }
     pub fn handle_break(&mut self) {
@@ -68,6 +174,10 @@ impl NewSessionInfo {
                 self.entering_new_session_info = EnteringState::EnteringName;
                 self.update_layout_search_term();
             },
+            EnteringState::EnteringTemplateParams => {
+                self.template_params = None;
+                self.entering_new_session_info = EnteringState::EnteringLayoutSearch;
+            },
         }
     }
     pub fn handle_key(&mut self, key: KeyWithModifier) {
@@ -93,39 +203,107 @@ impl NewSessionInfo {
             _ => {},
         }
     }
-    pub fn handle_selection(&mut self, current_session_name: &Option<String>) {
+    pub fn handle_selection(
+        &mut self,
+        current_session_name: &Option<String>,
+    ) -> Result<(), String> {
         match self.entering_new_session_info {
-            EnteringState::EnteringLayoutSearch => {
-                let new_session_layout: Option<LayoutInfo> = self.selected_layout_info();
-                let new_session_name = if self.name.is_empty() {
-                    None
-                } else {
-                    Some(self.name.as_str())
-                };
-                if new_session_name != current_session_name.as_ref().map(|s| s.as_str()) {
-                    match new_session_layout {
-                        Some(new_session_layout) => {
-                            let cwd = self.new_session_folder.as_ref().map(|c| PathBuf::from(c));
-                            switch_session_with_layout(new_session_name, new_session_layout, cwd)
-                        },
-                        None => {
-                            switch_session(new_session_name);
-                        },
+            EnteringState::EnteringLayoutSearch => match self.selected_layout() {
+                Some(NewSessionLayout::Template(template)) if !template.params.is_empty() => {
+                    self.template_params = Some(TemplateParams::new(template));
+                    self.entering_new_session_info = EnteringState::EnteringTemplateParams;
+                },
+                Some(NewSessionLayout::Template(template)) => {
+                    self.start_session_from_template(
+                        &template.name,
+                        BTreeMap::new(),
+                        current_session_name,
+                    )?;
+                },
+                new_session_layout => {
+                    let new_session_name = if self.name.is_empty() {
+                        None
+                    } else {
+                        Some(self.name.as_str())
+                    };
+                    if new_session_name != current_session_name.as_ref().map(|s| s.as_str()) {
+                        match new_session_layout {
+                            Some(NewSessionLayout::Layout(new_session_layout)) => {
+                                let cwd =
+                                    self.new_session_folder.as_ref().map(|c| PathBuf::from(c));
+                                switch_session_with_layout(
+                                    new_session_name,
+                                    new_session_layout,
+                                    cwd,
+                                )
+                            },
+                            _ => {
+                                switch_session(new_session_name);
+                            },
+                        }
                     }
+                    self.clear();
+                    hide_self();
+                },
+            },
+            EnteringState::EnteringTemplateParams => {
+                let Some(template_params) = self.template_params.as_mut() else {
+                    self.entering_new_session_info = EnteringState::EnteringLayoutSearch;
+                    return Ok(());
+                };
+                if !template_params.is_last_selected() {
+                    template_params.selected_index += 1;
+                } else {
+                    let template_name = template_params.template.name.clone();
+                    let args = template_params.args();
+                    self.start_session_from_template(&template_name, args, current_session_name)?;
                 }
-                self.name.clear();
-                self.layout_list.clear_selection();
-                hide_self();
             },
             EnteringState::EnteringName => {
                 self.entering_new_session_info = EnteringState::EnteringLayoutSearch;
+                match list_session_templates() {
+                    Ok(templates) => self.layout_list.update_template_list(templates),
+                    Err(e) => eprintln!("Failed to list session templates: {}", e),
+                }
             },
         }
+        Ok(())
+    }
+    fn start_session_from_template(
+        &mut self,
+        template_name: &str,
+        args: BTreeMap<String, String>,
+        current_session_name: &Option<String>,
+    ) -> Result<(), String> {
+        let new_session_name = if self.name.is_empty() {
+            None
+        } else {
+            Some(self.name.as_str())
+        };
+        if new_session_name != current_session_name.as_ref().map(|s| s.as_str()) {
+            let layout = resolve_session_template(template_name, args)?;
+            let cwd = self.new_session_folder.as_ref().map(|c| PathBuf::from(c));
+            switch_session_with_layout(new_session_name, LayoutInfo::Stringified(layout), cwd);
+        }
+        self.clear();
+        hide_self();
+        Ok(())
+    }
+    fn clear(&mut self) {
+        self.name.clear();
+        self.template_params = None;
+        self.layout_list.clear_selection();
+        self.entering_new_session_info = EnteringState::EnteringLayoutSearch;
+    }
+    fn selected_template_param_value(&mut self) -> Option<&mut String> {
+        self.template_params
+            .as_mut()
+            .and_then(|t| t.values.get_mut(t.selected_index))
     }
     pub fn update_layout_list(&mut self, layout_info: Vec<LayoutInfo>) {
         self.layout_list.update_layout_list(layout_info);
     }
-    pub fn layout_list(&self, max_rows: usize) -> Vec<(LayoutInfo, bool)> {
+    pub fn layout_list(&self, max_rows: usize) -> Vec<(NewSessionLayout, bool)> {
         // bool - is_selected
         let range_to_render = self.range_to_render(
             max_rows,
@@ -141,7 +319,7 @@ impl NewSessionInfo {
             .skip(range_to_render.0)
             .collect()
     }
-    pub fn layouts_to_render(&self, max_rows: usize) -> Vec<(LayoutInfo, Vec<usize>, bool)> {
+    pub fn layouts_to_render(&self, max_rows: usize) -> Vec<(NewSessionLayout, Vec<usize>, bool)> {
         // (layout_info,
         // search_indices,
         // is_selected)
@@ -150,7 +328,7 @@ impl NewSessionInfo {
                 .into_iter()
                 .map(|(layout_search_result, is_selected)| {
                     (
-                        layout_search_result.layout_info,
+                        layout_search_result.layout,
                         layout_search_result.indices,
                         is_selected,
                     )
@@ -205,8 +383,8 @@ impl NewSessionInfo {
     pub fn layout_count(&self) -> usize {
         self.layout_list.layout_list.len()
     }
-    pub fn selected_layout_info(&self) -> Option<LayoutInfo> {
-        self.layout_list.selected_layout_info()
+    pub fn selected_layout(&self) -> Option<NewSessionLayout> {
+        self.layout_list.selected_layout()
     }
     fn update_layout_search_term(&mut self) {
         if self.layout_list.layout_search_term.is_empty() {
@@ -215,12 +393,12 @@ impl NewSessionInfo {
         } else {
             let mut matches = vec![];
             let matcher = SkimMatcherV2::default().use_cache(true);
-            for layout_info in &self.layout_list.layout_list {
+            for layout in &self.layout_list.layout_list {
                 if let Some((score, indices)) =
-                    matcher.fuzzy_indices(&layout_info.name(), &self.layout_list.layout_search_term)
+                    matcher.fuzzy_indices(&layout.name(), &self.layout_list.layout_search_term)
                 {
                     matches.push(LayoutSearchResult {
-                        layout_info: layout_info.clone(),
+                        layout: layout.clone(),
                         score,
                         indices,
                     });
@@ -232,16 +410,24 @@ impl NewSessionInfo {
         }
     }
     fn move_selection_up(&mut self) {
-        self.layout_list.move_selection_up();
+        match self.template_params.as_mut() {
+            Some(template_params) => template_params.move_selection_up(),
+            None => self.layout_list.move_selection_up(),
+        }
     }
     fn move_selection_down(&mut self) {
-        self.layout_list.move_selection_down();
+        match self.template_params.as_mut() {
+            Some(template_params) => template_params.move_selection_down(),
+            None => self.layout_list.move_selection_down(),
+        }
     }
 }
 
 #[derive(Default)]
 pub struct LayoutList {
-    layout_list: Vec<LayoutInfo>,
+    layout_list: Vec<NewSessionLayout>,
+    layouts: Vec<LayoutInfo>,
+    templates: Vec<SessionTemplate>,
     layout_search_results: Vec<LayoutSearchResult>,
     selected_layout_index: usize,
     layout_search_term: String,
@@ -249,18 +435,37 @@ pub struct LayoutList {
 
 impl LayoutList {
     pub fn update_layout_list(&mut self, layout_list: Vec<LayoutInfo>) {
+        self.layouts = layout_list;
+        self.rebuild_layout_list();
+    }
+    pub fn update_template_list(&mut self, template_list: Vec<SessionTemplate>) {
+        self.templates = template_list;
+        self.rebuild_layout_list();
+    }
+    fn rebuild_layout_list(&mut self) {
         let old_layout_length = self.layout_list.len();
-        self.layout_list = layout_list;
+        self.layout_list = self
+            .layouts
+            .iter()
+            .cloned()
+            .map(NewSessionLayout::Layout)
+            .chain(
+                self.templates
+                    .iter()
+                    .cloned()
+                    .map(NewSessionLayout::Template),
+            )
+            .collect();
         if old_layout_length != self.layout_list.len() {
             // honestly, this is just the UX choice that sucks the least...
             self.clear_selection();
         }
     }
-    pub fn selected_layout_info(&self) -> Option<LayoutInfo> {
+    pub fn selected_layout(&self) -> Option<NewSessionLayout> {
         if !self.layout_search_term.is_empty() {
             self.layout_search_results
                 .get(self.selected_layout_index)
-                .map(|l| l.layout_info.clone())
+                .map(|l| l.layout.clone())
         } else {
             self.layout_list.get(self.selected_layout_index).cloned()
         }
@@ -295,7 +500,7 @@ impl LayoutList {
 
 #[derive(Clone)]
 pub struct LayoutSearchResult {
-    pub layout_info: LayoutInfo,
+    pub layout: NewSessionLayout,
     pub score: i64,
     pub indices: Vec<usize>,
 }
@@ -28,7 +28,12 @@ impl SessionList {
             } else if b.is_current_session {
                 std::cmp::Ordering::Greater
             } else {
-                a.name.cmp(&b.name)
+                // sessions are grouped by their first tag, untagged sessions last
+                (a.tags.is_empty(), a.tags.first(), &a.name).cmp(&(
+                    b.tags.is_empty(),
+                    b.tags.first(),
+                    &b.name,
+                ))
             }
         });
         forbidden_sessions.sort_unstable_by(|a, b| a.name.cmp(&b.name));
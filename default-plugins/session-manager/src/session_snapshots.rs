@@ -0,0 +1,338 @@
+use humantime::format_duration;
+
+use std::time::Duration;
+
+use zellij_tile::prelude::*;
+
+#[derive(Debug, Default)]
+pub struct SessionSnapshots {
+    pub all_snapshots: Vec<SessionSnapshot>,
+    pub selected_index: Option<usize>,
+    pub marked_for_diff: Vec<(String, String)>, // (session_name, snapshot_name)
+    pub is_showing_diff: bool,
+    diff_scroll: usize,
+}
+
+impl SessionSnapshots {
+    pub fn update(&mut self, mut list: Vec<SessionSnapshot>) {
+        list.sort_by(|a, b| a.created.cmp(&b.created));
+        self.marked_for_diff
+            .retain(|(session_name, name)| Self::find_in(&list, session_name, name).is_some());
+        if let Some(selected_index) = self.selected_index {
+            if selected_index >= list.len() {
+                self.selected_index = list.len().checked_sub(1);
+            }
+        }
+        self.all_snapshots = list;
+    }
+    pub fn render(&self, rows: usize, columns: usize, x: usize, y: usize) {
+        if self.is_showing_diff {
+            self.render_diff(rows, columns, x, y);
+            return;
+        }
+        if self.all_snapshots.is_empty() {
+            let text =
+                "No snapshots found, take one with: swarm action snapshot-session --name <NAME>";
+            print_text_with_coordinates(
+                Text::new(text).color_range(2, 34..),
+                x,
+                y + 3,
+                Some(columns),
+                None,
+            );
+            return;
+        }
+        let table_rows = rows.saturating_sub(5); // toggle row and some padding
+        let table = self.render_all_entries(table_rows);
+        print_table_with_coordinates(table, x, y + 3, Some(columns), Some(table_rows));
+    }
+    fn render_all_entries(&self, table_rows: usize) -> Table {
+        let mut table = Table::new().add_row(vec![" ", " ", " ", " "]); // skip the title row
+        let (first_row_index_to_render, last_row_index_to_render) =
+            self.range_to_render(table_rows, self.all_snapshots.len());
+        for i in first_row_index_to_render..last_row_index_to_render {
+            if let Some(snapshot) = self.all_snapshots.get(i) {
+                let is_selected = Some(i) == self.selected_index;
+                let is_marked = self.is_marked(snapshot);
+                let mut table_cells = vec![
+                    Text::new(&snapshot.session_name).color_range(0, ..),
+                    if is_marked {
+                        Text::new(format!("[x] {}", snapshot.name)).color_range(2, ..3)
+                    } else {
+                        Text::new(&snapshot.name)
+                    },
+                    self.render_ctime(&snapshot.created),
+                    self.render_more_indication_or_enter_as_needed(
+                        i,
+                        first_row_index_to_render,
+                        last_row_index_to_render,
+                        is_selected,
+                    ),
+                ];
+                if is_selected {
+                    table_cells = table_cells.drain(..).map(|t| t.selected()).collect();
+                }
+                table = table.add_styled_row(table_cells);
+            }
+        }
+        table
+    }
+    fn render_diff(&self, rows: usize, columns: usize, x: usize, y: usize) {
+        let (Some(old), Some(new)) = (
+            self.marked_snapshot(0).map(|s| s.clone()),
+            self.marked_snapshot(1).map(|s| s.clone()),
+        ) else {
+            return;
+        };
+        let title = format!(
+            "Diff: {}/{} -> {}/{}",
+            old.session_name, old.name, new.session_name, new.name
+        );
+        print_text_with_coordinates(
+            Text::new(&title).color_range(0, 6..),
+            x,
+            y + 2,
+            Some(columns),
+            None,
+        );
+        let diff = diff_lines(&old.layout, &new.layout);
+        let room_for_diff = rows.saturating_sub(5); // title, toggle and controls
+        for (i, line) in diff
+            .iter()
+            .skip(self.diff_scroll)
+            .take(room_for_diff)
+            .enumerate()
+        {
+            let text = match line {
+                DiffLine::Unchanged(line) => Text::new(format!("  {}", line)),
+                DiffLine::Added(line) => Text::new(format!("+ {}", line)).color_range(2, ..),
+                DiffLine::Removed(line) => Text::new(format!("- {}", line)).color_range(3, ..),
+            };
+            print_text_with_coordinates(text, x, y + 3 + i, Some(columns), None);
+        }
+    }
+    fn range_to_render(&self, table_rows: usize, results_len: usize) -> (usize, usize) {
+        if table_rows <= results_len {
+            let row_count_to_render = table_rows.saturating_sub(1); // 1 for the title
+            let first_row_index_to_render = self
+                .selected_index
+                .unwrap_or(0)
+                .saturating_sub(row_count_to_render / 2);
+            let last_row_index_to_render = first_row_index_to_render + row_count_to_render;
+            (first_row_index_to_render, last_row_index_to_render)
+        } else {
+            (0, results_len)
+        }
+    }
+    fn render_ctime(&self, ctime: &Duration) -> Text {
+        let duration = format_duration(ctime.clone()).to_string();
+        let mut formatted_duration = String::new();
+        for part in duration.split_whitespace() {
+            if !part.ends_with('s') {
+                if !formatted_duration.is_empty() {
+                    formatted_duration.push(' ');
+                }
+                formatted_duration.push_str(part);
+            }
+        }
+        if formatted_duration.is_empty() {
+            formatted_duration.push_str("<1m");
+        }
+        let duration_len = formatted_duration.chars().count();
+        Text::new(format!("Created {} ago", formatted_duration)).color_range(2, 8..9 + duration_len)
+    }
+    fn render_more_indication_or_enter_as_needed(
+        &self,
+        i: usize,
+        first_row_index_to_render: usize,
+        last_row_index_to_render: usize,
+        is_selected: bool,
+    ) -> Text {
+        let results_len = self.all_snapshots.len();
+        if is_selected {
+            Text::new(format!("<ENTER> - Restore Snapshot")).color_range(3, 0..7)
+        } else if i == first_row_index_to_render && i > 0 {
+            Text::new(format!("+ {} more", first_row_index_to_render)).color_range(1, ..)
+        } else if i == last_row_index_to_render.saturating_sub(1)
+            && last_row_index_to_render < results_len
+        {
+            Text::new(format!(
+                "+ {} more",
+                results_len.saturating_sub(last_row_index_to_render)
+            ))
+            .color_range(1, ..)
+        } else {
+            Text::new(" ")
+        }
+    }
+    pub fn move_selection_down(&mut self) {
+        if self.is_showing_diff {
+            self.diff_scroll += 1;
+        } else if let Some(selected_index) = self.selected_index.as_mut() {
+            if *selected_index == self.all_snapshots.len().saturating_sub(1) {
+                *selected_index = 0;
+            } else {
+                *selected_index = *selected_index + 1;
+            }
+        } else if !self.all_snapshots.is_empty() {
+            self.selected_index = Some(0);
+        }
+    }
+    pub fn move_selection_up(&mut self) {
+        if self.is_showing_diff {
+            self.diff_scroll = self.diff_scroll.saturating_sub(1);
+        } else if let Some(selected_index) = self.selected_index.as_mut() {
+            if *selected_index == 0 {
+                *selected_index = self.all_snapshots.len().saturating_sub(1);
+            } else {
+                *selected_index = selected_index.saturating_sub(1);
+            }
+        } else if !self.all_snapshots.is_empty() {
+            self.selected_index = Some(self.all_snapshots.len().saturating_sub(1));
+        }
+    }
+    pub fn get_selected_snapshot(&self) -> Option<&SessionSnapshot> {
+        self.selected_index.and_then(|i| self.all_snapshots.get(i))
+    }
+    // marks (or unmarks) the selected snapshot, once two are marked we show their diff
+    pub fn toggle_mark_selected(&mut self) {
+        let Some(selected) = self
+            .get_selected_snapshot()
+            .map(|s| (s.session_name.clone(), s.name.clone()))
+        else {
+            return;
+        };
+        if let Some(position) = self.marked_for_diff.iter().position(|m| m == &selected) {
+            self.marked_for_diff.remove(position);
+        } else {
+            self.marked_for_diff.push(selected);
+        }
+        if self.marked_for_diff.len() == 2 {
+            self.is_showing_diff = true;
+            self.diff_scroll = 0;
+        }
+    }
+    pub fn close_diff(&mut self) {
+        self.is_showing_diff = false;
+        self.marked_for_diff.clear();
+    }
+    fn is_marked(&self, snapshot: &SessionSnapshot) -> bool {
+        self.marked_for_diff.iter().any(|(session_name, name)| {
+            session_name == &snapshot.session_name && name == &snapshot.name
+        })
+    }
+    fn marked_snapshot(&self, index: usize) -> Option<&SessionSnapshot> {
+        self.marked_for_diff
+            .get(index)
+            .and_then(|(session_name, name)| Self::find_in(&self.all_snapshots, session_name, name))
+    }
+    fn find_in<'a>(
+        snapshots: &'a [SessionSnapshot],
+        session_name: &str,
+        name: &str,
+    ) -> Option<&'a SessionSnapshot> {
+        snapshots
+            .iter()
+            .find(|s| s.session_name == session_name && s.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DiffLine {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+}
+
+// a line based diff using the longest common subsequence of the two texts
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                std::cmp::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(DiffLine::Unchanged(old[i].to_owned()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Removed(old[i].to_owned()));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j].to_owned()));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    diff.extend(new[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_texts_are_unchanged() {
+        assert_eq!(
+            diff_lines("a\nb", "a\nb"),
+            vec![
+                DiffLine::Unchanged("a".to_owned()),
+                DiffLine::Unchanged("b".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn added_and_removed_lines_are_found() {
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nc\nd"),
+            vec![
+                DiffLine::Unchanged("a".to_owned()),
+                DiffLine::Removed("b".to_owned()),
+                DiffLine::Unchanged("c".to_owned()),
+                DiffLine::Added("d".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_changed_line_is_removed_and_added() {
+        assert_eq!(
+            diff_lines("pane command=\"htop\"", "pane command=\"btop\""),
+            vec![
+                DiffLine::Removed("pane command=\"htop\"".to_owned()),
+                DiffLine::Added("pane command=\"btop\"".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffing_against_an_empty_text() {
+        assert_eq!(diff_lines("", ""), vec![]);
+        assert_eq!(
+            diff_lines("", "a\nb"),
+            vec![
+                DiffLine::Added("a".to_owned()),
+                DiffLine::Added("b".to_owned())
+            ]
+        );
+        assert_eq!(
+            diff_lines("a\nb", ""),
+            vec![
+                DiffLine::Removed("a".to_owned()),
+                DiffLine::Removed("b".to_owned())
+            ]
+        );
+    }
+}
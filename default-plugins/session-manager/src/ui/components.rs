@@ -433,9 +433,63 @@ pub fn build_session_ui_line(session_ui_info: &SessionUiInfo, colors: Colors) ->
         ]));
         ui_spans.push(current_session_indication);
     }
+    if !session_ui_info.tags.is_empty() {
+        let tags = session_ui_info.tags.join(", ");
+        let tags_styled = colors.session_tags(&tags);
+        ui_spans.push(UiSpan::UiSpanTelescope(UiSpanTelescope::new(vec![
+            StringAndLength::new(format!(" #{tags_styled}"), 2 + tags.width()),
+            StringAndLength::new(
+                format!(" #{}", colors.session_tags(&session_ui_info.tags[0])),
+                2 + session_ui_info.tags[0].width(),
+            ),
+        ])));
+    }
+    if !session_ui_info.running_commands.is_empty() {
+        let running_commands = session_ui_info.running_commands.join(", ");
+        let running_commands_styled = colors.pane_count(&running_commands);
+        let running_command_count = format!("{}", session_ui_info.running_commands.len());
+        let running_command_count_styled = colors.pane_count(&running_command_count);
+        ui_spans.push(UiSpan::UiSpanTelescope(UiSpanTelescope::new(vec![
+            StringAndLength::new(
+                format!(" [running: {running_commands_styled}]"),
+                11 + running_commands.width() + 1,
+            ),
+            StringAndLength::new(
+                format!(" [{running_command_count_styled} running]"),
+                2 + running_command_count.width() + 9,
+            ),
+        ])));
+    }
+    if let Some(last_activity) = session_ui_info.last_activity {
+        let idle_time = format_idle_time(last_activity);
+        ui_spans.push(UiSpan::UiSpanTelescope(UiSpanTelescope::new(vec![
+            StringAndLength::new(
+                format!(" (active {} ago)", idle_time),
+                9 + idle_time.width() + 5,
+            ),
+            StringAndLength::new(format!(" ({})", idle_time), 2 + idle_time.width() + 1),
+        ])));
+    }
     ui_spans
 }
 
+fn format_idle_time(last_activity: u64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let idle_seconds = now.saturating_sub(last_activity);
+    if idle_seconds < 60 {
+        format!("{}s", idle_seconds)
+    } else if idle_seconds < 60 * 60 {
+        format!("{}m", idle_seconds / 60)
+    } else if idle_seconds < 60 * 60 * 24 {
+        format!("{}h", idle_seconds / (60 * 60))
+    } else {
+        format!("{}d", idle_seconds / (60 * 60 * 24))
+    }
+}
+
 pub fn build_tab_ui_line(tab_ui_info: &TabUiInfo, colors: Colors) -> Vec<UiSpan> {
     let mut ui_spans = vec![];
     let tab_name = &tab_ui_info.name;
@@ -542,21 +596,30 @@ pub fn render_screen_toggle(
     background: &PaletteColor,
 ) {
     let key_indication_text = "<TAB>";
-    let (new_session_text, running_sessions_text, exited_sessions_text) = if max_cols > 66 {
-        ("New Session", "Attach to Session", "Resurrect Session")
-    } else {
-        ("New", "Attach", "Resurrect")
-    };
+    let (new_session_text, running_sessions_text, exited_sessions_text, snapshots_text) =
+        if max_cols > 78 {
+            (
+                "New Session",
+                "Attach to Session",
+                "Resurrect Session",
+                "Snapshots",
+            )
+        } else {
+            ("New", "Attach", "Resurrect", "Snapshots")
+        };
     let key_indication_len = key_indication_text.chars().count() + 1;
     let first_ribbon_length = new_session_text.chars().count() + 4;
     let second_ribbon_length = running_sessions_text.chars().count() + 4;
     let key_indication_x = x;
     let first_ribbon_x = key_indication_x + key_indication_len;
     let second_ribbon_x = first_ribbon_x + first_ribbon_length;
+    let third_ribbon_length = exited_sessions_text.chars().count() + 4;
     let third_ribbon_x = second_ribbon_x + second_ribbon_length;
+    let fourth_ribbon_x = third_ribbon_x + third_ribbon_length;
     let mut new_session_text = Text::new(new_session_text);
     let mut running_sessions_text = Text::new(running_sessions_text);
     let mut exited_sessions_text = Text::new(exited_sessions_text);
+    let mut snapshots_text = Text::new(snapshots_text);
     match active_screen {
         ActiveScreen::NewSession => {
             new_session_text = new_session_text.selected();
@@ -567,6 +630,9 @@ pub fn render_screen_toggle(
         ActiveScreen::ResurrectSession => {
             exited_sessions_text = exited_sessions_text.selected();
         },
+        ActiveScreen::Snapshots => {
+            snapshots_text = snapshots_text.selected();
+        },
     }
     let bg_color = match background {
         PaletteColor::Rgb((r, g, b)) => format!("\u{1b}[48;2;{};{};{}m\u{1b}[0K", r, g, b),
@@ -583,6 +649,7 @@ pub fn render_screen_toggle(
     print_ribbon_with_coordinates(new_session_text, first_ribbon_x, y, None, None);
     print_ribbon_with_coordinates(running_sessions_text, second_ribbon_x, y, None, None);
     print_ribbon_with_coordinates(exited_sessions_text, third_ribbon_x, y, None, None);
+    print_ribbon_with_coordinates(snapshots_text, fourth_ribbon_x, y, None, None);
 }
 
 fn render_new_session_folder_prompt(
@@ -804,6 +871,25 @@ pub fn render_new_session_block(
             x,
             y + 1,
         );
+    } else if new_session_info.entering_template_params() {
+        let new_session_name = if new_session_info.name().is_empty() {
+            "<RANDOM>"
+        } else {
+            new_session_info.name()
+        };
+        println!(
+            "\u{1b}[m{}{}: {}",
+            format!("\u{1b}[{};{}H", y + 1, x + 1),
+            colors.session_name_prompt("New session name"),
+            colors.session_and_folder_entry(new_session_name),
+        );
+        render_template_params(
+            new_session_info,
+            max_rows_of_new_session_block.saturating_sub(8),
+            max_cols_of_new_session_block,
+            x,
+            y + 1,
+        );
     }
     render_new_session_folder_prompt(
         new_session_info,
@@ -851,6 +937,7 @@ pub fn render_layout_selection_list(
         let layout_name = layout_info.name();
         let layout_name_len = layout_name.width();
         let is_builtin = layout_info.is_builtin();
+        let is_template = layout_info.is_template();
         if i > max_rows_of_new_session_block.saturating_sub(1) {
             break;
         } else {
@@ -859,6 +946,11 @@ pub fn render_layout_selection_list(
                     .color_range(1, 0..layout_name_len)
                     .color_range(0, layout_name_len + 1..)
                     .color_indices(3, indices)
+            } else if is_template {
+                Text::new(format!("{} (template)", layout_name))
+                    .color_range(1, 0..layout_name_len)
+                    .color_range(2, layout_name_len + 1..)
+                    .color_indices(3, indices)
             } else {
                 Text::new(format!("{}", layout_name))
                     .color_range(1, ..)
@@ -879,6 +971,74 @@ pub fn render_layout_selection_list(
     print_table_with_coordinates(table, x, table_y, None, None);
 }
 
+pub fn render_template_params(
+    new_session_info: &NewSessionInfo,
+    max_rows_of_new_session_block: usize,
+    max_cols_of_new_session_block: usize,
+    x: usize,
+    y: usize,
+) {
+    let Some((template, params)) = new_session_info.template_params_to_render() else {
+        return;
+    };
+    let template_name_len = template.name.width();
+    let template_line = match template.description.as_ref() {
+        Some(description) => Text::new(format!("Template: {} - {}", template.name, description))
+            .color_range(2, ..9)
+            .color_range(1, 10..10 + template_name_len),
+        None => Text::new(format!("Template: {}", template.name))
+            .color_range(2, ..9)
+            .color_range(1, 10..),
+    };
+    print_text_with_coordinates(template_line, x, y + 1, None, None);
+    let help_line = if max_cols_of_new_session_block > 64 {
+        Text::new("<ENTER> next param / start, <↓↑> move between params, <ESC> back")
+            .color_range(3, ..7)
+            .color_range(3, 28..32)
+            .color_range(3, 54..59)
+    } else {
+        Text::new("<ENTER> / <↓↑> / <ESC>")
+            .color_range(3, ..7)
+            .color_range(3, 10..14)
+            .color_range(3, 17..)
+    };
+    print_text_with_coordinates(help_line, x, y + 2, None, None);
+    let mut table = Table::new();
+    for (i, (param, value, is_selected)) in params.into_iter().enumerate() {
+        if i > max_rows_of_new_session_block.saturating_sub(1) {
+            break;
+        }
+        let param_name_len = param.name.width();
+        let value = if is_selected {
+            format!("{}_", value)
+        } else {
+            value.to_owned()
+        };
+        let mut param_cell = Text::new(format!("{}: {}", param.name, value))
+            .color_range(2, ..param_name_len + 1)
+            .color_range(3, param_name_len + 2..);
+        let mut description_cell = match (param.is_required(), param.description.as_ref()) {
+            (true, Some(description)) => {
+                Text::new(format!("(required) {}", description)).color_range(1, ..10)
+            },
+            (true, None) => Text::new("(required)").color_range(1, ..),
+            (false, Some(description)) => Text::new(description),
+            (false, None) => Text::new(""),
+        };
+        if is_selected {
+            param_cell = param_cell.selected();
+            description_cell = description_cell.selected();
+        }
+        let arrow_cell = if is_selected {
+            Text::new(format!("<↓↑>")).selected().color_range(3, ..)
+        } else {
+            Text::new(format!("    ")).color_range(3, ..)
+        };
+        table = table.add_styled_row(vec![arrow_cell, param_cell, description_cell]);
+    }
+    print_table_with_coordinates(table, x, y + 4, None, None);
+}
+
 pub fn render_error(error_text: &str, rows: usize, columns: usize, x: usize, y: usize) {
     print_text_with_coordinates(
         Text::new(format!("Error: {}", error_text)).color_range(3, ..),
@@ -972,6 +1132,24 @@ pub fn render_controls_line(
                 print!("\u{1b}[m\u{1b}[{y};{x}H{arrows}/{enter}/{del}/{del_all}");
             }
         },
+        ActiveScreen::Snapshots => {
+            let arrows = colors.shortcuts("<↓↑>");
+            let navigate = colors.bold("Navigate");
+            let enter = colors.shortcuts("<ENTER>");
+            let select = colors.bold("Restore");
+            let space = colors.shortcuts("<SPACE>");
+            let space_text = colors.bold("Mark two to diff");
+            let esc = colors.shortcuts("<ESC>");
+            let esc_text = colors.bold("Close diff");
+
+            if max_cols > 88 {
+                print!(
+                    "\u{1b}[m\u{1b}[{y};{x}HHelp: {arrows} - {navigate}, {enter} - {select}, {space} - {space_text}, {esc} - {esc_text}"
+                );
+            } else if max_cols >= 28 {
+                print!("\u{1b}[m\u{1b}[{y};{x}H{arrows}/{enter}/{space}/{esc}");
+            }
+        },
     }
 }
 
@@ -1020,6 +1198,10 @@ impl Colors {
         self.color(&self.palette.text_unselected.emphasis_0, text)
     }
 
+    pub fn session_tags(&self, text: &str) -> String {
+        self.color(&self.palette.text_unselected.emphasis_3, text)
+    }
+
     pub fn pane_count(&self, text: &str) -> String {
         self.color(&self.palette.text_unselected.emphasis_2, text)
     }
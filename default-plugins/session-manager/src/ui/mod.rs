@@ -227,6 +227,30 @@ pub struct SessionUiInfo {
     pub tabs: Vec<TabUiInfo>,
     pub connected_users: usize,
     pub is_current_session: bool,
+    pub tags: Vec<String>,
+    pub running_commands: Vec<String>,
+    pub last_activity: Option<u64>,
+}
+
+// the program names of the commands still running in the command panes of the session
+fn running_commands(session_info: &SessionInfo) -> Vec<String> {
+    let mut running_commands: Vec<String> = vec![];
+    for pane in session_info.panes.panes.values().flatten() {
+        if pane.is_plugin || pane.exited {
+            continue;
+        }
+        let program = pane
+            .terminal_command
+            .as_ref()
+            .and_then(|command| command.split_whitespace().next())
+            .and_then(|program| program.rsplit('/').next());
+        if let Some(program) = program {
+            if !running_commands.iter().any(|c| c == program) {
+                running_commands.push(program.to_owned());
+            }
+        }
+    }
+    running_commands
 }
 
 impl SessionUiInfo {
@@ -240,6 +264,9 @@ impl SessionUiInfo {
                 .collect(),
             connected_users: session_info.connected_clients,
             is_current_session: session_info.is_current_session,
+            tags: session_info.tags.clone(),
+            running_commands: running_commands(session_info),
+            last_activity: session_info.last_activity,
         }
     }
     pub fn line_count(&self, selected_index: &SelectedIndex) -> usize {
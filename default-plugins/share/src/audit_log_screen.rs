@@ -0,0 +1,134 @@
+use zellij_tile::prelude::*;
+
+const TITLE: &str = "Web audit log";
+const HELP_LONG: &str = "<↓↑> - scroll, <r> - reload, <Esc> - go back";
+const HELP_SHORT: &str = "<↓↑>/<r>/<Esc>";
+const EMPTY_LOG: &str = "Nothing recorded yet.";
+const TRUNCATION_MARKER: &str = "...";
+
+// title, empty line, ..., empty line, help
+const FIXED_ROWS: usize = 4;
+
+pub struct AuditLogScreen<'a> {
+    events: &'a [WebAuditEvent],
+    scroll: usize, // lines scrolled up from the newest event
+    error: &'a Option<String>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<'a> AuditLogScreen<'a> {
+    pub fn new(
+        events: &'a [WebAuditEvent],
+        scroll: usize,
+        error: &'a Option<String>,
+        rows: usize,
+        cols: usize,
+    ) -> Self {
+        Self {
+            events,
+            scroll,
+            error,
+            rows,
+            cols,
+        }
+    }
+
+    pub fn visible_event_count(rows: usize) -> usize {
+        rows.saturating_sub(FIXED_ROWS)
+    }
+
+    pub fn render(&self) {
+        let title = format!("{} ({} events)", TITLE, self.events.len());
+        print_text_with_coordinates(
+            Text::new(self.truncate(&title)).color_range(2, ..TITLE.chars().count()),
+            0,
+            0,
+            None,
+            None,
+        );
+
+        if self.events.is_empty() {
+            print_text_with_coordinates(Text::new(EMPTY_LOG), 0, 2, None, None);
+        }
+        let visible_count = Self::visible_event_count(self.rows);
+        let end = self.events.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(visible_count);
+        for (i, event) in self.events[start..end].iter().enumerate() {
+            let timestamp = event.formatted_timestamp();
+            let line = self.truncate(&format!("{} {}", timestamp, event.describe()));
+            let kind_start = timestamp.chars().count() + 1;
+            let kind_end =
+                (kind_start + event.kind.to_string().chars().count()).min(line.chars().count());
+            let text = Text::new(&line)
+                .color_range(0, ..kind_start.saturating_sub(1).min(kind_end))
+                .color_range(kind_color(event.kind), kind_start..kind_end);
+            print_text_with_coordinates(text, 0, 2 + i, None, None);
+        }
+
+        let help_y = self.rows.saturating_sub(1);
+        match self.error {
+            Some(error) => {
+                print_text_with_coordinates(
+                    Text::new(self.truncate(error)).color_range(3, ..),
+                    0,
+                    help_y,
+                    None,
+                    None,
+                );
+            },
+            None => {
+                let help = if self.cols >= HELP_LONG.chars().count() {
+                    HELP_LONG
+                } else {
+                    HELP_SHORT
+                };
+                print_text_with_coordinates(
+                    Text::new(help).color_indices(3, help_shortcut_indices(help)),
+                    0,
+                    help_y,
+                    None,
+                    None,
+                );
+            },
+        }
+    }
+
+    fn truncate(&self, line: &str) -> String {
+        if line.chars().count() <= self.cols {
+            line.to_owned()
+        } else {
+            let truncated: String = line
+                .chars()
+                .take(self.cols.saturating_sub(TRUNCATION_MARKER.chars().count()))
+                .collect();
+            format!("{}{}", truncated, TRUNCATION_MARKER)
+        }
+    }
+}
+
+fn kind_color(kind: WebAuditEventKind) -> usize {
+    match kind {
+        WebAuditEventKind::LoginFailed | WebAuditEventKind::SessionKilled => 3,
+        WebAuditEventKind::Action => 1,
+        _ => 2,
+    }
+}
+
+// the characters of every <...> in the help text
+fn help_shortcut_indices(help: &str) -> Vec<usize> {
+    let mut indices = vec![];
+    let mut in_shortcut = false;
+    for (i, c) in help.chars().enumerate() {
+        if c == '<' {
+            in_shortcut = true;
+        }
+        if in_shortcut {
+            indices.push(i);
+        }
+        if c == '>' {
+            in_shortcut = false;
+        }
+    }
+    indices
+}
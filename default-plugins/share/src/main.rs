@@ -1,3 +1,4 @@
+mod audit_log_screen;
 mod main_screen;
 mod token_management_screen;
 mod token_screen;
@@ -8,6 +9,7 @@ use zellij_tile::prelude::*;
 
 use std::collections::{BTreeMap, HashMap};
 
+use audit_log_screen::AuditLogScreen;
 use main_screen::MainScreen;
 use token_management_screen::TokenManagementScreen;
 use token_screen::TokenScreen;
@@ -19,6 +21,7 @@ struct App {
     web_server: WebServerState,
     ui: UIState,
     tokens: TokenManager,
+    audit_log: AuditLog,
     state: AppState,
 }
 
@@ -44,6 +47,7 @@ impl SwarmPlugin for App {
                 self.handle_command_result(exit_code, context)
             },
             Event::FailedToStartWebServer(error) => self.handle_web_server_error(error),
+            Event::ListClients(clients) => self.handle_client_list(clients),
             _ => false,
         }
     }
@@ -59,6 +63,7 @@ impl SwarmPlugin for App {
             Screen::Main => self.render_main_screen(rows, cols),
             Screen::Token(token) => self.render_token_screen(rows, cols, token),
             Screen::ManageTokens => self.render_manage_tokens_screen(rows, cols),
+            Screen::AuditLog => self.render_audit_log_screen(rows, cols),
         }
     }
 }
@@ -81,6 +86,7 @@ impl App {
             EventType::RunCommandResult,
             EventType::FailedToStartWebServer,
             EventType::Timer,
+            EventType::ListClients,
         ]);
     }
 
@@ -93,10 +99,19 @@ impl App {
     fn handle_timer(&mut self) -> bool {
         query_web_server_status();
         self.retrieve_token_list();
+        list_clients();
         set_timeout(WEB_SERVER_QUERY_DURATION);
         false
     }
 
+    fn handle_client_list(&mut self, clients: Vec<ClientInfo>) -> bool {
+        let read_only = clients.iter().filter(|c| c.is_read_only).count();
+        let connected_clients = (clients.len().saturating_sub(read_only), read_only);
+        let should_render = self.state.connected_clients != connected_clients;
+        self.state.connected_clients = connected_clients;
+        should_render
+    }
+
     fn handle_mode_update(&mut self, mode_info: ModeInfo) -> bool {
         let mut should_render = false;
 
@@ -162,6 +177,7 @@ impl App {
             Screen::Main => self.handle_main_screen_keys(key),
             Screen::Token(_) => self.handle_token_screen_keys(key),
             Screen::ManageTokens => self.handle_manage_tokens_keys(key),
+            Screen::AuditLog => self.handle_audit_log_keys(key),
         }
     }
 
@@ -187,6 +203,10 @@ impl App {
                 self.handle_token_action();
                 true
             },
+            BareKey::Char('l') if key.has_no_modifiers() => {
+                self.change_to_audit_log_screen();
+                true
+            },
             BareKey::Esc if key.has_no_modifiers() => {
                 close_self();
                 false
@@ -195,6 +215,22 @@ impl App {
         }
     }
 
+    fn handle_audit_log_keys(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Up if key.has_no_modifiers() => self.audit_log.scroll_up(),
+            BareKey::Down if key.has_no_modifiers() => self.audit_log.scroll_down(),
+            BareKey::Char('r') if key.has_no_modifiers() => {
+                self.retrieve_audit_log();
+                true
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                self.change_to_main_screen();
+                true
+            },
+            _ => false,
+        }
+    }
+
     fn toggle_session_sharing(&self) {
         match self.web_server.sharing {
             WebSharing::Disabled => {},
@@ -205,7 +241,7 @@ impl App {
 
     fn handle_token_action(&mut self) {
         if self.tokens.list.is_empty() {
-            self.generate_new_token(None);
+            self.generate_new_token(None, WebTokenScope::Admin);
         } else {
             self.change_to_manage_tokens_screen();
         }
@@ -231,9 +267,16 @@ impl App {
             BareKey::Down if key.has_no_modifiers() => self.tokens.navigate_down(),
             BareKey::Up if key.has_no_modifiers() => self.tokens.navigate_up(),
             BareKey::Char('n') if key.has_no_modifiers() => {
-                self.tokens.start_new_token_input();
+                self.tokens.start_new_token_input(WebTokenScope::Admin);
+                true
+            },
+            BareKey::Char('v') if key.has_no_modifiers() => {
+                self.tokens.start_new_token_input(WebTokenScope::ReadOnly);
                 true
             },
+            BareKey::Char('s') if key.has_no_modifiers() => self.cycle_selected_token_scope(),
+            BareKey::Char('a') if key.has_no_modifiers() => self.tokens.start_sessions_input(),
+            BareKey::Char('e') if key.has_no_modifiers() => self.tokens.start_expiry_input(),
             BareKey::Enter if key.has_no_modifiers() => self.handle_enter_key(),
             BareKey::Char('r') if key.has_no_modifiers() => {
                 self.tokens.start_rename_input();
@@ -258,8 +301,8 @@ impl App {
     }
 
     fn handle_enter_key(&mut self) -> bool {
-        if let Some(token_name) = self.tokens.finish_new_token_input() {
-            self.generate_new_token(token_name);
+        if let Some((token_name, scope)) = self.tokens.finish_new_token_input() {
+            self.generate_new_token(token_name, scope);
             return true;
         }
 
@@ -268,11 +311,21 @@ impl App {
             return true;
         }
 
+        if let Some(allowed_sessions) = self.tokens.finish_sessions_input() {
+            self.update_selected_token_sessions(allowed_sessions);
+            return true;
+        }
+
+        if let Some(expires_in) = self.tokens.finish_expiry_input() {
+            self.update_selected_token_expiry(expires_in);
+            return true;
+        }
+
         false
     }
 
-    fn generate_new_token(&mut self, name: Option<String>) {
-        match generate_web_login_token(name) {
+    fn generate_new_token(&mut self, name: Option<String>, scope: WebTokenScope) {
+        match generate_web_login_token(name, scope) {
             Ok(token) => self.change_to_token_screen(token),
             Err(e) => self.web_server.error = Some(e),
         }
@@ -280,7 +333,7 @@ impl App {
 
     fn rename_current_token(&mut self, new_name: String) {
         if let Some(current_token) = self.tokens.get_selected_token() {
-            match rename_web_token(&current_token.0, &new_name) {
+            match rename_web_token(&current_token.name, &new_name) {
                 Ok(_) => {
                     self.retrieve_token_list();
                     if self.tokens.adjust_selection_after_list_change() {
@@ -292,9 +345,64 @@ impl App {
         }
     }
 
+    fn cycle_selected_token_scope(&mut self) -> bool {
+        if let Some(token) = self.tokens.get_selected_token() {
+            let next_scope = match token.scope {
+                WebTokenScope::ReadOnly => WebTokenScope::Interactive,
+                WebTokenScope::Interactive => WebTokenScope::Admin,
+                WebTokenScope::Admin => WebTokenScope::ReadOnly,
+            };
+            let result = update_web_token(&token.name, next_scope, token.allowed_sessions.clone());
+            self.handle_token_update_result(result);
+            return true;
+        }
+        false
+    }
+
+    fn update_selected_token_sessions(&mut self, allowed_sessions: String) {
+        if let Some(token) = self.tokens.get_selected_token() {
+            let allowed_sessions = allowed_sessions
+                .split(',')
+                .map(|s| s.trim().to_owned())
+                .filter(|s| !s.is_empty())
+                .collect();
+            let result = update_web_token(&token.name, token.scope, allowed_sessions);
+            self.handle_token_update_result(result);
+        }
+    }
+
+    fn update_selected_token_expiry(&mut self, expires_in: String) {
+        let expires_in = expires_in.trim();
+        let expires_in = if expires_in.is_empty() {
+            None
+        } else {
+            match humantime::parse_duration(expires_in) {
+                Ok(expires_in) => Some(expires_in),
+                Err(e) => {
+                    self.web_server.error = Some(format!("Invalid duration: {}", e));
+                    return;
+                },
+            }
+        };
+        if let Some(token) = self.tokens.get_selected_token() {
+            let result = set_web_token_expiry(&token.name, expires_in);
+            self.handle_token_update_result(result);
+        }
+    }
+
+    fn handle_token_update_result(&mut self, result: Result<(), String>) {
+        match result {
+            Ok(_) => {
+                self.retrieve_token_list();
+                self.state.info = Some("Updated. Connected clients not affected.".to_owned());
+            },
+            Err(e) => self.web_server.error = Some(e),
+        }
+    }
+
     fn revoke_selected_token(&mut self) -> bool {
         if let Some(token) = self.tokens.get_selected_token() {
-            match revoke_web_login_token(&token.0) {
+            match revoke_web_login_token(&token.name) {
                 Ok(_) => {
                     self.retrieve_token_list();
                     if self.tokens.adjust_selection_after_list_change() {
@@ -403,6 +511,13 @@ impl App {
         self.state.current_screen = Screen::ManageTokens;
     }
 
+    fn change_to_audit_log_screen(&mut self) {
+        self.retrieve_audit_log();
+        set_self_mouse_selection_support(true);
+        self.state.previous_screen = None;
+        self.state.current_screen = Screen::AuditLog;
+    }
+
     fn change_to_main_screen(&mut self) {
         self.retrieve_token_list();
         set_self_mouse_selection_support(false);
@@ -432,6 +547,7 @@ impl App {
             self.ui.hover_coordinates,
             &self.state.info,
             &self.ui.link_executable,
+            self.state.connected_clients,
         )
         .render(rows, cols);
 
@@ -453,6 +569,9 @@ impl App {
             self.tokens.selected_index,
             &self.tokens.renaming_token,
             &self.tokens.entering_new_name,
+            self.tokens.new_token_scope,
+            &self.tokens.editing_sessions,
+            &self.tokens.editing_expiry,
             &self.web_server.error,
             &self.state.info,
             rows,
@@ -461,11 +580,28 @@ impl App {
         .render();
     }
 
+    fn render_audit_log_screen(&self, rows: usize, cols: usize) {
+        AuditLogScreen::new(
+            &self.audit_log.events,
+            self.audit_log.scroll,
+            &self.web_server.error,
+            rows,
+            cols,
+        )
+        .render();
+    }
+
     fn retrieve_token_list(&mut self) {
         if let Err(e) = self.tokens.retrieve_list() {
             self.web_server.error = Some(e);
         }
     }
+
+    fn retrieve_audit_log(&mut self) {
+        if let Err(e) = self.audit_log.retrieve() {
+            self.web_server.error = Some(e);
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -497,12 +633,52 @@ impl UIState {
     }
 }
 
+#[derive(Debug, Default)]
+struct AuditLog {
+    events: Vec<WebAuditEvent>,
+    scroll: usize, // lines scrolled up from the newest event
+}
+
+impl AuditLog {
+    fn retrieve(&mut self) -> Result<(), String> {
+        self.scroll = 0;
+        match read_web_audit_log() {
+            Ok(events) => {
+                self.events = events;
+                Ok(())
+            },
+            Err(e) => Err(format!("Failed to read the audit log: {}", e)),
+        }
+    }
+
+    fn scroll_up(&mut self) -> bool {
+        if self.scroll + 1 < self.events.len() {
+            self.scroll += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn scroll_down(&mut self) -> bool {
+        if self.scroll > 0 {
+            self.scroll -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 struct TokenManager {
-    list: Vec<(String, String)>,
+    list: Vec<WebLoginToken>,
     selected_index: Option<usize>,
     entering_new_name: Option<String>,
+    new_token_scope: WebTokenScope,
     renaming_token: Option<String>,
+    editing_sessions: Option<String>,
+    editing_expiry: Option<String>,
 }
 
 impl TokenManager {
@@ -516,7 +692,7 @@ impl TokenManager {
         }
     }
 
-    fn get_selected_token(&self) -> Option<&(String, String)> {
+    fn get_selected_token(&self) -> Option<&WebLoginToken> {
         self.selected_index.and_then(|i| self.list.get(i))
     }
 
@@ -556,33 +732,50 @@ impl TokenManager {
         false
     }
 
-    fn start_new_token_input(&mut self) {
+    fn start_new_token_input(&mut self, scope: WebTokenScope) {
         self.entering_new_name = Some(String::new());
+        self.new_token_scope = scope;
     }
 
     fn start_rename_input(&mut self) {
         self.renaming_token = Some(String::new());
     }
 
+    fn start_sessions_input(&mut self) -> bool {
+        let Some(token) = self.get_selected_token() else {
+            return false;
+        };
+        self.editing_sessions = Some(token.allowed_sessions.join(", "));
+        true
+    }
+
+    fn start_expiry_input(&mut self) -> bool {
+        if self.get_selected_token().is_none() {
+            return false;
+        }
+        self.editing_expiry = Some(String::new());
+        true
+    }
+
+    fn current_text_input(&mut self) -> Option<&mut String> {
+        self.entering_new_name
+            .as_mut()
+            .or(self.renaming_token.as_mut())
+            .or(self.editing_sessions.as_mut())
+            .or(self.editing_expiry.as_mut())
+    }
+
     fn handle_text_input(&mut self, key: &KeyWithModifier) -> bool {
         match key.bare_key {
             BareKey::Char(c) if key.has_no_modifiers() => {
-                if let Some(ref mut name) = self.entering_new_name {
-                    name.push(c);
-                    return true;
-                }
-                if let Some(ref mut name) = self.renaming_token {
-                    name.push(c);
+                if let Some(text) = self.current_text_input() {
+                    text.push(c);
                     return true;
                 }
             },
             BareKey::Backspace if key.has_no_modifiers() => {
-                if let Some(ref mut name) = self.entering_new_name {
-                    name.pop();
-                    return true;
-                }
-                if let Some(ref mut name) = self.renaming_token {
-                    name.pop();
+                if let Some(text) = self.current_text_input() {
+                    text.pop();
                     return true;
                 }
             },
@@ -591,18 +784,31 @@ impl TokenManager {
         false
     }
 
-    fn finish_new_token_input(&mut self) -> Option<Option<String>> {
-        self.entering_new_name
-            .take()
-            .map(|name| if name.is_empty() { None } else { Some(name) })
+    fn finish_new_token_input(&mut self) -> Option<(Option<String>, WebTokenScope)> {
+        let scope = std::mem::take(&mut self.new_token_scope);
+        self.entering_new_name.take().map(|name| {
+            let name = if name.is_empty() { None } else { Some(name) };
+            (name, scope)
+        })
     }
 
     fn finish_rename_input(&mut self) -> Option<String> {
         self.renaming_token.take()
     }
 
+    fn finish_sessions_input(&mut self) -> Option<String> {
+        self.editing_sessions.take()
+    }
+
+    fn finish_expiry_input(&mut self) -> Option<String> {
+        self.editing_expiry.take()
+    }
+
     fn cancel_input(&mut self) -> bool {
-        self.entering_new_name.take().is_some() || self.renaming_token.take().is_some()
+        self.entering_new_name.take().is_some()
+            || self.renaming_token.take().is_some()
+            || self.editing_sessions.take().is_some()
+            || self.editing_expiry.take().is_some()
     }
 }
 
@@ -614,6 +820,7 @@ struct AppState {
     current_screen: Screen,
     previous_screen: Option<Screen>,
     info: Option<String>,
+    connected_clients: (usize, usize), // (interactive, read_only)
 }
 
 #[derive(Debug, Clone)]
@@ -621,6 +828,7 @@ enum Screen {
     Main,
     Token(String),
     ManageTokens,
+    AuditLog,
 }
 
 impl Default for Screen {
@@ -27,6 +27,7 @@ pub struct MainScreen<'a> {
     hover_coordinates: Option<(usize, usize)>,
     info: &'a Option<String>,
     link_executable: &'a Option<&'a str>,
+    connected_clients: (usize, usize),
 }
 
 impl<'a> MainScreen<'a> {
@@ -50,6 +51,7 @@ impl<'a> MainScreen<'a> {
         hover_coordinates: Option<(usize, usize)>,
         info: &'a Option<String>,
         link_executable: &'a Option<&'a str>,
+        connected_clients: (usize, usize),
     ) -> Self {
         Self {
             token_list_is_empty,
@@ -64,6 +66,7 @@ impl<'a> MainScreen<'a> {
             hover_coordinates,
             info,
             link_executable,
+            connected_clients,
         }
     }
 
@@ -96,6 +99,7 @@ impl<'a> MainScreen<'a> {
             self.session_name.clone(),
             self.web_sharing,
             self.connection_is_unencrypted(),
+            self.connected_clients,
         );
 
         let title_width = Self::TITLE_TEXT.chars().count();
@@ -125,6 +129,7 @@ impl<'a> MainScreen<'a> {
             base_y: rows.saturating_sub(total_height) / 2,
             title_text: Self::TITLE_TEXT,
             web_server_height,
+            current_session_height,
             usage_height,
             usage_width,
         }
@@ -194,6 +199,7 @@ impl<'a> MainScreen<'a> {
             self.session_name.clone(),
             self.web_sharing,
             self.connection_is_unencrypted(),
+            self.connected_clients,
         );
 
         current_session_section.render_current_session_status(
@@ -208,7 +214,7 @@ impl<'a> MainScreen<'a> {
             state.clickable_urls.insert(coordinates, url);
         }
 
-        y + layout.web_server_height + 1
+        y + layout.current_session_height + 1
     }
 
     fn render_usage_section(&self, layout: &Layout, y: usize) -> usize {
@@ -296,6 +302,7 @@ struct Layout<'a> {
     base_y: usize,
     title_text: &'a str,
     web_server_height: usize,
+    current_session_height: usize,
     usage_height: usize,
     usage_width: usize,
 }
@@ -1,8 +1,14 @@
 use zellij_tile::prelude::*;
 
+const READ_ONLY_MARKER: &str = " [read-only]";
+const SHORT_READ_ONLY_MARKER: &str = " [ro]";
+const INTERACTIVE_MARKER: &str = " [interactive]";
+const SHORT_INTERACTIVE_MARKER: &str = " [int]";
+
 struct ScreenContent {
     title: (String, Text),
     items: Vec<Vec<Text>>,
+    details: Option<(String, Text)>,
     help: (String, Text),
     status_message: Option<(String, Text)>,
     max_width: usize,
@@ -13,6 +19,7 @@ struct Layout {
     base_x: usize,
     base_y: usize,
     title_x: usize,
+    details_y: usize,
     help_y: usize,
     status_y: usize,
 }
@@ -31,10 +38,13 @@ struct ColumnWidths {
 }
 
 pub struct TokenManagementScreen<'a> {
-    token_list: &'a Vec<(String, String)>,
+    token_list: &'a Vec<WebLoginToken>,
     selected_list_index: Option<usize>,
     renaming_token: &'a Option<String>,
     entering_new_token_name: &'a Option<String>,
+    new_token_scope: WebTokenScope,
+    editing_sessions: &'a Option<String>,
+    editing_expiry: &'a Option<String>,
     error: &'a Option<String>,
     info: &'a Option<String>,
     rows: usize,
@@ -43,10 +53,13 @@ pub struct TokenManagementScreen<'a> {
 
 impl<'a> TokenManagementScreen<'a> {
     pub fn new(
-        token_list: &'a Vec<(String, String)>,
+        token_list: &'a Vec<WebLoginToken>,
         selected_list_index: Option<usize>,
         renaming_token: &'a Option<String>,
         entering_new_token_name: &'a Option<String>,
+        new_token_scope: WebTokenScope,
+        editing_sessions: &'a Option<String>,
+        editing_expiry: &'a Option<String>,
         error: &'a Option<String>,
         info: &'a Option<String>,
         rows: usize,
@@ -57,6 +70,9 @@ impl<'a> TokenManagementScreen<'a> {
             selected_list_index,
             renaming_token,
             entering_new_token_name,
+            new_token_scope,
+            editing_sessions,
+            editing_expiry,
             error,
             info,
             rows,
@@ -149,6 +165,22 @@ impl<'a> TokenManagementScreen<'a> {
         format!("{}{}{}", start_part, truncator, end_part)
     }
 
+    fn format_token_name(&self, token: &str, scope: WebTokenScope, max_width: usize) -> String {
+        let (marker, short_marker) = match scope {
+            WebTokenScope::ReadOnly => (READ_ONLY_MARKER, SHORT_READ_ONLY_MARKER),
+            WebTokenScope::Interactive => (INTERACTIVE_MARKER, SHORT_INTERACTIVE_MARKER),
+            WebTokenScope::Admin => return self.truncate_token_name(token, max_width),
+        };
+        // prefer truncating the name over dropping the marker, limited tokens should stand out
+        let marker = if max_width >= marker.chars().count() * 2 {
+            marker
+        } else {
+            short_marker
+        };
+        let name_width = max_width.saturating_sub(marker.chars().count());
+        format!("{}{}", self.truncate_token_name(token, name_width), marker)
+    }
+
     fn format_date(
         &self,
         created_at: &str,
@@ -209,10 +241,11 @@ impl<'a> TokenManagementScreen<'a> {
         // - 1 row for title
         // - 1 row for spacing after title (always preserved)
         // - 1 row for the "create new token" line (always visible)
-        // - 1 row for spacing before help (always preserved)
+        // - 1 row for spacing before the details of the selected token (always preserved)
+        // - 1 row for the details of the selected token
         // - 1 row for help text (or status message - they're mutually exclusive)
 
-        let fixed_rows = 4; // title + spacing + help/status + spacing before help
+        let fixed_rows = 5; // title + spacing + spacing before details + details + help/status
         let create_new_token_rows = 1; // "create new token" line
 
         let total_fixed_rows = fixed_rows + create_new_token_rows;
@@ -234,10 +267,15 @@ impl<'a> TokenManagementScreen<'a> {
         max_width = std::cmp::max(max_width, title_text.len());
 
         let mut items = vec![];
-        for (i, (token, created_at)) in self.token_list.iter().enumerate() {
+        for (i, token) in self.token_list.iter().enumerate() {
             let is_selected = Some(i) == self.selected_list_index;
-            let (row_text, row_items) =
-                self.create_token_item(token, created_at, is_selected, &column_widths);
+            let (row_text, row_items) = self.create_token_item(
+                &token.name,
+                &token.created_at,
+                token.scope,
+                is_selected,
+                &column_widths,
+            );
             max_width = std::cmp::max(max_width, row_text.chars().count());
             items.push(row_items);
         }
@@ -245,6 +283,11 @@ impl<'a> TokenManagementScreen<'a> {
         let (new_token_text, new_token_row) = self.create_new_token_item(&column_widths);
         max_width = std::cmp::max(max_width, new_token_text.chars().count());
 
+        let details = self.create_details_line();
+        if let Some((ref text, _)) = details {
+            max_width = std::cmp::max(max_width, text.chars().count());
+        }
+
         let (help_text, help_line) = self.create_help_line();
         max_width = std::cmp::max(max_width, help_text.chars().count());
 
@@ -258,6 +301,7 @@ impl<'a> TokenManagementScreen<'a> {
         ScreenContent {
             title: (title_text.to_string(), title),
             items,
+            details,
             help: (help_text, help_line),
             status_message,
             max_width,
@@ -357,6 +401,7 @@ impl<'a> TokenManagementScreen<'a> {
         &self,
         token: &str,
         created_at: &str,
+        scope: WebTokenScope,
         is_selected: bool,
         column_widths: &ColumnWidths,
     ) -> (String, Vec<Text>) {
@@ -364,10 +409,10 @@ impl<'a> TokenManagementScreen<'a> {
             if let Some(new_name) = &self.renaming_token {
                 self.create_renaming_item(new_name, created_at, column_widths)
             } else {
-                self.create_selected_item(token, created_at, column_widths)
+                self.create_selected_item(token, created_at, scope, column_widths)
             }
         } else {
-            self.create_regular_item(token, created_at, column_widths)
+            self.create_regular_item(token, created_at, scope, column_widths)
         }
     }
 
@@ -401,9 +446,10 @@ impl<'a> TokenManagementScreen<'a> {
         &self,
         token: &str,
         created_at: &str,
+        scope: WebTokenScope,
         column_widths: &ColumnWidths,
     ) -> (String, Vec<Text>) {
-        let mut item_text = self.truncate_token_name(token, column_widths.token);
+        let mut item_text = self.format_token_name(token, scope, column_widths.token);
         if item_text.is_empty() {
             // otherwise the table gets messed up
             item_text.push(' ');
@@ -430,7 +476,7 @@ impl<'a> TokenManagementScreen<'a> {
         };
 
         let items = vec![
-            Text::new(&item_text).color_range(0, ..).selected(),
+            self.color_token_name(&item_text, scope).selected(),
             Text::new(&date_text).selected(),
             controls_colored,
         ];
@@ -445,9 +491,10 @@ impl<'a> TokenManagementScreen<'a> {
         &self,
         token: &str,
         created_at: &str,
+        scope: WebTokenScope,
         column_widths: &ColumnWidths,
     ) -> (String, Vec<Text>) {
-        let mut item_text = self.truncate_token_name(token, column_widths.token);
+        let mut item_text = self.format_token_name(token, scope, column_widths.token);
         if item_text.is_empty() {
             // otherwise the table gets messed up
             item_text.push(' ');
@@ -456,7 +503,7 @@ impl<'a> TokenManagementScreen<'a> {
         let controls_text = " ".repeat(column_widths.controls);
 
         let items = vec![
-            Text::new(&item_text).color_range(0, ..),
+            self.color_token_name(&item_text, scope),
             Text::new(&date_text),
             Text::new(&controls_text),
         ];
@@ -466,6 +513,22 @@ impl<'a> TokenManagementScreen<'a> {
         )
     }
 
+    fn color_token_name(&self, item_text: &str, scope: WebTokenScope) -> Text {
+        let marker_start = if scope != WebTokenScope::Admin {
+            item_text
+                .rfind(" [")
+                .map(|byte_index| item_text[..byte_index].chars().count())
+        } else {
+            None
+        };
+        match marker_start {
+            Some(marker_start) => Text::new(item_text)
+                .color_range(0, ..marker_start)
+                .color_range(1, marker_start..),
+            None => Text::new(item_text).color_range(0, ..),
+        }
+    }
+
     fn create_new_token_item(&self, column_widths: &ColumnWidths) -> (String, Vec<Text>) {
         let create_new_token_text = "<n> - create new token".to_string();
         let short_create_text = "<n> - new".to_string();
@@ -509,33 +572,80 @@ impl<'a> TokenManagementScreen<'a> {
     }
 
     fn create_help_line(&self) -> (String, Text) {
-        let (text, highlight_range) = if self.entering_new_token_name.is_some() {
-            (
-                "Help: Enter optional name for new token, <Enter> to submit",
-                41..=47,
-            )
+        let (text, highlight_ranges) = if self.entering_new_token_name.is_some() {
+            if self.new_token_scope.is_read_only() {
+                (
+                    "Help: Enter optional name for new read-only token, <Enter> to submit",
+                    vec![51..=57],
+                )
+            } else {
+                (
+                    "Help: Enter optional name for new token, <Enter> to submit",
+                    vec![41..=47],
+                )
+            }
         } else if self.renaming_token.is_some() {
             (
                 "Help: Enter new name for this token, <Enter> to submit",
-                39..=45,
+                vec![39..=45],
+            )
+        } else if self.editing_sessions.is_some() {
+            (
+                "Help: Comma separated session names or globs, empty for all, <Enter> to submit",
+                vec![63..=69],
+            )
+        } else if self.editing_expiry.is_some() {
+            (
+                "Help: Duration from now (eg. 12h, 7days), empty for never, <Enter> to submit",
+                vec![61..=67],
             )
         } else {
             (
-                "Help: <Ctrl x> - revoke all tokens, <Esc> - go back",
-                6..=13,
+                "Help: <v> - new read-only token, <Ctrl x> - revoke all tokens, <Esc> - go back",
+                vec![6..=8, 33..=40, 63..=67],
             )
         };
 
-        let mut help_line = Text::new(text).color_range(3, highlight_range);
-
-        // Add second highlight for the back option
-        if self.entering_new_token_name.is_none() && self.renaming_token.is_none() {
-            help_line = help_line.color_range(3, 36..=40);
+        let mut help_line = Text::new(text);
+        for highlight_range in highlight_ranges {
+            help_line = help_line.color_range(3, highlight_range);
         }
 
         (text.to_string(), help_line)
     }
 
+    fn create_details_line(&self) -> Option<(String, Text)> {
+        if let Some(sessions) = self.editing_sessions {
+            let text = format!("Allowed sessions: {}_", sessions);
+            return Some((text.clone(), Text::new(&text).color_range(3, 18..)));
+        }
+        if let Some(expiry) = self.editing_expiry {
+            let text = format!("Expires in: {}_", expiry);
+            return Some((text.clone(), Text::new(&text).color_range(3, 12..)));
+        }
+        let token = self
+            .selected_list_index
+            .and_then(|i| self.token_list.get(i))?;
+        let sessions = if token.allowed_sessions.is_empty() {
+            "all".to_owned()
+        } else {
+            token.allowed_sessions.join(", ")
+        };
+        let expires = token.expires_at.as_deref().unwrap_or("never");
+
+        let scope_part = format!("<s> scope: {}", token.scope);
+        let sessions_part = format!("<a> sessions: {}", sessions);
+        let expires_part = format!("<e> expires: {}", expires);
+        let sessions_start = scope_part.chars().count() + 2;
+        let expires_start = sessions_start + sessions_part.chars().count() + 2;
+        let text = format!("{}, {}, {}", scope_part, sessions_part, expires_part);
+        let details = Text::new(&text)
+            .color_range(3, 0..=2)
+            .color_range(3, sessions_start..=sessions_start + 2)
+            .color_range(3, expires_start..=expires_start + 2);
+        Some((text, details))
+    }
+
     fn create_status_message(&self) -> Option<(String, Text)> {
         if let Some(error) = &self.error {
             Some((error.clone(), Text::new(error).color_range(3, ..)))
@@ -552,10 +662,11 @@ impl<'a> TokenManagementScreen<'a> {
         // - 1 row for spacing after title (always preserved)
         // - token items (variable, potentially truncated)
         // - 1 row for "create new token" line
-        // - 1 row for spacing before help (always preserved)
+        // - 1 row for spacing before the details of the selected token (always preserved)
+        // - 1 row for the details of the selected token
         // - 1 row for help text OR status message (mutually exclusive now)
 
-        let fixed_ui_rows = 4; // title + spacing after title + spacing before help + help/status
+        let fixed_ui_rows = 5; // title + spacings + details + help/status
         let create_new_token_rows = 1;
         let token_item_rows = content.items.len();
 
@@ -573,12 +684,14 @@ impl<'a> TokenManagementScreen<'a> {
         // Calculate positions relative to base_y
         let item_start_y = base_y + 2; // title + spacing after title
         let new_token_y = item_start_y + token_item_rows;
-        let help_y = new_token_y + 1 + 1; // new token line + spacing before help
+        let details_y = new_token_y + 1 + 1; // new token line + spacing before details
+        let help_y = details_y + 1;
 
         Layout {
             base_x: (self.cols.saturating_sub(content.max_width) as f64 / 2.0).floor() as usize,
             base_y,
             title_x: self.cols.saturating_sub(content.title.0.len()) / 2,
+            details_y,
             help_y,
             status_y: help_y, // Status message uses the same position as help
         }
@@ -598,6 +711,10 @@ impl<'a> TokenManagementScreen<'a> {
 
         print_table_with_coordinates(table, layout.base_x, layout.base_y + 1, None, None);
 
+        if let Some((_, details)) = content.details {
+            print_text_with_coordinates(details, layout.base_x, layout.details_y, None, None);
+        }
+
         if let Some((_, status_text)) = content.status_message {
             print_text_with_coordinates(status_text, layout.base_x, layout.status_y, None, None);
         } else {
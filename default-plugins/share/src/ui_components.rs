@@ -14,7 +14,7 @@ pub const BULLETIN_2_SHORT: &str = "- Base URL + session name: attach or create"
 pub const BULLETIN_3_FULL: &str =
     "- By default sessions not started from the web must be explicitly shared";
 pub const BULLETIN_3_SHORT: &str = "- Sessions not started from the web must be explicitly shared";
-pub const BULLETIN_4: &str = "- <t> manage login tokens";
+pub const BULLETIN_4: &str = "- <t> manage login tokens, <l> view the audit log";
 
 pub const WEB_SERVER_TITLE: &str = "Web server: ";
 pub const WEB_SERVER_RUNNING: &str = "RUNNING ";
@@ -34,6 +34,7 @@ pub const SHARING_DISABLED: &str = "SHARING IS DISABLED";
 pub const NOT_SHARING: &str = "NOT SHARING";
 pub const PRESS_SPACE_SHARE: &str = "Press <SPACE> to share";
 pub const WEB_SERVER_OFFLINE: &str = "...but web server is offline";
+pub const CONNECTED_CLIENTS_TITLE: &str = "Connected: ";
 
 pub const COLOR_INDEX_0: usize = 0;
 pub const COLOR_INDEX_1: usize = 1;
@@ -244,8 +245,11 @@ impl Usage {
         let bulletin_2_text = Text::new(bulletin_2);
         let bulletin_3_text = Text::new(bulletin_3);
 
-        let bulletin_4_text =
-            create_highlighted_shortcut(self.bulletin_4, "<t>", COLOR_HIGHLIGHT).0;
+        let bulletin_4_text = ColoredTextBuilder::new(self.bulletin_4.to_string())
+            .highlight_substring("<t>", COLOR_HIGHLIGHT)
+            .highlight_substring("<l>", COLOR_HIGHLIGHT)
+            .build()
+            .0;
 
         let texts_and_positions = vec![
             (usage_title, y),
@@ -441,6 +445,7 @@ pub struct CurrentSessionSection {
     web_sharing: WebSharing,
     session_name: Option<String>,
     connection_is_unencrypted: bool,
+    connected_clients: (usize, usize), // (interactive, read_only)
     pub clickable_urls: HashMap<CoordinatesInLine, String>,
     pub currently_hovering_over_link: bool,
 }
@@ -453,6 +458,7 @@ impl CurrentSessionSection {
         session_name: Option<String>,
         web_sharing: WebSharing,
         connection_is_unencrypted: bool,
+        connected_clients: (usize, usize),
     ) -> Self {
         CurrentSessionSection {
             web_server_started,
@@ -463,9 +469,14 @@ impl CurrentSessionSection {
             clickable_urls: HashMap::new(),
             currently_hovering_over_link: false,
             connection_is_unencrypted,
+            connected_clients,
         }
     }
 
+    fn shows_connected_clients(&self) -> bool {
+        self.web_sharing.web_clients_allowed() && self.web_server_started
+    }
+
     pub fn current_session_status_width_and_height(&self) -> (usize, usize) {
         let mut max_len = self.get_session_status_line_length();
 
@@ -478,13 +489,15 @@ impl CurrentSessionSection {
                 max_len,
                 SESSION_URL_TITLE.chars().count() + url_display.chars().count(),
             );
+            max_len = std::cmp::max(max_len, self.render_connected_clients().1);
         } else if self.web_sharing.web_clients_allowed() {
             max_len = std::cmp::max(max_len, WEB_SERVER_OFFLINE.chars().count());
         } else {
             max_len = std::cmp::max(max_len, self.press_space_to_share().1);
         }
 
-        (max_len, 2)
+        let height = if self.shows_connected_clients() { 3 } else { 2 };
+        (max_len, height)
     }
 
     fn get_session_status_line_length(&self) -> usize {
@@ -518,6 +531,22 @@ impl CurrentSessionSection {
             let info_line = self.press_space_to_share().0;
             print_text_with_coordinates(info_line, x, y + 1, None, None);
         }
+
+        if self.shows_connected_clients() {
+            let connected_clients_line = self.render_connected_clients().0;
+            print_text_with_coordinates(connected_clients_line, x, y + 2, None, None);
+        }
+    }
+
+    fn render_connected_clients(&self) -> (Text, usize) {
+        let (interactive, read_only) = self.connected_clients;
+        let value = format!("{} interactive, {} read-only", interactive, read_only);
+        create_titled_text(
+            CONNECTED_CLIENTS_TITLE,
+            &value,
+            COLOR_INDEX_0,
+            COLOR_INDEX_1,
+        )
     }
 
     fn render_session_url(
@@ -666,15 +666,21 @@ fn render_secondary_info(
     let colored_elements = color_elements(help.style.colors, !supports_arrow_fonts);
     let secondary_keybinds = secondary_keybinds(&help, tab_info, max_len);
     secondary_info.append(&secondary_keybinds);
-    let remaining_space = max_len.saturating_sub(secondary_info.len).saturating_sub(1); // 1 for the end padding of the line
+    // 1 for the end padding of the line and 1 to keep it apart from the keybinds
+    let presence =
+        presence_list(help, max_len.saturating_sub(secondary_info.len + 2)).unwrap_or_default();
+    let remaining_space = max_len
+        .saturating_sub(secondary_info.len)
+        .saturating_sub(presence.len)
+        .saturating_sub(1); // 1 for the end padding of the line
     let mut padding = String::new();
     let mut padding_len = 0;
     for _ in 0..remaining_space {
         padding.push_str(&ANSIStrings(&[colored_elements.superkey_prefix.paint(" ")]).to_string());
         padding_len += 1;
     }
-    secondary_info.part = format!("{}{}", padding, secondary_info.part);
-    secondary_info.len += padding_len;
+    secondary_info.part = format!("{}{}{}", presence.part, padding, secondary_info.part);
+    secondary_info.len += presence.len + padding_len;
     if secondary_info.len <= max_len {
         Some(secondary_info)
     } else {
@@ -682,6 +688,59 @@ fn render_secondary_info(
     }
 }
 
+// the users connected to the session, each on their color (the one marking the panes they focus)
+fn presence_list(help: &ModeInfo, max_len: usize) -> Option<LinePart> {
+    if help.connected_users.len() < 2 {
+        return None;
+    }
+    let palette = help.style.colors;
+    let user_style = |user: &ConnectedUser| {
+        let style = match client_id_to_colors(user.client_id, palette.multiplayer_user_colors) {
+            Some((primary, secondary)) => Style::new()
+                .fg(palette_match!(secondary))
+                .on(palette_match!(primary)),
+            None => Style::new()
+                .fg(palette_match!(palette.ribbon_unselected.base))
+                .on(palette_match!(palette.ribbon_unselected.background)),
+        };
+        if user.is_current_client {
+            style.bold().underline()
+        } else {
+            style.bold()
+        }
+    };
+    let separator = Style::new()
+        .on(palette_match!(palette.text_unselected.background))
+        .paint(" ");
+
+    let mut names = vec![separator.clone()];
+    let mut names_len = 1;
+    let mut blocks = vec![separator.clone()];
+    let mut blocks_len = 1;
+    for user in &help.connected_users {
+        let name = format!(" {} ", user.display_name());
+        names_len += name.width() + 1;
+        names.push(user_style(user).paint(name));
+        names.push(separator.clone());
+        blocks_len += 2;
+        blocks.push(user_style(user).paint(" "));
+        blocks.push(separator.clone());
+    }
+    if names_len <= max_len {
+        Some(LinePart {
+            part: ANSIStrings(&names).to_string(),
+            len: names_len,
+        })
+    } else if blocks_len <= max_len {
+        Some(LinePart {
+            part: ANSIStrings(&blocks).to_string(),
+            len: blocks_len,
+        })
+    } else {
+        None
+    }
+}
+
 fn should_show_focus_and_resize_shortcuts(tab_info: Option<&TabInfo>) -> bool {
     let Some(tab_info) = tab_info else {
         return false;
@@ -1,8 +1,19 @@
-use dialoguer::Confirm;
-use std::{fs::File, io::prelude::*, path::PathBuf, process, time::Duration};
+use dialoguer::{Confirm, Input, Select};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{prelude::*, IsTerminal},
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
 
 #[cfg(feature = "web_server_capability")]
-use isahc::{config::RedirectPolicy, prelude::*, HttpClient, Request};
+use isahc::{
+    config::{Dialer, RedirectPolicy},
+    prelude::*,
+    HttpClient, Request,
+};
 
 use nix;
 use zellij_client::{
@@ -10,14 +21,16 @@ use zellij_client::{
         config_yaml_to_config_kdl, convert_old_yaml_files, layout_yaml_to_layout_kdl,
     },
     os_input_output::get_client_os_input,
-    start_client as start_client_impl, ClientInfo,
+    start_client as start_client_impl, start_headless_server as start_headless_server_impl,
+    ClientInfo,
 };
 use zellij_utils::sessions::{
     assert_dead_session, assert_session, assert_session_ne, delete_session as delete_session_impl,
-    generate_unique_session_name, get_active_session, get_resurrectable_sessions, get_sessions,
-    get_sessions_sorted_by_mtime, kill_session as kill_session_impl, match_session_name,
-    print_sessions, print_sessions_with_index, resurrection_layout, session_exists, ActiveSession,
-    SessionNameMatch,
+    export_session as export_session_impl, generate_unique_session_name, get_active_session,
+    get_resurrectable_sessions, get_sessions, get_sessions_in_group, get_sessions_sorted_by_mtime,
+    import_session as import_session_impl, kill_session as kill_session_impl, match_session_name,
+    print_sessions, print_sessions_with_index, resurrection_layout, session_exists,
+    session_snapshot_layout, set_session_tags, ActiveSession, SessionNameMatch,
 };
 
 #[cfg(feature = "web_server_capability")]
@@ -28,25 +41,29 @@ use zellij_utils::web_server_commands::shutdown_all_webserver_instances;
 
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_authentication_tokens::{
-    create_token, list_tokens, revoke_all_tokens, revoke_token,
+    create_token, list_tokens, revoke_all_tokens, revoke_token, TokenAccess,
 };
 
 use miette::{Report, Result};
 use zellij_server::{os_input_output::get_server_os_input, start_server as start_server_impl};
 use zellij_utils::{
-    cli::{CliArgs, Command, SessionCommand, Sessions},
-    data::{ConnectToSession, LayoutInfo},
+    cli::{CliArgs, Command, SessionCommand, SessionEventFormat, Sessions},
+    consts::{SWARM_TRUSTED_PROJECTS_CACHE, VERSION},
+    data::{ConnectToSession, LayoutInfo, SessionEventType, WebTokenScope},
     envs,
+    home::get_template_dir,
     input::{
         actions::Action,
         config::{Config, ConfigError},
         layout::Layout,
         options::Options,
+        session_template::session_template,
     },
+    project::Project,
     setup::{find_default_config_dir, get_layout_dir, Setup},
 };
 
-pub(crate) use zellij_utils::sessions::list_sessions;
+pub(crate) use zellij_utils::sessions::{list_session_snapshots, list_sessions};
 
 pub(crate) fn kill_all_sessions(yes: bool) {
     match get_sessions() {
@@ -125,6 +142,36 @@ pub(crate) fn kill_session(target_session: &Option<String>) {
     }
 }
 
+pub(crate) fn kill_sessions_in_group(group: &str, yes: bool) {
+    let sessions_in_group: Vec<String> = get_sessions_in_group(group)
+        .into_iter()
+        .filter(|(_, is_dead)| !is_dead)
+        .map(|(name, _)| name)
+        .collect();
+    if sessions_in_group.is_empty() {
+        eprintln!("No active swarm sessions found in group \"{}\".", group);
+        process::exit(1);
+    }
+    if !yes {
+        println!(
+            "WARNING: this action will kill the following sessions: {}",
+            sessions_in_group.join(", ")
+        );
+        if !Confirm::new()
+            .with_prompt("Do you want to continue?")
+            .interact()
+            .unwrap()
+        {
+            println!("Abort.");
+            process::exit(1);
+        }
+    }
+    for session in &sessions_in_group {
+        kill_session_impl(session);
+    }
+    process::exit(0);
+}
+
 pub(crate) fn delete_session(target_session: &Option<String>, force: bool) {
     match target_session {
         Some(target_session) => {
@@ -139,6 +186,34 @@ pub(crate) fn delete_session(target_session: &Option<String>, force: bool) {
     }
 }
 
+pub(crate) fn export_session(session_name: &str, output: &PathBuf) {
+    match export_session_impl(session_name, output) {
+        Ok(()) => println!(
+            "Exported session {:?} to {}",
+            session_name,
+            output.display()
+        ),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        },
+    }
+}
+
+pub(crate) fn import_session(bundle: &PathBuf, root: Option<PathBuf>, name: Option<String>) {
+    let root = root.map(|root| std::fs::canonicalize(&root).unwrap_or(root));
+    match import_session_impl(bundle, root, name) {
+        Ok(session_name) => println!(
+            "Imported session {:?}, attach to it with: swarm attach {}",
+            session_name, session_name
+        ),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        },
+    }
+}
+
 fn get_os_input<OsInputOutput>(
     fn_get_os_input: fn() -> Result<OsInputOutput, nix::Error>,
 ) -> OsInputOutput {
@@ -211,15 +286,27 @@ pub(crate) fn stop_web_server() -> Result<(), String> {
 }
 
 #[cfg(feature = "web_server_capability")]
-pub(crate) fn create_auth_token() -> Result<String, String> {
+pub(crate) fn create_auth_token(
+    scope: WebTokenScope,
+    allowed_sessions: Vec<String>,
+    expires_in: Option<Duration>,
+) -> Result<String, String> {
     // returns the token and it's name
-    create_token(None)
+    let access = TokenAccess {
+        scope,
+        allowed_sessions,
+    };
+    create_token(None, &access, expires_in)
         .map(|(token_name, token)| format!("{}: {}", token, token_name))
         .map_err(|e| e.to_string())
 }
 
 #[cfg(not(feature = "web_server_capability"))]
-pub(crate) fn create_auth_token() -> Result<String, String> {
+pub(crate) fn create_auth_token(
+    _scope: WebTokenScope,
+    _allowed_sessions: Vec<String>,
+    _expires_in: Option<Duration>,
+) -> Result<String, String> {
     log::error!(
         "This version of Swarm was compiled without web server support, cannot create auth token!"
     );
@@ -269,7 +356,15 @@ pub(crate) fn list_auth_tokens() -> Result<Vec<String>, String> {
         .map(|tokens| {
             let mut res = vec![];
             for t in tokens {
-                res.push(format!("{}: created at {}", t.name, t.created_at))
+                let mut line = format!("{}: created at {} ({}", t.name, t.created_at, t.scope);
+                if !t.allowed_sessions.is_empty() {
+                    line.push_str(&format!(", sessions: {}", t.allowed_sessions.join(", ")));
+                }
+                if let Some(expires_at) = t.expires_at {
+                    line.push_str(&format!(", expires at {}", expires_at));
+                }
+                line.push(')');
+                res.push(line);
             }
             res
         })
@@ -287,14 +382,32 @@ pub(crate) fn list_auth_tokens() -> Result<Vec<String>, String> {
     std::process::exit(2);
 }
 
+pub(crate) fn web_audit_log() -> Result<Vec<String>, String> {
+    // returns the audit log line by line, oldest first
+    zellij_utils::web_audit_log::read_audit_log()
+        .map(|events| {
+            events
+                .iter()
+                .map(|e| format!("{} {}", e.formatted_timestamp(), e.describe()))
+                .collect()
+        })
+        .map_err(|e| e.to_string())
+}
+
 #[cfg(feature = "web_server_capability")]
-pub(crate) fn web_server_status(web_server_base_url: &str) -> Result<String, String> {
+pub(crate) fn web_server_status(
+    web_server_base_url: &str,
+    web_server_socket: Option<&Path>,
+) -> Result<String, String> {
     let http_client = HttpClient::builder()
         // TODO: timeout?
         .redirect_policy(RedirectPolicy::Follow)
         .build()
         .map_err(|e| e.to_string())?;
-    let request = Request::get(format!("{}/info/version", web_server_base_url,));
+    let mut request = Request::get(format!("{}/info/version", web_server_base_url,));
+    if let Some(web_server_socket) = web_server_socket {
+        request = request.dial(Dialer::unix_socket(web_server_socket));
+    }
     let req = request.body(()).map_err(|e| e.to_string())?;
     let mut res = http_client.send(req).map_err(|e| e.to_string())?;
     let status_code = res.status();
@@ -310,7 +423,10 @@ pub(crate) fn web_server_status(web_server_base_url: &str) -> Result<String, Str
 }
 
 #[cfg(not(feature = "web_server_capability"))]
-pub(crate) fn web_server_status(_web_server_base_url: &str) -> Result<String, String> {
+pub(crate) fn web_server_status(
+    _web_server_base_url: &str,
+    _web_server_socket: Option<&Path>,
+) -> Result<String, String> {
     log::error!(
         "This version of Swarm was compiled without web server support, cannot get web server status!"
     );
@@ -320,6 +436,20 @@ pub(crate) fn web_server_status(_web_server_base_url: &str) -> Result<String, St
     std::process::exit(2);
 }
 
+pub(crate) fn print_web_server_status_as_json(
+    web_server_version: Option<String>,
+    web_server_base_url: &str,
+) {
+    let web_server_version = web_server_version.map(|v| v.trim().to_owned());
+    let status = serde_json::json!({
+        "online": web_server_version.is_some(),
+        "url": web_server_base_url,
+        "matches_current_version": web_server_version.as_deref() == Some(VERSION),
+        "version": web_server_version,
+    });
+    println!("{}", status);
+}
+
 fn find_indexed_session(
     sessions: Vec<String>,
     config_options: Options,
@@ -347,7 +477,41 @@ pub(crate) fn send_action_to_session(
     cli_action: zellij_utils::cli::CliAction,
     requested_session_name: Option<String>,
     config: Option<Config>,
+    json: bool,
 ) {
+    let session_name = session_for_cli_client(requested_session_name);
+    attach_with_cli_client(cli_action, &session_name, config, json);
+}
+
+/// Blocks until the command pane exits, exiting with its exit status
+pub(crate) fn wait_for_pane_exit(requested_session_name: Option<String>, pane_id: u32) {
+    let session_name = session_for_cli_client(requested_session_name);
+    let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
+    zellij_client::cli_client::start_cli_client(
+        Box::new(os_input),
+        &session_name,
+        vec![Action::WaitForPaneExit(pane_id)],
+        false,
+    );
+}
+
+/// Prints the events of the session as they happen, until it exits
+pub(crate) fn subscribe_to_session_events(
+    requested_session_name: Option<String>,
+    event_types: Vec<SessionEventType>,
+    format: SessionEventFormat,
+) {
+    let session_name = session_for_cli_client(requested_session_name);
+    let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
+    zellij_client::cli_client::start_session_event_stream(
+        Box::new(os_input),
+        &session_name,
+        event_types,
+        format,
+    );
+}
+
+fn session_for_cli_client(requested_session_name: Option<String>) -> String {
     match get_active_session() {
         ActiveSession::None => {
             eprintln!("There is no active session!");
@@ -364,7 +528,7 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             }
-            attach_with_cli_client(cli_action, &session_name, config);
+            session_name
         },
         ActiveSession::Many => {
             let existing_sessions: Vec<String> = get_sessions()
@@ -374,25 +538,26 @@ pub(crate) fn send_action_to_session(
                 .collect();
             if let Some(session_name) = requested_session_name {
                 if existing_sessions.contains(&session_name) {
-                    attach_with_cli_client(cli_action, &session_name, config);
+                    session_name
                 } else {
                     eprintln!(
                         "Session '{}' not found. The following sessions are active:",
                         session_name
                     );
-                    list_sessions(false, false, true);
+                    list_sessions(false, false, true, None, false);
                     std::process::exit(1);
                 }
             } else if let Ok(session_name) = envs::get_session_name() {
-                attach_with_cli_client(cli_action, &session_name, config);
+                session_name
             } else {
                 eprintln!("Please specify the session name to send actions to. The following sessions are active:");
-                list_sessions(false, false, true);
+                list_sessions(false, false, true, None, false);
                 std::process::exit(1);
             }
         },
-    };
+    }
 }
+
 pub(crate) fn convert_old_config_file(old_config_file: PathBuf) {
     match File::open(&old_config_file) {
         Ok(mut handle) => {
@@ -466,12 +631,18 @@ fn attach_with_cli_client(
     cli_action: zellij_utils::cli::CliAction,
     session_name: &str,
     config: Option<Config>,
+    json: bool,
 ) {
     let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
     let get_current_dir = || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
     match Action::actions_from_cli(cli_action, Box::new(get_current_dir), config) {
         Ok(actions) => {
-            zellij_client::cli_client::start_cli_client(Box::new(os_input), session_name, actions);
+            zellij_client::cli_client::start_cli_client(
+                Box::new(os_input),
+                session_name,
+                actions,
+                json,
+            );
             std::process::exit(0);
         },
         Err(e) => {
@@ -548,13 +719,39 @@ fn attach_with_session_name(
             ActiveSession::One(session_name) => ClientInfo::Attach(session_name, config_options),
             ActiveSession::Many => {
                 println!("Please specify the session to attach to, either by using the full name or a unique prefix.\nThe following sessions are active:");
-                list_sessions(false, false, true);
+                list_sessions(false, false, true, None, false);
                 process::exit(1);
             },
         },
     }
 }
 
+fn attach_from_snapshot(
+    session_name: Option<String>,
+    snapshot_name: &str,
+    force_run_commands: bool,
+) -> ClientInfo {
+    match session_snapshot_layout(session_name.as_deref(), snapshot_name) {
+        Ok((session_name, mut snapshot_layout)) => {
+            if session_exists(&session_name).unwrap_or(false) {
+                eprintln!(
+                    "Session \"{}\" is running, please kill it before restoring it from a snapshot.",
+                    session_name
+                );
+                process::exit(1);
+            }
+            if force_run_commands {
+                snapshot_layout.recursively_add_start_suspended(Some(false));
+            }
+            ClientInfo::Resurrect(session_name, snapshot_layout)
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        },
+    }
+}
+
 pub(crate) fn start_client(opts: CliArgs) {
     // look for old YAML config/layout/theme files and convert them to KDL
     convert_old_yaml_files(&opts);
@@ -599,13 +796,24 @@ pub(crate) fn start_client(opts: CliArgs) {
                 &opts,
             );
             if reconnect_to_session.name.is_some() {
+                // read-only clients stay read-only when switching sessions
+                let previously_read_only = matches!(
+                    opts.command,
+                    Some(Command::Sessions(Sessions::Attach {
+                        read_only: true,
+                        ..
+                    }))
+                );
                 opts.command = Some(Command::Sessions(Sessions::Attach {
                     session_name: reconnect_to_session.name.clone(),
-                    create: true,
+                    create: !previously_read_only,
                     create_background: false,
                     force_run_commands: false,
                     index: None,
                     options: None,
+                    from_snapshot: None,
+                    group: None,
+                    read_only: previously_read_only,
                 }));
             } else {
                 opts.command = None;
@@ -627,10 +835,14 @@ pub(crate) fn start_client(opts: CliArgs) {
                         Some(&PathBuf::from(layout_name)),
                         layout_dir.clone(),
                         config_without_layout.clone(),
+                        BTreeMap::new(),
                     ),
-                    LayoutInfo::Url(url) => Layout::from_url(&url, config_without_layout.clone()),
+                    LayoutInfo::Url(url) => {
+                        Layout::from_url(&url, config_without_layout.clone(), BTreeMap::new())
+                    },
                     LayoutInfo::Stringified(stringified_layout) => Layout::from_stringified_layout(
                         &stringified_layout,
+                        layout_dir.clone(),
                         config_without_layout.clone(),
                     ),
                 };
@@ -675,6 +887,9 @@ pub(crate) fn start_client(opts: CliArgs) {
             force_run_commands,
             index,
             options,
+            from_snapshot,
+            group: _,
+            read_only,
         })) = opts.command.clone()
         {
             let config_options = match options.as_deref() {
@@ -685,7 +900,9 @@ pub(crate) fn start_client(opts: CliArgs) {
             };
             should_create_detached = create_background;
 
-            let client = if let Some(idx) = index {
+            let client = if let Some(snapshot_name) = from_snapshot {
+                attach_from_snapshot(session_name, &snapshot_name, force_run_commands)
+            } else if let Some(idx) = index {
                 attach_with_session_index(
                     config_options.clone(),
                     idx,
@@ -696,8 +913,17 @@ pub(crate) fn start_client(opts: CliArgs) {
                     .as_ref()
                     .and_then(|s| session_exists(&s).ok())
                     .unwrap_or(false);
-                let resurrection_layout =
-                    session_name.as_ref().and_then(|s| resurrection_layout(&s));
+                // when switching sessions with an explicit layout (eg. restoring a snapshot from the
+                // session-manager), the layout takes precedence over the resurrection layout
+                let switched_with_layout = reconnect_to_session
+                    .as_ref()
+                    .map(|r| r.layout.is_some())
+                    .unwrap_or(false);
+                let resurrection_layout = if switched_with_layout {
+                    None
+                } else {
+                    session_name.as_ref().and_then(|s| resurrection_layout(&s))
+                };
                 if (create || should_create_detached)
                     && !session_exists
                     && resurrection_layout.is_none()
@@ -719,6 +945,14 @@ pub(crate) fn start_client(opts: CliArgs) {
                 }
             };
 
+            if read_only && !matches!(client, ClientInfo::Attach(..)) {
+                eprintln!(
+                    "Session \"{}\" is not running, read-only clients can only attach to running sessions.",
+                    client.get_session_name()
+                );
+                process::exit(1);
+            }
+
             if let Ok(val) = std::env::var(envs::SESSION_NAME_ENV_KEY) {
                 if val == *client.get_session_name() {
                     panic!("You are trying to attach to the current session (\"{}\"). This is not supported.", val);
@@ -854,6 +1088,261 @@ pub(crate) fn start_client(opts: CliArgs) {
     }
 }
 
+/// Starts a session in the background without attaching to it, printing its name once its layout
+/// is applied
+pub(crate) fn start_headless_session(mut opts: CliArgs) {
+    convert_old_yaml_files(&opts);
+    let (config, layout, config_options, _config_without_layout, _config_options_without_layout) =
+        match Setup::from_cli_args(&opts) {
+            Ok(results) => results,
+            Err(e) => {
+                if let ConfigError::KdlError(error) = e {
+                    let report: Report = error.into();
+                    eprintln!("{:?}", report);
+                } else {
+                    eprintln!("{}", e);
+                }
+                process::exit(1);
+            },
+        };
+    let session_name = opts
+        .session
+        .clone()
+        .or_else(|| config_options.session_name.clone())
+        .unwrap_or_else(generate_unique_session_name_or_exit);
+    assert_session_ne(&session_name);
+    opts.session = Some(session_name.clone());
+    let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
+    match start_headless_server_impl(
+        Box::new(os_input),
+        opts,
+        config,
+        config_options,
+        ClientInfo::New(session_name.clone()),
+        Some(layout),
+    ) {
+        Ok(()) => println!("{}", session_name),
+        Err(e) => {
+            eprintln!("Failed to start session \"{}\": {}", session_name, e);
+            process::exit(1);
+        },
+    }
+}
+
+pub(crate) fn new_session(mut opts: CliArgs) {
+    if let Some(Command::Sessions(Sessions::New {
+        session_name,
+        template,
+        args,
+        tags,
+    })) = opts.command.clone()
+    {
+        if let Some(session_name) = session_name.as_ref() {
+            assert_session_ne(session_name);
+        }
+        let args = match template.as_ref() {
+            Some(template_name) => prompt_for_template_args(&opts, template_name, args),
+            None => args,
+        };
+        // the tags are written before the session starts so that it reports them from the start
+        let session_name = if tags.is_empty() {
+            session_name
+        } else {
+            let session_name = session_name.unwrap_or_else(generate_unique_session_name_or_exit);
+            if let Err(e) = set_session_tags(&session_name, &tags) {
+                eprintln!("Failed to tag session \"{}\": {}", session_name, e);
+                process::exit(1);
+            }
+            Some(session_name)
+        };
+        opts.session = session_name.clone();
+        opts.command = Some(Command::Sessions(Sessions::New {
+            session_name,
+            template,
+            args,
+            tags,
+        }));
+    }
+    start_client(opts);
+}
+
+pub(crate) fn attach_to_session_in_group(mut opts: CliArgs, group: &str) {
+    let sessions_in_group = get_sessions_in_group(group);
+    let session_name = match sessions_in_group.as_slice() {
+        [] => {
+            eprintln!("No swarm sessions found in group \"{}\".", group);
+            process::exit(1);
+        },
+        [(session_name, _)] => session_name.clone(),
+        sessions_in_group => {
+            let items: Vec<String> = sessions_in_group
+                .iter()
+                .map(|(name, is_dead)| {
+                    if *is_dead {
+                        format!("{} (EXITED - attach to resurrect)", name)
+                    } else {
+                        name.clone()
+                    }
+                })
+                .collect();
+            let selection = Select::new()
+                .with_prompt(format!("Sessions in group \"{}\"", group))
+                .items(&items)
+                .default(0)
+                .interact_opt()
+                .unwrap();
+            match selection {
+                Some(index) => sessions_in_group[index].0.clone(),
+                None => {
+                    println!("Abort.");
+                    process::exit(1);
+                },
+            }
+        },
+    };
+    if let Some(Command::Sessions(Sessions::Attach {
+        session_name: attach_to,
+        group,
+        ..
+    })) = opts.command.as_mut()
+    {
+        *attach_to = Some(session_name);
+        *group = None;
+    }
+    start_client(opts);
+}
+
+pub(crate) fn start_project_session(mut opts: CliArgs, project: Project) {
+    let session_name = project.session_name.clone();
+    if envs::get_session_name().ok().as_ref() == Some(&session_name) {
+        eprintln!(
+            "You are already in the session of this project (\"{}\"), use --no-project to start a new one.",
+            session_name
+        );
+        process::exit(1);
+    }
+    let session_is_running = session_exists(&session_name).unwrap_or(false);
+    if session_is_running || resurrection_layout(&session_name).is_some() {
+        // resurrected command panes wait for the user before running, so there is nothing to trust
+        opts.command = Some(Command::Sessions(Sessions::Attach {
+            session_name: Some(session_name),
+            create: true,
+            create_background: false,
+            force_run_commands: false,
+            index: None,
+            options: None,
+            from_snapshot: None,
+            group: None,
+            read_only: false,
+        }));
+        start_client(opts);
+        return;
+    }
+    let trusted = !project.runs_commands()
+        || project.is_trusted(&SWARM_TRUSTED_PROJECTS_CACHE)
+        || prompt_to_trust_project(&project);
+    if trusted {
+        for (key, value) in &project.env {
+            std::env::set_var(key, value);
+        }
+        opts.layout = project.layout.clone();
+    } else {
+        println!("Starting the project session without the project layout and env.");
+    }
+    // the session is rooted at the project rather than wherever inside it swarm was started
+    if let Err(e) = std::env::set_current_dir(&project.root) {
+        log::error!("Failed to change to the project root: {}", e);
+    }
+    opts.session = Some(session_name);
+    start_client(opts);
+}
+
+fn prompt_to_trust_project(project: &Project) -> bool {
+    println!("The project in {} defines:", project.root.display());
+    if let Some(layout) = project.layout.as_ref() {
+        println!(
+            "  - a layout ({}), which can run commands",
+            layout.display()
+        );
+    }
+    if !project.env.is_empty() {
+        let env_keys: Vec<&str> = project.env.keys().map(|k| k.as_str()).collect();
+        println!("  - environment variables: {}", env_keys.join(", "));
+    }
+    let trusted = Confirm::new()
+        .with_prompt("Do you trust this project?")
+        .default(false)
+        .interact()
+        .unwrap_or(false);
+    if trusted {
+        if let Err(e) = project.trust(&SWARM_TRUSTED_PROJECTS_CACHE) {
+            eprintln!("Failed to remember trusting this project: {}", e);
+        }
+    }
+    trusted
+}
+
+fn prompt_for_template_args(
+    opts: &CliArgs,
+    template_name: &str,
+    mut args: Vec<(String, String)>,
+) -> Vec<(String, String)> {
+    let config_dir = opts.config_dir.clone().or_else(find_default_config_dir);
+    let layout_dir = Config::try_from(opts)
+        .ok()
+        .and_then(|config| config.options.layout_dir)
+        .or_else(|| get_layout_dir(config_dir.clone()));
+    let template_file =
+        match session_template(template_name, get_template_dir(config_dir), layout_dir) {
+            Ok(template_file) => template_file,
+            Err(e) => {
+                if let ConfigError::KdlError(error) = e {
+                    let report: Report = error.into();
+                    eprintln!("{:?}", report);
+                } else {
+                    eprintln!("{}", e);
+                }
+                process::exit(1);
+            },
+        };
+    let missing_params: Vec<_> = template_file
+        .template
+        .params
+        .iter()
+        .filter(|param| param.is_required() && !args.iter().any(|(name, _)| name == &param.name))
+        .collect();
+    if missing_params.is_empty() {
+        return args;
+    }
+    let exit_with_missing_params = |args: &[(String, String)]| -> ! {
+        let missing_names: Vec<&str> = missing_params
+            .iter()
+            .map(|param| param.name.as_str())
+            .filter(|param_name| !args.iter().any(|(name, _)| name == param_name))
+            .collect();
+        eprintln!(
+            "Missing values for the parameters of the \"{}\" template: {} (set them with --arg NAME=VALUE)",
+            template_name,
+            missing_names.join(", ")
+        );
+        process::exit(1);
+    };
+    if !std::io::stdin().is_terminal() {
+        exit_with_missing_params(&args);
+    }
+    for param in missing_params.iter() {
+        let prompt = match param.description.as_ref() {
+            Some(description) => format!("{} ({})", param.name, description),
+            None => param.name.clone(),
+        };
+        match Input::<String>::new().with_prompt(prompt).interact_text() {
+            Ok(value) => args.push((param.name.clone(), value)),
+            Err(_) => exit_with_missing_params(&args),
+        }
+    }
+    args
+}
+
 fn generate_unique_session_name_or_exit() -> String {
     let Some(unique_session_name) = generate_unique_session_name() else {
         eprintln!("Failed to generate a unique session name, giving up");
@@ -876,8 +1365,18 @@ pub(crate) fn list_aliases(opts: CliArgs) {
                 process::exit(1);
             },
         };
-    for alias in config.plugins.list() {
-        println!("{}", alias);
+    if opts.json {
+        match serde_json::to_string(&config.plugins.list()) {
+            Ok(aliases) => println!("{}", aliases),
+            Err(e) => {
+                eprintln!("Failed to serialize plugin aliases: {}", e);
+                process::exit(1);
+            },
+        }
+    } else {
+        for alias in config.plugins.list() {
+            println!("{}", alias);
+        }
     }
     process::exit(0);
 }
@@ -12,6 +12,7 @@ use zellij_utils::{
     envs,
     input::config::Config,
     logging::*,
+    project::Project,
     setup::Setup,
     shared::web_server_base_url_from_config,
 };
@@ -50,7 +51,7 @@ fn main() {
     {
         let config = Config::try_from(&opts).ok();
         if let Some(Command::Sessions(Sessions::Action(cli_action))) = opts.command {
-            commands::send_action_to_session(cli_action, opts.session, config);
+            commands::send_action_to_session(cli_action, opts.session, config, opts.json);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Run {
@@ -91,7 +92,7 @@ fn main() {
                 pinned,
                 stacked,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, opts.session, config, opts.json);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Plugin {
@@ -128,7 +129,7 @@ fn main() {
                 pinned,
                 stacked,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, opts.session, config, opts.json);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Edit {
@@ -165,7 +166,7 @@ fn main() {
                 height,
                 pinned,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, opts.session, config, opts.json);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::ConvertConfig { old_config_file })) = opts.command {
@@ -202,7 +203,7 @@ fn main() {
                 plugin_cwd: None,
                 plugin_title: None,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, opts.session, config, opts.json);
             std::process::exit(0);
         }
     }
@@ -211,28 +212,83 @@ fn main() {
         no_formatting,
         short,
         reverse,
+        ref group,
     })) = opts.command
     {
-        commands::list_sessions(no_formatting, short, reverse);
+        commands::list_sessions(no_formatting, short, reverse, group.clone(), opts.json);
     } else if let Some(Command::Sessions(Sessions::ListAliases)) = opts.command {
         commands::list_aliases(opts);
+    } else if let Some(Command::Sessions(Sessions::ListSnapshots {
+        ref session_name,
+        no_formatting,
+    })) = opts.command
+    {
+        commands::list_session_snapshots(session_name.clone(), no_formatting, opts.json);
     } else if let Some(Command::Sessions(Sessions::KillAllSessions { yes })) = opts.command {
         commands::kill_all_sessions(yes);
-    } else if let Some(Command::Sessions(Sessions::KillSession { ref target_session })) =
-        opts.command
+    } else if let Some(Command::Sessions(Sessions::KillSession {
+        ref target_session,
+        ref group,
+        yes,
+    })) = opts.command
     {
-        commands::kill_session(target_session);
+        match group {
+            Some(group) => commands::kill_sessions_in_group(group, yes),
+            None => commands::kill_session(target_session),
+        }
     } else if let Some(Command::Sessions(Sessions::DeleteAllSessions { yes, force })) = opts.command
     {
         commands::delete_all_sessions(yes, force);
+    } else if let Some(Command::Sessions(Sessions::ExportSession {
+        ref session_name,
+        ref output,
+    })) = opts.command
+    {
+        commands::export_session(session_name, output);
+    } else if let Some(Command::Sessions(Sessions::ImportSession {
+        ref bundle,
+        ref root,
+        ref name,
+    })) = opts.command
+    {
+        commands::import_session(bundle, root.clone(), name.clone());
+    } else if let Some(Command::Sessions(Sessions::New { .. })) = opts.command {
+        commands::new_session(opts);
+    } else if let Some(Command::Sessions(Sessions::Attach {
+        group: Some(ref group),
+        ..
+    })) = opts.command
+    {
+        let group = group.clone();
+        commands::attach_to_session_in_group(opts, &group);
     } else if let Some(Command::Sessions(Sessions::DeleteSession {
         ref target_session,
         force,
     })) = opts.command
     {
         commands::delete_session(target_session, force);
+    } else if let Some(Command::Sessions(Sessions::Wait {
+        ref session,
+        pane_id,
+        ..
+    })) = opts.command
+    {
+        commands::wait_for_pane_exit(session.clone().or(opts.session.clone()), pane_id);
+    } else if let Some(Command::Sessions(Sessions::Subscribe {
+        ref session,
+        ref events,
+        format,
+    })) = opts.command
+    {
+        commands::subscribe_to_session_events(
+            session.clone().or(opts.session.clone()),
+            events.clone(),
+            format,
+        );
     } else if let Some(path) = opts.server {
         commands::start_server(path, opts.debug);
+    } else if opts.headless {
+        commands::start_headless_session(opts);
     } else if let Some(layout) = &opts.layout {
         if let Some(session_name) = opts
             .session
@@ -245,10 +301,16 @@ fn main() {
             let new_layout_cli_action = CliAction::NewTab {
                 layout: Some(layout.clone()),
                 layout_dir: options.as_ref().and_then(|o| o.layout_dir.clone()),
+                layout_args: opts.layout_args.clone(),
                 name: None,
                 cwd: options.as_ref().and_then(|o| o.default_cwd.clone()),
             };
-            commands::send_action_to_session(new_layout_cli_action, Some(session_name), config);
+            commands::send_action_to_session(
+                new_layout_cli_action,
+                Some(session_name),
+                config,
+                opts.json,
+            );
         } else {
             commands::start_client(opts);
         }
@@ -274,8 +336,17 @@ fn main() {
         } else if web_opts.status {
             let config_options = commands::get_config_options_from_cli_args(&opts)
                 .expect("Can't find config options");
+            let web_server_socket = config_options.web_server_socket.clone();
             let web_server_base_url = web_server_base_url_from_config(config_options);
-            match commands::web_server_status(&web_server_base_url) {
+            let web_server_status =
+                commands::web_server_status(&web_server_base_url, web_server_socket.as_deref());
+            match web_server_status {
+                _ if opts.json => {
+                    commands::print_web_server_status_as_json(
+                        web_server_status.ok(),
+                        &web_server_base_url,
+                    );
+                },
                 Ok(version) => {
                     let version = version.trim();
                     println!(
@@ -297,9 +368,14 @@ fn main() {
                 },
             }
         } else if web_opts.create_token {
-            match commands::create_auth_token() {
+            let scope = web_opts.token_scope();
+            match commands::create_auth_token(
+                scope,
+                web_opts.allowed_sessions.clone(),
+                web_opts.expires_in,
+            ) {
                 Ok(token_and_name) => {
-                    println!("Created token successfully");
+                    println!("Created {} token successfully", scope);
                     println!("");
                     println!("{}", token_and_name);
                 },
@@ -345,14 +421,35 @@ fn main() {
                     std::process::exit(2)
                 },
             }
+        } else if web_opts.audit_log {
+            match commands::web_audit_log() {
+                Ok(lines) => {
+                    for line in lines {
+                        println!("{}", line);
+                    }
+                },
+                Err(e) => {
+                    eprintln!("Failed to read the audit log: {}", e);
+                    std::process::exit(2)
+                },
+            }
         }
     } else {
         // Check if this is a default startup (no specific session, layout, etc.)
         let should_show_intro = opts.session.is_none() 
             && opts.layout.is_none() 
             && opts.new_session_with_layout.is_none();
-            
-        if should_show_intro {
+        let project = if should_show_intro && !opts.no_project {
+            std::env::current_dir()
+                .ok()
+                .and_then(|cwd| Project::find(&cwd))
+        } else {
+            None
+        };
+
+        if let Some(project) = project {
+            commands::start_project_session(opts, project);
+        } else if should_show_intro {
             // Show native intro screen
             let mut intro_app = IntroApp::new();
             match intro_app.run() {
@@ -2,7 +2,7 @@
  * Authentication logic and token management
  */
 
-import { is_https } from './utils.js';
+import { base_path, is_https } from './utils.js';
 
 /**
  * Wait for user to provide a security token
@@ -36,7 +36,7 @@ export async function getClientId(token, rememberMe, hasAuthenticationCookie) {
     let url_prefix = is_https() ? "https" : "http";
     
     if (!hasAuthenticationCookie) {
-        let login_res = await fetch(`${url_prefix}://${window.location.host}/command/login`, {
+        let login_res = await fetch(`${url_prefix}://${window.location.host}${base_path()}/command/login`, {
             method: 'POST',
             headers: {
                 'Content-Type': 'application/json',
@@ -57,7 +57,7 @@ export async function getClientId(token, rememberMe, hasAuthenticationCookie) {
         }
     }
     
-    let data = await fetch(`${url_prefix}://${window.location.host}/session`, {
+    let data = await fetch(`${url_prefix}://${window.location.host}${base_path()}/session`, {
         method: "POST",
         headers: {
             'Content-Type': 'application/json',
@@ -2,7 +2,7 @@
  * Connection-related utility functions and management
  */
 
-import { is_https } from './utils.js';
+import { base_path, is_https } from './utils.js';
 
 // Connection state
 let reconnectionAttempt = 0;
@@ -28,7 +28,7 @@ export function getReconnectionDelay(attempt) {
 export async function checkConnection() {
     try {
         let url_prefix = is_https() ? "https" : "http";
-        const response = await fetch(`${url_prefix}://${window.location.host}/info/version`, {
+        const response = await fetch(`${url_prefix}://${window.location.host}${base_path()}/info/version`, {
             method: 'GET',
             timeout: 5000
         });
@@ -0,0 +1,68 @@
+/**
+ * File transfer between the browser and the host: files dropped onto the terminal are uploaded
+ * into the cwd of the focused pane, files sent with `swarm action send-file` are downloaded
+ */
+
+import { base_path } from './utils.js';
+
+/**
+ * Upload files dropped anywhere on the page
+ * @param {string} webClientId - Client ID from authentication
+ */
+export function initFileUploads(webClientId) {
+    document.addEventListener("dragover", (event) => {
+        if (event.dataTransfer?.types.includes("Files")) {
+            event.preventDefault();
+            event.dataTransfer.dropEffect = "copy";
+        }
+    });
+    document.addEventListener("drop", (event) => {
+        if (!event.dataTransfer?.files.length) {
+            return;
+        }
+        event.preventDefault();
+        for (const file of event.dataTransfer.files) {
+            uploadFile(webClientId, file);
+        }
+    });
+}
+
+/**
+ * @param {string} webClientId - Client ID from authentication
+ * @param {File} file - The file to upload
+ */
+async function uploadFile(webClientId, file) {
+    const query = `web_client_id=${encodeURIComponent(webClientId)}&file_name=${encodeURIComponent(file.name)}`;
+    try {
+        const response = await fetch(`${base_path()}/upload?${query}`, {
+            method: "POST",
+            credentials: "include",
+            body: file,
+        });
+        if (response.status === 413) {
+            console.error(`Failed to upload ${file.name}: file is too large`);
+        } else if (response.status === 403) {
+            console.error(`Failed to upload ${file.name}: this login does not allow uploads`);
+        } else if (!response.ok) {
+            console.error(`Failed to upload ${file.name}: ${response.status}`);
+        }
+    } catch (error) {
+        console.error(`Failed to upload ${file.name}:`, error);
+    }
+}
+
+/**
+ * Download a file the session offered to this client
+ * @param {string} webClientId - Client ID from authentication
+ * @param {string} downloadId - Id of the offered download, valid once
+ * @param {string} fileName - Name to save the file under
+ */
+export function downloadFile(webClientId, downloadId, fileName) {
+    const link = document.createElement("a");
+    link.href = `${base_path()}/download/${encodeURIComponent(downloadId)}?web_client_id=${encodeURIComponent(webClientId)}`;
+    link.download = fileName;
+    link.style.display = "none";
+    document.body.appendChild(link);
+    link.click();
+    link.remove();
+}
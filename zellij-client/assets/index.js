@@ -3,6 +3,8 @@ import { initAuthentication } from './auth.js';
 import { initTerminal } from './terminal.js';
 import { setupInputHandlers } from './input.js';
 import { initWebSockets } from './websockets.js';
+import { initFileUploads } from './files.js';
+import { parse_location_path } from './utils.js';
 
 document.addEventListener("DOMContentLoaded", async (event) => {
     initConnectionHandlers();
@@ -10,15 +12,16 @@ document.addEventListener("DOMContentLoaded", async (event) => {
     const webClientId = await initAuthentication();
 
     const { term, fitAddon } = initTerminal();
-    const sessionName = location.pathname.split("/").pop();
+    const { sessionName, deepLink } = parse_location_path();
 
     let sendAnsiKey = (ansiKey) => {
         // This will be replaced by the WebSocket module
     };
     
     setupInputHandlers(term, sendAnsiKey);
+    initFileUploads(webClientId);
 
-    const websockets = initWebSockets(webClientId, sessionName, term, fitAddon, sendAnsiKey);
+    const websockets = initWebSockets(webClientId, sessionName, deepLink, term, fitAddon, sendAnsiKey);
     
     // Update sendAnsiKey to use the actual WebSocket function returned by initWebSockets
     sendAnsiKey = websockets.sendAnsiKey;
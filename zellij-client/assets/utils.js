@@ -9,3 +9,31 @@
 export function is_https() {
     return document.location.protocol === "https:";
 }
+
+/**
+ * The path prefix the web server serves everything under (eg. `/swarm` when behind a reverse
+ * proxy), empty when served from the root
+ * @returns {string}
+ */
+export function base_path() {
+    return window.base_path || "";
+}
+
+/**
+ * Read the session and the optional tab or pane to focus from the page path, which is one of
+ * `/`, `/<session>`, `/<session>/tab/<name>` or `/<session>/pane/<id>` after the base path
+ * @returns {{sessionName: string, deepLink: ({tab: string}|{pane: string}|null)}}
+ */
+export function parse_location_path() {
+    const path = location.pathname.startsWith(base_path())
+        ? location.pathname.slice(base_path().length)
+        : location.pathname;
+    const [sessionName = "", kind, target] = path.split("/").filter((s) => s !== "");
+    let deepLink = null;
+    if (kind === "tab" && target !== undefined) {
+        deepLink = { tab: decodeURIComponent(target) };
+    } else if (kind === "pane" && target !== undefined) {
+        deepLink = { pane: decodeURIComponent(target) };
+    }
+    return { sessionName, deepLink };
+}
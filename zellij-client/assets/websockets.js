@@ -2,29 +2,36 @@
  * WebSocket management for terminal and control connections
  */
 
-import { is_https } from './utils.js';
+import { base_path, is_https, parse_location_path } from './utils.js';
 import { handleReconnection, markConnectionEstablished } from './connection.js';
+import { downloadFile } from './files.js';
 
 /**
  * Initialize both terminal and control WebSocket connections
  * @param {string} webClientId - Client ID from authentication
  * @param {string} sessionName - Session name from URL
+ * @param {object|null} deepLink - Tab ({tab}) or pane ({pane}) to focus once attached, from URL
  * @param {Terminal} term - Terminal instance
  * @param {FitAddon} fitAddon - Terminal fit addon
  * @param {function} sendAnsiKey - Function to send ANSI key sequences
  * @returns {object} Object containing WebSocket instances and cleanup function
  */
-export function initWebSockets(webClientId, sessionName, term, fitAddon, sendAnsiKey) {
+export function initWebSockets(webClientId, sessionName, deepLink, term, fitAddon, sendAnsiKey) {
     let ownWebClientId = "";
     let wsTerminal;
     let wsControl;
     
     const wsUrlPrefix = is_https() ? "wss" : "ws";
     const url = sessionName === ""
-        ? `${wsUrlPrefix}://${window.location.host}/ws/terminal`
-        : `${wsUrlPrefix}://${window.location.host}/ws/terminal/${sessionName}`;
+        ? `${wsUrlPrefix}://${window.location.host}${base_path()}/ws/terminal`
+        : `${wsUrlPrefix}://${window.location.host}${base_path()}/ws/terminal/${sessionName}`;
     
-    const queryString = `?web_client_id=${encodeURIComponent(webClientId)}`;
+    let queryString = `?web_client_id=${encodeURIComponent(webClientId)}`;
+    if (deepLink?.tab !== undefined) {
+        queryString += `&tab=${encodeURIComponent(deepLink.tab)}`;
+    } else if (deepLink?.pane !== undefined) {
+        queryString += `&pane=${encodeURIComponent(deepLink.pane)}`;
+    }
     const wsTerminalUrl = `${url}${queryString}`;
     
     wsTerminal = new WebSocket(wsTerminalUrl);
@@ -36,7 +43,7 @@ export function initWebSockets(webClientId, sessionName, term, fitAddon, sendAns
     wsTerminal.onmessage = function (event) {
         if (ownWebClientId == "") {
             ownWebClientId = webClientId;
-            const wsControlUrl = `${wsUrlPrefix}://${window.location.host}/ws/control`;
+            const wsControlUrl = `${wsUrlPrefix}://${window.location.host}${base_path()}/ws/control`;
             wsControl = new WebSocket(wsControlUrl);
             startWsControl(wsControl, term, fitAddon, ownWebClientId);
         }
@@ -211,7 +218,14 @@ function startWsControl(wsControl, term, fitAddon, ownWebClientId) {
             }
         } else if (msg.type === "SwitchedSession") {
             const { new_session_name } = msg;
-            window.location.pathname = `/${new_session_name}`;
+            // keep a tab or pane link to this session in the address bar rather than reloading
+            const { sessionName } = parse_location_path();
+            if (decodeURIComponent(sessionName) !== new_session_name) {
+                window.location.pathname = `${base_path()}/${new_session_name}`;
+            }
+        } else if (msg.type === "DownloadFile") {
+            const { download_id, file_name } = msg;
+            downloadFile(ownWebClientId, download_id, file_name);
         }
     };
 
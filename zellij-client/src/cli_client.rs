@@ -1,23 +1,21 @@
 //! The `[cli_client]` is used to attach to a running server session
 //! and dispatch actions, that are specified through the command line.
 use std::collections::BTreeMap;
-use std::io::BufRead;
+use std::io::{BufRead, Write};
 use std::process;
 use std::{fs, path::PathBuf};
 
 use crate::os_input_output::ClientOsApi;
 use uuid::Uuid;
 use zellij_utils::{
+    cli::SessionEventFormat,
+    data::SessionEventType,
     errors::prelude::*,
     input::actions::Action,
     ipc::{ClientToServerMsg, ExitReason, ServerToClientMsg},
 };
 
-pub fn start_cli_client(
-    mut os_input: Box<dyn ClientOsApi>,
-    session_name: &str,
-    actions: Vec<Action>,
-) {
+fn connect_to_session(os_input: &mut Box<dyn ClientOsApi>, session_name: &str) {
     let swarm_ipc_pipe: PathBuf = {
         let mut sock_dir = zellij_utils::consts::SWARM_SOCK_DIR.clone();
         fs::create_dir_all(&sock_dir).unwrap();
@@ -26,11 +24,25 @@ pub fn start_cli_client(
         sock_dir
     };
     os_input.connect_to_server(&*swarm_ipc_pipe);
+}
+
+pub fn start_cli_client(
+    mut os_input: Box<dyn ClientOsApi>,
+    session_name: &str,
+    actions: Vec<Action>,
+    json: bool, // answer queries with JSON
+) {
+    connect_to_session(&mut os_input, session_name);
     let pane_id = os_input
         .env_variable("ZELLIJ_PANE_ID")
         .and_then(|e| e.trim().parse().ok());
 
     for action in actions {
+        let action = if json {
+            action.with_json_output()
+        } else {
+            action
+        };
         match action {
             Action::CliPipe {
                 pipe_id,
@@ -63,6 +75,9 @@ pub fn start_cli_client(
                     pane_title,
                 );
             },
+            Action::WaitForPaneExit(terminal_id) => {
+                wait_for_pane_exit_client(&mut os_input, terminal_id);
+            },
             action => {
                 individual_messages_client(&mut os_input, action, pane_id);
             },
@@ -71,6 +86,62 @@ pub fn start_cli_client(
     os_input.send_to_server(ClientToServerMsg::ClientExited);
 }
 
+/// Prints the events of the session as they happen, until the session exits
+pub fn start_session_event_stream(
+    mut os_input: Box<dyn ClientOsApi>,
+    session_name: &str,
+    event_types: Vec<SessionEventType>,
+    format: SessionEventFormat,
+) {
+    connect_to_session(&mut os_input, session_name);
+    let msg = ClientToServerMsg::Action(Action::SubscribeToSessionEvents(event_types), None, None);
+    os_input.send_to_server(msg);
+    let mut stdout = std::io::stdout();
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::SessionEvent(session_event), _)) => {
+                let line = match format {
+                    SessionEventFormat::Jsonl => match serde_json::to_string(&session_event) {
+                        Ok(line) => line,
+                        Err(e) => {
+                            eprintln!("Failed to serialize session event: {}", e);
+                            continue;
+                        },
+                    },
+                    SessionEventFormat::Text => session_event.describe(),
+                };
+                // whoever reads the stream (eg. a script waiting for a build) should see each
+                // event as it happens rather than when the buffer fills up
+                if writeln!(stdout, "{}", line)
+                    .and_then(|_| stdout.flush())
+                    .is_err()
+                {
+                    // the reading end went away
+                    os_input.send_to_server(ClientToServerMsg::ClientExited);
+                    process::exit(0);
+                }
+            },
+            Some((ServerToClientMsg::LogError(log_lines), _)) => {
+                log_lines.iter().for_each(|line| eprintln!("{line}"));
+                process::exit(2);
+            },
+            Some((ServerToClientMsg::Exit(exit_reason), _)) => match exit_reason {
+                ExitReason::Error(e) => {
+                    eprintln!("{}", e);
+                    process::exit(2);
+                },
+                _ => process::exit(0),
+            },
+            None => {
+                eprintln!("Lost the connection to the session");
+                process::exit(2);
+            },
+            // other clients' input is unblocked while we listen
+            _ => {},
+        }
+    }
+}
+
 fn pipe_client(
     os_input: &mut Box<dyn ClientOsApi>,
     pipe_id: String,
@@ -232,3 +303,39 @@ fn individual_messages_client(
         }
     }
 }
+
+fn wait_for_pane_exit_client(os_input: &mut Box<dyn ClientOsApi>, terminal_id: u32) {
+    let msg = ClientToServerMsg::Action(Action::WaitForPaneExit(terminal_id), None, None);
+    os_input.send_to_server(msg);
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::PaneExited(exit_status), _)) => {
+                os_input.send_to_server(ClientToServerMsg::ClientExited);
+                match exit_status {
+                    Some(exit_status) => process::exit(exit_status),
+                    None => {
+                        eprintln!("Pane {} exited without an exit status", terminal_id);
+                        process::exit(1);
+                    },
+                }
+            },
+            Some((ServerToClientMsg::LogError(log_lines), _)) => {
+                log_lines.iter().for_each(|line| eprintln!("{line}"));
+                process::exit(2);
+            },
+            Some((ServerToClientMsg::Exit(exit_reason), _)) => {
+                match exit_reason {
+                    ExitReason::Error(e) => eprintln!("{}", e),
+                    _ => eprintln!("The session exited before pane {} did", terminal_id),
+                }
+                process::exit(2);
+            },
+            None => {
+                eprintln!("Lost the connection to the session");
+                process::exit(2);
+            },
+            // other clients' input is unblocked while we wait
+            _ => {},
+        }
+    }
+}
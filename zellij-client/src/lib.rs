@@ -19,7 +19,7 @@ use std::process::Command;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use zellij_utils::errors::FatalError;
-use zellij_utils::shared::web_server_base_url;
+use zellij_utils::shared::{web_server_base_path, web_server_base_url};
 
 use crate::stdin_ansi_parser::{AnsiStdinInstruction, StdinAnsiParser, SyncOutput};
 use crate::{
@@ -40,7 +40,10 @@ use zellij_utils::{
     ipc::{ClientAttributes, ClientToServerMsg, ExitReason, ServerToClientMsg},
     pane_size::Size,
 };
-use zellij_utils::{cli::CliArgs, input::layout::Layout};
+use zellij_utils::{
+    cli::{CliArgs, Command as CliCommand, Sessions},
+    input::layout::Layout,
+};
 
 /// Instructions related to the client-side application
 #[derive(Debug, Clone)]
@@ -86,6 +89,15 @@ impl From<ServerToClientMsg> for ClientInstruction {
                 ClientInstruction::WriteConfigToDisk { config }
             },
             ServerToClientMsg::StartWebServer => ClientInstruction::StartWebServer,
+            // the server only sends these to web clients
+            ServerToClientMsg::DownloadFile(path) => ClientInstruction::Log(vec![format!(
+                "Not downloading {}, only web clients can download files",
+                path.display()
+            )]),
+            // and these only to `swarm wait`, `swarm subscribe` and headless starts
+            ServerToClientMsg::PaneExited(_exit_status) => ClientInstruction::UnblockInputThread,
+            ServerToClientMsg::SessionEvent(_session_event) => ClientInstruction::UnblockInputThread,
+            ServerToClientMsg::LayoutApplied => ClientInstruction::UnblockInputThread,
         }
     }
 }
@@ -263,6 +275,13 @@ pub fn start_client(
         .unwrap_or_else(|| os_input.load_palette().into());
 
     let full_screen_ws = os_input.get_terminal_size_using_fd(0);
+    let read_only = matches!(
+        opts.command,
+        Some(CliCommand::Sessions(Sessions::Attach {
+            read_only: true,
+            ..
+        }))
+    );
     let client_attributes = ClientAttributes {
         size: full_screen_ws,
         style: Style {
@@ -270,6 +289,9 @@ pub fn start_client(
             rounded_corners: config.ui.pane_frames.rounded_corners,
             hide_session_name: config.ui.pane_frames.hide_session_name,
         },
+        read_only,
+        token_name: None,
+        user_name: opts.user.clone(),
     };
     let web_server_ip = config_options
         .web_server_ip
@@ -278,6 +300,7 @@ pub fn start_client(
     let has_certificate =
         config_options.web_server_cert.is_some() && config_options.web_server_key.is_some();
     let enforce_https_for_localhost = config_options.enforce_https_for_localhost.unwrap_or(false);
+    let web_server_base_path = web_server_base_path(&config_options);
 
     let create_ipc_pipe = || -> std::path::PathBuf {
         let mut sock_dir = SWARM_SOCK_DIR.clone();
@@ -627,6 +650,7 @@ pub fn start_client(
                     web_server_port,
                     has_certificate,
                     enforce_https_for_localhost,
+                    &web_server_base_path,
                 );
                 match spawn_web_server(&opts) {
                     Ok(_) => {
@@ -705,6 +729,9 @@ pub fn start_server_detached(
             rounded_corners: config.ui.pane_frames.rounded_corners,
             hide_session_name: config.ui.pane_frames.hide_session_name,
         },
+        read_only: false,
+        token_name: None,
+        user_name: None,
     };
 
     let create_ipc_pipe = || -> std::path::PathBuf {
@@ -755,6 +782,30 @@ pub fn start_server_detached(
     os_input.send_to_server(first_msg);
 }
 
+/// Starts the server in the background like `start_server_detached`, but stays connected to it
+/// until every tab of the layout has been applied
+pub fn start_headless_server(
+    os_input: Box<dyn ClientOsApi>,
+    opts: CliArgs,
+    config: Config,
+    config_options: Options,
+    info: ClientInfo,
+    layout: Option<Layout>,
+) -> Result<(), String> {
+    start_server_detached(os_input.clone(), opts, config, config_options, info, layout);
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::LayoutApplied, _)) => break,
+            Some((ServerToClientMsg::Exit(ExitReason::Error(e)), _)) => return Err(e),
+            Some((ServerToClientMsg::Exit(exit_reason), _)) => return Err(exit_reason.to_string()),
+            Some(_) => {},
+            None => return Err("lost the connection to the session".to_owned()),
+        }
+    }
+    os_input.send_to_server(ClientToServerMsg::ClientExited);
+    Ok(())
+}
+
 pub fn report_changes_in_config_file(opts: &CliArgs, os_input: &Box<dyn ClientOsApi>) {
     if let Some(config_file_path) = Config::config_file_path(&opts) {
         let os_input = os_input.clone();
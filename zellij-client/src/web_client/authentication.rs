@@ -1,11 +1,14 @@
+use crate::web_client::types::{LoginTokenName, SessionToken};
 use crate::web_client::utils::parse_cookies;
 use axum::body::Body;
 use axum::http::header::SET_COOKIE;
 use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
 use axum_extra::extract::cookie::{Cookie, SameSite};
-use zellij_utils::web_authentication_tokens::validate_session_token;
+use zellij_utils::web_authentication_tokens::{
+    session_token_access, session_token_name, validate_session_token, TokenAccess,
+};
 
-pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
+pub async fn auth_middleware(mut request: Request, next: Next) -> Result<Response, StatusCode> {
     let cookies = parse_cookies(&request);
 
     let session_token = match cookies.get("session_token") {
@@ -15,6 +18,16 @@ pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, S
 
     match validate_session_token(&session_token) {
         Ok(true) => {
+            // if we can't tell, err on the side of not letting this client type into sessions
+            let access =
+                session_token_access(&session_token).unwrap_or_else(|_| TokenAccess::read_only());
+            request.extensions_mut().insert(access);
+            request
+                .extensions_mut()
+                .insert(LoginTokenName(session_token_name(&session_token).ok()));
+            request
+                .extensions_mut()
+                .insert(SessionToken(session_token.clone()));
             let response = next.run(request).await;
             Ok(response)
         },
@@ -41,3 +54,36 @@ pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, S
         },
     }
 }
+
+/// The access a session token grants now, `None` if it or the token it was created with expired or
+/// was revoked
+pub fn current_access(session_token: &str) -> Option<TokenAccess> {
+    match validate_session_token(session_token) {
+        Ok(true) => session_token_access(session_token).ok(),
+        Ok(false) | Err(_) => None,
+    }
+}
+
+/// Why a connected client has to be disconnected, if its login token no longer grants the access
+/// it connected with
+pub fn lost_access_reason(
+    connected_with: &TokenAccess,
+    current_access: Option<&TokenAccess>,
+    session_name: Option<&str>,
+) -> Option<String> {
+    match current_access {
+        None => Some("Your login token expired or was revoked.".to_owned()),
+        Some(current_access) if current_access.scope != connected_with.scope => Some(format!(
+            "Your login token is now {}, please reconnect.",
+            current_access.scope
+        )),
+        Some(current_access) => session_name
+            .filter(|session_name| !current_access.allows_session(session_name))
+            .map(|session_name| {
+                format!(
+                    "Your login token no longer allows access to session \"{}\".",
+                    session_name
+                )
+            }),
+    }
+}
@@ -2,13 +2,23 @@ use crate::os_input_output::ClientOsApi;
 use crate::web_client::control_message::WebServerToWebClientControlMessage;
 use crate::web_client::types::{ClientChannels, ClientConnectionBus, ConnectionTable};
 use axum::extract::ws::{CloseFrame, Message};
+use std::path::PathBuf;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio_util::sync::CancellationToken;
+use zellij_utils::web_authentication_tokens::TokenAccess;
 
 impl ConnectionTable {
-    pub fn add_new_client(&mut self, client_id: String, client_os_api: Box<dyn ClientOsApi>) {
-        self.client_id_to_channels
-            .insert(client_id, ClientChannels::new(client_os_api));
+    pub fn add_new_client(
+        &mut self,
+        client_id: String,
+        client_os_api: Box<dyn ClientOsApi>,
+        access: TokenAccess,
+        token_name: Option<String>,
+    ) {
+        self.client_id_to_channels.insert(
+            client_id,
+            ClientChannels::new(client_os_api, access, token_name),
+        );
     }
 
     pub fn add_client_control_tx(
@@ -45,6 +55,18 @@ impl ConnectionTable {
         self.client_id_to_channels.get(client_id).map(|c| &c.os_api)
     }
 
+    pub fn get_client_access(&self, client_id: &str) -> Option<TokenAccess> {
+        self.client_id_to_channels
+            .get(client_id)
+            .map(|c| c.access.clone())
+    }
+
+    pub fn get_client_token_name(&self, client_id: &str) -> Option<String> {
+        self.client_id_to_channels
+            .get(client_id)
+            .and_then(|c| c.token_name.clone())
+    }
+
     pub fn get_client_terminal_tx(&self, client_id: &str) -> Option<UnboundedSender<String>> {
         self.client_id_to_channels
             .get(client_id)
@@ -57,6 +79,30 @@ impl ConnectionTable {
             .and_then(|c| c.control_channel_tx.clone())
     }
 
+    pub fn set_client_session_name(&mut self, client_id: &str, session_name: &str) {
+        self.client_id_to_channels
+            .get_mut(client_id)
+            .map(|c| c.session_name = Some(session_name.to_owned()));
+    }
+
+    pub fn get_client_session_name(&self, client_id: &str) -> Option<String> {
+        self.client_id_to_channels
+            .get(client_id)
+            .and_then(|c| c.session_name.clone())
+    }
+
+    pub fn offer_download(&mut self, client_id: &str, path: PathBuf) -> Option<String> {
+        self.client_id_to_channels
+            .get_mut(client_id)
+            .map(|c| c.offer_download(path))
+    }
+
+    pub fn take_offered_download(&mut self, client_id: &str, download_id: &str) -> Option<PathBuf> {
+        self.client_id_to_channels
+            .get_mut(client_id)
+            .and_then(|c| c.take_offered_download(download_id))
+    }
+
     pub fn remove_client(&mut self, client_id: &str) {
         if let Some(mut client_channels) = self.client_id_to_channels.remove(client_id).take() {
             client_channels.cleanup();
@@ -19,9 +19,19 @@ pub(super) enum WebClientToWebServerControlMessagePayload {
 pub(super) enum WebServerToWebClientControlMessage {
     SetConfig(SetConfigPayload),
     QueryTerminalSize,
-    Log { lines: Vec<String> },
-    LogError { lines: Vec<String> },
-    SwitchedSession { new_session_name: String },
+    Log {
+        lines: Vec<String>,
+    },
+    LogError {
+        lines: Vec<String>,
+    },
+    SwitchedSession {
+        new_session_name: String,
+    },
+    DownloadFile {
+        download_id: String,
+        file_name: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
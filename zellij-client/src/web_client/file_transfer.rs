@@ -0,0 +1,198 @@
+//! Moving files between the browser and the host. Files dropped onto the web client are uploaded
+//! here and moved by the session into the cwd of the uploading client's focused pane, files sent
+//! from within a pane with `swarm action send-file` are offered to the web clients of that session
+//! and fetched from here.
+
+use crate::web_client::types::{AppState, DownloadParams, LoginTokenName, UploadParams};
+use axum::{
+    body::Bytes,
+    extract::{Path as AxumPath, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use uuid::Uuid;
+use zellij_utils::{
+    consts::SWARM_WEB_UPLOADS_DIR, data::WebAuditEventKind, input::actions::Action,
+    ipc::ClientToServerMsg, shared::set_permissions, web_audit_log,
+};
+
+/// Uploads and downloads larger than this are refused
+pub const MAX_FILE_TRANSFER_SIZE: usize = 100 * 1024 * 1024;
+
+pub async fn upload_file(
+    State(state): State<AppState>,
+    Query(params): Query<UploadParams>,
+    Extension(LoginTokenName(token_name)): Extension<LoginTokenName>,
+    body: Bytes,
+) -> StatusCode {
+    let Some(file_name) = sanitized_file_name(&params.file_name) else {
+        return StatusCode::BAD_REQUEST;
+    };
+    let (os_api, access, session_name, token_name) = {
+        let connection_table = state.connection_table.lock().unwrap();
+        let Some(os_api) = connection_table
+            .get_client_os_api(&params.web_client_id)
+            .cloned()
+        else {
+            return StatusCode::NOT_FOUND;
+        };
+        (
+            os_api,
+            connection_table.get_client_access(&params.web_client_id),
+            connection_table.get_client_session_name(&params.web_client_id),
+            connection_table
+                .get_client_token_name(&params.web_client_id)
+                .or(token_name),
+        )
+    };
+    if access.map(|a| a.scope.is_read_only()).unwrap_or(true) {
+        return StatusCode::FORBIDDEN;
+    }
+    let Some(session_name) = session_name else {
+        // not attached to a session yet, there's no pane to upload next to
+        return StatusCode::CONFLICT;
+    };
+    match stage_upload(&body) {
+        Ok(staged_file) => {
+            os_api.send_to_server(ClientToServerMsg::Action(
+                Action::ReceiveFile(staged_file, file_name.clone()),
+                None,
+                None,
+            ));
+            audit_file_transfer(
+                WebAuditEventKind::FileUploaded,
+                session_name,
+                token_name,
+                params.web_client_id,
+                format!("{} ({} bytes)", file_name, body.len()),
+            );
+            StatusCode::OK
+        },
+        Err(e) => {
+            log::error!("Failed to stage uploaded file {}: {}", file_name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        },
+    }
+}
+
+pub async fn download_file(
+    State(state): State<AppState>,
+    AxumPath(download_id): AxumPath<String>,
+    Query(params): Query<DownloadParams>,
+) -> Response {
+    let (path, session_name, token_name) = {
+        let mut connection_table = state.connection_table.lock().unwrap();
+        let Some(path) =
+            connection_table.take_offered_download(&params.web_client_id, &download_id)
+        else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        (
+            path,
+            connection_table.get_client_session_name(&params.web_client_id),
+            connection_table.get_client_token_name(&params.web_client_id),
+        )
+    };
+    let contents = match fs::read(&path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            log::error!("Failed to read {} for download: {}", path.display(), e);
+            return StatusCode::NOT_FOUND.into_response();
+        },
+    };
+    // checked when offering the download too, but the file might have grown since
+    if contents.len() > MAX_FILE_TRANSFER_SIZE {
+        return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+    }
+    if let Some(session_name) = session_name {
+        audit_file_transfer(
+            WebAuditEventKind::FileDownloaded,
+            session_name,
+            token_name,
+            params.web_client_id,
+            format!("{} ({} bytes)", path.display(), contents.len()),
+        );
+    }
+    // the browser names the file after the download link, this is just a fallback
+    let file_name: String = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_graphic() || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .filter(|c| *c != '"' && *c != '\\')
+        .collect();
+    (
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_owned()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        contents,
+    )
+        .into_response()
+}
+
+/// Whether a file the session asked to send to a web client can be downloaded by it
+pub fn can_be_downloaded(path: &Path) -> Result<(), String> {
+    match fs::metadata(path) {
+        Ok(metadata) if !metadata.is_file() => Err(format!("{} is not a file", path.display())),
+        Ok(metadata) if metadata.len() > MAX_FILE_TRANSFER_SIZE as u64 => Err(format!(
+            "{} is larger than the {}MB web clients can download",
+            path.display(),
+            MAX_FILE_TRANSFER_SIZE / 1024 / 1024
+        )),
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Cannot read {}: {}", path.display(), e)),
+    }
+}
+
+// only plain file names, uploads cannot be placed anywhere but the cwd of the focused pane
+fn sanitized_file_name(file_name: &str) -> Option<String> {
+    let file_name = file_name.trim();
+    if file_name.is_empty()
+        || file_name == "."
+        || file_name == ".."
+        || file_name.contains(|c: char| c == '/' || c == '\\' || c.is_control())
+    {
+        None
+    } else {
+        Some(file_name.to_owned())
+    }
+}
+
+fn stage_upload(contents: &[u8]) -> io::Result<PathBuf> {
+    fs::create_dir_all(&*SWARM_WEB_UPLOADS_DIR)?;
+    set_permissions(&SWARM_WEB_UPLOADS_DIR, 0o700)?;
+    let staged_file = SWARM_WEB_UPLOADS_DIR.join(Uuid::new_v4().to_string());
+    fs::write(&staged_file, contents)?;
+    Ok(staged_file)
+}
+
+fn audit_file_transfer(
+    kind: WebAuditEventKind,
+    session_name: String,
+    token_name: Option<String>,
+    web_client_id: String,
+    details: String,
+) {
+    let mut event = web_audit_log::new_event(kind);
+    event.session_name = Some(session_name);
+    event.token_name = token_name;
+    event.client_id = Some(web_client_id);
+    event.details = Some(details);
+    web_audit_log::record(event);
+}
@@ -0,0 +1,108 @@
+//! Telling where a request came from when the web server sits behind a reverse proxy. The
+//! `X-Forwarded-For` and `X-Forwarded-Proto` headers are only believed when the request reached
+//! us from one of the configured trusted proxies or over our unix socket, anyone else could just
+//! make them up.
+
+use crate::web_client::types::{AppState, ProxySettings};
+use axum::{
+    extract::{ConnectInfo, FromRequestParts, Request, State},
+    http::{request::Parts, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+const FORWARDED_FOR: &str = "x-forwarded-for";
+const FORWARDED_PROTO: &str = "x-forwarded-proto";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientAddress {
+    /// The address of the browser as best we can tell, None when it connected over our unix socket
+    /// through a proxy that did not tell us
+    pub remote_address: Option<String>,
+    pub is_https: bool,
+}
+
+impl ClientAddress {
+    /// `peer` is None for connections over our unix socket
+    pub fn new(headers: &HeaderMap, peer: Option<SocketAddr>, settings: &ProxySettings) -> Self {
+        let peer_is_trusted = match peer {
+            Some(peer) => settings.trusted_proxies.contains(&peer.ip()),
+            None => true,
+        };
+        if !peer_is_trusted {
+            return ClientAddress {
+                remote_address: peer.map(|p| p.to_string()),
+                is_https: settings.serves_https,
+            };
+        }
+        let forwarded_for = header_values(headers, FORWARDED_FOR)
+            .filter_map(|address| address.parse::<IpAddr>().ok())
+            .collect::<Vec<_>>();
+        // every proxy appends the address it got the request from, so we walk back from the end
+        // until we reach one we do not trust
+        let remote_address = forwarded_for
+            .iter()
+            .rev()
+            .find(|address| !settings.trusted_proxies.contains(address))
+            .or_else(|| forwarded_for.first())
+            .map(|address| address.to_string())
+            .or_else(|| peer.map(|p| p.to_string()));
+        // like with the addresses, the last value is the one set by the proxy closest to us
+        let is_https = header_values(headers, FORWARDED_PROTO)
+            .last()
+            .map(|proto| proto.eq_ignore_ascii_case("https"))
+            .unwrap_or(settings.serves_https);
+        ClientAddress {
+            remote_address,
+            is_https,
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for ClientAddress {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Infallible> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(peer)| *peer);
+        Ok(ClientAddress::new(
+            &parts.headers,
+            peer,
+            &state.proxy_settings,
+        ))
+    }
+}
+
+/// Turns away plain HTTP requests when we rely on a proxy in front of us to provide HTTPS
+pub async fn https_middleware(
+    State(state): State<AppState>,
+    client_address: ClientAddress,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.proxy_settings.require_forwarded_https && !client_address.is_https {
+        log::warn!(
+            "Refusing plain HTTP request from {}",
+            client_address
+                .remote_address
+                .as_deref()
+                .unwrap_or("unix socket")
+        );
+        return (StatusCode::FORBIDDEN, "HTTPS is required").into_response();
+    }
+    next.run(request).await
+}
+
+fn header_values<'a>(headers: &'a HeaderMap, name: &str) -> impl Iterator<Item = &'a str> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+}
@@ -1,15 +1,24 @@
-use crate::web_client::types::{AppState, CreateClientIdResponse, LoginRequest, LoginResponse};
+use crate::web_client::forwarded::ClientAddress;
+use crate::web_client::session_management::DeepLink;
+use crate::web_client::types::{
+    AppState, CreateClientIdResponse, LoginRequest, LoginResponse, LoginTokenName,
+};
 use crate::web_client::utils::{get_mime_type, parse_cookies};
 use axum::{
     extract::{Path as AxumPath, Request, State},
     http::{header, StatusCode},
-    response::{Html, IntoResponse},
-    Json,
+    response::{Html, IntoResponse, Response},
+    Extension, Json,
 };
 use axum_extra::extract::cookie::{Cookie, SameSite};
 use include_dir;
 use uuid::Uuid;
-use zellij_utils::{consts::VERSION, web_authentication_tokens::create_session_token};
+use zellij_utils::{
+    consts::VERSION,
+    data::WebAuditEventKind,
+    web_audit_log,
+    web_authentication_tokens::{create_session_token, session_token_name, TokenAccess},
+};
 
 const WEB_CLIENT_PAGE: &str = include_str!(concat!(
     env!("CARGO_MANIFEST_DIR"),
@@ -19,20 +28,48 @@ const WEB_CLIENT_PAGE: &str = include_str!(concat!(
 
 const ASSETS_DIR: include_dir::Dir<'_> = include_dir::include_dir!("$CARGO_MANIFEST_DIR/assets");
 
-pub async fn serve_html(request: Request) -> Html<String> {
+pub async fn serve_html(State(state): State<AppState>, request: Request) -> Html<String> {
     let cookies = parse_cookies(&request);
     let is_authenticated = cookies.get("session_token").is_some();
     let auth_value = if is_authenticated { "true" } else { "false" };
-    let html = Html(WEB_CLIENT_PAGE.replace("IS_AUTHENTICATED", &format!("{}", auth_value)));
+    let base_path = &state.proxy_settings.base_path;
+    let html = Html(
+        WEB_CLIENT_PAGE
+            .replace("IS_AUTHENTICATED", &format!("{}", auth_value))
+            .replace("BASE_PATH", base_path)
+            .replace("\"/assets/", &format!("\"{}/assets/", base_path)),
+    );
     html
 }
 
-pub async fn login_handler(Json(login_request): Json<LoginRequest>) -> impl IntoResponse {
+/// Serves the client page for `/<session>/tab/<name>` and `/<session>/pane/<id>`, the page itself
+/// reads the link from its url and asks for it when opening the terminal websocket
+pub async fn serve_deep_link_html(
+    AxumPath((session_name, kind, target)): AxumPath<(String, String, String)>,
+    state: State<AppState>,
+    request: Request,
+) -> Response {
+    if let Err(e) = DeepLink::new(&kind, &target) {
+        log::warn!("Not serving link into session {}: {}", session_name, e);
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    serve_html(state, request).await.into_response()
+}
+
+pub async fn login_handler(
+    client_address: ClientAddress,
+    Json(login_request): Json<LoginRequest>,
+) -> impl IntoResponse {
     match create_session_token(
         &login_request.auth_token,
         login_request.remember_me.unwrap_or(false),
     ) {
         Ok(session_token) => {
+            let mut event = web_audit_log::new_event(WebAuditEventKind::Login);
+            event.token_name = session_token_name(&session_token).ok();
+            event.remote_address = client_address.remote_address;
+            web_audit_log::record(event);
+
             let cookie = if login_request.remember_me.unwrap_or(false) {
                 // Persistent cookie for remember_me
                 Cookie::build(("session_token", session_token))
@@ -64,19 +101,27 @@ pub async fn login_handler(Json(login_request): Json<LoginRequest>) -> impl Into
 
             response
         },
-        Err(_) => (
-            StatusCode::UNAUTHORIZED,
-            Json(LoginResponse {
-                success: false,
-                message: "Invalid authentication token".to_string(),
-            }),
-        )
-            .into_response(),
+        Err(_) => {
+            let mut event = web_audit_log::new_event(WebAuditEventKind::LoginFailed);
+            event.remote_address = client_address.remote_address;
+            web_audit_log::record(event);
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(LoginResponse {
+                    success: false,
+                    message: "Invalid authentication token".to_string(),
+                }),
+            )
+                .into_response()
+        },
     }
 }
 
 pub async fn create_new_client(
+    client_address: ClientAddress,
     State(state): State<AppState>,
+    Extension(access): Extension<TokenAccess>,
+    Extension(LoginTokenName(token_name)): Extension<LoginTokenName>,
 ) -> Result<Json<CreateClientIdResponse>, (StatusCode, impl IntoResponse)> {
     let web_client_id = String::from(Uuid::new_v4());
     let os_input = state
@@ -84,11 +129,18 @@ pub async fn create_new_client(
         .create_client_os_api()
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e.to_string())))?;
 
-    state
-        .connection_table
-        .lock()
-        .unwrap()
-        .add_new_client(web_client_id.to_owned(), os_input);
+    state.connection_table.lock().unwrap().add_new_client(
+        web_client_id.to_owned(),
+        os_input,
+        access,
+        token_name.clone(),
+    );
+
+    let mut event = web_audit_log::new_event(WebAuditEventKind::TokenUsed);
+    event.token_name = token_name;
+    event.client_id = Some(web_client_id.clone());
+    event.remote_address = client_address.remote_address;
+    web_audit_log::record(event);
 
     Ok(Json(CreateClientIdResponse { web_client_id }))
 }
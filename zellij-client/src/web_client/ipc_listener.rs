@@ -1,9 +1,9 @@
 use super::control_message::{SetConfigPayload, WebServerToWebClientControlMessage};
 use super::types::ConnectionTable;
-use axum_server::Handle;
 use std::sync::{Arc, Mutex};
 use tokio::io::AsyncReadExt;
 use tokio::net::{UnixListener, UnixStream};
+use tokio_util::sync::CancellationToken;
 use zellij_utils::consts::WEBSERVER_SOCKET_PATH;
 use zellij_utils::web_server_commands::InstructionForWebServer;
 
@@ -33,7 +33,7 @@ pub async fn receive_webserver_instruction(
 }
 
 pub async fn listen_to_web_server_instructions(
-    server_handle: Handle,
+    shutdown: CancellationToken,
     connection_table: Arc<Mutex<ConnectionTable>>,
     id: &str,
 ) {
@@ -44,7 +44,7 @@ pub async fn listen_to_web_server_instructions(
                 match receive_webserver_instruction(&mut receiver).await {
                     Ok(instruction) => match instruction {
                         InstructionForWebServer::ShutdownWebServer => {
-                            server_handle.shutdown();
+                            shutdown.cancel();
                             break;
                         },
                         InstructionForWebServer::ConfigWrittenToDisk(new_config) => {
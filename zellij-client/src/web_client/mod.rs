@@ -2,6 +2,8 @@ mod control_message;
 
 mod authentication;
 mod connection_manager;
+mod file_transfer;
+mod forwarded;
 mod http_handlers;
 mod ipc_listener;
 mod message_handlers;
@@ -12,14 +14,17 @@ mod utils;
 mod websocket_handlers;
 
 use std::{
-    net::{IpAddr, Ipv4Addr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    os::unix::fs::FileTypeExt,
     path::PathBuf,
     sync::{Arc, Mutex},
     thread,
 };
 
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
+    response::Redirect,
     routing::{any, get, post},
     Router,
 };
@@ -33,6 +38,7 @@ use nix::sys::stat::{umask, Mode};
 use interprocess::unnamed_pipe::pipe;
 use std::io::{prelude::*, BufRead, BufReader};
 use tokio::runtime::Runtime;
+use tokio_util::sync::CancellationToken;
 use tower_http::cors::CorsLayer;
 use zellij_utils::input::{
     config::{watch_config_file_changes, Config},
@@ -40,13 +46,16 @@ use zellij_utils::input::{
 };
 
 use authentication::auth_middleware;
+use file_transfer::{download_file, upload_file, MAX_FILE_TRANSFER_SIZE};
+use forwarded::https_middleware;
 use http_handlers::{
-    create_new_client, get_static_asset, login_handler, serve_html, version_handler,
+    create_new_client, get_static_asset, login_handler, serve_deep_link_html, serve_html,
+    version_handler,
 };
 use ipc_listener::listen_to_web_server_instructions;
 use types::{
-    AppState, ClientOsApiFactory, ConnectionTable, RealClientOsApiFactory, RealSessionManager,
-    SessionManager,
+    AppState, ClientOsApiFactory, ConnectionTable, ProxySettings, RealClientOsApiFactory,
+    RealSessionManager, SessionManager,
 };
 use utils::should_use_https;
 use uuid::Uuid;
@@ -88,30 +97,44 @@ pub fn start_web_client(
         .web_server_ip
         .unwrap_or_else(|| IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
     let web_server_port = config_options.web_server_port.unwrap_or_else(|| 8082);
+    let web_server_socket = &config_options.web_server_socket;
     let web_server_cert = &config.options.web_server_cert;
     let web_server_key = &config.options.web_server_key;
     let has_https_certificate = web_server_cert.is_some() && web_server_key.is_some();
+    let has_trusted_proxies = config_options
+        .web_server_trusted_proxies
+        .as_ref()
+        .map(|trusted_proxies| !trusted_proxies.is_empty())
+        .unwrap_or(false);
 
-    if let Err(e) = should_use_https(
+    if web_server_socket.is_some() {
+        if has_https_certificate {
+            eprintln!("Cannot serve HTTPS over a unix socket, web_server_cert and web_server_key should be configured in the proxy in front of it instead.");
+            std::process::exit(2);
+        }
+    } else if let Err(e) = should_use_https(
         web_server_ip,
         has_https_certificate,
         config.options.enforce_https_for_localhost.unwrap_or(false),
     ) {
-        eprintln!("{}", e);
-        std::process::exit(2);
+        // a trusted proxy can provide HTTPS for us, plain HTTP requests are turned away when
+        // they did not come through it
+        if !has_trusted_proxies {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
     };
     let (runtime, listener, tls_config) = if run_daemonized {
         daemonize_web_server(
             web_server_ip,
             web_server_port,
+            web_server_socket,
             web_server_cert,
             web_server_key,
         )
     } else {
         let runtime = Runtime::new().unwrap();
-        let listener = runtime.block_on(async move {
-            std::net::TcpListener::bind(format!("{}:{}", web_server_ip, web_server_port))
-        });
+        let listener = WebServerListener::bind(web_server_ip, web_server_port, web_server_socket);
         let tls_config = match (web_server_cert, web_server_key) {
             (Some(web_server_cert), Some(web_server_key)) => {
                 let tls_config = runtime.block_on(async move {
@@ -139,10 +162,7 @@ pub fn start_web_client(
 
         match listener {
             Ok(listener) => {
-                println!(
-                    "Web Server started on {} port {}",
-                    web_server_ip, web_server_port
-                );
+                println!("Web Server started on {}", listener);
                 (runtime, listener, tls_config)
             },
             Err(e) => {
@@ -185,7 +205,7 @@ pub async fn serve_web_client(
     config: Config,
     config_options: Options,
     config_file_path: Option<PathBuf>,
-    listener: std::net::TcpListener,
+    listener: WebServerListener,
     rustls_config: Option<RustlsConfig>,
     session_manager: Option<Arc<dyn SessionManager>>,
     client_os_api_factory: Option<Arc<dyn ClientOsApiFactory>>,
@@ -196,7 +216,7 @@ pub async fn serve_web_client(
         return;
     };
     let connection_table = Arc::new(Mutex::new(ConnectionTable::default()));
-    let server_handle = Handle::new();
+    let shutdown = CancellationToken::new();
     let session_manager = session_manager.unwrap_or_else(|| Arc::new(RealSessionManager));
     let client_os_api_factory =
         client_os_api_factory.unwrap_or_else(|| Arc::new(RealClientOsApiFactory));
@@ -221,14 +241,16 @@ pub async fn serve_web_client(
     });
 
     tokio::spawn({
-        let server_handle = server_handle.clone();
+        let shutdown = shutdown.clone();
         let connection_table = connection_table.clone();
         async move {
-            listen_to_web_server_instructions(server_handle, connection_table, &format!("{}", id))
-                .await;
+            listen_to_web_server_instructions(shutdown, connection_table, &format!("{}", id)).await;
         }
     });
 
+    let proxy_settings = ProxySettings::new(&config_options, &listener, rustls_config.is_some());
+    let base_path = proxy_settings.base_path.clone();
+
     let state = AppState {
         connection_table,
         config,
@@ -236,6 +258,7 @@ pub async fn serve_web_client(
         config_file_path,
         session_manager,
         client_os_api_factory,
+        proxy_settings,
     };
 
     let app = Router::new()
@@ -243,48 +266,167 @@ pub async fn serve_web_client(
         .route("/ws/terminal", any(ws_handler_terminal))
         .route("/ws/terminal/{session}", any(ws_handler_terminal))
         .route("/session", post(create_new_client))
+        .route(
+            "/upload",
+            post(upload_file).layer(DefaultBodyLimit::max(MAX_FILE_TRANSFER_SIZE)),
+        )
+        .route("/download/{download_id}", get(download_file))
         .route_layer(middleware::from_fn(auth_middleware))
         .route("/", get(serve_html))
         .route("/{session}", get(serve_html))
+        .route("/{session}/{kind}/{target}", get(serve_deep_link_html))
         .route("/assets/{*path}", get(get_static_asset))
         .route("/command/login", post(login_handler))
         .route("/info/version", get(version_handler))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            https_middleware,
+        ))
         .layer(CorsLayer::permissive()) // TODO: configure properly
         .with_state(state);
+    let app = if base_path.is_empty() {
+        app
+    } else {
+        Router::new()
+            .route(
+                &format!("{}/", base_path),
+                get({
+                    let base_path = base_path.clone();
+                    move || async move { Redirect::permanent(&base_path) }
+                }),
+            )
+            .nest(&base_path, app)
+    };
 
-    match rustls_config {
-        Some(rustls_config) => {
-            let _ = axum_server::from_tcp_rustls(listener, rustls_config)
-                .handle(server_handle)
-                .serve(app.into_make_service())
-                .await;
+    match listener {
+        WebServerListener::Tcp(listener) => {
+            let server_handle = Handle::new();
+            tokio::spawn({
+                let server_handle = server_handle.clone();
+                async move {
+                    shutdown.cancelled().await;
+                    server_handle.shutdown();
+                }
+            });
+            match rustls_config {
+                Some(rustls_config) => {
+                    let _ = axum_server::from_tcp_rustls(listener, rustls_config)
+                        .handle(server_handle)
+                        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+                        .await;
+                },
+                None => {
+                    let _ = axum_server::from_tcp(listener)
+                        .handle(server_handle)
+                        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+                        .await;
+                },
+            }
         },
-        None => {
-            let _ = axum_server::from_tcp(listener)
-                .handle(server_handle)
-                .serve(app.into_make_service())
-                .await;
+        WebServerListener::Unix(listener) => {
+            let socket_path = listener
+                .local_addr()
+                .ok()
+                .and_then(|address| address.as_pathname().map(|path| path.to_path_buf()));
+            let listener = listener
+                .set_nonblocking(true)
+                .and_then(|_| tokio::net::UnixListener::from_std(listener));
+            match listener {
+                Ok(listener) => {
+                    // no graceful shutdown, it would wait for the websockets of connected clients
+                    tokio::select! {
+                        _ = axum::serve(listener, app.into_make_service()) => {},
+                        _ = shutdown.cancelled() => {},
+                    }
+                },
+                Err(e) => log::error!("Failed to listen on unix socket: {}", e),
+            }
+            if let Some(socket_path) = socket_path {
+                let _ = std::fs::remove_file(socket_path);
+            }
         },
     }
 }
 
+/// Where the web server accepts connections, either `web_server_ip`:`web_server_port` or
+/// `web_server_socket` for when it sits behind a reverse proxy on the same machine
+pub enum WebServerListener {
+    Tcp(std::net::TcpListener),
+    Unix(std::os::unix::net::UnixListener),
+}
+
+impl WebServerListener {
+    pub fn bind(
+        web_server_ip: IpAddr,
+        web_server_port: u16,
+        web_server_socket: &Option<PathBuf>,
+    ) -> std::io::Result<Self> {
+        match web_server_socket {
+            Some(socket_path) => {
+                if socket_path.exists() {
+                    if std::os::unix::net::UnixStream::connect(socket_path).is_ok() {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::AddrInUse,
+                            format!("{} is already being listened on", socket_path.display()),
+                        ));
+                    }
+                    // left behind by a web server that did not shut down cleanly
+                    if std::fs::metadata(socket_path)?.file_type().is_socket() {
+                        std::fs::remove_file(socket_path)?;
+                    }
+                }
+                let listener = std::os::unix::net::UnixListener::bind(socket_path)?;
+                // connecting to the socket skips authentication of the proxy in front of us, so
+                // only our user and group (eg. the proxy's) may do so
+                zellij_utils::shared::set_permissions(socket_path, 0o660)?;
+                Ok(WebServerListener::Unix(listener))
+            },
+            None => std::net::TcpListener::bind(format!("{}:{}", web_server_ip, web_server_port))
+                .map(WebServerListener::Tcp),
+        }
+    }
+}
+
+impl std::fmt::Display for WebServerListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebServerListener::Tcp(listener) => match listener.local_addr() {
+                Ok(address) => write!(f, "{} port {}", address.ip(), address.port()),
+                Err(_) => write!(f, "an unknown address"),
+            },
+            WebServerListener::Unix(listener) => {
+                match listener
+                    .local_addr()
+                    .ok()
+                    .and_then(|address| address.as_pathname().map(|path| path.to_path_buf()))
+                {
+                    Some(socket_path) => write!(f, "unix socket {}", socket_path.display()),
+                    None => write!(f, "an unnamed unix socket"),
+                }
+            },
+        }
+    }
+}
+
 fn daemonize_web_server(
     web_server_ip: IpAddr,
     web_server_port: u16,
+    web_server_socket: &Option<PathBuf>,
     web_server_cert: &Option<PathBuf>,
     web_server_key: &Option<PathBuf>,
-) -> (Runtime, std::net::TcpListener, Option<RustlsConfig>) {
+) -> (Runtime, WebServerListener, Option<RustlsConfig>) {
     let (mut exit_message_tx, exit_message_rx) = pipe().unwrap();
     let (mut exit_status_tx, mut exit_status_rx) = pipe().unwrap();
     let current_umask = umask(Mode::all());
     umask(current_umask);
     let web_server_key = web_server_key.clone();
     let web_server_cert = web_server_cert.clone();
+    let web_server_socket = web_server_socket.clone();
     let daemonization_outcome = daemonize::Daemonize::new()
         .working_directory(std::env::current_dir().unwrap())
         .umask(current_umask.bits() as u32)
         .privileged_action(
-            move || -> Result<(Runtime, std::net::TcpListener, Option<RustlsConfig>), String> {
+            move || -> Result<(Runtime, WebServerListener, Option<RustlsConfig>), String> {
                 let runtime = Runtime::new().map_err(|e| e.to_string())?;
                 let tls_config = match (web_server_cert, web_server_key) {
                     (Some(web_server_cert), Some(web_server_key)) => {
@@ -311,9 +453,8 @@ fn daemonize_web_server(
                     },
                 };
 
-                let listener = runtime.block_on(async move {
-                    std::net::TcpListener::bind(format!("{}:{}", web_server_ip, web_server_port))
-                });
+                let listener =
+                    WebServerListener::bind(web_server_ip, web_server_port, &web_server_socket);
                 listener
                     .map(|listener| (runtime, listener, tls_config))
                     .map_err(|e| e.to_string())
@@ -350,8 +491,8 @@ fn daemonize_web_server(
             Ok(listener_and_runtime) => {
                 let _ = writeln!(
                     exit_message_tx,
-                    "Web Server started on {} port {}",
-                    web_server_ip, web_server_port
+                    "Web Server started on {}",
+                    listener_and_runtime.1
                 );
                 let _ = exit_status_tx.write_all(&[0]);
                 listener_and_runtime
@@ -1,7 +1,8 @@
 use crate::os_input_output::ClientOsApi;
 use crate::report_changes_in_config_file;
 use crate::web_client::control_message::WebServerToWebClientControlMessage;
-use crate::web_client::session_management::build_initial_connection;
+use crate::web_client::file_transfer::can_be_downloaded;
+use crate::web_client::session_management::{build_initial_connection, DeepLink};
 use crate::web_client::types::{ClientConnectionBus, ConnectionTable, SessionManager};
 use crate::web_client::utils::terminal_init_messages;
 
@@ -11,11 +12,13 @@ use std::{
 };
 use zellij_utils::{
     cli::CliArgs,
-    data::Style,
+    data::{Style, WebAuditEventKind},
     input::{config::Config, options::Options},
     ipc::{ClientToServerMsg, ExitReason, ServerToClientMsg},
     sessions::generate_unique_session_name,
     setup::Setup,
+    web_audit_log,
+    web_authentication_tokens::TokenAccess,
 };
 
 pub fn swarm_server_listener(
@@ -27,6 +30,9 @@ pub fn swarm_server_listener(
     config_file_path: Option<PathBuf>,
     web_client_id: String,
     session_manager: Arc<dyn SessionManager>,
+    access: TokenAccess,
+    token_name: Option<String>,
+    mut deep_link: Option<DeepLink>,
 ) {
     let _server_listener_thread = std::thread::Builder::new()
         .name("server_listener".to_string())
@@ -73,6 +79,10 @@ pub fn swarm_server_listener(
                             rounded_corners: config.ui.pane_frames.rounded_corners,
                             hide_session_name: config.ui.pane_frames.hide_session_name,
                         },
+                        read_only: access.scope.is_read_only(),
+                        token_name: token_name.clone(),
+                        // web users are known by the name of the token they logged in with
+                        user_name: token_name.clone(),
                     };
 
                     let session_name = PathBuf::from(path.clone())
@@ -82,6 +92,48 @@ pub fn swarm_server_listener(
                         .unwrap()
                         .to_owned();
 
+                    // checked on every reconnect, since clients can switch sessions from within
+                    if !access.allows_session(&session_name) {
+                        handle_exit_reason(
+                            &mut client_connection_bus,
+                            ExitReason::Error(format!(
+                                "This login token does not allow access to session \"{}\".",
+                                session_name
+                            )),
+                        );
+                        break;
+                    }
+                    let session_is_running = session_manager
+                        .session_exists(&session_name)
+                        .unwrap_or(false);
+                    if !access.scope.can_create_sessions() && !session_is_running {
+                        handle_exit_reason(
+                            &mut client_connection_bus,
+                            ExitReason::Error(format!(
+                                "Session \"{}\" is not running, {} clients can only join running sessions.",
+                                session_name, access.scope
+                            )),
+                        );
+                        break;
+                    }
+
+                    connection_table
+                        .lock()
+                        .unwrap()
+                        .set_client_session_name(&web_client_id, &session_name);
+
+                    // only followed on the first connection, and only into a running session since
+                    // a new one does not have the tab or pane yet
+                    let (tab_position_to_focus, pane_id_to_focus) = deep_link
+                        .take()
+                        .filter(|_| session_is_running)
+                        .map(|deep_link| {
+                            deep_link.focus(|tab_name| {
+                                session_manager.tab_position(&session_name, tab_name)
+                            })
+                        })
+                        .unwrap_or_default();
+
                     let is_web_client = true;
                     let (first_message, swarm_ipc_pipe) = session_manager.spawn_session_if_needed(
                         &session_name,
@@ -92,10 +144,18 @@ pub fn swarm_server_listener(
                         is_web_client,
                         os_input.clone(),
                         reconnect_info.as_ref().and_then(|r| r.layout.clone()),
+                        tab_position_to_focus,
+                        pane_id_to_focus,
                     );
 
                     os_input.connect_to_server(&swarm_ipc_pipe);
                     os_input.send_to_server(first_message);
+                    audit_client(
+                        WebAuditEventKind::ClientAttached,
+                        &session_name,
+                        &token_name,
+                        &web_client_id,
+                    );
 
                     let mut args_for_report = CliArgs::default();
                     args_for_report.config = config_file_path.clone();
@@ -125,6 +185,12 @@ pub fn swarm_server_listener(
                                 client_connection_bus.send_stdout(bytes);
                             },
                             Some((ServerToClientMsg::SwitchSession(connect_to_session), _)) => {
+                                audit_client(
+                                    WebAuditEventKind::ClientDetached,
+                                    &session_name,
+                                    &token_name,
+                                    &web_client_id,
+                                );
                                 reconnect_to_session = Some(connect_to_session);
                                 continue 'reconnect_loop;
                             },
@@ -136,6 +202,15 @@ pub fn swarm_server_listener(
                                     WebServerToWebClientControlMessage::QueryTerminalSize,
                                 );
                             },
+                            Some((ServerToClientMsg::DownloadFile(path), _)) => {
+                                offer_download(
+                                    &mut client_connection_bus,
+                                    &connection_table,
+                                    &web_client_id,
+                                    &access,
+                                    path,
+                                );
+                            },
                             Some((ServerToClientMsg::Log(lines), _)) => {
                                 client_connection_bus.send_control(
                                     WebServerToWebClientControlMessage::Log { lines },
@@ -153,6 +228,12 @@ pub fn swarm_server_listener(
                             },
                         }
                     }
+                    audit_client(
+                        WebAuditEventKind::ClientDetached,
+                        &session_name,
+                        &token_name,
+                        &web_client_id,
+                    );
                     if reconnect_to_session.is_none() {
                         break;
                     }
@@ -161,7 +242,55 @@ pub fn swarm_server_listener(
         });
 }
 
-fn handle_exit_reason(client_connection_bus: &mut ClientConnectionBus, exit_reason: ExitReason) {
+fn audit_client(
+    kind: WebAuditEventKind,
+    session_name: &str,
+    token_name: &Option<String>,
+    web_client_id: &str,
+) {
+    let mut event = web_audit_log::new_event(kind);
+    event.session_name = Some(session_name.to_owned());
+    event.token_name = token_name.clone();
+    event.client_id = Some(web_client_id.to_owned());
+    web_audit_log::record(event);
+}
+
+fn offer_download(
+    client_connection_bus: &mut ClientConnectionBus,
+    connection_table: &Arc<Mutex<ConnectionTable>>,
+    web_client_id: &str,
+    access: &TokenAccess,
+    path: PathBuf,
+) {
+    if access.scope.is_read_only() {
+        log::info!("Not offering {} to read-only web client", path.display());
+        return;
+    }
+    if let Err(e) = can_be_downloaded(&path) {
+        client_connection_bus
+            .send_control(WebServerToWebClientControlMessage::LogError { lines: vec![e] });
+        return;
+    }
+    let file_name = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let download_id = connection_table
+        .lock()
+        .unwrap()
+        .offer_download(web_client_id, path);
+    if let Some(download_id) = download_id {
+        client_connection_bus.send_control(WebServerToWebClientControlMessage::DownloadFile {
+            download_id,
+            file_name,
+        });
+    }
+}
+
+pub fn handle_exit_reason(
+    client_connection_bus: &mut ClientConnectionBus,
+    exit_reason: ExitReason,
+) {
     match exit_reason {
         ExitReason::WebClientsForbidden => {
             client_connection_bus.send_stdout(format!(
@@ -1,10 +1,11 @@
 use crate::os_input_output::ClientOsApi;
 use crate::spawn_server;
 
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::PathBuf, str::FromStr};
 use zellij_utils::{
     cli::CliArgs,
-    data::{ConnectToSession, LayoutInfo, WebSharing},
+    consts::session_info_cache_file_name,
+    data::{ConnectToSession, LayoutInfo, PaneId, SessionInfo, WebSharing},
     envs,
     input::{
         config::{Config, ConfigError},
@@ -16,6 +17,59 @@ use zellij_utils::{
     setup::{find_default_config_dir, get_layout_dir},
 };
 
+/// Where in a session a web client should land, from a `/<session>/tab/<name>` or
+/// `/<session>/pane/<id>` url
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLink {
+    Tab(String),
+    Pane(PaneId),
+}
+
+impl DeepLink {
+    pub fn new(kind: &str, target: &str) -> Result<Self, String> {
+        match kind {
+            "tab" if !target.is_empty() => Ok(DeepLink::Tab(target.to_owned())),
+            "pane" => PaneId::from_str(target)
+                .map(DeepLink::Pane)
+                .map_err(|e| format!("Invalid pane id \"{}\": {}", target, e)),
+            _ => Err(format!("Unknown link: /{}/{}", kind, target)),
+        }
+    }
+    pub fn from_terminal_params(
+        tab: Option<&str>,
+        pane: Option<&str>,
+    ) -> Result<Option<Self>, String> {
+        match (tab, pane) {
+            (Some(tab), _) => DeepLink::new("tab", tab).map(Some),
+            (None, Some(pane)) => DeepLink::new("pane", pane).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+    /// The tab position (starting at 1) and pane id a client asks to focus when attaching, to
+    /// follow this link
+    pub fn focus(
+        &self,
+        tab_position: impl Fn(&str) -> Option<usize>,
+    ) -> (Option<usize>, Option<(u32, bool)>) {
+        match self {
+            DeepLink::Tab(tab_name) => (tab_position(tab_name).map(|position| position + 1), None),
+            DeepLink::Pane(PaneId::Terminal(id)) => (None, Some((*id, false))),
+            DeepLink::Pane(PaneId::Plugin(id)) => (None, Some((*id, true))),
+        }
+    }
+}
+
+/// The position of a tab in a running session, as of the last time the session wrote its metadata
+pub fn tab_position(session_name: &str, tab_name: &str) -> Option<usize> {
+    let raw_session_info = fs::read_to_string(session_info_cache_file_name(session_name)).ok()?;
+    SessionInfo::from_string(&raw_session_info, session_name)
+        .ok()?
+        .tabs
+        .iter()
+        .find(|tab| tab.name == tab_name)
+        .map(|tab| tab.position)
+}
+
 pub fn build_initial_connection(
     session_name: Option<String>,
     config: &Config,
@@ -68,10 +122,11 @@ fn layout_for_new_session(
             Some(&PathBuf::from(layout_name)),
             layout_dir.clone(),
             config.clone(),
+            BTreeMap::new(),
         ),
-        Some(LayoutInfo::Url(url)) => Layout::from_url(&url, config.clone()),
+        Some(LayoutInfo::Url(url)) => Layout::from_url(&url, config.clone(), BTreeMap::new()),
         Some(LayoutInfo::Stringified(stringified_layout)) => {
-            Layout::from_stringified_layout(&stringified_layout, config.clone())
+            Layout::from_stringified_layout(&stringified_layout, layout_dir.clone(), config.clone())
         },
         None => Layout::from_default_assets(
             &PathBuf::from("default"),
@@ -90,6 +145,8 @@ pub fn spawn_session_if_needed(
     is_web_client: bool,
     os_input: Box<dyn ClientOsApi>,
     requested_layout: Option<LayoutInfo>,
+    tab_position_to_focus: Option<usize>,
+    pane_id_to_focus: Option<(u32, bool)>,
 ) -> (ClientToServerMsg, PathBuf) {
     if session_exists(&session_name).unwrap_or(false) {
         ipc_pipe_and_first_message_for_existing_session(
@@ -98,6 +155,8 @@ pub fn spawn_session_if_needed(
             &config,
             &config_options,
             is_web_client,
+            tab_position_to_focus,
+            pane_id_to_focus,
         )
     } else {
         let force_run_commands = false;
@@ -187,6 +246,8 @@ fn ipc_pipe_and_first_message_for_existing_session(
     config: &Config,
     config_options: &Options,
     is_web_client: bool,
+    tab_position_to_focus: Option<usize>,
+    pane_id_to_focus: Option<(u32, bool)>,
 ) -> (ClientToServerMsg, PathBuf) {
     let swarm_ipc_pipe: PathBuf = {
         let mut sock_dir = zellij_utils::consts::SWARM_SOCK_DIR.clone();
@@ -199,8 +260,8 @@ fn ipc_pipe_and_first_message_for_existing_session(
         client_attributes,
         config.clone(),
         config_options.clone(),
-        None,
-        None,
+        tab_position_to_focus,
+        pane_id_to_focus,
         is_web_client,
     );
     (first_message, swarm_ipc_pipe)
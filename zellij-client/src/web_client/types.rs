@@ -1,16 +1,21 @@
 use axum::extract::ws::Message;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio_util::sync::CancellationToken;
 
 use crate::os_input_output::ClientOsApi;
+use crate::web_client::utils::should_use_https;
+use crate::web_client::WebServerListener;
 use std::path::PathBuf;
 use zellij_utils::{
     data::LayoutInfo,
     input::{config::Config, options::Options},
     ipc::{ClientAttributes, ClientToServerMsg},
+    shared::web_server_base_path,
+    web_authentication_tokens::TokenAccess,
 };
 
 pub trait ClientOsApiFactory: Send + Sync + std::fmt::Debug {
@@ -34,6 +39,7 @@ pub trait SessionManager: Send + Sync + std::fmt::Debug {
         &self,
         session_name: &str,
     ) -> Option<zellij_utils::input::layout::Layout>;
+    fn tab_position(&self, session_name: &str, tab_name: &str) -> Option<usize>;
     fn spawn_session_if_needed(
         &self,
         session_name: &str,
@@ -44,6 +50,8 @@ pub trait SessionManager: Send + Sync + std::fmt::Debug {
         is_web_client: bool,
         os_input: Box<dyn ClientOsApi>,
         requested_layout: Option<LayoutInfo>,
+        tab_position_to_focus: Option<usize>,
+        pane_id_to_focus: Option<(u32, bool)>,
     ) -> (ClientToServerMsg, PathBuf);
 }
 
@@ -63,6 +71,10 @@ impl SessionManager for RealSessionManager {
         zellij_utils::sessions::resurrection_layout(session_name)
     }
 
+    fn tab_position(&self, session_name: &str, tab_name: &str) -> Option<usize> {
+        crate::web_client::session_management::tab_position(session_name, tab_name)
+    }
+
     fn spawn_session_if_needed(
         &self,
         session_name: &str,
@@ -73,6 +85,8 @@ impl SessionManager for RealSessionManager {
         is_web_client: bool,
         os_input: Box<dyn ClientOsApi>,
         requested_layout: Option<LayoutInfo>,
+        tab_position_to_focus: Option<usize>,
+        pane_id_to_focus: Option<(u32, bool)>,
     ) -> (ClientToServerMsg, PathBuf) {
         crate::web_client::session_management::spawn_session_if_needed(
             session_name,
@@ -83,6 +97,8 @@ impl SessionManager for RealSessionManager {
             is_web_client,
             os_input,
             requested_layout,
+            tab_position_to_focus,
+            pane_id_to_focus,
         )
     }
 }
@@ -95,18 +111,30 @@ pub struct ConnectionTable {
 #[derive(Debug, Clone)]
 pub struct ClientChannels {
     pub os_api: Box<dyn ClientOsApi>,
+    pub access: TokenAccess,
+    pub token_name: Option<String>,
     pub control_channel_tx: Option<UnboundedSender<Message>>,
     pub terminal_channel_tx: Option<UnboundedSender<String>>,
     terminal_channel_cancellation_token: Option<CancellationToken>,
+    pub session_name: Option<String>, // the session this client is currently attached to
+    offered_downloads: HashMap<String, PathBuf>, // download id => file sent to this client
 }
 
 impl ClientChannels {
-    pub fn new(os_api: Box<dyn ClientOsApi>) -> Self {
+    pub fn new(
+        os_api: Box<dyn ClientOsApi>,
+        access: TokenAccess,
+        token_name: Option<String>,
+    ) -> Self {
         ClientChannels {
             os_api,
+            access,
+            token_name,
             control_channel_tx: None,
             terminal_channel_tx: None,
             terminal_channel_cancellation_token: None,
+            session_name: None,
+            offered_downloads: HashMap::new(),
         }
     }
 
@@ -124,6 +152,17 @@ impl ClientChannels {
     ) {
         self.terminal_channel_cancellation_token = Some(terminal_channel_cancellation_token);
     }
+    pub fn offer_download(&mut self, path: PathBuf) -> String {
+        let download_id = uuid::Uuid::new_v4().to_string();
+        self.offered_downloads.insert(download_id.clone(), path);
+        download_id
+    }
+
+    /// Each offered download can be fetched once
+    pub fn take_offered_download(&mut self, download_id: &str) -> Option<PathBuf> {
+        self.offered_downloads.remove(download_id)
+    }
+
     pub fn cleanup(&mut self) {
         if let Some(terminal_channel_cancellation_token) =
             self.terminal_channel_cancellation_token.take()
@@ -169,6 +208,49 @@ pub struct AppState {
     pub config_file_path: PathBuf,
     pub session_manager: Arc<dyn SessionManager>,
     pub client_os_api_factory: Arc<dyn ClientOsApiFactory>,
+    pub proxy_settings: ProxySettings,
+}
+
+/// How the web server is reached, set once when it starts
+#[derive(Debug, Clone, Default)]
+pub struct ProxySettings {
+    /// The prefix all routes are served under, either empty or eg. "/swarm"
+    pub base_path: String,
+    /// Peers whose `X-Forwarded-For` and `X-Forwarded-Proto` headers we believe, connections
+    /// over a unix socket are always trusted
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Whether we terminate TLS ourselves
+    pub serves_https: bool,
+    /// Whether requests must reach us over HTTPS as reported by a trusted proxy, for when we are
+    /// not serving HTTPS ourselves but are not allowed to serve plain HTTP either
+    pub require_forwarded_https: bool,
+}
+
+impl ProxySettings {
+    pub fn new(config_options: &Options, listener: &WebServerListener, serves_https: bool) -> Self {
+        let enforce_https_for_localhost =
+            config_options.enforce_https_for_localhost.unwrap_or(false);
+        let https_required_but_not_served = match listener {
+            WebServerListener::Tcp(listener) => listener
+                .local_addr()
+                .map(|address| {
+                    should_use_https(address.ip(), serves_https, enforce_https_for_localhost)
+                        .is_err()
+                })
+                .unwrap_or(!serves_https),
+            // only reachable from this machine, same as being bound to localhost
+            WebServerListener::Unix(_) => !serves_https && enforce_https_for_localhost,
+        };
+        ProxySettings {
+            base_path: web_server_base_path(config_options),
+            trusted_proxies: config_options
+                .web_server_trusted_proxies
+                .clone()
+                .unwrap_or_default(),
+            serves_https,
+            require_forwarded_https: https_required_but_not_served,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -192,11 +274,37 @@ pub struct CreateClientIdResponse {
     pub web_client_id: String,
 }
 
+#[derive(Deserialize)]
+pub struct UploadParams {
+    pub web_client_id: String,
+    pub file_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct DownloadParams {
+    pub web_client_id: String,
+}
+
 #[derive(Deserialize)]
 pub struct TerminalParams {
     pub web_client_id: String,
+    /// Name of the tab to focus once attached, from a `/<session>/tab/<name>` url
+    #[serde(default)]
+    pub tab: Option<String>,
+    /// Id of the pane to focus once attached, from a `/<session>/pane/<id>` url
+    #[serde(default)]
+    pub pane: Option<String>,
 }
 
+/// The name of the login token a request was authenticated with, recorded in the audit log
+#[derive(Debug, Clone, Default)]
+pub struct LoginTokenName(pub Option<String>);
+
+/// The session token a request was authenticated with, long lived connections check it again
+/// later on
+#[derive(Debug, Clone)]
+pub struct SessionToken(pub String);
+
 #[derive(Deserialize)]
 pub struct LoginRequest {
     pub auth_token: String,
@@ -9,7 +9,9 @@ use tokio::time::timeout;
 use tokio_tungstenite::tungstenite::http::Request;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use zellij_utils::input::layout::Layout;
-use zellij_utils::{consts::VERSION, input::config::Config, input::options::Options};
+use zellij_utils::{
+    consts::VERSION, input::actions::Action, input::config::Config, input::options::Options,
+};
 
 use crate::os_input_output::ClientOsApi;
 use crate::web_client::control_message::{
@@ -18,11 +20,12 @@ use crate::web_client::control_message::{
 };
 use crate::web_client::ClientOsApiFactory;
 use zellij_utils::{
-    data::{LayoutInfo, Palette},
+    data::{LayoutInfo, Palette, WebAuditEventKind, WebTokenScope},
     errors::ErrorContext,
     ipc::{ClientAttributes, ClientToServerMsg, ServerToClientMsg},
     pane_size::Size,
-    web_authentication_tokens::{create_token, delete_db, revoke_token},
+    web_audit_log::read_audit_log,
+    web_authentication_tokens::{create_token, delete_db, revoke_token, TokenAccess},
 };
 
 use serial_test::serial;
@@ -30,8 +33,21 @@ use serial_test::serial;
 mod web_client_tests {
     use super::*;
 
+    use std::os::unix::fs::PermissionsExt;
     use std::time::{Duration, Instant};
 
+    fn recorded_logins(token_name: &str) -> usize {
+        read_audit_log()
+            .unwrap_or_default()
+            .iter()
+            .filter(|e| {
+                e.kind == WebAuditEventKind::Login
+                    && e.token_name.as_deref() == Some(token_name)
+                    && e.remote_address.is_some()
+            })
+            .count()
+    }
+
     async fn wait_for_server(port: u16, timeout: Duration) -> Result<(), String> {
         let start = Instant::now();
         let url = format!("http://127.0.0.1:{}/info/version", port);
@@ -83,7 +99,7 @@ mod web_client_tests {
             config,
             options,
             Some(temp_config_path),
-            listener,
+            WebServerListener::Tcp(listener),
             None,
             Some(session_manager),
             Some(client_os_api_factory),
@@ -121,8 +137,13 @@ mod web_client_tests {
         let _ = delete_db();
 
         let test_token_name = "test_token_login";
-        let (auth_token, _) =
-            create_token(Some(test_token_name.to_string())).expect("Failed to create test token");
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
+        let logins_before = recorded_logins(test_token_name);
 
         let session_manager = Arc::new(MockSessionManager::new());
         let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
@@ -141,7 +162,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -182,6 +203,11 @@ mod web_client_tests {
 
         assert_eq!(response_json["success"], true);
         assert_eq!(response_json["message"], "Login successful");
+        assert_eq!(
+            recorded_logins(test_token_name),
+            logins_before + 1,
+            "the login is recorded in the audit log"
+        );
 
         println!("✓ Login endpoint test passed");
 
@@ -212,7 +238,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -257,8 +283,12 @@ mod web_client_tests {
         let _ = delete_db();
 
         let test_token_name = "test_token_session_flow";
-        let (auth_token, _) =
-            create_token(Some(test_token_name.to_string())).expect("Failed to create test token");
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
 
         let session_manager = Arc::new(MockSessionManager::new());
         let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
@@ -277,7 +307,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -341,50 +371,833 @@ mod web_client_tests {
                 }
             }),
         )
-        .await
-        .unwrap()
-        .unwrap()
-        .unwrap();
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+
+        assert!(client_response.status().is_success());
+
+        let client_data: serde_json::Value =
+            serde_json::from_str(&client_response.text().unwrap()).unwrap();
+        let web_client_id = client_data["web_client_id"].as_str().unwrap().to_string();
+
+        println!("✓ Successfully created client session");
+
+        let control_ws_url = format!("ws://127.0.0.1:{}/ws/control", port);
+        let (control_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&control_ws_url, session_token),
+        )
+        .await
+        .expect("Control WebSocket connection timed out")
+        .expect("Failed to connect to control WebSocket");
+
+        let (mut control_sink, mut control_stream) = control_ws.split();
+
+        let control_message = timeout(Duration::from_secs(2), control_stream.next())
+            .await
+            .expect("Timeout waiting for control message")
+            .expect("Control stream ended")
+            .expect("Error receiving control message");
+
+        if let Message::Text(text) = control_message {
+            let parsed: WebServerToWebClientControlMessage =
+                serde_json::from_str(&text).expect("Failed to parse control message");
+
+            match parsed {
+                WebServerToWebClientControlMessage::SetConfig(_) => {
+                    println!("✓ Received expected SetConfig message");
+                },
+                _ => panic!("Expected SetConfig message, got: {:?}", parsed),
+            }
+        } else {
+            panic!("Expected text message, got: {:?}", control_message);
+        }
+
+        let resize_msg = WebClientToWebServerControlMessage {
+            web_client_id: web_client_id.clone(),
+            payload: WebClientToWebServerControlMessagePayload::TerminalResize(Size {
+                rows: 30,
+                cols: 100,
+            }),
+        };
+
+        control_sink
+            .send(Message::Text(serde_json::to_string(&resize_msg).unwrap()))
+            .await
+            .expect("Failed to send resize message");
+
+        println!("✓ Sent terminal resize message");
+
+        let terminal_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/terminal?web_client_id={}",
+            port, web_client_id
+        );
+        let (terminal_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&terminal_ws_url, session_token),
+        )
+        .await
+        .expect("Terminal WebSocket connection timed out")
+        .expect("Failed to connect to terminal WebSocket");
+
+        let (mut terminal_sink, _terminal_stream) = terminal_ws.split();
+
+        terminal_sink
+            .send(Message::Text("echo hello\n".to_string()))
+            .await
+            .expect("Failed to send terminal input");
+
+        println!("✓ Sent terminal input");
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let mock_apis = factory_for_verification.mock_apis.lock().unwrap();
+        let mut found_resize = false;
+        let mut found_terminal_input = false;
+
+        for (_, mock_api) in mock_apis.iter() {
+            let messages = mock_api.get_sent_messages();
+            for msg in messages {
+                match msg {
+                    ClientToServerMsg::TerminalResize(_) => {
+                        found_resize = true;
+                    },
+                    ClientToServerMsg::Key(_, _, _) | ClientToServerMsg::Action(_, _, _) => {
+                        found_terminal_input = true;
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        assert!(
+            found_resize,
+            "Terminal resize message was not received by mock OS API"
+        );
+        println!("✓ Verified terminal resize message was processed by mock OS API");
+
+        assert!(
+            found_terminal_input,
+            "Terminal input message was not received by mock OS API"
+        );
+        println!("✓ Verified terminal input message was processed by mock OS API");
+
+        let _ = control_sink.close().await;
+        let _ = terminal_sink.close().await;
+        server_handle.abort();
+
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        println!("✓ Full session flow test completed successfully");
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_read_only_token_does_not_forward_terminal_input() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_read_only";
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::read_only(),
+            None,
+        )
+        .expect("Failed to create test token");
+
+        let mut session_manager = MockSessionManager::new();
+        session_manager
+            .mock_sessions
+            .insert("watched-session".to_owned(), true);
+        let session_manager = Arc::new(session_manager);
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+        let factory_for_verification = client_os_api_factory.clone();
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                WebServerListener::Tcp(listener),
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let session_token = login_and_get_session_token(port, &auth_token).await;
+        let web_client_id = create_client_session(port, &session_token).await;
+
+        let terminal_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/terminal/watched-session?web_client_id={}",
+            port, web_client_id
+        );
+        let (terminal_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&terminal_ws_url, &session_token),
+        )
+        .await
+        .expect("Terminal WebSocket connection timed out")
+        .expect("Failed to connect to terminal WebSocket");
+
+        let (mut terminal_sink, _terminal_stream) = terminal_ws.split();
+
+        terminal_sink
+            .send(Message::Text("echo hello\n".to_string()))
+            .await
+            .expect("Failed to send terminal input");
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let mock_apis = factory_for_verification.mock_apis.lock().unwrap();
+        let mut attached_as_read_only = false;
+        let mut found_terminal_input = false;
+
+        for (_, mock_api) in mock_apis.iter() {
+            for msg in mock_api.get_sent_messages() {
+                match msg {
+                    ClientToServerMsg::AttachClient(client_attributes, ..) => {
+                        attached_as_read_only = client_attributes.read_only;
+                    },
+                    ClientToServerMsg::Key(_, _, _) | ClientToServerMsg::Action(_, _, _) => {
+                        found_terminal_input = true;
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        assert!(
+            attached_as_read_only,
+            "Client logged in with a read-only token should attach as read-only"
+        );
+        assert!(
+            !found_terminal_input,
+            "Terminal input from a read-only client should not be sent to the server"
+        );
+
+        let _ = terminal_sink.close().await;
+        server_handle.abort();
+
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_token_restricted_to_sessions_cannot_attach_to_other_sessions() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_restricted";
+        let access = TokenAccess {
+            scope: WebTokenScope::Interactive,
+            allowed_sessions: vec!["work-*".to_owned()],
+        };
+        let (auth_token, _) = create_token(Some(test_token_name.to_string()), &access, None)
+            .expect("Failed to create test token");
+
+        let mut session_manager = MockSessionManager::new();
+        session_manager
+            .mock_sessions
+            .insert("work-api".to_owned(), true);
+        session_manager
+            .mock_sessions
+            .insert("personal".to_owned(), true);
+        let session_manager = Arc::new(session_manager);
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+        let factory_for_verification = client_os_api_factory.clone();
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                WebServerListener::Tcp(listener),
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let session_token = login_and_get_session_token(port, &auth_token).await;
+        let web_client_id = create_client_session(port, &session_token).await;
+
+        let forbidden_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/terminal/personal?web_client_id={}",
+            port, web_client_id
+        );
+        let forbidden_connection = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&forbidden_ws_url, &session_token),
+        )
+        .await
+        .expect("Terminal WebSocket connection timed out");
+        assert!(
+            forbidden_connection.is_err(),
+            "Session outside the token's allow-list should be refused"
+        );
+
+        let allowed_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/terminal/work-api?web_client_id={}",
+            port, web_client_id
+        );
+        let (terminal_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&allowed_ws_url, &session_token),
+        )
+        .await
+        .expect("Terminal WebSocket connection timed out")
+        .expect("Failed to connect to an allowed session");
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let mock_apis = factory_for_verification.mock_apis.lock().unwrap();
+        let attached = mock_apis.iter().any(|(_, mock_api)| {
+            mock_api
+                .get_sent_messages()
+                .iter()
+                .any(|msg| matches!(msg, ClientToServerMsg::AttachClient(..)))
+        });
+        assert!(
+            attached,
+            "Client should attach to a session in its allow-list"
+        );
+        drop(mock_apis);
+
+        let (mut terminal_sink, _terminal_stream) = terminal_ws.split();
+        let _ = terminal_sink.close().await;
+        server_handle.abort();
+
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_clients_are_disconnected_when_their_token_is_revoked() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_revoked_while_connected";
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
+
+        let mut session_manager = MockSessionManager::new();
+        session_manager.mock_sessions.insert("ci".to_owned(), true);
+        let session_manager = Arc::new(session_manager);
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                WebServerListener::Tcp(listener),
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let session_token = login_and_get_session_token(port, &auth_token).await;
+        let web_client_id = create_client_session(port, &session_token).await;
+        let terminal_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/terminal/ci?web_client_id={}",
+            port, web_client_id
+        );
+        let (terminal_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&terminal_ws_url, &session_token),
+        )
+        .await
+        .expect("Terminal WebSocket connection timed out")
+        .expect("Failed to connect to terminal WebSocket");
+        let (_terminal_sink, mut terminal_stream) = terminal_ws.split();
+
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+
+        let mut received_close_frame = false;
+        while let Ok(Some(Ok(msg))) = timeout(Duration::from_secs(10), terminal_stream.next()).await
+        {
+            if let Message::Close(_) = msg {
+                received_close_frame = true;
+                break;
+            }
+        }
+        assert!(
+            received_close_frame,
+            "Terminal WebSocket should be closed once its token is revoked"
+        );
+
+        server_handle.abort();
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_tab_and_pane_links_serve_the_client_page() {
+        let _ = delete_db();
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+
+        let server_handle = tokio::spawn(serve_web_client(
+            config,
+            options,
+            Some(temp_config_path),
+            WebServerListener::Tcp(listener),
+            None,
+            Some(session_manager),
+            Some(client_os_api_factory),
+        ));
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let get_status = |path: &str| {
+            let url = format!("http://127.0.0.1:{}{}", port, path);
+            async move {
+                timeout(
+                    Duration::from_secs(5),
+                    tokio::task::spawn_blocking(move || isahc::get(&url)),
+                )
+                .await
+                .expect("Request timed out")
+                .expect("Spawn blocking failed")
+                .expect("Request failed")
+                .status()
+            }
+        };
+
+        assert!(get_status("/ci/tab/build").await.is_success());
+        assert!(get_status("/ci/tab/build%20logs").await.is_success());
+        assert!(get_status("/ci/pane/3").await.is_success());
+        assert!(get_status("/ci/pane/plugin_2").await.is_success());
+        assert_eq!(get_status("/ci/pane/not-a-pane").await.as_u16(), 404);
+        assert_eq!(get_status("/ci/window/1").await.as_u16(), 404);
+
+        server_handle.abort();
+
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_links_are_followed_when_attaching_to_a_running_session() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_deep_links";
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
+
+        let mut session_manager = MockSessionManager::new();
+        session_manager.mock_sessions.insert("ci".to_owned(), true);
+        session_manager
+            .mock_tab_positions
+            .insert("build logs".to_owned(), 1);
+        let session_manager = Arc::new(session_manager);
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+        let factory_for_verification = client_os_api_factory.clone();
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                WebServerListener::Tcp(listener),
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let session_token = login_and_get_session_token(port, &auth_token).await;
+        let mut terminal_sinks = vec![];
+        for link in ["tab=build%20logs", "pane=terminal_3"] {
+            let web_client_id = create_client_session(port, &session_token).await;
+            let terminal_ws_url = format!(
+                "ws://127.0.0.1:{}/ws/terminal/ci?web_client_id={}&{}",
+                port, web_client_id, link
+            );
+            let (terminal_ws, _) = timeout(
+                Duration::from_secs(5),
+                connect_async_with_cookie(&terminal_ws_url, &session_token),
+            )
+            .await
+            .expect("Terminal WebSocket connection timed out")
+            .expect("Failed to connect to terminal WebSocket");
+            let (terminal_sink, _terminal_stream) = terminal_ws.split();
+            terminal_sinks.push(terminal_sink);
+        }
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let mock_apis = factory_for_verification.mock_apis.lock().unwrap();
+        let mut focus_when_attaching = vec![];
+        for (_, mock_api) in mock_apis.iter() {
+            for msg in mock_api.get_sent_messages() {
+                match msg {
+                    ClientToServerMsg::AttachClient(_, _, _, tab_position, pane_id, _) => {
+                        focus_when_attaching.push((tab_position, pane_id));
+                    },
+                    ClientToServerMsg::Action(..) => {
+                        panic!("Links should be followed by the attach message, not by actions")
+                    },
+                    _ => {},
+                }
+            }
+        }
+        drop(mock_apis);
+
+        assert!(focus_when_attaching.contains(&(Some(2), None)));
+        assert!(focus_when_attaching.contains(&(None, Some((3, false)))));
+
+        for mut terminal_sink in terminal_sinks {
+            let _ = terminal_sink.close().await;
+        }
+        server_handle.abort();
+
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_uploaded_files_are_sent_to_the_session() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_upload";
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
+
+        let mut session_manager = MockSessionManager::new();
+        session_manager
+            .mock_sessions
+            .insert("work".to_owned(), true);
+        let session_manager = Arc::new(session_manager);
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+        let factory_for_verification = client_os_api_factory.clone();
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                WebServerListener::Tcp(listener),
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let session_token = login_and_get_session_token(port, &auth_token).await;
+        let web_client_id = create_client_session(port, &session_token).await;
+
+        let terminal_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/terminal/work?web_client_id={}",
+            port, web_client_id
+        );
+        let (terminal_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&terminal_ws_url, &session_token),
+        )
+        .await
+        .expect("Terminal WebSocket connection timed out")
+        .expect("Failed to connect to terminal WebSocket");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(
+            upload_file(port, &session_token, &web_client_id, "notes.txt", "hello").await,
+            200
+        );
+        assert_eq!(
+            upload_file(
+                port,
+                &session_token,
+                &web_client_id,
+                "../notes.txt",
+                "hello"
+            )
+            .await,
+            400,
+            "Uploads can only name a file, not a path"
+        );
+        assert_eq!(
+            upload_file(port, &session_token, "unknown-client", "notes.txt", "hello").await,
+            404
+        );
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let mock_apis = factory_for_verification.mock_apis.lock().unwrap();
+        let received_files: Vec<(PathBuf, String)> = mock_apis
+            .iter()
+            .flat_map(|(_, mock_api)| mock_api.get_sent_messages())
+            .filter_map(|msg| match msg {
+                ClientToServerMsg::Action(Action::ReceiveFile(staged_file, file_name), ..) => {
+                    Some((staged_file, file_name))
+                },
+                _ => None,
+            })
+            .collect();
+        drop(mock_apis);
+        assert_eq!(
+            received_files.len(),
+            1,
+            "Only the valid upload reached the session"
+        );
+        let (staged_file, file_name) = &received_files[0];
+        assert_eq!(file_name, "notes.txt");
+        assert_eq!(std::fs::read_to_string(staged_file).unwrap(), "hello");
+        let _ = std::fs::remove_file(staged_file);
+
+        let recorded_upload = read_audit_log().unwrap_or_default().iter().any(|e| {
+            e.kind == WebAuditEventKind::FileUploaded
+                && e.token_name.as_deref() == Some(test_token_name)
+                && e.session_name.as_deref() == Some("work")
+        });
+        assert!(
+            recorded_upload,
+            "Upload should be recorded in the audit log"
+        );
+
+        let (mut terminal_sink, _terminal_stream) = terminal_ws.split();
+        let _ = terminal_sink.close().await;
+        server_handle.abort();
+
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_read_only_clients_cannot_upload_files() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_read_only_upload";
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::read_only(),
+            None,
+        )
+        .expect("Failed to create test token");
+
+        let mut session_manager = MockSessionManager::new();
+        session_manager
+            .mock_sessions
+            .insert("work".to_owned(), true);
+        let session_manager = Arc::new(session_manager);
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                WebServerListener::Tcp(listener),
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let session_token = login_and_get_session_token(port, &auth_token).await;
+        let web_client_id = create_client_session(port, &session_token).await;
+
+        let terminal_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/terminal/work?web_client_id={}",
+            port, web_client_id
+        );
+        let (terminal_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&terminal_ws_url, &session_token),
+        )
+        .await
+        .expect("Terminal WebSocket connection timed out")
+        .expect("Failed to connect to terminal WebSocket");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(
+            upload_file(port, &session_token, &web_client_id, "notes.txt", "hello").await,
+            403
+        );
+
+        let (mut terminal_sink, _terminal_stream) = terminal_ws.split();
+        let _ = terminal_sink.close().await;
+        server_handle.abort();
+
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_files_sent_by_the_session_can_be_downloaded_once() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_download";
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
 
-        assert!(client_response.status().is_success());
+        let mut session_manager = MockSessionManager::new();
+        session_manager
+            .mock_sessions
+            .insert("work".to_owned(), true);
+        let session_manager = Arc::new(session_manager);
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+        let factory_for_verification = client_os_api_factory.clone();
 
-        let client_data: serde_json::Value =
-            serde_json::from_str(&client_response.text().unwrap()).unwrap();
-        let web_client_id = client_data["web_client_id"].as_str().unwrap().to_string();
+        let config = Config::default();
+        let options = Options::default();
 
-        println!("✓ Successfully created client session");
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                WebServerListener::Tcp(listener),
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let session_token = login_and_get_session_token(port, &auth_token).await;
+        let web_client_id = create_client_session(port, &session_token).await;
 
         let control_ws_url = format!("ws://127.0.0.1:{}/ws/control", port);
         let (control_ws, _) = timeout(
             Duration::from_secs(5),
-            connect_async_with_cookie(&control_ws_url, session_token),
+            connect_async_with_cookie(&control_ws_url, &session_token),
         )
         .await
         .expect("Control WebSocket connection timed out")
         .expect("Failed to connect to control WebSocket");
-
         let (mut control_sink, mut control_stream) = control_ws.split();
-
-        let control_message = timeout(Duration::from_secs(2), control_stream.next())
-            .await
-            .expect("Timeout waiting for control message")
-            .expect("Control stream ended")
-            .expect("Error receiving control message");
-
-        if let Message::Text(text) = control_message {
-            let parsed: WebServerToWebClientControlMessage =
-                serde_json::from_str(&text).expect("Failed to parse control message");
-
-            match parsed {
-                WebServerToWebClientControlMessage::SetConfig(_) => {
-                    println!("✓ Received expected SetConfig message");
-                },
-                _ => panic!("Expected SetConfig message, got: {:?}", parsed),
-            }
-        } else {
-            panic!("Expected text message, got: {:?}", control_message);
-        }
-
         let resize_msg = WebClientToWebServerControlMessage {
             web_client_id: web_client_id.clone(),
             payload: WebClientToWebServerControlMessagePayload::TerminalResize(Size {
@@ -392,74 +1205,344 @@ mod web_client_tests {
                 cols: 100,
             }),
         };
-
         control_sink
             .send(Message::Text(serde_json::to_string(&resize_msg).unwrap()))
             .await
             .expect("Failed to send resize message");
+        tokio::time::sleep(Duration::from_millis(100)).await;
 
-        println!("✓ Sent terminal resize message");
+        let file_to_send = std::env::temp_dir().join("swarm_test_report.html");
+        std::fs::write(&file_to_send, "<h1>report</h1>").unwrap();
+        for (_, mock_api) in factory_for_verification.mock_apis.lock().unwrap().iter() {
+            mock_api.messages_from_server.lock().unwrap().push_back((
+                ServerToClientMsg::DownloadFile(file_to_send.clone()),
+                ErrorContext::new(),
+            ));
+        }
 
         let terminal_ws_url = format!(
-            "ws://127.0.0.1:{}/ws/terminal?web_client_id={}",
+            "ws://127.0.0.1:{}/ws/terminal/work?web_client_id={}",
             port, web_client_id
         );
         let (terminal_ws, _) = timeout(
             Duration::from_secs(5),
-            connect_async_with_cookie(&terminal_ws_url, session_token),
+            connect_async_with_cookie(&terminal_ws_url, &session_token),
         )
         .await
         .expect("Terminal WebSocket connection timed out")
         .expect("Failed to connect to terminal WebSocket");
 
+        let download_id = timeout(Duration::from_secs(5), async {
+            while let Some(Ok(Message::Text(text))) = control_stream.next().await {
+                if let Ok(WebServerToWebClientControlMessage::DownloadFile {
+                    download_id,
+                    file_name,
+                }) = serde_json::from_str(&text)
+                {
+                    assert_eq!(file_name, "swarm_test_report.html");
+                    return Some(download_id);
+                }
+            }
+            None
+        })
+        .await
+        .expect("Timeout waiting for the download to be offered")
+        .expect("Control stream ended before the download was offered");
+
+        let download = |download_id: String| {
+            let download_url = format!(
+                "http://127.0.0.1:{}/download/{}?web_client_id={}",
+                port, download_id, web_client_id
+            );
+            let session_token = session_token.clone();
+            async move {
+                timeout(
+                    Duration::from_secs(5),
+                    tokio::task::spawn_blocking(move || {
+                        isahc::Request::get(&download_url)
+                            .header("Cookie", format!("session_token={}", session_token))
+                            .body(())
+                            .unwrap()
+                            .send()
+                    }),
+                )
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap()
+            }
+        };
+
+        let mut response = download(download_id.clone()).await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers().get("content-disposition").unwrap(),
+            "attachment; filename=\"swarm_test_report.html\""
+        );
+        assert_eq!(response.text().unwrap(), "<h1>report</h1>");
+        assert_eq!(
+            download(download_id).await.status().as_u16(),
+            404,
+            "An offered download can only be fetched once"
+        );
+
+        let recorded_download = read_audit_log().unwrap_or_default().iter().any(|e| {
+            e.kind == WebAuditEventKind::FileDownloaded
+                && e.token_name.as_deref() == Some(test_token_name)
+                && e.session_name.as_deref() == Some("work")
+        });
+        assert!(
+            recorded_download,
+            "Download should be recorded in the audit log"
+        );
+
+        let _ = std::fs::remove_file(&file_to_send);
         let (mut terminal_sink, _terminal_stream) = terminal_ws.split();
+        let _ = terminal_sink.close().await;
+        let _ = control_sink.close().await;
+        server_handle.abort();
 
-        terminal_sink
-            .send(Message::Text("echo hello\n".to_string()))
-            .await
-            .expect("Failed to send terminal input");
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
 
-        println!("✓ Sent terminal input");
+    #[tokio::test]
+    #[serial]
+    async fn test_routes_and_assets_are_served_under_the_base_path() {
+        let _ = delete_db();
 
-        tokio::time::sleep(Duration::from_millis(500)).await;
+        let mut options = Options::default();
+        options.web_server_base_path = Some("swarm/".to_owned());
+        let (port, server_handle) = serve_with_options(options).await;
+
+        let get = |path: &str| {
+            let url = format!("http://127.0.0.1:{}{}", port, path);
+            async move {
+                timeout(
+                    Duration::from_secs(5),
+                    tokio::task::spawn_blocking(move || isahc::get(&url)),
+                )
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap()
+            }
+        };
 
-        let mock_apis = factory_for_verification.mock_apis.lock().unwrap();
-        let mut found_resize = false;
-        let mut found_terminal_input = false;
+        assert_eq!(get("/swarm/info/version").await.status(), 200);
+        assert_eq!(get("/info/version").await.status(), 404);
+        assert_eq!(get("/swarm/assets/utils.js").await.status(), 200);
+        assert_eq!(get("/swarm").await.status(), 200);
+        let response = get("/swarm/").await;
+        assert_eq!(response.status(), 308);
+        assert_eq!(response.headers().get("location").unwrap(), "/swarm");
+
+        let mut response = get("/swarm/work/tab/editor").await;
+        assert_eq!(response.status(), 200);
+        let page = response.text().unwrap();
+        assert!(page.contains("window.base_path = \"/swarm\";"));
+        assert!(page.contains("src=\"/swarm/assets/index.js\""));
+        assert!(!page.contains("\"/assets/"));
 
-        for (_, mock_api) in mock_apis.iter() {
-            let messages = mock_api.get_sent_messages();
-            for msg in messages {
-                match msg {
-                    ClientToServerMsg::TerminalResize(_) => {
-                        found_resize = true;
-                    },
-                    ClientToServerMsg::Key(_, _, _) | ClientToServerMsg::Action(_, _, _) => {
-                        found_terminal_input = true;
-                    },
-                    _ => {},
-                }
+        server_handle.abort();
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_forwarded_client_address_is_only_trusted_from_configured_proxies() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_forwarded";
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
+        let logins_from = |remote_address: &'static str| {
+            read_audit_log()
+                .unwrap_or_default()
+                .iter()
+                .filter(|e| {
+                    e.kind == WebAuditEventKind::Login
+                        && e.token_name.as_deref() == Some(test_token_name)
+                        && e.remote_address.as_deref() == Some(remote_address)
+                })
+                .count()
+        };
+        let login = |port: u16| {
+            let login_url = format!("http://127.0.0.1:{}/command/login", port);
+            let login_payload = serde_json::json!({ "auth_token": auth_token });
+            async move {
+                timeout(
+                    Duration::from_secs(5),
+                    tokio::task::spawn_blocking(move || {
+                        isahc::Request::post(&login_url)
+                            .header("Content-Type", "application/json")
+                            .header("X-Forwarded-For", "198.51.100.1, 203.0.113.7")
+                            .body(login_payload.to_string())
+                            .unwrap()
+                            .send()
+                    }),
+                )
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap()
             }
-        }
+        };
+        let logins_before = logins_from("203.0.113.7");
+
+        let (port, server_handle) = serve_with_options(Options::default()).await;
+        assert!(login(port).await.status().is_success());
+        assert_eq!(
+            logins_from("203.0.113.7"),
+            logins_before,
+            "headers from an untrusted peer are ignored"
+        );
+        server_handle.abort();
+        tokio::time::sleep(Duration::from_millis(100)).await;
 
-        assert!(
-            found_resize,
-            "Terminal resize message was not received by mock OS API"
+        let mut options = Options::default();
+        options.web_server_trusted_proxies = Some(vec![IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))]);
+        let (port, server_handle) = serve_with_options(options).await;
+        assert!(login(port).await.status().is_success());
+        assert_eq!(
+            logins_from("203.0.113.7"),
+            logins_before + 1,
+            "the address the trusted proxy got the request from is recorded"
         );
-        println!("✓ Verified terminal resize message was processed by mock OS API");
+        server_handle.abort();
 
-        assert!(
-            found_terminal_input,
-            "Terminal input message was not received by mock OS API"
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_plain_http_is_refused_when_https_is_expected_from_a_proxy() {
+        let _ = delete_db();
+
+        let mut options = Options::default();
+        options.enforce_https_for_localhost = Some(true);
+        options.web_server_trusted_proxies = Some(vec![IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))]);
+        let (port, server_handle) = serve_with_options(options).await;
+
+        let get_version = |forwarded_proto: Option<&'static str>| {
+            let url = format!("http://127.0.0.1:{}/info/version", port);
+            async move {
+                timeout(
+                    Duration::from_secs(5),
+                    tokio::task::spawn_blocking(move || {
+                        let mut request = isahc::Request::get(&url);
+                        if let Some(forwarded_proto) = forwarded_proto {
+                            request = request.header("X-Forwarded-Proto", forwarded_proto);
+                        }
+                        request.body(()).unwrap().send()
+                    }),
+                )
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap()
+                .status()
+                .as_u16()
+            }
+        };
+
+        assert_eq!(get_version(None).await, 403);
+        assert_eq!(get_version(Some("http")).await, 403);
+        assert_eq!(get_version(Some("https")).await, 200);
+        assert_eq!(
+            get_version(Some("https, http")).await,
+            403,
+            "the protocol is the one the closest proxy got the request with"
         );
-        println!("✓ Verified terminal input message was processed by mock OS API");
+        assert_eq!(get_version(Some("http, https")).await, 200);
 
-        let _ = control_sink.close().await;
-        let _ = terminal_sink.close().await;
         server_handle.abort();
+        // time for cleanup
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
 
-        revoke_token(test_token_name).expect("Failed to revoke test token");
-        println!("✓ Full session flow test completed successfully");
+    #[tokio::test]
+    #[serial]
+    async fn test_web_server_listens_on_a_unix_socket() {
+        let _ = delete_db();
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+
+        let socket_path = std::env::temp_dir().join(format!(
+            "swarm-web-{}.sock",
+            &Uuid::new_v4().to_string()[..8]
+        ));
+        let listener = WebServerListener::bind(
+            IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+            0,
+            &Some(socket_path.clone()),
+        )
+        .expect("Failed to bind unix socket");
+        assert_eq!(
+            std::fs::metadata(&socket_path)
+                .unwrap()
+                .permissions()
+                .mode()
+                & 0o777,
+            0o660,
+            "only our user and group can connect to the socket"
+        );
+        assert!(
+            WebServerListener::bind(
+                IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+                0,
+                &Some(socket_path.clone())
+            )
+            .is_err(),
+            "a socket that is being listened on is not taken over"
+        );
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(serve_web_client(
+            Config::default(),
+            Options::default(),
+            Some(temp_config_path),
+            listener,
+            None,
+            Some(session_manager),
+            Some(client_os_api_factory),
+        ));
+
+        let get_version = {
+            let socket_path = socket_path.clone();
+            move || {
+                isahc::Request::get("http://localhost/info/version")
+                    .dial(isahc::config::Dialer::unix_socket(socket_path.clone()))
+                    .body(())
+                    .unwrap()
+                    .send()
+            }
+        };
+        let start = Instant::now();
+        let mut response = loop {
+            let get_version = get_version.clone();
+            match tokio::task::spawn_blocking(get_version).await.unwrap() {
+                Ok(response) => break response,
+                Err(_) if start.elapsed() < Duration::from_secs(5) => {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                },
+                Err(e) => panic!("Server failed to start on unix socket: {:?}", e),
+            }
+        };
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().unwrap(), VERSION);
+
+        server_handle.abort();
+        let _ = std::fs::remove_file(&socket_path);
         // time for cleanup
         tokio::time::sleep(Duration::from_millis(100)).await;
     }
@@ -485,7 +1568,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -534,7 +1617,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -575,8 +1658,12 @@ mod web_client_tests {
         let _ = delete_db();
 
         let test_token_name = "test_token_server_shutdown";
-        let (auth_token, _) =
-            create_token(Some(test_token_name.to_string())).expect("Failed to create test token");
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
 
         let session_manager = Arc::new(MockSessionManager::new());
         let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
@@ -594,7 +1681,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -719,8 +1806,12 @@ mod web_client_tests {
         let _ = delete_db();
 
         let test_token_name = "test_token_client_cleanup";
-        let (auth_token, _) =
-            create_token(Some(test_token_name.to_string())).expect("Failed to create test token");
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
 
         let session_manager = Arc::new(MockSessionManager::new());
         let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
@@ -739,7 +1830,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -894,8 +1985,12 @@ mod web_client_tests {
         let _ = delete_db();
 
         let test_token_name = "test_token_cancellation";
-        let (auth_token, _) =
-            create_token(Some(test_token_name.to_string())).expect("Failed to create test token");
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
 
         let session_manager = Arc::new(MockSessionManager::new());
         let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
@@ -913,7 +2008,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -1043,8 +2138,12 @@ mod web_client_tests {
         let _ = delete_db();
 
         let test_token_name = "test_token_exit_reasons";
-        let (auth_token, _) =
-            create_token(Some(test_token_name.to_string())).expect("Failed to create test token");
+        let (auth_token, _) = create_token(
+            Some(test_token_name.to_string()),
+            &TokenAccess::default(),
+            None,
+        )
+        .expect("Failed to create test token");
 
         let session_manager = Arc::new(MockSessionManager::new());
         let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
@@ -1063,7 +2162,7 @@ mod web_client_tests {
                 config,
                 options,
                 Some(temp_config_path),
-                listener,
+                WebServerListener::Tcp(listener),
                 None,
                 Some(session_manager),
                 Some(client_os_api_factory),
@@ -1229,6 +2328,63 @@ mod web_client_tests {
         client_data["web_client_id"].as_str().unwrap().to_string()
     }
 
+    async fn serve_with_options(options: Options) -> (u16, tokio::task::JoinHandle<()>) {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(serve_web_client(
+            Config::default(),
+            options,
+            Some(temp_config_path),
+            WebServerListener::Tcp(listener),
+            None,
+            Some(session_manager),
+            Some(client_os_api_factory),
+        ));
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+        (port, server_handle)
+    }
+
+    async fn upload_file(
+        port: u16,
+        session_token: &str,
+        web_client_id: &str,
+        file_name: &str,
+        contents: &str,
+    ) -> u16 {
+        let upload_url = format!(
+            "http://127.0.0.1:{}/upload?web_client_id={}&file_name={}",
+            port,
+            web_client_id,
+            file_name.replace('/', "%2F")
+        );
+        let session_token = session_token.to_owned();
+        let contents = contents.to_owned();
+        timeout(
+            Duration::from_secs(5),
+            tokio::task::spawn_blocking(move || {
+                isahc::Request::post(&upload_url)
+                    .header("Cookie", format!("session_token={}", session_token))
+                    .body(contents)
+                    .unwrap()
+                    .send()
+            }),
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap()
+        .status()
+        .as_u16()
+    }
+
     async fn connect_async_with_cookie(
         url: &str,
         session_token: &str,
@@ -1262,6 +2418,7 @@ mod web_client_tests {
 pub struct MockSessionManager {
     pub mock_sessions: HashMap<String, bool>,
     pub mock_layouts: HashMap<String, Layout>,
+    pub mock_tab_positions: HashMap<String, usize>,
 }
 
 impl MockSessionManager {
@@ -1269,6 +2426,7 @@ impl MockSessionManager {
         Self {
             mock_sessions: HashMap::new(),
             mock_layouts: HashMap::new(),
+            mock_tab_positions: HashMap::new(),
         }
     }
 }
@@ -1287,6 +2445,10 @@ impl SessionManager for MockSessionManager {
         self.mock_layouts.get(session_name).cloned()
     }
 
+    fn tab_position(&self, _session_name: &str, tab_name: &str) -> Option<usize> {
+        self.mock_tab_positions.get(tab_name).copied()
+    }
+
     fn spawn_session_if_needed(
         &self,
         session_name: &str,
@@ -1297,6 +2459,8 @@ impl SessionManager for MockSessionManager {
         is_web_client: bool,
         _os_input: Box<dyn ClientOsApi>,
         _requested_layout: Option<LayoutInfo>,
+        tab_position_to_focus: Option<usize>,
+        pane_id_to_focus: Option<(u32, bool)>,
     ) -> (ClientToServerMsg, PathBuf) {
         let mock_ipc_path = PathBuf::from(format!("/tmp/mock_swarm_{}", session_name));
 
@@ -1304,8 +2468,8 @@ impl SessionManager for MockSessionManager {
             client_attributes,
             config.clone(),
             config_options.clone(),
-            None,
-            None,
+            tab_position_to_focus,
+            pane_id_to_focus,
             is_web_client,
         );
 
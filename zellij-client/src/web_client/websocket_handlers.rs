@@ -1,3 +1,4 @@
+use crate::web_client::authentication::{current_access, lost_access_reason};
 use crate::web_client::control_message::{
     SetConfigPayload, WebClientToWebServerControlMessage,
     WebClientToWebServerControlMessagePayload, WebServerToWebClientControlMessage,
@@ -5,19 +6,33 @@ use crate::web_client::control_message::{
 use crate::web_client::message_handlers::{
     parse_stdin, render_to_client, send_control_messages_to_client,
 };
-use crate::web_client::server_listener::swarm_server_listener;
-use crate::web_client::types::{AppState, TerminalParams};
+use crate::web_client::server_listener::{handle_exit_reason, swarm_server_listener};
+use crate::web_client::session_management::DeepLink;
+use crate::web_client::types::{
+    AppState, ClientConnectionBus, ConnectionTable, LoginTokenName, SessionToken, TerminalParams,
+};
 
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        Path as AxumPath, Query, State,
+        Extension, Path as AxumPath, Query, State,
     },
-    response::IntoResponse,
+    http::StatusCode,
+    response::{IntoResponse, Response},
 };
 use futures::StreamExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio_util::sync::CancellationToken;
-use zellij_utils::{input::mouse::MouseEvent, ipc::ClientToServerMsg};
+use zellij_utils::{
+    input::mouse::MouseEvent,
+    ipc::{ClientToServerMsg, ExitReason},
+    web_authentication_tokens::TokenAccess,
+};
+
+/// How often the login tokens of connected clients are checked again, they might have expired or
+/// been revoked since
+const ACCESS_CHECK_INTERVAL: Duration = Duration::from_secs(5);
 
 pub async fn ws_handler_control(
     ws: WebSocketUpgrade,
@@ -32,8 +47,30 @@ pub async fn ws_handler_terminal(
     session_name: Option<AxumPath<String>>,
     Query(params): Query<TerminalParams>,
     State(state): State<AppState>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_ws_terminal(socket, session_name, params, state))
+    Extension(access): Extension<TokenAccess>,
+    Extension(LoginTokenName(token_name)): Extension<LoginTokenName>,
+    Extension(SessionToken(session_token)): Extension<SessionToken>,
+) -> Response {
+    if let Some(AxumPath(session_name)) = &session_name {
+        if !access.allows_session(session_name) {
+            log::warn!(
+                "Refusing web client access to session {}: not allowed by its login token",
+                session_name
+            );
+            return StatusCode::FORBIDDEN.into_response();
+        }
+    }
+    ws.on_upgrade(move |socket| {
+        handle_ws_terminal(
+            socket,
+            session_name,
+            params,
+            state,
+            access,
+            token_name,
+            session_token,
+        )
+    })
 }
 
 async fn handle_ws_control(socket: WebSocket, state: AppState) {
@@ -111,8 +148,34 @@ async fn handle_ws_terminal(
     session_name: Option<AxumPath<String>>,
     params: TerminalParams,
     state: AppState,
+    access: TokenAccess,
+    token_name: Option<String>,
+    session_token: String,
 ) {
     let web_client_id = params.web_client_id;
+    let deep_link = DeepLink::from_terminal_params(params.tab.as_deref(), params.pane.as_deref())
+        .unwrap_or_else(|e| {
+            log::warn!(
+                "Ignoring link requested by web client {}: {}",
+                web_client_id,
+                e
+            );
+            None
+        });
+    // the client id was created with the same login, but prefer what was recorded for it then
+    let access = state
+        .connection_table
+        .lock()
+        .unwrap()
+        .get_client_access(&web_client_id)
+        .unwrap_or(access);
+    let token_name = state
+        .connection_table
+        .lock()
+        .unwrap()
+        .get_client_token_name(&web_client_id)
+        .or(token_name);
+    let read_only = access.scope.is_read_only();
     let Some(os_input) = state
         .connection_table
         .lock()
@@ -141,6 +204,9 @@ async fn handle_ws_terminal(
         Some(state.config_file_path.clone()),
         web_client_id.clone(),
         state.session_manager.clone(),
+        access.clone(),
+        token_name,
+        deep_link,
     );
 
     let terminal_channel_cancellation_token = CancellationToken::new();
@@ -155,8 +221,15 @@ async fn handle_ws_terminal(
         .unwrap()
         .add_client_terminal_channel_cancellation_token(
             &web_client_id,
-            terminal_channel_cancellation_token,
+            terminal_channel_cancellation_token.clone(),
         );
+    disconnect_when_access_is_lost(
+        session_token,
+        access,
+        web_client_id.clone(),
+        state.connection_table.clone(),
+        terminal_channel_cancellation_token,
+    );
 
     let explicitly_disable_kitty_keyboard_protocol = state
         .config
@@ -167,6 +240,9 @@ async fn handle_ws_terminal(
     let mut mouse_old_event = MouseEvent::new();
     while let Some(Ok(msg)) = client_terminal_channel_rx.next().await {
         match msg {
+            Message::Text(_) if read_only => {
+                // the server drops these as well, there's no need to send them over
+            },
             Message::Text(msg) => {
                 let Some(client_connection) = state
                     .connection_table
@@ -200,3 +276,43 @@ async fn handle_ws_terminal(
     }
     os_input.send_to_server(ClientToServerMsg::ClientExited);
 }
+
+// the token is checked when connecting, but it can expire, be revoked or have its access changed
+// while the client is connected
+fn disconnect_when_access_is_lost(
+    session_token: String,
+    connected_with: TokenAccess,
+    web_client_id: String,
+    connection_table: Arc<Mutex<ConnectionTable>>,
+    cancellation_token: CancellationToken,
+) {
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(ACCESS_CHECK_INTERVAL) => {},
+                _ = cancellation_token.cancelled() => break,
+            }
+            let session_token = session_token.clone();
+            let current_access =
+                tokio::task::spawn_blocking(move || current_access(&session_token))
+                    .await
+                    .ok()
+                    .flatten();
+            let session_name = connection_table
+                .lock()
+                .unwrap()
+                .get_client_session_name(&web_client_id);
+            if let Some(reason) = lost_access_reason(
+                &connected_with,
+                current_access.as_ref(),
+                session_name.as_deref(),
+            ) {
+                log::info!("Disconnecting web client {}: {}", web_client_id, reason);
+                let mut client_connection_bus =
+                    ClientConnectionBus::new(&web_client_id, &connection_table);
+                handle_exit_reason(&mut client_connection_bus, ExitReason::Error(reason));
+                break;
+            }
+        }
+    });
+}
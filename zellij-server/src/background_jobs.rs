@@ -1,23 +1,32 @@
 use async_std::task;
+use uuid::Uuid;
 use zellij_utils::consts::{
     session_info_cache_file_name, session_info_folder_for_session, session_layout_cache_file_name,
-    VERSION, SWARM_SESSION_INFO_CACHE_DIR, SWARM_SOCK_DIR,
+    session_snapshot_layout_file_name, session_snapshots_folder_for_session,
+    SESSION_SNAPSHOT_LAYOUT_FILE_NAME, SWARM_SESSION_INFO_CACHE_DIR, SWARM_SOCK_DIR,
+    SWARM_WEB_UPLOADS_DIR, VERSION,
 };
-use zellij_utils::data::{Event, HttpVerb, SessionInfo, WebServerStatus};
+use zellij_utils::data::{ConnectionKind, Event, HttpVerb, SessionInfo, WebServerStatus};
 use zellij_utils::errors::{prelude::*, BackgroundJobContext, ContextType};
 use zellij_utils::input::layout::RunPlugin;
 
 use isahc::prelude::*;
 use isahc::AsyncReadResponseExt;
-use isahc::{config::RedirectPolicy, HttpClient, Request};
+use isahc::{
+    config::{Dialer, RedirectPolicy},
+    HttpClient, Request,
+};
 
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io::Write;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
 use std::os::unix::fs::FileTypeExt;
-use std::path::PathBuf;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
+    mpsc::{channel, Receiver, Sender, TryRecvError},
     Arc, Mutex,
 };
 use std::time::{Duration, Instant};
@@ -25,8 +34,8 @@ use std::time::{Duration, Instant};
 use crate::panes::PaneId;
 use crate::plugins::{PluginId, PluginInstruction};
 use crate::screen::ScreenInstruction;
-use crate::thread_bus::Bus;
-use crate::ClientId;
+use crate::thread_bus::{Bus, ThreadSenders};
+use crate::{ClientId, ServerInstruction};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum BackgroundJob {
@@ -35,6 +44,7 @@ pub enum BackgroundJob {
     StopPluginLoadingAnimation(u32),                      // u32 - plugin_id
     ReadAllSessionInfosOnMachine,                         // u32 - plugin_id
     ReportSessionInfo(String, SessionInfo),               // String - session name
+    ReportSessionActivity(u64),                           // u64 - seconds since the unix epoch
     ReportPluginList(BTreeMap<PluginId, RunPlugin>),      // String - session name
     ReportLayoutInfo((String, BTreeMap<String, String>)), // BTreeMap<file_name, pane_contents>
     RunCommand(
@@ -55,9 +65,22 @@ pub enum BackgroundJob {
         Vec<u8>,                  // body
         BTreeMap<String, String>, // context
     ),
+    OpenConnection(
+        PluginId,
+        ClientId,
+        ConnectionKind,
+        String,                   // address
+        BTreeMap<String, String>, // context
+    ),
+    SendToConnection(PluginId, u32, Vec<u8>), // u32 - connection_id
+    CloseConnection(PluginId, u32),           // u32 - connection_id
+    ClosePluginConnections(PluginId),
+    ConnectionEnded(u32), // u32 - connection_id
     HighlightPanesWithMessage(Vec<PaneId>, String),
     RenderToClients,
     QuerySwarmWebServerStatus,
+    WriteSessionSnapshot(String, (String, BTreeMap<String, String>), ClientId), // String - name
+    MoveUploadedFile(PathBuf, PathBuf, String, ClientId), // staged file, destination folder, name
     Exit,
 }
 
@@ -73,9 +96,17 @@ impl From<&BackgroundJob> for BackgroundJobContext {
                 BackgroundJobContext::ReadAllSessionInfosOnMachine
             },
             BackgroundJob::ReportSessionInfo(..) => BackgroundJobContext::ReportSessionInfo,
+            BackgroundJob::ReportSessionActivity(..) => BackgroundJobContext::ReportSessionActivity,
             BackgroundJob::ReportLayoutInfo(..) => BackgroundJobContext::ReportLayoutInfo,
             BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
             BackgroundJob::WebRequest(..) => BackgroundJobContext::WebRequest,
+            BackgroundJob::OpenConnection(..) => BackgroundJobContext::OpenConnection,
+            BackgroundJob::SendToConnection(..) => BackgroundJobContext::SendToConnection,
+            BackgroundJob::CloseConnection(..) => BackgroundJobContext::CloseConnection,
+            BackgroundJob::ClosePluginConnections(..) => {
+                BackgroundJobContext::ClosePluginConnections
+            },
+            BackgroundJob::ConnectionEnded(..) => BackgroundJobContext::ConnectionEnded,
             BackgroundJob::ReportPluginList(..) => BackgroundJobContext::ReportPluginList,
             BackgroundJob::RenderToClients => BackgroundJobContext::ReportPluginList,
             BackgroundJob::HighlightPanesWithMessage(..) => {
@@ -84,6 +115,8 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::QuerySwarmWebServerStatus => {
                 BackgroundJobContext::QuerySwarmWebServerStatus
             },
+            BackgroundJob::WriteSessionSnapshot(..) => BackgroundJobContext::WriteSessionSnapshot,
+            BackgroundJob::MoveUploadedFile(..) => BackgroundJobContext::MoveUploadedFile,
             BackgroundJob::Exit => BackgroundJobContext::Exit,
         }
     }
@@ -95,12 +128,16 @@ static PLUGIN_ANIMATION_OFFSET_DURATION_MD: u64 = 500;
 static SESSION_READ_DURATION: u64 = 1000;
 static DEFAULT_SERIALIZATION_INTERVAL: u64 = 60000;
 static REPAINT_DELAY_MS: u64 = 10;
+static CONNECTION_POLL_INTERVAL_MS: u64 = 20;
+static CONNECTION_READ_BUFFER_SIZE: usize = 8192;
+static CONNECTION_TIMEOUT_MS: u64 = 10000;
 
 pub(crate) fn background_jobs_main(
     bus: Bus<BackgroundJob>,
     serialization_interval: Option<u64>,
     disable_session_metadata: bool,
     web_server_base_url: String,
+    web_server_socket: Option<PathBuf>,
 ) -> Result<()> {
     let err_context = || "failed to write to pty".to_string();
     let mut running_jobs: HashMap<BackgroundJob, Instant> = HashMap::new();
@@ -114,6 +151,9 @@ pub(crate) fn background_jobs_main(
     let serialization_interval = serialization_interval.map(|s| s * 1000); // convert to
                                                                            // milliseconds
     let last_render_request: Arc<Mutex<Option<Instant>>> = Arc::new(Mutex::new(None));
+    let mut plugin_connections: HashMap<u32, (PluginId, Sender<ConnectionCommand>)> =
+        HashMap::new(); // u32 - connection_id
+    let mut next_connection_id: u32 = 0;
 
     let http_client = HttpClient::builder()
         // TODO: timeout?
@@ -177,12 +217,81 @@ pub(crate) fn background_jobs_main(
                 *current_session_name.lock().unwrap() = session_name;
                 *current_session_info.lock().unwrap() = session_info;
             },
+            BackgroundJob::ReportSessionActivity(last_activity) => {
+                current_session_info.lock().unwrap().last_activity = Some(last_activity);
+            },
             BackgroundJob::ReportPluginList(plugin_list) => {
                 *current_session_plugin_list.lock().unwrap() = plugin_list;
             },
             BackgroundJob::ReportLayoutInfo(session_layout) => {
                 *current_session_layout.lock().unwrap() = session_layout;
             },
+            BackgroundJob::WriteSessionSnapshot(snapshot_name, session_layout, client_id) => {
+                let current_session_name = current_session_name.lock().unwrap().to_string();
+                match write_session_snapshot_to_disk(
+                    &current_session_name,
+                    &snapshot_name,
+                    session_layout,
+                ) {
+                    Ok(layout_file_name) => {
+                        let _ = bus.senders.send_to_server(ServerInstruction::Log(
+                            vec![format!(
+                                "Saved snapshot \"{}\" of session \"{}\" to {}",
+                                snapshot_name,
+                                current_session_name,
+                                layout_file_name.display()
+                            )],
+                            client_id,
+                        ));
+                    },
+                    Err(e) => {
+                        log::error!("Failed to write session snapshot: {}", e);
+                        let _ = bus.senders.send_to_server(ServerInstruction::LogError(
+                            vec![format!("Failed to write session snapshot: {}", e)],
+                            client_id,
+                        ));
+                    },
+                }
+            },
+            BackgroundJob::MoveUploadedFile(
+                staged_file,
+                destination_folder,
+                file_name,
+                client_id,
+            ) => {
+                // the staged file comes with the action, only ever move (or remove) files the web
+                // server staged
+                if !is_staged_upload(&staged_file, &SWARM_WEB_UPLOADS_DIR) {
+                    log::error!(
+                        "Refusing to move {}, it is not a staged upload",
+                        staged_file.display()
+                    );
+                    let _ = bus.senders.send_to_server(ServerInstruction::LogError(
+                        vec![format!(
+                            "Failed to upload {}: not a staged upload",
+                            file_name
+                        )],
+                        client_id,
+                    ));
+                    continue;
+                }
+                match move_uploaded_file(&staged_file, &destination_folder, &file_name) {
+                    Ok(destination) => {
+                        let _ = bus.senders.send_to_server(ServerInstruction::Log(
+                            vec![format!("Uploaded {}", destination.display())],
+                            client_id,
+                        ));
+                    },
+                    Err(e) => {
+                        log::error!("Failed to move uploaded file {}: {}", file_name, e);
+                        let _ = fs::remove_file(&staged_file);
+                        let _ = bus.senders.send_to_server(ServerInstruction::LogError(
+                            vec![format!("Failed to upload {}: {}", file_name, e)],
+                            client_id,
+                        ));
+                    },
+                }
+            },
             BackgroundJob::ReadAllSessionInfosOnMachine => {
                 // this job should only be run once and it keeps track of other sessions (as well
                 // as this one's) infos (metadata mostly) and sends it to the screen which in turn
@@ -376,6 +485,93 @@ pub(crate) fn background_jobs_main(
                     }
                 });
             },
+            BackgroundJob::OpenConnection(plugin_id, client_id, kind, address, context) => {
+                let connection_id = next_connection_id;
+                next_connection_id += 1;
+                let (command_sender, command_receiver) = channel();
+                plugin_connections.insert(connection_id, (plugin_id, command_sender));
+                // connections are blocking, so each one gets its own thread (same as RunCommand)
+                std::thread::spawn({
+                    let senders = bus.senders.clone();
+                    move || {
+                        match PluginConnectionStream::connect(kind, &address) {
+                            Ok(stream) => {
+                                let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                                    Some(plugin_id),
+                                    Some(client_id),
+                                    Event::ConnectionOpened(connection_id, context.clone()),
+                                )]));
+                                let error = run_plugin_connection(
+                                    stream,
+                                    command_receiver,
+                                    &senders,
+                                    plugin_id,
+                                    client_id,
+                                    connection_id,
+                                );
+                                let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                                    Some(plugin_id),
+                                    Some(client_id),
+                                    Event::ConnectionClosed(connection_id, error, context),
+                                )]));
+                            },
+                            Err(e) => {
+                                log::error!("Failed to open connection to {}: {}", address, e);
+                                let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                                    Some(plugin_id),
+                                    Some(client_id),
+                                    Event::ConnectionClosed(
+                                        connection_id,
+                                        Some(e.to_string()),
+                                        context,
+                                    ),
+                                )]));
+                            },
+                        }
+                        // so that its sender is not kept around until the plugin is unloaded
+                        let _ = senders
+                            .send_to_background_jobs(BackgroundJob::ConnectionEnded(connection_id));
+                    }
+                });
+            },
+            BackgroundJob::SendToConnection(plugin_id, connection_id, data) => {
+                match plugin_connections.get(&connection_id) {
+                    Some((owner_plugin_id, command_sender)) if *owner_plugin_id == plugin_id => {
+                        if command_sender.send(ConnectionCommand::Send(data)).is_err() {
+                            // the connection was closed on the remote end
+                            plugin_connections.remove(&connection_id);
+                        }
+                    },
+                    _ => {
+                        log::error!(
+                            "Plugin {} cannot send to unknown connection {}",
+                            plugin_id,
+                            connection_id
+                        );
+                    },
+                }
+            },
+            BackgroundJob::CloseConnection(plugin_id, connection_id) => {
+                match plugin_connections.get(&connection_id) {
+                    Some((owner_plugin_id, _)) if *owner_plugin_id == plugin_id => {
+                        // dropping the sender signals the connection thread to close
+                        plugin_connections.remove(&connection_id);
+                    },
+                    _ => {
+                        log::error!(
+                            "Plugin {} cannot close unknown connection {}",
+                            plugin_id,
+                            connection_id
+                        );
+                    },
+                }
+            },
+            BackgroundJob::ClosePluginConnections(plugin_id) => {
+                plugin_connections.retain(|_, (owner_plugin_id, _)| *owner_plugin_id != plugin_id);
+            },
+            BackgroundJob::ConnectionEnded(connection_id) => {
+                plugin_connections.remove(&connection_id);
+            },
             BackgroundJob::QuerySwarmWebServerStatus => {
                 if !cfg!(feature = "web_server_capability") {
                     // no web server capability, no need to query
@@ -386,16 +582,21 @@ pub(crate) fn background_jobs_main(
                     let http_client = http_client.clone();
                     let senders = bus.senders.clone();
                     let web_server_base_url = web_server_base_url.clone();
+                    let web_server_socket = web_server_socket.clone();
                     async move {
                         async fn web_request(
                             http_client: HttpClient,
                             web_server_base_url: &str,
+                            web_server_socket: Option<PathBuf>,
                         ) -> Result<
                             (u16, Vec<u8>), // status_code, body
                             isahc::Error,
                         > {
-                            let request =
+                            let mut request =
                                 Request::get(format!("{}/info/version", web_server_base_url,));
+                            if let Some(web_server_socket) = web_server_socket {
+                                request = request.dial(Dialer::unix_socket(web_server_socket));
+                            }
                             let req = request.body(())?;
                             let mut res = http_client.send_async(req).await?;
 
@@ -409,7 +610,9 @@ pub(crate) fn background_jobs_main(
                         };
 
                         let http_client = http_client.clone();
-                        match web_request(http_client, &web_server_base_url).await {
+                        match web_request(http_client, &web_server_base_url, web_server_socket)
+                            .await
+                        {
                             Ok((status, body)) => {
                                 if status == 200 && &body == VERSION.as_bytes() {
                                     // online
@@ -530,6 +733,7 @@ pub(crate) fn background_jobs_main(
                 for loading_plugin in loading_plugins.values() {
                     loading_plugin.store(false, Ordering::SeqCst);
                 }
+                plugin_connections.clear();
 
                 let cache_file_name =
                     session_info_cache_file_name(&current_session_name.lock().unwrap().to_owned());
@@ -562,6 +766,162 @@ fn job_already_running(
     }
 }
 
+enum ConnectionCommand {
+    Send(Vec<u8>),
+}
+
+enum ConnectionRead {
+    Data(Vec<u8>),
+    Idle,
+    Closed,
+}
+
+enum PluginConnectionStream {
+    Tcp(TcpStream),
+    UnixSocket(UnixStream),
+    WebSocket(tungstenite::WebSocket<TcpStream>),
+}
+
+impl PluginConnectionStream {
+    fn connect(kind: ConnectionKind, address: &str) -> std::io::Result<Self> {
+        let poll_interval = Some(Duration::from_millis(CONNECTION_POLL_INTERVAL_MS));
+        match kind {
+            ConnectionKind::Tcp => {
+                let stream = connect_tcp_with_timeout(address)?;
+                stream.set_read_timeout(poll_interval)?;
+                Ok(PluginConnectionStream::Tcp(stream))
+            },
+            ConnectionKind::UnixSocket => {
+                let stream = UnixStream::connect(address)?;
+                stream.set_read_timeout(poll_interval)?;
+                Ok(PluginConnectionStream::UnixSocket(stream))
+            },
+            ConnectionKind::WebSocket => {
+                let url = url::Url::parse(address).map_err(to_io_error)?;
+                if url.scheme() != "ws" {
+                    return Err(to_io_error(format!(
+                        "Unsupported WebSocket scheme: {}",
+                        url.scheme()
+                    )));
+                }
+                let host = url
+                    .host_str()
+                    .ok_or_else(|| to_io_error("WebSocket url has no host"))?;
+                let port = url.port_or_known_default().unwrap_or(80);
+                let stream = connect_tcp_with_timeout((host, port))?;
+                // so that a server that never answers the handshake does not hang the connection
+                stream.set_read_timeout(Some(Duration::from_millis(CONNECTION_TIMEOUT_MS)))?;
+                let (web_socket, _response) =
+                    tungstenite::client(address, stream).map_err(to_io_error)?;
+                web_socket.get_ref().set_read_timeout(poll_interval)?;
+                Ok(PluginConnectionStream::WebSocket(web_socket))
+            },
+        }
+    }
+    fn send(&mut self, data: Vec<u8>) -> std::io::Result<()> {
+        match self {
+            PluginConnectionStream::Tcp(stream) => stream.write_all(&data),
+            PluginConnectionStream::UnixSocket(stream) => stream.write_all(&data),
+            PluginConnectionStream::WebSocket(web_socket) => {
+                let message = match String::from_utf8(data) {
+                    Ok(text) => tungstenite::Message::Text(text),
+                    Err(e) => tungstenite::Message::Binary(e.into_bytes()),
+                };
+                web_socket.send(message).map_err(to_io_error)
+            },
+        }
+    }
+    fn read(&mut self) -> std::io::Result<ConnectionRead> {
+        let mut buf = [0u8; CONNECTION_READ_BUFFER_SIZE];
+        let read_result = match self {
+            PluginConnectionStream::Tcp(stream) => stream.read(&mut buf),
+            PluginConnectionStream::UnixSocket(stream) => stream.read(&mut buf),
+            PluginConnectionStream::WebSocket(web_socket) => {
+                return match web_socket.read() {
+                    Ok(tungstenite::Message::Text(text)) => {
+                        Ok(ConnectionRead::Data(text.into_bytes()))
+                    },
+                    Ok(tungstenite::Message::Binary(data)) => Ok(ConnectionRead::Data(data)),
+                    Ok(tungstenite::Message::Close(_)) => Ok(ConnectionRead::Closed),
+                    Ok(_) => Ok(ConnectionRead::Idle), // ping/pong are handled by tungstenite
+                    Err(tungstenite::Error::ConnectionClosed)
+                    | Err(tungstenite::Error::AlreadyClosed) => Ok(ConnectionRead::Closed),
+                    Err(tungstenite::Error::Io(e)) if is_idle_error(&e) => Ok(ConnectionRead::Idle),
+                    Err(e) => Err(to_io_error(e)),
+                };
+            },
+        };
+        match read_result {
+            Ok(0) => Ok(ConnectionRead::Closed),
+            Ok(read_bytes) => Ok(ConnectionRead::Data(buf[..read_bytes].to_vec())),
+            Err(e) if is_idle_error(&e) => Ok(ConnectionRead::Idle),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn connect_tcp_with_timeout(address: impl ToSocketAddrs) -> std::io::Result<TcpStream> {
+    let timeout = Duration::from_millis(CONNECTION_TIMEOUT_MS);
+    let mut last_error = None;
+    for socket_address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| to_io_error("Address did not resolve to anything")))
+}
+
+fn is_idle_error(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::WouldBlock
+            | std::io::ErrorKind::TimedOut
+            | std::io::ErrorKind::Interrupted
+    )
+}
+
+fn to_io_error<E: Into<Box<dyn std::error::Error + Send + Sync>>>(e: E) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, e)
+}
+
+// returns an error if the connection was terminated because of one, None if it was closed by
+// either side
+fn run_plugin_connection(
+    mut stream: PluginConnectionStream,
+    command_receiver: Receiver<ConnectionCommand>,
+    senders: &ThreadSenders,
+    plugin_id: PluginId,
+    client_id: ClientId,
+    connection_id: u32,
+) -> Option<String> {
+    loop {
+        loop {
+            match command_receiver.try_recv() {
+                Ok(ConnectionCommand::Send(data)) => {
+                    if let Err(e) = stream.send(data) {
+                        return Some(e.to_string());
+                    }
+                },
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return None, // closed by the plugin
+            }
+        }
+        match stream.read() {
+            Ok(ConnectionRead::Data(data)) => {
+                let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                    Some(plugin_id),
+                    Some(client_id),
+                    Event::ConnectionMessage(connection_id, data),
+                )]));
+            },
+            Ok(ConnectionRead::Idle) => {},
+            Ok(ConnectionRead::Closed) => return None,
+            Err(e) => return Some(e.to_string()),
+        }
+    }
+}
+
 fn write_session_state_to_disk(
     current_session_name: String,
     current_session_info: SessionInfo,
@@ -595,6 +955,122 @@ fn write_session_state_to_disk(
     }
 }
 
+fn write_session_snapshot_to_disk(
+    current_session_name: &str,
+    snapshot_name: &str,
+    session_layout: (String, BTreeMap<String, String>),
+) -> std::io::Result<PathBuf> {
+    let (session_layout, layout_files_to_write) = session_layout;
+    let layout_file_name = session_snapshot_layout_file_name(current_session_name, snapshot_name)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let snapshots_folder = session_snapshots_folder_for_session(current_session_name);
+    let snapshot_folder = snapshots_folder.join(snapshot_name);
+    // the snapshot is written next to where it goes (dotted names are not valid snapshot names,
+    // so it is not listed meanwhile) and only replaces a snapshot by the same name once it is
+    // complete, so that a failed write does not lose the existing snapshot
+    let id = Uuid::new_v4();
+    let new_snapshot_folder = snapshots_folder.join(format!(".{}.new-{}", snapshot_name, id));
+    let write_new_snapshot = || -> std::io::Result<()> {
+        fs::create_dir_all(&new_snapshot_folder)?;
+        fs::write(
+            new_snapshot_folder.join(SESSION_SNAPSHOT_LAYOUT_FILE_NAME),
+            session_layout,
+        )?;
+        for (external_file_name, external_file_contents) in layout_files_to_write {
+            fs::write(
+                new_snapshot_folder.join(external_file_name),
+                external_file_contents,
+            )?;
+        }
+        Ok(())
+    };
+    if let Err(e) = write_new_snapshot() {
+        let _ = fs::remove_dir_all(&new_snapshot_folder);
+        return Err(e);
+    }
+    // a directory cannot be renamed over a non-empty one, so the old snapshot is moved aside
+    // first and only removed once the new one is in place
+    let old_snapshot_folder = snapshots_folder.join(format!(".{}.old-{}", snapshot_name, id));
+    let replaces_existing_snapshot = snapshot_folder.exists();
+    if replaces_existing_snapshot {
+        fs::rename(&snapshot_folder, &old_snapshot_folder)?;
+    }
+    if let Err(e) = fs::rename(&new_snapshot_folder, &snapshot_folder) {
+        if replaces_existing_snapshot {
+            let _ = fs::rename(&old_snapshot_folder, &snapshot_folder);
+        }
+        let _ = fs::remove_dir_all(&new_snapshot_folder);
+        return Err(e);
+    }
+    if replaces_existing_snapshot {
+        let _ = fs::remove_dir_all(&old_snapshot_folder);
+    }
+    Ok(layout_file_name)
+}
+
+// a regular file directly in the uploads folder (not eg. a symlink placed there)
+fn is_staged_upload(staged_file: &Path, uploads_folder: &Path) -> bool {
+    let is_regular_file = fs::symlink_metadata(staged_file)
+        .map(|metadata| metadata.file_type().is_file())
+        .unwrap_or(false);
+    let staged_file_folder = staged_file.parent().and_then(|f| fs::canonicalize(f).ok());
+    is_regular_file
+        && staged_file_folder.is_some()
+        && staged_file_folder == fs::canonicalize(uploads_folder).ok()
+}
+
+// never overwrites, an upload named like an existing file gets a " (1)", " (2)"... suffix
+fn move_uploaded_file(
+    staged_file: &Path,
+    destination_folder: &Path,
+    file_name: &str,
+) -> std::io::Result<PathBuf> {
+    let file_name = Path::new(file_name)
+        .file_name()
+        .map(Path::new)
+        .ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid file name")
+        })?;
+    let stem = file_name
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = file_name
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let mut destination = destination_folder.join(file_name);
+    let mut suffix = 1;
+    // creating the destination claims its name, so nothing created in the meantime is replaced
+    let mut destination_file = loop {
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&destination)
+        {
+            Ok(destination_file) => break destination_file,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                destination =
+                    destination_folder.join(format!("{} ({}){}", stem, suffix, extension));
+                suffix += 1;
+            },
+            Err(e) => return Err(e),
+        }
+    };
+    // the staging folder might be on another filesystem, in which case we can't just rename
+    // (over the file we just created)
+    if fs::rename(staged_file, &destination).is_err() {
+        let copied = fs::File::open(staged_file)
+            .and_then(|mut staged| std::io::copy(&mut staged, &mut destination_file));
+        if let Err(e) = copied {
+            let _ = fs::remove_file(&destination);
+            return Err(e);
+        }
+        fs::remove_file(staged_file)?;
+    }
+    Ok(destination)
+}
+
 fn read_other_live_session_states(current_session_name: &str) -> BTreeMap<String, SessionInfo> {
     let mut other_session_names = vec![];
     let mut session_infos_on_machine = BTreeMap::new();
@@ -673,3 +1149,116 @@ fn find_resurrectable_sessions(
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use zellij_utils::channels::{self, SenderWithContext};
+
+    #[test]
+    fn plugin_connections_exchange_data_until_the_remote_end_closes() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let remote_end = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(b"hello").unwrap();
+            let mut received = [0u8; 4];
+            stream.read_exact(&mut received).unwrap();
+            received.to_vec() // the stream is dropped here, closing the connection
+        });
+        let (to_plugin, plugin_receiver) = channels::unbounded();
+        let senders = ThreadSenders {
+            to_screen: None,
+            to_pty: None,
+            to_plugin: Some(SenderWithContext::new(to_plugin)),
+            to_server: None,
+            to_pty_writer: None,
+            to_background_jobs: None,
+            should_silently_fail: false,
+        };
+        let (command_sender, command_receiver) = channel();
+        command_sender
+            .send(ConnectionCommand::Send(b"ping".to_vec()))
+            .unwrap();
+
+        let stream = PluginConnectionStream::connect(ConnectionKind::Tcp, &address).unwrap();
+        let error = run_plugin_connection(stream, command_receiver, &senders, 1, 1, 0);
+
+        assert_eq!(error, None);
+        assert_eq!(remote_end.join().unwrap(), b"ping");
+        let received_data: Vec<u8> = plugin_receiver
+            .try_iter()
+            .flat_map(|(instruction, _)| match instruction {
+                PluginInstruction::Update(updates) => updates,
+                _ => vec![],
+            })
+            .flat_map(|(_, _, event)| match event {
+                Event::ConnectionMessage(0, data) => data,
+                _ => vec![],
+            })
+            .collect();
+        assert_eq!(received_data, b"hello");
+    }
+
+    #[test]
+    fn uploaded_files_never_replace_existing_files() {
+        let uploads_folder = tempfile::tempdir().unwrap();
+        let destination_folder = tempfile::tempdir().unwrap();
+        fs::write(destination_folder.path().join("notes.txt"), "existing").unwrap();
+        fs::write(destination_folder.path().join("notes (1).txt"), "existing").unwrap();
+        let staged_file = uploads_folder.path().join("staged");
+        fs::write(&staged_file, "uploaded").unwrap();
+
+        let destination =
+            move_uploaded_file(&staged_file, destination_folder.path(), "notes.txt").unwrap();
+        assert_eq!(destination, destination_folder.path().join("notes (2).txt"));
+        assert_eq!(fs::read_to_string(&destination).unwrap(), "uploaded");
+        assert_eq!(
+            fs::read_to_string(destination_folder.path().join("notes.txt")).unwrap(),
+            "existing"
+        );
+        assert!(!staged_file.exists());
+
+        fs::write(&staged_file, "uploaded").unwrap();
+        let destination =
+            move_uploaded_file(&staged_file, destination_folder.path(), "../../escape.txt")
+                .unwrap();
+        assert_eq!(destination, destination_folder.path().join("escape.txt"));
+    }
+
+    #[test]
+    fn only_files_in_the_uploads_folder_are_moved() {
+        let uploads_folder = tempfile::tempdir().unwrap();
+        let other_folder = tempfile::tempdir().unwrap();
+        let staged_file = uploads_folder.path().join("staged");
+        fs::write(&staged_file, "uploaded").unwrap();
+        let other_file = other_folder.path().join("secret");
+        fs::write(&other_file, "secret").unwrap();
+        let symlink = uploads_folder.path().join("symlink");
+        std::os::unix::fs::symlink(&other_file, &symlink).unwrap();
+
+        assert!(is_staged_upload(&staged_file, uploads_folder.path()));
+        assert!(!is_staged_upload(&other_file, uploads_folder.path()));
+        assert!(!is_staged_upload(&symlink, uploads_folder.path()));
+        assert!(!is_staged_upload(
+            &uploads_folder.path().join("..").join("secret"),
+            uploads_folder.path()
+        ));
+        assert!(!is_staged_upload(
+            uploads_folder.path(),
+            uploads_folder.path()
+        ));
+    }
+
+    #[test]
+    fn failing_to_connect_is_an_error() {
+        // bind and drop a listener to get a local port nothing is listening on
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        assert!(PluginConnectionStream::connect(ConnectionKind::Tcp, &address).is_err());
+    }
+}
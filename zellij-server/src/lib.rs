@@ -4,6 +4,7 @@ pub mod panes;
 pub mod tab;
 
 mod background_jobs;
+mod listings;
 mod logging_pipe;
 mod pane_groups;
 mod plugins;
@@ -48,9 +49,14 @@ use zellij_utils::{
     consts::{
         DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE, SWARM_SEEN_RELEASE_NOTES_CACHE_FILE,
     },
-    data::{ConnectToSession, Event, InputMode, KeyWithModifier, PluginCapabilities, WebSharing},
+    data::{
+        ConnectToSession, Event, InputMode, KeyWithModifier, PluginCapabilities, SessionEvent,
+        WebSharing,
+    },
     errors::{prelude::*, ContextType, ErrorInstruction, FatalError, ServerContext},
-    home::{default_layout_dir, get_default_data_dir},
+    home::{
+        default_layout_dir, find_default_config_dir, get_default_data_dir, get_template_dir,
+    },
     input::{
         actions::Action,
         command::{RunCommand, TerminalAction},
@@ -62,7 +68,7 @@ use zellij_utils::{
         plugins::PluginAliases,
     },
     ipc::{ClientAttributes, ExitReason, ServerToClientMsg},
-    shared::{default_palette, web_server_base_url},
+    shared::{default_palette, web_server_base_path, web_server_base_url},
 };
 
 pub type ClientId = u16;
@@ -129,6 +135,10 @@ pub enum ServerInstruction {
     SendWebClientsForbidden(ClientId),
     WebServerStarted(String), // String -> base_url
     FailedToStartWebServer(String),
+    SendFileToWebClients(PathBuf, ClientId),
+    PaneExited(Option<i32>, ClientId), // exit status, client waiting for the pane to exit
+    SessionEvent(SessionEvent, ClientId), // client subscribed to this kind of event
+    LayoutApplied(ClientId),              // client that opened the layout
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -175,6 +185,10 @@ impl From<&ServerInstruction> for ServerContext {
             ServerInstruction::SendWebClientsForbidden(..) => {
                 ServerContext::SendWebClientsForbidden
             },
+            ServerInstruction::SendFileToWebClients(..) => ServerContext::SendFileToWebClients,
+            ServerInstruction::PaneExited(..) => ServerContext::PaneExited,
+            ServerInstruction::LayoutApplied(..) => ServerContext::LayoutApplied,
+            ServerInstruction::SessionEvent(..) => ServerContext::SessionEvent,
         }
     }
 }
@@ -780,6 +794,20 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         true,
                     );
                 }
+                if client_attributes.user_name.is_some() || client_attributes.token_name.is_some() {
+                    session_data
+                        .read()
+                        .unwrap()
+                        .as_ref()
+                        .unwrap()
+                        .senders
+                        .send_to_screen(ScreenInstruction::SetClientIdentity(
+                            client_id,
+                            client_attributes.user_name,
+                            client_attributes.token_name,
+                        ))
+                        .unwrap();
+                }
                 session_data
                     .read()
                     .unwrap()
@@ -834,10 +862,21 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .send_to_screen(ScreenInstruction::AddClient(
                         client_id,
                         is_web_client,
+                        attrs.read_only,
                         tab_position_to_focus,
                         pane_id_to_focus,
                     ))
                     .unwrap();
+                if attrs.user_name.is_some() || attrs.token_name.is_some() {
+                    session_data
+                        .senders
+                        .send_to_screen(ScreenInstruction::SetClientIdentity(
+                            client_id,
+                            attrs.user_name.clone(),
+                            attrs.token_name.clone(),
+                        ))
+                        .unwrap();
+                }
                 session_data
                     .senders
                     .send_to_plugin(PluginInstruction::AddClient(client_id))
@@ -1132,6 +1171,60 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     session_state
                 );
             },
+            ServerInstruction::SendFileToWebClients(path, client_id) => {
+                let web_client_ids = session_state.read().unwrap().web_client_ids();
+                if web_client_ids.is_empty() {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::LogError(vec![
+                            "No web clients are attached to this session".to_owned()
+                        ]),
+                        session_state
+                    );
+                } else {
+                    for web_client_id in &web_client_ids {
+                        let _ = os_input.send_to_client(
+                            *web_client_id,
+                            ServerToClientMsg::DownloadFile(path.clone()),
+                        );
+                    }
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::Log(vec![format!(
+                            "Sent {} to {} web client(s)",
+                            path.display(),
+                            web_client_ids.len()
+                        )]),
+                        session_state
+                    );
+                }
+            },
+            ServerInstruction::PaneExited(exit_status, client_id) => {
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::PaneExited(exit_status),
+                    session_state
+                );
+            },
+            ServerInstruction::LayoutApplied(client_id) => {
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::LayoutApplied,
+                    session_state
+                );
+            },
+            ServerInstruction::SessionEvent(session_event, client_id) => {
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::SessionEvent(session_event),
+                    session_state
+                );
+            },
             ServerInstruction::SwitchSession(mut connect_to_session, client_id) => {
                 let current_session_name = envs::get_session_name();
                 if connect_to_session.name == current_session_name.ok() {
@@ -1480,6 +1573,8 @@ fn init_session(
     let has_certificate =
         config_options.web_server_cert.is_some() && config_options.web_server_key.is_some();
     let enforce_https_for_localhost = config_options.enforce_https_for_localhost.unwrap_or(false);
+    let web_server_base_path = web_server_base_path(&config_options);
+    let web_server_socket = config_options.web_server_socket.clone();
 
     let default_shell = config_options.default_shell.clone().map(|command| {
         TerminalAction::RunCommand(RunCommand {
@@ -1575,6 +1670,8 @@ fn init_session(
             let default_shell = default_shell.clone();
             let capabilities = capabilities.clone();
             let layout_dir = config_options.layout_dir.clone();
+            let template_dir =
+                get_template_dir(opts.config_dir.clone().or_else(find_default_config_dir));
             let background_plugins = config.background_plugins.clone();
             move || {
                 plugin_thread_main(
@@ -1583,6 +1680,7 @@ fn init_session(
                     data_dir,
                     layout,
                     layout_dir,
+                    template_dir,
                     path_to_default_shell,
                     swarm_cwd,
                     capabilities,
@@ -1626,7 +1724,7 @@ fn init_session(
                 Some(&to_plugin),
                 Some(&to_server),
                 Some(&to_pty_writer),
-                None,
+                Some(&to_background_jobs),
                 Some(os_input.clone()),
             );
             let web_server_base_url = web_server_base_url(
@@ -1634,6 +1732,7 @@ fn init_session(
                 web_server_port,
                 has_certificate,
                 enforce_https_for_localhost,
+                &web_server_base_path,
             );
             move || {
                 background_jobs_main(
@@ -1641,6 +1740,7 @@ fn init_session(
                     serialization_interval,
                     disable_session_metadata,
                     web_server_base_url,
+                    web_server_socket,
                 )
                 .fatal()
             }
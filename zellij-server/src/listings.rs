@@ -0,0 +1,164 @@
+//! The panes and tabs of a session as listed by `swarm action list-panes` and `list-tabs`.
+
+use serde::Serialize;
+use std::path::PathBuf;
+use zellij_utils::data::{PaneInfo, TabInfo};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaneListing {
+    pub tab_position: usize,
+    pub tab_name: String,
+    #[serde(flatten)]
+    pub pane: PaneInfo,
+    /// What is running in a terminal pane right now (eg. the shell or a program started from it),
+    /// command panes also have the command they were started with in `terminal_command`
+    pub running_command: Option<String>,
+    pub cwd: Option<PathBuf>,
+}
+
+impl PaneListing {
+    pub fn new(tab_position: usize, tab_name: String, pane: PaneInfo) -> Self {
+        PaneListing {
+            tab_position,
+            tab_name,
+            pane,
+            running_command: None,
+            cwd: None,
+        }
+    }
+    pub fn terminal_id(&self) -> Option<u32> {
+        if self.pane.is_plugin {
+            None
+        } else {
+            Some(self.pane.id)
+        }
+    }
+    fn stringify_pane_id(&self) -> String {
+        if self.pane.is_plugin {
+            format!("plugin_{}", self.pane.id)
+        } else {
+            format!("terminal_{}", self.pane.id)
+        }
+    }
+    fn stringify_status(&self) -> String {
+        match (self.pane.exited, self.pane.exit_status) {
+            (true, Some(exit_status)) => format!("exited {}", exit_status),
+            (true, None) => String::from("exited"),
+            (false, _) => String::from("running"),
+        }
+    }
+    fn stringify_command(&self) -> String {
+        self.pane
+            .terminal_command
+            .as_ref()
+            .or(self.running_command.as_ref())
+            .or(self.pane.plugin_url.as_ref())
+            .cloned()
+            .unwrap_or_else(|| "N/A".to_owned())
+    }
+}
+
+pub fn render_panes(pane_listings: &[PaneListing]) -> String {
+    let mut lines = vec![];
+    lines.push(String::from(
+        "PANE_ID        TAB GEOMETRY          STATUS     COMMAND",
+    ));
+    for pane_listing in pane_listings {
+        // 14 - PANE_ID, 3 - TAB, 17 - GEOMETRY, 10 - STATUS
+        let pane = &pane_listing.pane;
+        let geometry = format!(
+            "{}x{}+{}+{}",
+            pane.pane_columns, pane.pane_rows, pane.pane_x, pane.pane_y
+        );
+        lines.push(format!(
+            "{: <14} {: <3} {: <17} {: <10} {}",
+            pane_listing.stringify_pane_id(),
+            pane_listing.tab_position,
+            geometry,
+            pane_listing.stringify_status(),
+            pane_listing.stringify_command(),
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn render_tabs(tab_infos: &[TabInfo]) -> String {
+    let mut lines = vec![];
+    lines.push(String::from("POSITION PANES FOCUSED_BY NAME"));
+    for tab_info in tab_infos {
+        // 8 - POSITION, 5 - PANES, 10 - FOCUSED_BY
+        let focused_by: Vec<String> = tab_info
+            .other_focused_clients
+            .iter()
+            .map(|client_id| client_id.to_string())
+            .collect();
+        lines.push(format!(
+            "{: <8} {: <5} {: <10} {}",
+            tab_info.position,
+            tab_info.selectable_tiled_panes_count + tab_info.selectable_floating_panes_count,
+            focused_by.join(","),
+            tab_info.name,
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_pane_listing(id: u32, is_plugin: bool) -> PaneListing {
+        PaneListing::new(
+            1,
+            "Tab #2".to_owned(),
+            PaneInfo {
+                id,
+                is_plugin,
+                pane_x: 0,
+                pane_y: 1,
+                pane_columns: 80,
+                pane_rows: 20,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn panes_are_listed_as_json_with_their_pane_info() {
+        let mut terminal_pane = create_pane_listing(1, false);
+        terminal_pane.running_command = Some("vim Cargo.toml".to_owned());
+        terminal_pane.cwd = Some(PathBuf::from("/tmp/project"));
+        let plugin_pane = create_pane_listing(2, true);
+        assert_eq!(terminal_pane.terminal_id(), Some(1));
+        assert_eq!(plugin_pane.terminal_id(), None);
+        let listings: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&[terminal_pane, plugin_pane]).unwrap())
+                .unwrap();
+        // the pane info is flattened into the listing
+        assert_eq!(listings[0]["id"], 1);
+        assert_eq!(listings[0]["is_plugin"], false);
+        assert_eq!(listings[0]["pane_columns"], 80);
+        assert_eq!(listings[0]["tab_position"], 1);
+        assert_eq!(listings[0]["tab_name"], "Tab #2");
+        assert_eq!(listings[0]["running_command"], "vim Cargo.toml");
+        assert_eq!(listings[0]["cwd"], "/tmp/project");
+        assert_eq!(listings[1]["id"], 2);
+        assert_eq!(listings[1]["is_plugin"], true);
+        assert!(listings[1]["running_command"].is_null());
+    }
+
+    #[test]
+    fn panes_are_rendered_in_aligned_columns() {
+        let mut terminal_pane = create_pane_listing(1, false);
+        terminal_pane.running_command = Some("vim Cargo.toml".to_owned());
+        let rendered = render_panes(&[terminal_pane]);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "PANE_ID        TAB GEOMETRY          STATUS     COMMAND",
+                "terminal_1     1   80x20+0+1         running    vim Cargo.toml",
+            ]
+        );
+    }
+}
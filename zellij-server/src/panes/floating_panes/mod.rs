@@ -38,6 +38,7 @@ pub struct FloatingPanes {
     viewport: Rc<RefCell<Viewport>>,
     connected_clients: Rc<RefCell<HashSet<ClientId>>>,
     connected_clients_in_app: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+    client_user_names: Rc<RefCell<HashMap<ClientId, String>>>,
     mode_info: Rc<RefCell<HashMap<ClientId, ModeInfo>>>,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     default_mode_info: ModeInfo,
@@ -60,6 +61,7 @@ impl FloatingPanes {
         viewport: Rc<RefCell<Viewport>>,
         connected_clients: Rc<RefCell<HashSet<ClientId>>>,
         connected_clients_in_app: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+        client_user_names: Rc<RefCell<HashMap<ClientId, String>>>,
         mode_info: Rc<RefCell<HashMap<ClientId, ModeInfo>>>,
         character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
         session_is_mirrored: bool,
@@ -74,6 +76,7 @@ impl FloatingPanes {
             viewport,
             connected_clients,
             connected_clients_in_app,
+            client_user_names,
             mode_info,
             character_cell_size,
             session_is_mirrored,
@@ -412,6 +415,7 @@ impl FloatingPanes {
                 self.style,
                 &active_panes,
                 multiple_users_exist_in_session,
+                &self.client_user_names.borrow(),
                 Some(z_index + 1), // +1 because 0 is reserved for non-floating panes
                 false,
                 false,
@@ -1213,6 +1213,21 @@ impl Grid {
         self.reset_terminal_state();
         self.mark_for_rerender();
     }
+    /// Moves the written part of the viewport into the scrollback, leaving an empty viewport with
+    /// the cursor at its top
+    pub fn push_viewport_to_scrollback(&mut self) {
+        if self.alternate_screen_state.is_some() {
+            log::warn!("Tried to push viewport to scrollback with alternate_screen_state");
+            return;
+        }
+        self.viewport.truncate(self.cursor.y + 1);
+        let rows_to_transfer = self.viewport.len();
+        self.transfer_rows_to_lines_above(rows_to_transfer);
+        self.viewport = vec![Row::new().canonical()];
+        self.cursor = Cursor::new(0, 0, self.styled_underlines);
+        self.output_buffer.update_all_lines();
+        self.mark_for_rerender();
+    }
     /// Dumps all lines above terminal vieport and the viewport itself to a string
     pub fn dump_screen(&self, full: bool) -> String {
         let viewport: String = dump_screen!(self.viewport);
@@ -20,7 +20,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use vte;
 use zellij_utils::data::{
-    BareKey, KeyWithModifier, PermissionStatus, PermissionType, PluginPermission,
+    BareKey, KeyWithModifier, PathPermission, PermissionStatus, PermissionType, PluginPermission,
 };
 use zellij_utils::pane_size::{Offset, SizeInPixels};
 use zellij_utils::position::Position;
@@ -266,17 +266,20 @@ impl Pane for PluginPane {
             None
         } else if let Some(requesting_permissions) = &self.requesting_permissions {
             let permissions = requesting_permissions.permissions.clone();
+            let paths = requesting_permissions.paths.clone();
             if let Some(key_with_modifier) = key_with_modifier {
                 match key_with_modifier.bare_key {
                     BareKey::Char('y') if key_with_modifier.has_no_modifiers() => {
                         Some(AdjustedInput::PermissionRequestResult(
                             permissions,
+                            paths,
                             PermissionStatus::Granted,
                         ))
                     },
                     BareKey::Char('n') if key_with_modifier.has_no_modifiers() => {
                         Some(AdjustedInput::PermissionRequestResult(
                             permissions,
+                            paths,
                             PermissionStatus::Denied,
                         ))
                     },
@@ -287,11 +290,13 @@ impl Pane for PluginPane {
                     // Y or y
                     &[89] | &[121] => Some(AdjustedInput::PermissionRequestResult(
                         permissions,
+                        paths,
                         PermissionStatus::Granted,
                     )),
                     // N or n
                     &[78] | &[110] => Some(AdjustedInput::PermissionRequestResult(
                         permissions,
+                        paths,
                         PermissionStatus::Denied,
                     )),
                     _ => None,
@@ -880,8 +885,14 @@ impl PluginPane {
         let mut messages = String::new();
         let permissions: BTreeSet<PermissionType> =
             plugin_permission.permissions.clone().into_iter().collect();
+        let paths: BTreeSet<PathPermission> = plugin_permission.paths.clone().into_iter().collect();
+        let requests: Vec<String> = permissions
+            .iter()
+            .map(|p| p.display_name())
+            .chain(paths.iter().map(|p| p.display_name()))
+            .collect();
 
-        let min_row_count = permissions.len() + 4;
+        let min_row_count = requests.len() + 4;
 
         if self.rows() >= min_row_count {
             messages.push_str(&format!(
@@ -890,11 +901,11 @@ impl PluginPane {
                 cyan.paint(&plugin_permission.name),
                 bold_white.paint("asks permission to:"),
             ));
-            permissions.iter().enumerate().for_each(|(i, p)| {
+            requests.iter().enumerate().for_each(|(i, r)| {
                 messages.push_str(&format!(
                     "\n\r{}. {}",
                     bold_white.paint(&format!("{}", i + 1)),
-                    orange.paint(p.display_name())
+                    orange.paint(r)
                 ));
             });
 
@@ -911,6 +922,7 @@ impl PluginPane {
                     permissions
                         .iter()
                         .map(|p| p.to_string())
+                        .chain(paths.iter().map(|p| p.display_name()))
                         .collect::<Vec<_>>()
                         .join(", ")
                 ),
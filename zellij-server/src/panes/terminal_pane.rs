@@ -138,6 +138,7 @@ pub struct TerminalPane {
     // held on startup and can possibly be used to display some errors
     pane_frame_color_override: Option<(PaletteColor, Option<String>)>,
     invoked_with: Option<Run>,
+    restored_contents: Option<String>, // a previous session's output, replayed until the pane runs
     #[allow(dead_code)]
     arrow_fonts: bool,
 }
@@ -810,7 +811,7 @@ impl Pane for TerminalPane {
         // is not in the right sort of state
         self.is_held.take().map(|(_, _, run_command)| {
             self.is_held = None;
-            self.grid.reset_terminal_state();
+            self.reset_terminal_state();
             self.set_should_render(true);
             self.remove_banner();
             run_command.clone()
@@ -942,6 +943,7 @@ impl TerminalPane {
             banner: None,
             pane_frame_color_override: None,
             invoked_with,
+            restored_contents: None,
             arrow_fonts,
         }
     }
@@ -974,7 +976,7 @@ impl TerminalPane {
         let cols = self.get_content_columns();
         self.grid.force_change_size(rows, cols);
         if self.banner.is_some() {
-            self.grid.reset_terminal_state();
+            self.reset_terminal_state();
             self.render_first_run_banner();
         }
         self.set_should_render(true);
@@ -1002,11 +1004,45 @@ impl TerminalPane {
         self.banner = Some(banner.clone());
         self.handle_pty_bytes(banner.as_bytes().to_vec());
     }
+    pub fn restore_previous_contents(&mut self, contents: &str) {
+        self.restored_contents = Some(contents.to_owned());
+        self.replay_restored_contents();
+    }
+    fn reset_terminal_state(&mut self) {
+        self.grid.reset_terminal_state();
+        self.replay_restored_contents();
+        if self.banner.is_none() {
+            // the pane is running, from now on its history is its own
+            self.restored_contents = None;
+        }
+    }
+    fn replay_restored_contents(&mut self) {
+        // the restored contents are pushed to the scrollback so that they scroll together with
+        // the pane's history but are never overwritten by the command's own output
+        if let Some(restored_contents) = self.restored_contents.clone() {
+            let columns = self.get_content_columns();
+            let separator_text = " restored output ";
+            let separator = if columns > separator_text.chars().count() + 4 {
+                let fill = columns - separator_text.chars().count();
+                format!(
+                    "{}{}{}",
+                    "─".repeat(fill / 2),
+                    separator_text,
+                    "─".repeat(fill - fill / 2)
+                )
+            } else {
+                "─".repeat(columns)
+            };
+            self.handle_pty_bytes(restored_contents.into_bytes());
+            self.handle_pty_bytes(format!("\n\r\u{1b}[2m{}\u{1b}[m", separator).into_bytes());
+            self.grid.push_viewport_to_scrollback();
+        }
+    }
     fn remove_banner(&mut self) {
         if self.banner.is_some() {
-            self.grid.reset_terminal_state();
-            self.set_should_render(true);
             self.banner = None;
+            self.reset_terminal_state();
+            self.set_should_render(true);
         }
     }
     fn adjust_input_to_terminal_with_kitty_keyboard_protocol(
@@ -1117,7 +1153,7 @@ impl TerminalPane {
     fn handle_held_run(&mut self) -> Option<AdjustedInput> {
         self.is_held.take().map(|(_, _, run_command)| {
             self.is_held = None;
-            self.grid.reset_terminal_state();
+            self.reset_terminal_state();
             self.set_should_render(true);
             self.remove_banner();
             AdjustedInput::ReRunCommandInThisPane(run_command.clone())
@@ -1128,7 +1164,7 @@ impl TerminalPane {
             // Drop to shell in the same working directory as the command was run
             let working_dir = run_command.cwd.clone();
             self.is_held = None;
-            self.grid.reset_terminal_state();
+            self.reset_terminal_state();
             self.set_should_render(true);
             self.remove_banner();
             AdjustedInput::DropToShellInThisPane { working_dir }
@@ -59,6 +59,7 @@ pub struct TiledPanes {
     viewport: Rc<RefCell<Viewport>>,
     connected_clients: Rc<RefCell<HashSet<ClientId>>>,
     connected_clients_in_app: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+    client_user_names: Rc<RefCell<HashMap<ClientId, String>>>,
     mode_info: Rc<RefCell<HashMap<ClientId, ModeInfo>>>,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     stacked_resize: Rc<RefCell<bool>>,
@@ -83,6 +84,7 @@ impl TiledPanes {
         viewport: Rc<RefCell<Viewport>>,
         connected_clients: Rc<RefCell<HashSet<ClientId>>>,
         connected_clients_in_app: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+        client_user_names: Rc<RefCell<HashMap<ClientId, String>>>,
         mode_info: Rc<RefCell<HashMap<ClientId, ModeInfo>>>,
         character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
         stacked_resize: Rc<RefCell<bool>>,
@@ -99,6 +101,7 @@ impl TiledPanes {
             viewport,
             connected_clients,
             connected_clients_in_app,
+            client_user_names,
             mode_info,
             character_cell_size,
             stacked_resize,
@@ -1040,6 +1043,7 @@ impl TiledPanes {
                     self.style,
                     &active_panes,
                     multiple_users_exist_in_session,
+                    &self.client_user_names.borrow(),
                     None,
                     pane_is_stacked_under,
                     pane_is_stacked_over,
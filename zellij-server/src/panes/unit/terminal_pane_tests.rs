@@ -8,6 +8,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use zellij_utils::{
     data::{Palette, Style},
+    input::command::RunCommand,
     pane_size::{Offset, PaneGeom, SizeInPixels},
     position::Position,
 };
@@ -764,3 +765,72 @@ pub fn frameless_pane_position_is_on_frame() {
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 130)));
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 131)));
 }
+
+#[test]
+pub fn restored_contents_are_kept_in_scrollback_when_command_is_rerun() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(40);
+    fake_win_size.rows.set_inner(10);
+
+    let pid = 1;
+    let style = Style::default();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_colors = Rc::new(RefCell::new(Palette::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        terminal_emulator_colors,
+        terminal_emulator_color_codes,
+        None,
+        None,
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    ); // 0 is the pane index
+    terminal_pane.restore_previous_contents("previous line 1\n\rprevious line 2");
+    terminal_pane.hold(None, true, RunCommand::default());
+    terminal_pane.rerun();
+    terminal_pane.handle_pty_bytes("new output".as_bytes().to_vec());
+
+    let viewport = terminal_pane.grid.dump_screen(false);
+    let full_screen = terminal_pane.grid.dump_screen(true);
+    assert_eq!(
+        viewport.trim_end(),
+        "new output",
+        "restored contents are not part of the viewport"
+    );
+    assert_eq!(
+        full_screen.matches("previous line 1").count(),
+        1,
+        "restored contents are replayed exactly once"
+    );
+    assert!(
+        full_screen.contains("restored output"),
+        "separator is shown"
+    );
+    assert!(
+        full_screen.find("previous line 2") < full_screen.find("new output"),
+        "restored contents are above the new output"
+    );
+
+    terminal_pane.hold(Some(0), false, RunCommand::default());
+    terminal_pane.rerun();
+    terminal_pane.handle_pty_bytes("newer output".as_bytes().to_vec());
+    let full_screen = terminal_pane.grid.dump_screen(true);
+    assert!(
+        !full_screen.contains("previous line 1"),
+        "restored contents are not replayed once the pane ran"
+    );
+}
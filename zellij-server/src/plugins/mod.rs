@@ -26,8 +26,8 @@ use async_std::{channel, future::timeout, task};
 use zellij_utils::{
     data::{
         ClientInfo, Event, EventType, FloatingPaneCoordinates, InputMode, MessageToPlugin,
-        PermissionStatus, PermissionType, PipeMessage, PipeSource, PluginCapabilities,
-        WebServerStatus,
+        PathPermission, PermissionStatus, PermissionType, PipeMessage, PipeSource,
+        PluginCapabilities, WebServerStatus,
     },
     errors::{prelude::*, ContextType, PluginContext},
     input::{
@@ -107,11 +107,12 @@ pub enum PluginInstruction {
         PluginId,
         Option<ClientId>,
         Vec<PermissionType>,
+        Vec<PathPermission>,
         PermissionStatus,
         Option<PathBuf>,
     ),
-    DumpLayout(SessionLayoutMetadata, ClientId),
-    ListClientsMetadata(SessionLayoutMetadata, ClientId),
+    DumpLayout(SessionLayoutMetadata, ClientId, bool), // bool -> as JSON
+    ListClientsMetadata(SessionLayoutMetadata, ClientId, bool), // bool -> as JSON
     DumpLayoutToPlugin(SessionLayoutMetadata, PluginId),
     LogLayoutToHd(SessionLayoutMetadata),
     CliPipe {
@@ -165,6 +166,7 @@ pub enum PluginInstruction {
     ChangePluginHostDir(PathBuf, PluginId, ClientId),
     WebServerStarted(String), // String -> the base url of the web server
     FailedToStartWebServer(String),
+    PluginWasmFileChanged(PathBuf), // the wasm file of a plugin loaded with _dev_watch
     Exit,
 }
 
@@ -214,6 +216,7 @@ impl From<&PluginInstruction> for PluginContext {
             PluginInstruction::ChangePluginHostDir(..) => PluginContext::ChangePluginHostDir,
             PluginInstruction::WebServerStarted(..) => PluginContext::WebServerStarted,
             PluginInstruction::FailedToStartWebServer(..) => PluginContext::FailedToStartWebServer,
+            PluginInstruction::PluginWasmFileChanged(..) => PluginContext::PluginWasmFileChanged,
         }
     }
 }
@@ -224,6 +227,7 @@ pub(crate) fn plugin_thread_main(
     data_dir: PathBuf,
     mut layout: Box<Layout>,
     layout_dir: Option<PathBuf>,
+    template_dir: Option<PathBuf>,
     path_to_default_shell: PathBuf,
     swarm_cwd: PathBuf,
     capabilities: PluginCapabilities,
@@ -259,6 +263,7 @@ pub(crate) fn plugin_thread_main(
         default_shell,
         layout.clone(),
         layout_dir,
+        template_dir,
         default_mode,
         default_keybinds,
     );
@@ -546,6 +551,7 @@ pub(crate) fn plugin_thread_main(
                 plugin_id,
                 client_id,
                 permissions,
+                paths,
                 status,
                 cache_path,
             ) => {
@@ -553,6 +559,7 @@ pub(crate) fn plugin_thread_main(
                     plugin_id,
                     client_id,
                     permissions,
+                    paths,
                     status,
                     cache_path,
                 ) {
@@ -572,7 +579,7 @@ pub(crate) fn plugin_thread_main(
                     shutdown_send.clone(),
                 )?;
             },
-            PluginInstruction::DumpLayout(mut session_layout_metadata, client_id) => {
+            PluginInstruction::DumpLayout(mut session_layout_metadata, client_id, json) => {
                 populate_session_layout_metadata(
                     &mut session_layout_metadata,
                     &wasm_bridge,
@@ -581,9 +588,14 @@ pub(crate) fn plugin_thread_main(
                 drop(bus.senders.send_to_pty(PtyInstruction::DumpLayout(
                     session_layout_metadata,
                     client_id,
+                    json,
                 )));
             },
-            PluginInstruction::ListClientsMetadata(mut session_layout_metadata, client_id) => {
+            PluginInstruction::ListClientsMetadata(
+                mut session_layout_metadata,
+                client_id,
+                json,
+            ) => {
                 populate_session_layout_metadata(
                     &mut session_layout_metadata,
                     &wasm_bridge,
@@ -592,6 +604,7 @@ pub(crate) fn plugin_thread_main(
                 drop(bus.senders.send_to_pty(PtyInstruction::ListClientsMetadata(
                     session_layout_metadata,
                     client_id,
+                    json,
                 )));
             },
             PluginInstruction::DumpLayoutToPlugin(mut session_layout_metadata, plugin_id) => {
@@ -644,6 +657,7 @@ pub(crate) fn plugin_thread_main(
                         client_metadata.get_pane_id().into(),
                         client_metadata.stringify_command(&default_editor),
                         is_current_client,
+                        client_metadata.is_read_only(),
                     ));
                 }
                 let updates = vec![(
@@ -936,6 +950,9 @@ pub(crate) fn plugin_thread_main(
                     .update_plugins(updates, shutdown_send.clone())
                     .non_fatal();
             },
+            PluginInstruction::PluginWasmFileChanged(wasm_file) => {
+                wasm_bridge.reload_plugins_watching_file(&wasm_file);
+            },
             PluginInstruction::Exit => {
                 break;
             },
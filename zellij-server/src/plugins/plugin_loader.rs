@@ -26,11 +26,12 @@ use crate::{
 use zellij_utils::plugin_api::action::ProtobufPluginConfiguration;
 use zellij_utils::{
     consts::{SWARM_CACHE_DIR, SWARM_SESSION_CACHE_DIR, SWARM_TMP_DIR},
-    data::{InputMode, PluginCapabilities},
+    data::{InputMode, PathAccess, PathPermission, PluginCapabilities},
     errors::prelude::*,
     input::command::TerminalAction,
     input::keybinds::Keybinds,
     input::layout::Layout,
+    input::permission::PermissionCache,
     input::plugins::PluginConfig,
     ipc::ClientAttributes,
     pane_size::Size,
@@ -70,6 +71,7 @@ pub struct PluginLoader<'a> {
     default_shell: Option<TerminalAction>,
     default_layout: Box<Layout>,
     layout_dir: Option<PathBuf>,
+    template_dir: Option<PathBuf>,
     default_mode: InputMode,
     keybinds: Keybinds,
 }
@@ -91,6 +93,7 @@ impl<'a> PluginLoader<'a> {
         default_shell: Option<TerminalAction>,
         default_layout: Box<Layout>,
         layout_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
     ) -> Result<()> {
         let err_context = || format!("failed to reload plugin {plugin_id} from memory");
         let mut connected_clients: Vec<ClientId> =
@@ -116,6 +119,7 @@ impl<'a> PluginLoader<'a> {
             default_shell,
             default_layout,
             layout_dir,
+            template_dir,
         )?;
         plugin_loader
             .load_module_from_memory()
@@ -152,6 +156,7 @@ impl<'a> PluginLoader<'a> {
         default_layout: Box<Layout>,
         skip_cache: bool,
         layout_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
         default_mode: InputMode,
         keybinds: Keybinds,
     ) -> Result<()> {
@@ -174,6 +179,7 @@ impl<'a> PluginLoader<'a> {
             default_shell,
             default_layout,
             layout_dir,
+            template_dir,
             default_mode,
             keybinds,
         )?;
@@ -228,6 +234,7 @@ impl<'a> PluginLoader<'a> {
         default_shell: Option<TerminalAction>,
         default_layout: Box<Layout>,
         layout_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
         default_mode: InputMode,
         keybinds: Keybinds,
     ) -> Result<()> {
@@ -252,6 +259,7 @@ impl<'a> PluginLoader<'a> {
                 default_shell.clone(),
                 default_layout.clone(),
                 layout_dir.clone(),
+                template_dir.clone(),
                 default_mode,
                 keybinds.clone(),
             )?;
@@ -281,6 +289,7 @@ impl<'a> PluginLoader<'a> {
         default_shell: Option<TerminalAction>,
         default_layout: Box<Layout>,
         layout_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
     ) -> Result<()> {
         let err_context = || format!("failed to reload plugin id {plugin_id}");
 
@@ -307,6 +316,7 @@ impl<'a> PluginLoader<'a> {
             default_shell,
             default_layout,
             layout_dir,
+            template_dir,
         )?;
         plugin_loader
             .compile_module()
@@ -339,6 +349,7 @@ impl<'a> PluginLoader<'a> {
         default_shell: Option<TerminalAction>,
         default_layout: Box<Layout>,
         layout_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
         default_mode: InputMode,
         keybinds: Keybinds,
     ) -> Result<Self> {
@@ -372,6 +383,7 @@ impl<'a> PluginLoader<'a> {
             default_shell,
             default_layout,
             layout_dir,
+            template_dir,
             default_mode,
             keybinds,
         })
@@ -392,6 +404,7 @@ impl<'a> PluginLoader<'a> {
         default_shell: Option<TerminalAction>,
         default_layout: Box<Layout>,
         layout_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
     ) -> Result<Self> {
         let err_context = || "Failed to find existing plugin";
         let (running_plugin, _subscriptions, _workers) = {
@@ -432,6 +445,7 @@ impl<'a> PluginLoader<'a> {
             default_shell,
             default_layout,
             layout_dir,
+            template_dir,
             default_mode,
             keybinds,
         )
@@ -452,6 +466,7 @@ impl<'a> PluginLoader<'a> {
         default_shell: Option<TerminalAction>,
         default_layout: Box<Layout>,
         layout_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
         default_mode: InputMode,
         keybinds: Keybinds,
     ) -> Result<Self> {
@@ -489,6 +504,7 @@ impl<'a> PluginLoader<'a> {
             default_shell,
             default_layout,
             layout_dir,
+            template_dir,
             default_mode,
             keybinds,
         )
@@ -742,6 +758,7 @@ impl<'a> PluginLoader<'a> {
                     self.default_shell.clone(),
                     self.default_layout.clone(),
                     self.layout_dir.clone(),
+                    self.template_dir.clone(),
                     self.default_mode,
                     self.keybinds.clone(),
                 )?;
@@ -789,6 +806,7 @@ impl<'a> PluginLoader<'a> {
         data_dir: &PathBuf,
         cache_dir: &PathBuf,
         tmp_dir: &PathBuf,
+        granted_paths: &[PathPermission],
         plugin_url: &String,
         plugin_id: PluginId,
         stdin_pipe: Arc<Mutex<VecDeque<u8>>>,
@@ -816,6 +834,30 @@ impl<'a> PluginLoader<'a> {
                 .preopened_dir(host_path, guest_path, DirPerms::all(), FilePerms::all())
                 .with_context(err_context)?;
         }
+        // paths the user explicitly granted this plugin are mounted under their own (absolute)
+        // path, these come after the default dirs so that their file descriptors stay stable
+        for granted_path in granted_paths {
+            if !granted_path.path.is_absolute() || !granted_path.path.is_dir() {
+                log::error!(
+                    "Not mounting granted path {} for plugin {}: not an existing absolute folder",
+                    granted_path.path.display(),
+                    plugin_url
+                );
+                continue;
+            }
+            let (dir_perms, file_perms) = match granted_path.access {
+                PathAccess::ReadOnly => (DirPerms::READ, FilePerms::READ),
+                PathAccess::ReadWrite => (DirPerms::all(), FilePerms::all()),
+            };
+            wasi_ctx_builder
+                .preopened_dir(
+                    &granted_path.path,
+                    granted_path.path.display().to_string(),
+                    dir_perms,
+                    file_perms,
+                )
+                .with_context(err_context)?;
+        }
         wasi_ctx_builder
             .stdin(VecDequeInputStream(stdin_pipe.clone()))
             .stdout(WriteOutputStream(stdout_pipe.clone()))
@@ -835,11 +877,18 @@ impl<'a> PluginLoader<'a> {
         let stdin_pipe = Arc::new(Mutex::new(VecDeque::new()));
         let stdout_pipe = Arc::new(Mutex::new(VecDeque::new()));
 
+        // paths granted in a previous session are available from the start, so that the
+        // plugin does not have to wait for its permission request to be answered to use them
+        let granted_paths = PermissionCache::from_path_or_default(None)
+            .get_paths(self.plugin.location.to_string())
+            .cloned()
+            .unwrap_or_default();
         let wasi_ctx = PluginLoader::create_wasi_ctx(
             &self.plugin_cwd,
             &self.plugin_own_data_dir,
             &self.plugin_own_cache_dir,
             &SWARM_TMP_DIR,
+            &granted_paths,
             &self.plugin.location.to_string(),
             self.plugin_id,
             stdin_pipe.clone(),
@@ -851,6 +900,7 @@ impl<'a> PluginLoader<'a> {
             client_id: self.client_id,
             plugin,
             permissions: Arc::new(Mutex::new(None)),
+            granted_paths,
             senders: self.senders.clone(),
             wasi_ctx,
             plugin_own_data_dir: self.plugin_own_data_dir.clone(),
@@ -865,6 +915,7 @@ impl<'a> PluginLoader<'a> {
             input_pipes_to_unblock: Arc::new(Mutex::new(HashSet::new())),
             input_pipes_to_block: Arc::new(Mutex::new(HashSet::new())),
             layout_dir: self.layout_dir.clone(),
+            template_dir: self.template_dir.clone(),
             default_mode: self.default_mode.clone(),
             subscriptions: Arc::new(Mutex::new(HashSet::new())),
             keybinds: self.keybinds.clone(),
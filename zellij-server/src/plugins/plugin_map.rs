@@ -27,7 +27,10 @@ use zellij_utils::{
     input::plugins::PluginConfig,
     ipc::ClientAttributes,
 };
-use zellij_utils::{data::PermissionType, errors::prelude::*};
+use zellij_utils::{
+    data::{PathPermission, PermissionType},
+    errors::prelude::*,
+};
 
 // the idea here is to provide atomicity when adding/removing plugins from the map (eg. when a new
 // client connects) but to also allow updates/renders not to block each other
@@ -292,6 +295,7 @@ pub struct PluginEnv {
     pub plugin_id: PluginId,
     pub plugin: PluginConfig,
     pub permissions: Arc<Mutex<Option<HashSet<PermissionType>>>>,
+    pub granted_paths: Vec<PathPermission>,
     pub senders: ThreadSenders,
     pub wasi_ctx: WasiP1Ctx,
     pub tab_index: Option<usize>,
@@ -305,6 +309,7 @@ pub struct PluginEnv {
     pub default_shell: Option<TerminalAction>,
     pub default_layout: Box<Layout>,
     pub layout_dir: Option<PathBuf>,
+    pub template_dir: Option<PathBuf>,
     pub plugin_cwd: PathBuf,
     pub input_pipes_to_unblock: Arc<Mutex<HashSet<String>>>,
     pub input_pipes_to_block: Arc<Mutex<HashSet<String>>>,
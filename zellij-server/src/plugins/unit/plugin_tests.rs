@@ -116,6 +116,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        vec![],
                                         PermissionStatus::Granted,
                                         Some(cache_path.clone()),
                                     ),
@@ -126,6 +127,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        vec![],
                                         PermissionStatus::Denied,
                                         Some(cache_path.clone()),
                                     ),
@@ -169,6 +171,7 @@ macro_rules! deny_permissions_and_log_actions_in_thread {
                                     0,
                                     Some($client_id),
                                     plugin_permission.permissions,
+                                    vec![],
                                     PermissionStatus::Denied,
                                     Some(cache_path.clone()),
                                 ),
@@ -213,6 +216,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread_naked_variant {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        vec![],
                                         PermissionStatus::Granted,
                                         Some(cache_path.clone()),
                                     ),
@@ -223,6 +227,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread_naked_variant {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        vec![],
                                         PermissionStatus::Denied,
                                         Some(cache_path.clone()),
                                     ),
@@ -267,6 +272,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread_struct_variant {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        vec![],
                                         PermissionStatus::Granted,
                                         Some(cache_path.clone()),
                                     ),
@@ -277,6 +283,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread_struct_variant {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        vec![],
                                         PermissionStatus::Denied,
                                         Some(cache_path.clone()),
                                     ),
@@ -360,6 +367,7 @@ fn create_plugin_thread(
                 data_dir,
                 Box::new(Layout::default()),
                 None,
+                None,
                 default_shell,
                 swarm_cwd,
                 plugin_capabilities,
@@ -444,6 +452,7 @@ fn create_plugin_thread_with_server_receiver(
                 data_dir,
                 Box::new(Layout::default()),
                 None,
+                None,
                 default_shell,
                 swarm_cwd,
                 plugin_capabilities,
@@ -534,6 +543,7 @@ fn create_plugin_thread_with_pty_receiver(
                 data_dir,
                 Box::new(Layout::default()),
                 None,
+                None,
                 default_shell,
                 swarm_cwd,
                 plugin_capabilities,
@@ -619,6 +629,7 @@ fn create_plugin_thread_with_background_jobs_receiver(
                 data_dir,
                 Box::new(Layout::default()),
                 None,
+                None,
                 default_shell,
                 swarm_cwd,
                 plugin_capabilities,
@@ -5688,6 +5699,7 @@ pub fn granted_permission_request_result() {
                                 0,
                                 Some(client_id),
                                 plugin_permission.permissions,
+                                vec![],
                                 PermissionStatus::Granted,
                                 Some(cache_path.clone()),
                             ));
@@ -5781,6 +5793,7 @@ pub fn denied_permission_request_result() {
                                 0,
                                 Some(client_id),
                                 plugin_permission.permissions,
+                                vec![],
                                 PermissionStatus::Denied,
                                 Some(cache_path.clone()),
                             ));
@@ -6,7 +6,7 @@ use crate::plugins::plugin_loader::PluginLoader;
 use crate::plugins::plugin_map::{AtomicEvent, PluginEnv, PluginMap, RunningPlugin, Subscriptions};
 
 use crate::plugins::plugin_worker::MessageToWorker;
-use crate::plugins::watch_filesystem::watch_filesystem;
+use crate::plugins::watch_filesystem::{watch_filesystem, watch_plugin_wasm_file};
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
 use async_channel::Sender;
 use async_std::task::{self, JoinHandle};
@@ -22,7 +22,8 @@ use std::{
 use wasmtime::{Engine, Module};
 use zellij_utils::consts::{SWARM_CACHE_DIR, SWARM_TMP_DIR};
 use zellij_utils::data::{
-    FloatingPaneCoordinates, InputMode, PermissionStatus, PermissionType, PipeMessage, PipeSource,
+    FloatingPaneCoordinates, InputMode, PathPermission, PermissionStatus, PermissionType,
+    PipeMessage, PipeSource,
 };
 use zellij_utils::downloader::Downloader;
 use zellij_utils::input::keybinds::Keybinds;
@@ -97,6 +98,7 @@ pub struct WasmBridge {
     pending_plugin_reloads: HashSet<RunPlugin>,
     path_to_default_shell: PathBuf,
     watcher: Option<Debouncer<RecommendedWatcher, FileIdMap>>,
+    dev_watchers: HashMap<PathBuf, (Debouncer<RecommendedWatcher, FileIdMap>, HashSet<PluginId>)>, // wasm file -> watcher and the plugins loaded from it
     swarm_cwd: PathBuf,
     capabilities: PluginCapabilities,
     client_attributes: ClientAttributes,
@@ -106,6 +108,7 @@ pub struct WasmBridge {
         HashMap<RunPluginLocation, HashMap<PluginUserConfiguration, Vec<(PluginId, ClientId)>>>,
     pending_pipes: PendingPipes,
     layout_dir: Option<PathBuf>,
+    template_dir: Option<PathBuf>,
     default_mode: InputMode,
     default_keybinds: Keybinds,
     keybinds: HashMap<ClientId, Keybinds>,
@@ -125,6 +128,7 @@ impl WasmBridge {
         default_shell: Option<TerminalAction>,
         default_layout: Box<Layout>,
         layout_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
         default_mode: InputMode,
         default_keybinds: Keybinds,
     ) -> Self {
@@ -143,6 +147,7 @@ impl WasmBridge {
             plugin_map,
             path_to_default_shell,
             watcher,
+            dev_watchers: HashMap::new(),
             next_plugin_id: 0,
             cached_events_for_pending_plugins: HashMap::new(),
             plugin_ids_waiting_for_permission_request: HashSet::new(),
@@ -158,6 +163,7 @@ impl WasmBridge {
             cached_plugin_map: HashMap::new(),
             pending_pipes: Default::default(),
             layout_dir,
+            template_dir,
             default_mode,
             default_keybinds,
             keybinds: HashMap::new(),
@@ -199,6 +205,11 @@ impl WasmBridge {
                     .with_context(|| format!("failed to resolve plugin {run:?}"))
                     .with_context(err_context)?;
                 let plugin_name = run.location.to_string();
+                if run.configuration.dev_watch() {
+                    if let RunPluginLocation::File(wasm_file) = &run.location {
+                        self.watch_plugin_wasm_file(plugin_id, wasm_file);
+                    }
+                }
 
                 self.cached_events_for_pending_plugins
                     .insert(plugin_id, vec![]);
@@ -219,6 +230,7 @@ impl WasmBridge {
                     let default_shell = self.default_shell.clone();
                     let default_layout = self.default_layout.clone();
                     let layout_dir = self.layout_dir.clone();
+                    let template_dir = self.template_dir.clone();
                     let downloader = self.downloader.clone();
                     let default_mode = self
                         .base_modes
@@ -278,6 +290,7 @@ impl WasmBridge {
                             default_layout,
                             skip_cache,
                             layout_dir,
+                            template_dir,
                             default_mode,
                             keybinds,
                         ) {
@@ -369,6 +382,14 @@ impl WasmBridge {
             }
         }
         self.cached_plugin_map.clear();
+        self.dev_watchers
+            .retain(|_wasm_file, (_watcher, plugin_ids)| {
+                plugin_ids.remove(&pid);
+                !plugin_ids.is_empty()
+            });
+        let _ = self
+            .senders
+            .send_to_background_jobs(BackgroundJob::ClosePluginConnections(pid));
         let mut pipes_to_unblock = self.pending_pipes.unload_plugin(&pid);
         for pipe_name in pipes_to_unblock.drain(..) {
             let _ = self
@@ -409,6 +430,7 @@ impl WasmBridge {
             let default_shell = self.default_shell.clone();
             let default_layout = self.default_layout.clone();
             let layout_dir = self.layout_dir.clone();
+            let template_dir = self.template_dir.clone();
             async move {
                 match PluginLoader::reload_plugin(
                     plugin_id,
@@ -425,6 +447,7 @@ impl WasmBridge {
                     default_shell.clone(),
                     default_layout.clone(),
                     layout_dir.clone(),
+                    template_dir.clone(),
                 ) {
                     Ok(_) => {
                         let plugin_list = plugin_map.lock().unwrap().list_plugins();
@@ -484,6 +507,7 @@ impl WasmBridge {
             let default_shell = self.default_shell.clone();
             let default_layout = self.default_layout.clone();
             let layout_dir = self.layout_dir.clone();
+            let template_dir = self.template_dir.clone();
             async move {
                 match PluginLoader::reload_plugin(
                     first_plugin_id,
@@ -500,6 +524,7 @@ impl WasmBridge {
                     default_shell.clone(),
                     default_layout.clone(),
                     layout_dir.clone(),
+                    template_dir.clone(),
                 ) {
                     Ok(_) => {
                         let plugin_list = plugin_map.lock().unwrap().list_plugins();
@@ -526,6 +551,7 @@ impl WasmBridge {
                                 default_shell.clone(),
                                 default_layout.clone(),
                                 layout_dir.clone(),
+                                template_dir.clone(),
                             ) {
                                 Ok(_) => {
                                     let plugin_list = plugin_map.lock().unwrap().list_plugins();
@@ -585,6 +611,7 @@ impl WasmBridge {
             self.default_shell.clone(),
             self.default_layout.clone(),
             self.layout_dir.clone(),
+            self.template_dir.clone(),
             self.default_mode,
             self.keybinds
                 .get(&client_id)
@@ -845,6 +872,7 @@ impl WasmBridge {
                             &plugin_env.plugin_own_data_dir,
                             &plugin_env.plugin_own_cache_dir,
                             &SWARM_TMP_DIR,
+                            &plugin_env.granted_paths,
                             &plugin_env.plugin.location.to_string(),
                             plugin_env.plugin_id,
                             stdin_pipe.clone(),
@@ -1030,6 +1058,9 @@ impl WasmBridge {
         if let Some(watcher) = self.watcher.take() {
             watcher.stop_nonblocking();
         }
+        for (_wasm_file, (watcher, _plugin_ids)) in self.dev_watchers.drain() {
+            watcher.stop_nonblocking();
+        }
     }
     pub fn run_plugin_of_loading_plugin_id(&self, plugin_id: PluginId) -> Option<&RunPlugin> {
         self.loading_plugins
@@ -1352,11 +1383,57 @@ impl WasmBridge {
             };
         }
     }
+    fn watch_plugin_wasm_file(&mut self, plugin_id: PluginId, wasm_file: &PathBuf) {
+        let wasm_file = self.swarm_cwd.join(wasm_file);
+        // the watcher reports paths relative to the canonical folder, so we make sure to compare
+        // against the same path
+        let wasm_file = match (wasm_file.parent(), wasm_file.file_name()) {
+            (Some(folder), Some(file_name)) => folder
+                .canonicalize()
+                .map(|folder| folder.join(file_name))
+                .unwrap_or(wasm_file.clone()),
+            _ => wasm_file.clone(),
+        };
+        if let Some((_watcher, plugin_ids)) = self.dev_watchers.get_mut(&wasm_file) {
+            plugin_ids.insert(plugin_id);
+            return;
+        }
+        match watch_plugin_wasm_file(self.senders.clone(), &wasm_file) {
+            Ok(watcher) => {
+                let mut plugin_ids = HashSet::new();
+                plugin_ids.insert(plugin_id);
+                self.dev_watchers.insert(wasm_file, (watcher, plugin_ids));
+            },
+            Err(e) => {
+                log::error!(
+                    "Failed to watch plugin file {}: {:?}",
+                    wasm_file.display(),
+                    e
+                );
+            },
+        }
+    }
+    pub fn reload_plugins_watching_file(&mut self, wasm_file: &PathBuf) {
+        let plugin_ids: Vec<PluginId> = self
+            .dev_watchers
+            .get(wasm_file)
+            .map(|(_watcher, plugin_ids)| plugin_ids.iter().copied().collect())
+            .unwrap_or_default();
+        for plugin_id in plugin_ids {
+            log::info!(
+                "Plugin file {} changed, reloading plugin {}",
+                wasm_file.display(),
+                plugin_id
+            );
+            self.reload_plugin_with_id(plugin_id).non_fatal();
+        }
+    }
     pub fn cache_plugin_permissions(
         &mut self,
         plugin_id: PluginId,
         client_id: Option<ClientId>,
         permissions: Vec<PermissionType>,
+        paths: Vec<PathPermission>,
         status: PermissionStatus,
         cache_path: Option<PathBuf>,
     ) -> Result<()> {
@@ -1370,23 +1447,49 @@ impl WasmBridge {
             .ok_or_else(|| anyhow!("Failed to get running plugin"))?;
 
         let mut running_plugin = running_plugin.lock().unwrap();
+        let plugin_name = running_plugin.store.data().plugin.location.to_string();
+        let mut permission_cache = PermissionCache::from_path_or_default(cache_path);
 
-        let permissions = if status == PermissionStatus::Granted {
-            permissions
-        } else {
-            vec![]
-        };
+        // a request for paths alone should not affect the permissions the plugin already has
+        if !permissions.is_empty() || paths.is_empty() {
+            let permissions = if status == PermissionStatus::Granted {
+                permissions
+            } else {
+                vec![]
+            };
 
-        running_plugin
-            .store
-            .data_mut()
-            .set_permissions(HashSet::from_iter(permissions.clone()));
+            running_plugin
+                .store
+                .data_mut()
+                .set_permissions(HashSet::from_iter(permissions.clone()));
+            permission_cache.cache(plugin_name.clone(), permissions);
+        }
 
-        let mut permission_cache = PermissionCache::from_path_or_default(cache_path);
-        permission_cache.cache(
-            running_plugin.store.data().plugin.location.to_string(),
-            permissions,
-        );
+        if !paths.is_empty() && status == PermissionStatus::Granted {
+            let plugin_env = running_plugin.store.data_mut();
+            let mut granted_paths = plugin_env.granted_paths.clone();
+            for path in &paths {
+                granted_paths.retain(|p| p.path != path.path);
+                granted_paths.push(path.clone());
+            }
+            if granted_paths != plugin_env.granted_paths {
+                let wasi_ctx = PluginLoader::create_wasi_ctx(
+                    &plugin_env.plugin_cwd,
+                    &plugin_env.plugin_own_data_dir,
+                    &plugin_env.plugin_own_cache_dir,
+                    &SWARM_TMP_DIR,
+                    &granted_paths,
+                    &plugin_name,
+                    plugin_env.plugin_id,
+                    plugin_env.stdin_pipe.clone(),
+                    plugin_env.stdout_pipe.clone(),
+                )
+                .with_context(err_context)?;
+                drop(std::mem::replace(&mut plugin_env.wasi_ctx, wasi_ctx));
+                plugin_env.granted_paths = granted_paths;
+            }
+            permission_cache.cache_paths(plugin_name, paths);
+        }
 
         permission_cache.write_to_file().with_context(err_context)
     }
@@ -10,7 +10,10 @@ use notify_debouncer_full::{
     notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher},
     DebounceEventResult, Debouncer, FileIdMap,
 };
-use zellij_utils::{data::Event, errors::prelude::Result};
+use zellij_utils::{
+    data::Event,
+    errors::prelude::{anyhow, Result},
+};
 
 const DEBOUNCE_DURATION_MS: u64 = 400;
 
@@ -133,3 +136,41 @@ pub fn watch_filesystem(
         .watch(swarm_cwd, RecursiveMode::Recursive)?;
     Ok(debouncer)
 }
+
+pub fn watch_plugin_wasm_file(
+    senders: ThreadSenders,
+    wasm_file: &Path,
+) -> Result<Debouncer<RecommendedWatcher, FileIdMap>> {
+    // we watch the containing folder rather than the file itself because build tools tend to
+    // replace the file when rebuilding it, which would drop a watch placed on the file
+    let watched_folder = wasm_file
+        .parent()
+        .ok_or_else(|| anyhow!("Plugin file {} has no parent folder", wasm_file.display()))?
+        .to_path_buf();
+    let watched_file = wasm_file.to_path_buf();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(DEBOUNCE_DURATION_MS),
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let wasm_file_changed = events.iter().any(|event| {
+                    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                        && event.paths.iter().any(|p| p == &watched_file)
+                });
+                if wasm_file_changed {
+                    let _ = senders.send_to_plugin(PluginInstruction::PluginWasmFileChanged(
+                        watched_file.clone(),
+                    ));
+                }
+            },
+            Err(errors) => errors
+                .iter()
+                .for_each(|error| log::error!("watch error: {error:?}")),
+        },
+    )?;
+
+    debouncer
+        .watcher()
+        .watch(&watched_folder, RecursiveMode::NonRecursive)?;
+    Ok(debouncer)
+}
@@ -20,14 +20,16 @@ use std::{
 };
 use wasmtime::{Caller, Linker};
 use zellij_utils::data::{
-    CommandType, ConnectToSession, FloatingPaneCoordinates, HttpVerb, KeyWithModifier, LayoutInfo,
-    MessageToPlugin, OriginatingPlugin, PermissionStatus, PermissionType, PluginPermission,
+    CommandType, ConnectToSession, ConnectionKind, FloatingPaneCoordinates, HttpVerb,
+    KeyWithModifier, LayoutInfo, MessageToPlugin, OriginatingPlugin, PathPermission,
+    PermissionStatus, PermissionType, PluginPermission, WebTokenScope,
 };
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::ipc::{ClientToServerMsg, IpcSenderWithContext};
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_authentication_tokens::{
-    create_token, list_tokens, rename_token, revoke_all_tokens, revoke_token,
+    create_token, list_tokens, rename_token, revoke_all_tokens, revoke_token, set_token_expiry,
+    update_token_access, TokenAccess,
 };
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_server_commands::shutdown_all_webserver_instances;
@@ -35,6 +37,7 @@ use zellij_utils::web_server_commands::shutdown_all_webserver_instances;
 use crate::{panes::PaneId, screen::ScreenInstruction};
 
 use prost::Message;
+use zellij_utils::input::{config::ConfigError, session_template};
 use zellij_utils::{
     consts::{VERSION, SWARM_SESSION_INFO_CACHE_DIR, SWARM_SOCK_DIR},
     data::{
@@ -48,15 +51,21 @@ use zellij_utils::{
         layout::{Layout, RunPluginOrAlias},
     },
     plugin_api::{
-        plugin_command::ProtobufPluginCommand,
+        plugin_command::{
+            ListSessionSnapshotsResponse, ListSessionTemplatesResponse, ProtobufPluginCommand,
+            ReadWebAuditLogResponse, ResolveSessionTemplateResponse,
+        },
         plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
     },
+    sessions::get_session_snapshots,
+    web_audit_log,
 };
 
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::plugin_api::plugin_command::{
-    CreateTokenResponse, ListTokensResponse, RenameWebTokenResponse, RevokeAllWebTokensResponse,
-    RevokeTokenResponse,
+    AllowedSessions, CreateTokenResponse, ListTokensResponse, ProtobufWebTokenScope,
+    RenameWebTokenResponse, RevokeAllWebTokensResponse, RevokeTokenResponse,
+    UpdateWebTokenResponse,
 };
 
 macro_rules! apply_action {
@@ -248,6 +257,14 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::RequestPluginPermissions(permissions) => {
                         request_permission(env, permissions)?
                     },
+                    PluginCommand::RequestPathPermissions(paths) => {
+                        request_path_permissions(env, paths)?
+                    },
+                    PluginCommand::ListSessionSnapshots => list_session_snapshots(env),
+                    PluginCommand::ListSessionTemplates => list_session_templates(env),
+                    PluginCommand::ResolveSessionTemplate(template_name, args) => {
+                        resolve_session_template(env, template_name, args)
+                    },
                     PluginCommand::SwitchSession(connect_to_session) => switch_session(
                         env,
                         connect_to_session.name,
@@ -476,8 +493,8 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::SetSelfMouseSelectionSupport(selection_support) => {
                         set_self_mouse_selection_support(env, selection_support);
                     },
-                    PluginCommand::GenerateWebLoginToken(token_label) => {
-                        generate_web_login_token(env, token_label);
+                    PluginCommand::GenerateWebLoginToken(token_label, scope) => {
+                        generate_web_login_token(env, token_label, scope);
                     },
                     PluginCommand::RevokeWebLoginToken(label) => {
                         revoke_web_login_token(env, label);
@@ -491,6 +508,13 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::RenameWebLoginToken(old_name, new_name) => {
                         rename_web_login_token(env, old_name, new_name);
                     },
+                    PluginCommand::UpdateWebLoginToken(token_label, scope, allowed_sessions) => {
+                        update_web_login_token(env, token_label, scope, allowed_sessions);
+                    },
+                    PluginCommand::SetWebLoginTokenExpiry(token_label, expires_in) => {
+                        set_web_login_token_expiry(env, token_label, expires_in);
+                    },
+                    PluginCommand::ReadWebAuditLog => read_web_audit_log(env),
                     PluginCommand::InterceptKeyPresses => intercept_key_presses(&mut env),
                     PluginCommand::ClearKeyPressesIntercepts => {
                         clear_key_presses_intercepts(&mut env)
@@ -503,6 +527,15 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                         pane_id_to_replace.into(),
                         existing_pane_id.into(),
                     ),
+                    PluginCommand::OpenConnection(kind, address, context) => {
+                        open_connection(env, kind, address, context)
+                    },
+                    PluginCommand::SendToConnection(connection_id, data) => {
+                        send_to_connection(env, connection_id, data)
+                    },
+                    PluginCommand::CloseConnection(connection_id) => {
+                        close_connection(env, connection_id)
+                    },
                 },
                 (PermissionStatus::Denied, permission) => {
                     log::error!(
@@ -594,6 +627,7 @@ fn request_permission(env: &PluginEnv, permissions: Vec<PermissionType>) -> Resu
                 env.plugin_id,
                 Some(env.client_id),
                 permissions.to_vec(),
+                vec![],
                 PermissionStatus::Granted,
                 None,
             ));
@@ -614,6 +648,52 @@ fn request_permission(env: &PluginEnv, permissions: Vec<PermissionType>) -> Resu
         ))
 }
 
+fn request_path_permissions(env: &PluginEnv, paths: Vec<PathPermission>) -> Result<()> {
+    // we make sure the user is shown (and the grant is stored with) the exact path that will be
+    // mounted for the plugin
+    let paths: Vec<PathPermission> = paths
+        .into_iter()
+        .map(|p| PathPermission {
+            path: absolute_path_for_plugin(env, &p.path),
+            access: p.access,
+        })
+        .collect();
+    if PermissionCache::from_path_or_default(None)
+        .check_paths(env.plugin.location.to_string(), &paths)
+    {
+        return env
+            .senders
+            .send_to_plugin(PluginInstruction::PermissionRequestResult(
+                env.plugin_id,
+                Some(env.client_id),
+                vec![],
+                paths,
+                PermissionStatus::Granted,
+                None,
+            ));
+    }
+
+    let _ = env
+        .senders
+        .send_to_plugin(PluginInstruction::CachePluginEvents {
+            plugin_id: env.plugin_id,
+        });
+
+    env.senders
+        .send_to_screen(ScreenInstruction::RequestPluginPermissions(
+            env.plugin_id,
+            PluginPermission::new(env.plugin.location.to_string(), vec![]).with_paths(paths),
+        ))
+}
+
+fn absolute_path_for_plugin(env: &PluginEnv, path: &PathBuf) -> PathBuf {
+    let path = match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(relative_to_home), Some(home)) => PathBuf::from(home).join(relative_to_home),
+        _ => env.plugin_cwd.join(path), // no-op if path is absolute
+    };
+    path.canonicalize().unwrap_or(path)
+}
+
 fn get_plugin_ids(env: &PluginEnv) {
     let ids = PluginIds {
         plugin_id: env.plugin_id,
@@ -967,6 +1047,7 @@ fn open_command_pane_in_place_of_plugin(
             context,
         )),
         use_terminal_title,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env
@@ -1006,6 +1087,7 @@ fn open_command_pane(
             context,
         )),
         use_terminal_title,
+        ..Default::default()
     };
     let action = Action::NewTiledPane(direction, Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -1037,6 +1119,7 @@ fn open_command_pane_near_plugin(
             context,
         )),
         use_terminal_title,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -1076,6 +1159,7 @@ fn open_command_pane_floating(
             context,
         )),
         use_terminal_title,
+        ..Default::default()
     };
     let action = Action::NewFloatingPane(Some(run_command_action), name, floating_pane_coordinates);
     apply_action!(action, error_msg, env);
@@ -1108,6 +1192,7 @@ fn open_command_pane_floating_near_plugin(
             context,
         )),
         use_terminal_title,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -1146,6 +1231,7 @@ fn open_command_pane_in_place(
             context,
         )),
         use_terminal_title,
+        ..Default::default()
     };
     let action = Action::NewInPlacePane(Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -1181,6 +1267,7 @@ fn open_command_pane_background(
             context,
         )),
         use_terminal_title,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -1315,6 +1402,43 @@ fn web_request(
         ));
 }
 
+fn open_connection(
+    env: &PluginEnv,
+    kind: ConnectionKind,
+    address: String,
+    context: BTreeMap<String, String>,
+) {
+    let address = match kind {
+        ConnectionKind::UnixSocket => env.plugin_cwd.join(address).display().to_string(),
+        ConnectionKind::Tcp | ConnectionKind::WebSocket => address,
+    };
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::OpenConnection(
+            env.plugin_id,
+            env.client_id,
+            kind,
+            address,
+            context,
+        ));
+}
+
+fn send_to_connection(env: &PluginEnv, connection_id: u32, data: Vec<u8>) {
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::SendToConnection(
+            env.plugin_id,
+            connection_id,
+            data,
+        ));
+}
+
+fn close_connection(env: &PluginEnv, connection_id: u32) {
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::CloseConnection(env.plugin_id, connection_id));
+}
+
 fn post_message_to(env: &PluginEnv, plugin_message: PluginMessage) -> Result<()> {
     let worker_name = plugin_message
         .worker_name
@@ -1434,6 +1558,8 @@ fn new_tabs_with_layout(env: &PluginEnv, raw_layout: &str) -> Result<()> {
         format!("Layout from plugin: {}", env.name()),
         None,
         None,
+        BTreeMap::new(),
+        env.layout_dir.clone(),
     )
     .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))?;
     apply_layout(env, layout);
@@ -2325,8 +2451,12 @@ fn embed_multiple_panes(env: &PluginEnv, pane_ids: Vec<PaneId>) {
 }
 
 #[cfg(feature = "web_server_capability")]
-fn generate_web_login_token(env: &PluginEnv, token_label: Option<String>) {
-    let serialized = match create_token(token_label) {
+fn generate_web_login_token(env: &PluginEnv, token_label: Option<String>, scope: WebTokenScope) {
+    let access = TokenAccess {
+        scope,
+        allowed_sessions: vec![],
+    };
+    let serialized = match create_token(token_label, &access, None) {
         Ok((token, token_label)) => CreateTokenResponse {
             token: Some(token),
             token_label: Some(token_label),
@@ -2342,7 +2472,7 @@ fn generate_web_login_token(env: &PluginEnv, token_label: Option<String>) {
 }
 
 #[cfg(not(feature = "web_server_capability"))]
-fn generate_web_login_token(env: &PluginEnv, _token_label: Option<String>) {
+fn generate_web_login_token(env: &PluginEnv, _token_label: Option<String>, _scope: WebTokenScope) {
     log::error!("This version of Swarm was compiled without the web server capabilities!");
     let empty_vec: Vec<&str> = vec![];
     let _ = wasi_write_object(env, &empty_vec);
@@ -2418,6 +2548,68 @@ fn rename_web_login_token(env: &PluginEnv, _old_name: String, _new_name: String)
     let _ = wasi_write_object(env, &empty_vec);
 }
 
+#[cfg(feature = "web_server_capability")]
+fn update_web_login_token(
+    env: &PluginEnv,
+    token_label: String,
+    scope: WebTokenScope,
+    allowed_sessions: Vec<String>,
+) {
+    let access = TokenAccess {
+        scope,
+        allowed_sessions,
+    };
+    let serialized = match update_token_access(&token_label, &access) {
+        Ok(_) => UpdateWebTokenResponse {
+            successfully_updated: true,
+            error: None,
+        },
+        Err(e) => UpdateWebTokenResponse {
+            successfully_updated: false,
+            error: Some(e.to_string()),
+        },
+    };
+    let _ = wasi_write_object(env, &serialized.encode_to_vec());
+}
+
+#[cfg(not(feature = "web_server_capability"))]
+fn update_web_login_token(
+    env: &PluginEnv,
+    _token_label: String,
+    _scope: WebTokenScope,
+    _allowed_sessions: Vec<String>,
+) {
+    log::error!("This version of Swarm was compiled without the web server capabilities!");
+    let empty_vec: Vec<&str> = vec![];
+    let _ = wasi_write_object(env, &empty_vec);
+}
+
+#[cfg(feature = "web_server_capability")]
+fn set_web_login_token_expiry(env: &PluginEnv, token_label: String, expires_in: Option<Duration>) {
+    let serialized = match set_token_expiry(&token_label, expires_in) {
+        Ok(_) => UpdateWebTokenResponse {
+            successfully_updated: true,
+            error: None,
+        },
+        Err(e) => UpdateWebTokenResponse {
+            successfully_updated: false,
+            error: Some(e.to_string()),
+        },
+    };
+    let _ = wasi_write_object(env, &serialized.encode_to_vec());
+}
+
+#[cfg(not(feature = "web_server_capability"))]
+fn set_web_login_token_expiry(
+    env: &PluginEnv,
+    _token_label: String,
+    _expires_in: Option<Duration>,
+) {
+    log::error!("This version of Swarm was compiled without the web server capabilities!");
+    let empty_vec: Vec<&str> = vec![];
+    let _ = wasi_write_object(env, &empty_vec);
+}
+
 #[cfg(feature = "web_server_capability")]
 fn list_web_login_tokens(env: &PluginEnv) {
     let serialized = match list_tokens() {
@@ -2425,11 +2617,31 @@ fn list_web_login_tokens(env: &PluginEnv) {
             tokens: token_list.iter().map(|t| t.name.clone()).collect(),
             creation_times: token_list.iter().map(|t| t.created_at.clone()).collect(),
             error: None,
+            scopes: token_list
+                .iter()
+                .map(|t| {
+                    let scope: ProtobufWebTokenScope = t.scope.into();
+                    scope as i32
+                })
+                .collect(),
+            expiration_times: token_list
+                .iter()
+                .map(|t| t.expires_at.clone().unwrap_or_default())
+                .collect(),
+            allowed_sessions: token_list
+                .iter()
+                .map(|t| AllowedSessions {
+                    session_names: t.allowed_sessions.clone(),
+                })
+                .collect(),
         },
         Err(e) => ListTokensResponse {
             tokens: vec![],
             creation_times: vec![],
             error: Some(e.to_string()),
+            scopes: vec![],
+            expiration_times: vec![],
+            allowed_sessions: vec![],
         },
     };
     let _ = wasi_write_object(env, &serialized.encode_to_vec());
@@ -2442,6 +2654,75 @@ fn list_web_login_tokens(env: &PluginEnv) {
     let _ = wasi_write_object(env, &empty_vec);
 }
 
+fn read_web_audit_log(env: &PluginEnv) {
+    let serialized = match web_audit_log::read_audit_log() {
+        Ok(events) => ReadWebAuditLogResponse {
+            events: events.into_iter().map(|e| e.into()).collect(),
+            error: None,
+        },
+        Err(e) => ReadWebAuditLogResponse {
+            events: vec![],
+            error: Some(e.to_string()),
+        },
+    };
+    let _ = wasi_write_object(env, &serialized.encode_to_vec());
+}
+
+fn list_session_snapshots(env: &PluginEnv) {
+    let serialized = ListSessionSnapshotsResponse {
+        snapshots: get_session_snapshots(None)
+            .into_iter()
+            .map(|s| s.into())
+            .collect(),
+        error: None,
+    };
+    let _ = wasi_write_object(env, &serialized.encode_to_vec());
+}
+
+fn list_session_templates(env: &PluginEnv) {
+    let serialized = ListSessionTemplatesResponse {
+        templates: session_template::session_templates(
+            env.template_dir.clone(),
+            env.layout_dir.clone(),
+        )
+        .into_iter()
+        .map(|t| t.into())
+        .collect(),
+        error: None,
+    };
+    let _ = wasi_write_object(env, &serialized.encode_to_vec());
+}
+
+fn resolve_session_template(
+    env: &PluginEnv,
+    template_name: String,
+    args: BTreeMap<String, String>,
+) {
+    let resolved = session_template::session_template(
+        &template_name,
+        env.template_dir.clone(),
+        env.layout_dir.clone(),
+    )
+    .and_then(|template_file| {
+        session_template::resolve_session_template(&template_file, &args, env.layout_dir.clone())
+    });
+    let serialized = match resolved {
+        Ok(resolved) => ResolveSessionTemplateResponse {
+            layout: Some(resolved.layout),
+            error: None,
+        },
+        Err(ConfigError::KdlError(kdl_error)) => ResolveSessionTemplateResponse {
+            layout: None,
+            error: Some(kdl_error.error_message),
+        },
+        Err(e) => ResolveSessionTemplateResponse {
+            layout: None,
+            error: Some(e.to_string()),
+        },
+    };
+    let _ = wasi_write_object(env, &serialized.encode_to_vec());
+}
+
 fn set_self_mouse_selection_support(env: &PluginEnv, selection_support: bool) {
     env.senders
         .send_to_screen(ScreenInstruction::SetMouseSelectionSupport(
@@ -2571,6 +2852,10 @@ fn check_command_permission(
         | PluginCommand::RunCommand(..)
         | PluginCommand::ExecCmd(..) => PermissionType::RunCommands,
         PluginCommand::WebRequest(..) => PermissionType::WebAccess,
+        PluginCommand::OpenConnection(ConnectionKind::UnixSocket, ..) => {
+            PermissionType::LocalSocketAccess
+        },
+        PluginCommand::OpenConnection(..) => PermissionType::NetworkAccess,
         PluginCommand::Write(..)
         | PluginCommand::WriteChars(..)
         | PluginCommand::WriteToPaneId(..)
@@ -2660,9 +2945,11 @@ fn check_command_permission(
         | PluginCommand::BlockCliPipeInput(..)
         | PluginCommand::CliPipeOutput(..) => PermissionType::ReadCliPipes,
         PluginCommand::MessageToPlugin(..) => PermissionType::MessageAndLaunchOtherPlugins,
-        PluginCommand::ListClients | PluginCommand::DumpSessionLayout => {
-            PermissionType::ReadApplicationState
-        },
+        PluginCommand::ListClients
+        | PluginCommand::DumpSessionLayout
+        | PluginCommand::ListSessionSnapshots
+        | PluginCommand::ListSessionTemplates
+        | PluginCommand::ResolveSessionTemplate(..) => PermissionType::ReadApplicationState,
         PluginCommand::RebindKeys { .. } | PluginCommand::Reconfigure(..) => {
             PermissionType::Reconfigure
         },
@@ -2675,7 +2962,10 @@ fn check_command_permission(
         | PluginCommand::RevokeWebLoginToken(..)
         | PluginCommand::RevokeAllWebLoginTokens
         | PluginCommand::RenameWebLoginToken(..)
+        | PluginCommand::UpdateWebLoginToken(..)
+        | PluginCommand::SetWebLoginTokenExpiry(..)
         | PluginCommand::ListWebLoginTokens
+        | PluginCommand::ReadWebAuditLog
         | PluginCommand::StartWebServer => PermissionType::StartWebServer,
         PluginCommand::InterceptKeyPresses | PluginCommand::ClearKeyPressesIntercepts => {
             PermissionType::InterceptInput
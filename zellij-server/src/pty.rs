@@ -1,6 +1,7 @@
 use crate::background_jobs::BackgroundJob;
 use crate::terminal_bytes::TerminalBytes;
 use crate::{
+    listings::{self, PaneListing},
     panes::PaneId,
     plugins::{PluginId, PluginInstruction},
     screen::ScreenInstruction,
@@ -152,7 +153,7 @@ pub enum PtyInstruction {
         bool, // close replaced pane
         ClientTabIndexOrPaneId,
     ), // String is an optional pane name
-    DumpLayout(SessionLayoutMetadata, ClientId),
+    DumpLayout(SessionLayoutMetadata, ClientId, bool), // bool -> as JSON
     DumpLayoutToPlugin(SessionLayoutMetadata, PluginId),
     LogLayoutToHd(SessionLayoutMetadata),
     FillPluginCwd(
@@ -169,13 +170,16 @@ pub enum PtyInstruction {
         Option<bool>,    // should focus plugin
         Option<FloatingPaneCoordinates>,
     ),
-    ListClientsMetadata(SessionLayoutMetadata, ClientId),
+    ListClientsMetadata(SessionLayoutMetadata, ClientId, bool), // bool -> as JSON
     Reconfigure {
         client_id: ClientId,
         default_editor: Option<PathBuf>,
         post_command_discovery_hook: Option<String>,
     },
     ListClientsToPlugin(SessionLayoutMetadata, PluginId, ClientId),
+    SnapshotSession(SessionLayoutMetadata, String, ClientId), // String - snapshot name
+    ReceiveFile(PathBuf, String, ClientId), // PathBuf - the staged upload, String - file name
+    ListPanes(Vec<PaneListing>, ClientId, bool), // bool -> as JSON
     Exit,
 }
 
@@ -199,6 +203,9 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::ListClientsMetadata(..) => PtyContext::ListClientsMetadata,
             PtyInstruction::Reconfigure { .. } => PtyContext::Reconfigure,
             PtyInstruction::ListClientsToPlugin(..) => PtyContext::ListClientsToPlugin,
+            PtyInstruction::SnapshotSession(..) => PtyContext::SnapshotSession,
+            PtyInstruction::ReceiveFile(..) => PtyContext::ReceiveFile,
+            PtyInstruction::ListPanes(..) => PtyContext::ListPanes,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -610,13 +617,18 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
-            PtyInstruction::DumpLayout(mut session_layout_metadata, client_id) => {
+            PtyInstruction::DumpLayout(mut session_layout_metadata, client_id, json) => {
                 let err_context = || format!("Failed to dump layout");
                 pty.populate_session_layout_metadata(&mut session_layout_metadata);
                 match session_serialization::serialize_session_layout(
                     session_layout_metadata.into(),
                 ) {
                     Ok((kdl_layout, _pane_contents)) => {
+                        let kdl_layout = if json {
+                            serde_json::json!({ "layout": kdl_layout }).to_string()
+                        } else {
+                            kdl_layout
+                        };
                         pty.bus
                             .senders
                             .send_to_server(ServerInstruction::Log(vec![kdl_layout], client_id))
@@ -632,7 +644,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
-            PtyInstruction::ListClientsMetadata(mut session_layout_metadata, client_id) => {
+            PtyInstruction::ListClientsMetadata(mut session_layout_metadata, client_id, json) => {
                 let err_context = || format!("Failed to dump layout");
                 pty.populate_session_layout_metadata(&mut session_layout_metadata);
                 pty.bus
@@ -640,7 +652,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     .send_to_server(ServerInstruction::Log(
                         vec![format!(
                             "{}",
-                            session_layout_metadata.list_clients_metadata()
+                            session_layout_metadata.list_clients_metadata(json)
                         )],
                         client_id,
                     ))
@@ -676,6 +688,75 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     .with_context(err_context)
                     .non_fatal();
             },
+            PtyInstruction::SnapshotSession(
+                mut session_layout_metadata,
+                snapshot_name,
+                client_id,
+            ) => {
+                let err_context = || format!("Failed to snapshot session");
+                pty.populate_session_layout_metadata(&mut session_layout_metadata);
+                match session_serialization::serialize_session_layout(
+                    session_layout_metadata.into(),
+                ) {
+                    Ok(kdl_layout_and_pane_contents) => {
+                        pty.bus
+                            .senders
+                            .send_to_background_jobs(BackgroundJob::WriteSessionSnapshot(
+                                snapshot_name,
+                                kdl_layout_and_pane_contents,
+                                client_id,
+                            ))
+                            .with_context(err_context)?;
+                    },
+                    Err(e) => {
+                        pty.bus
+                            .senders
+                            .send_to_server(ServerInstruction::LogError(
+                                vec![e.to_owned()],
+                                client_id,
+                            ))
+                            .with_context(err_context)
+                            .non_fatal();
+                    },
+                }
+            },
+            PtyInstruction::ReceiveFile(staged_file, file_name, client_id) => {
+                // uploads land next to whatever the uploading client is looking at
+                let destination_folder = pty
+                    .active_pane_cwd(client_id)
+                    .or_else(|| std::env::current_dir().ok())
+                    .unwrap_or_else(|| PathBuf::from("."));
+                pty.bus
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::MoveUploadedFile(
+                        staged_file,
+                        destination_folder,
+                        file_name,
+                        client_id,
+                    ))
+                    .with_context(|| format!("failed to receive uploaded file"))?;
+            },
+            PtyInstruction::ListPanes(mut pane_listings, client_id, json) => {
+                pty.populate_pane_listings(&mut pane_listings);
+                let instruction = if json {
+                    match serde_json::to_string(&pane_listings) {
+                        Ok(pane_listings) => ServerInstruction::Log(vec![pane_listings], client_id),
+                        Err(e) => {
+                            log::error!("Failed to serialize pane listings: {}", e);
+                            ServerInstruction::LogError(
+                                vec![format!("Failed to serialize pane listings: {}", e)],
+                                client_id,
+                            )
+                        },
+                    }
+                } else {
+                    ServerInstruction::Log(vec![listings::render_panes(&pane_listings)], client_id)
+                };
+                pty.bus
+                    .senders
+                    .send_to_server(instruction)
+                    .with_context(|| format!("failed to list panes"))?;
+            },
             PtyInstruction::LogLayoutToHd(mut session_layout_metadata) => {
                 let err_context = || format!("Failed to dump layout");
                 pty.populate_session_layout_metadata(&mut session_layout_metadata);
@@ -799,22 +880,24 @@ impl Pty {
     fn fill_cwd(&self, terminal_action: &mut TerminalAction, client_id: ClientId) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
-                run_command.cwd = self
-                    .active_panes
-                    .get(&client_id)
-                    .and_then(|pane| match pane {
-                        PaneId::Plugin(..) => None,
-                        PaneId::Terminal(id) => self.id_to_child_pid.get(id),
-                    })
-                    .and_then(|&id| {
-                        self.bus
-                            .os_input
-                            .as_ref()
-                            .and_then(|input| input.get_cwd(Pid::from_raw(id)))
-                    });
+                run_command.cwd = self.active_pane_cwd(client_id);
             };
         };
     }
+    fn active_pane_cwd(&self, client_id: ClientId) -> Option<PathBuf> {
+        self.active_panes
+            .get(&client_id)
+            .and_then(|pane| match pane {
+                PaneId::Plugin(..) => None,
+                PaneId::Terminal(id) => self.id_to_child_pid.get(id),
+            })
+            .and_then(|&id| {
+                self.bus
+                    .os_input
+                    .as_ref()
+                    .and_then(|input| input.get_cwd(Pid::from_raw(id)))
+            })
+    }
     fn fill_cwd_from_pane_id(&self, terminal_action: &mut TerminalAction, pane_id: &u32) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
@@ -1192,9 +1275,13 @@ impl Pty {
                     }
                 }
             },
-            Some(Run::Cwd(cwd)) => {
+            Some(run_instruction @ Run::Cwd(_)) | Some(run_instruction @ Run::Shell(..)) => {
                 let starts_held = false; // we do not hold Cwd panes
-                let shell = self.get_default_terminal(Some(cwd), Some(default_shell.clone()));
+                let mut shell = self
+                    .get_default_terminal(run_instruction.get_cwd(), Some(default_shell.clone()));
+                if let TerminalAction::RunCommand(shell_command) = &mut shell {
+                    shell_command.env.extend(run_instruction.get_env());
+                }
                 match self
                     .bus
                     .os_input
@@ -1417,6 +1504,33 @@ impl Pty {
         session_layout_metadata: &mut SessionLayoutMetadata,
     ) {
         let terminal_ids = session_layout_metadata.all_terminal_ids();
+        let (terminal_ids_to_commands, terminal_ids_to_cwds) =
+            self.terminal_commands_and_cwds(terminal_ids);
+        session_layout_metadata.update_default_shell(get_default_shell());
+        session_layout_metadata.update_terminal_commands(terminal_ids_to_commands);
+        session_layout_metadata.update_terminal_cwds(terminal_ids_to_cwds);
+        session_layout_metadata.update_default_editor(&self.default_editor)
+    }
+    pub fn populate_pane_listings(&self, pane_listings: &mut Vec<PaneListing>) {
+        let terminal_ids = pane_listings
+            .iter()
+            .filter_map(|pane_listing| pane_listing.terminal_id())
+            .collect();
+        let (mut terminal_ids_to_commands, mut terminal_ids_to_cwds) =
+            self.terminal_commands_and_cwds(terminal_ids);
+        for pane_listing in pane_listings.iter_mut() {
+            if let Some(terminal_id) = pane_listing.terminal_id() {
+                pane_listing.running_command = terminal_ids_to_commands
+                    .remove(&terminal_id)
+                    .map(|command| command.join(" "));
+                pane_listing.cwd = terminal_ids_to_cwds.remove(&terminal_id);
+            }
+        }
+    }
+    fn terminal_commands_and_cwds(
+        &self,
+        terminal_ids: Vec<u32>,
+    ) -> (HashMap<u32, Vec<String>>, HashMap<u32, PathBuf>) {
         let mut terminal_ids_to_commands: HashMap<u32, Vec<String>> = HashMap::new();
         let mut terminal_ids_to_cwds: HashMap<u32, PathBuf> = HashMap::new();
 
@@ -1453,10 +1567,7 @@ impl Pty {
                 terminal_ids_to_cwds.insert(terminal_id, cwd.clone());
             }
         }
-        session_layout_metadata.update_default_shell(get_default_shell());
-        session_layout_metadata.update_terminal_commands(terminal_ids_to_commands);
-        session_layout_metadata.update_terminal_cwds(terminal_ids_to_cwds);
-        session_layout_metadata.update_default_editor(&self.default_editor)
+        (terminal_ids_to_commands, terminal_ids_to_cwds)
     }
     pub fn fill_plugin_cwd(
         &self,
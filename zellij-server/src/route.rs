@@ -15,7 +15,8 @@ use std::time::Duration;
 use uuid::Uuid;
 use zellij_utils::{
     channels::SenderWithContext,
-    data::{Direction, Event, InputMode, PluginCapabilities, ResizeStrategy},
+    data::{Direction, Event, InputMode, PluginCapabilities, ResizeStrategy, WebAuditEventKind},
+    envs,
     errors::prelude::*,
     input::{
         actions::{Action, SearchDirection, SearchOption},
@@ -27,6 +28,7 @@ use zellij_utils::{
     ipc::{
         ClientAttributes, ClientToServerMsg, ExitReason, IpcReceiverWithContext, ServerToClientMsg,
     },
+    web_audit_log,
 };
 
 use crate::ClientId;
@@ -192,13 +194,17 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::DumpScreen(val, client_id, full))
                 .with_context(err_context)?;
         },
-        Action::DumpLayout => {
+        Action::DumpLayout(json) => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
                 _ => None,
             };
             senders
-                .send_to_screen(ScreenInstruction::DumpLayout(default_shell, client_id))
+                .send_to_screen(ScreenInstruction::DumpLayout(
+                    default_shell,
+                    client_id,
+                    json,
+                ))
                 .with_context(err_context)?;
         },
         Action::EditScrollback => {
@@ -633,9 +639,9 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::NextSwapLayout(client_id))
                 .with_context(err_context)?;
         },
-        Action::QueryTabNames => {
+        Action::QueryTabNames(json) => {
             senders
-                .send_to_screen(ScreenInstruction::QueryTabNames(client_id))
+                .send_to_screen(ScreenInstruction::QueryTabNames(client_id, json))
                 .with_context(err_context)?;
         },
         Action::NewTiledPluginPane(run_plugin, name, skip_cache, cwd) => {
@@ -809,6 +815,11 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::RenameSession(name, client_id))
                 .with_context(err_context)?;
         },
+        Action::SetSessionTags(tags) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetSessionTags(tags, client_id))
+                .with_context(err_context)?;
+        },
         Action::CliPipe {
             pipe_id,
             mut name,
@@ -904,7 +915,7 @@ pub(crate) fn route_action(
                 log::error!("Message must have a name");
             }
         },
-        Action::ListClients => {
+        Action::ListClients(json) => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
                 _ => None,
@@ -913,6 +924,70 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ListClientsMetadata(
                     default_shell,
                     client_id,
+                    json,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ListPanes(json) => {
+            senders
+                .send_to_screen(ScreenInstruction::ListPanes(client_id, json))
+                .with_context(err_context)?;
+        },
+        Action::ListTabs(json) => {
+            senders
+                .send_to_screen(ScreenInstruction::ListTabs(client_id, json))
+                .with_context(err_context)?;
+        },
+        Action::SnapshotSession(snapshot_name, with_scrollback) => {
+            let default_shell = match default_shell {
+                Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
+                _ => None,
+            };
+            senders
+                .send_to_screen(ScreenInstruction::SnapshotSession(
+                    default_shell,
+                    snapshot_name,
+                    with_scrollback,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::RestrictInput(target, pane_ids) => {
+            senders
+                .send_to_screen(ScreenInstruction::RestrictInput(
+                    target,
+                    pane_ids.map(|pane_ids| pane_ids.into_iter().map(PaneId::from).collect()),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SendFile(path) => {
+            senders
+                .send_to_server(ServerInstruction::SendFileToWebClients(path, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ReceiveFile(staged_file, file_name) => {
+            senders
+                .send_to_pty(PtyInstruction::ReceiveFile(
+                    staged_file,
+                    file_name,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::WaitForPaneExit(terminal_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::WaitForPaneExit(
+                    PaneId::Terminal(terminal_id),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SubscribeToSessionEvents(event_types) => {
+            senders
+                .send_to_screen(ScreenInstruction::SubscribeToSessionEvents(
+                    event_types,
+                    client_id,
                 ))
                 .with_context(err_context)?;
         },
@@ -951,6 +1026,39 @@ pub(crate) fn route_action(
     Ok(should_break)
 }
 
+// web clients are audited, `token_name` is the login token they authenticated with
+fn audit_web_client(
+    kind: WebAuditEventKind,
+    client_id: ClientId,
+    token_name: &Option<String>,
+    details: Option<String>,
+) {
+    let mut event = web_audit_log::new_event(kind);
+    event.session_name = envs::get_session_name().ok();
+    event.token_name = token_name.clone();
+    event.client_id = Some(client_id.to_string());
+    event.details = details;
+    web_audit_log::record(event);
+}
+
+fn audit_web_client_action(action: &Action, client_id: ClientId, token_name: &Option<String>) {
+    match action {
+        Action::Quit => audit_web_client(
+            WebAuditEventKind::SessionKilled,
+            client_id,
+            token_name,
+            None,
+        ),
+        action if action.is_audited() => audit_web_client(
+            WebAuditEventKind::Action,
+            client_id,
+            token_name,
+            Some(format!("{:?}", action)),
+        ),
+        _ => {},
+    }
+}
+
 // this should only be used for one-off startup instructions
 macro_rules! send_to_screen_or_retry_queue {
     ($rlocked_sessions:expr, $message:expr, $instruction: expr, $retry_queue:expr) => {{
@@ -978,6 +1086,12 @@ pub(crate) fn route_thread_main(
     let mut retry_queue = VecDeque::new();
     let err_context = || format!("failed to handle instruction for client {client_id}");
     let mut seen_cli_pipes = HashSet::new();
+    // set when the client connects, input and actions from read-only clients are dropped here
+    // unless they only affect what the client itself is viewing
+    let mut client_is_read_only = false;
+    // set when a web client connects, what it does in the session ends up in the audit log
+    let mut client_is_web_client = false;
+    let mut client_token_name = None;
     'route_loop: loop {
         match receiver.recv() {
             Some((instruction, err_ctx)) => {
@@ -1035,8 +1149,17 @@ pub(crate) fn route_thread_main(
                                                 actions);
                                         }
                                         
-                                        for action in actions
-                                        {
+                                        for action in actions.into_iter().filter(|action| {
+                                            !client_is_read_only
+                                                || action.is_allowed_for_read_only_clients()
+                                        }) {
+                                            if client_is_web_client {
+                                                audit_web_client_action(
+                                                    &action,
+                                                    client_id,
+                                                    &client_token_name,
+                                                );
+                                            }
                                             if route_action(
                                                 action,
                                                 client_id,
@@ -1066,8 +1189,21 @@ pub(crate) fn route_thread_main(
                                 }
                             }
                         },
+                        ClientToServerMsg::Action(action, _, _)
+                            if client_is_read_only
+                                && !action.is_allowed_for_read_only_clients() =>
+                        {
+                            log::warn!(
+                                "Dropping action {:?} from read-only client {}",
+                                action,
+                                client_id
+                            );
+                        },
                         ClientToServerMsg::Action(action, maybe_pane_id, maybe_client_id) => {
                             let client_id = maybe_client_id.unwrap_or(client_id);
+                            if client_is_web_client {
+                                audit_web_client_action(&action, client_id, &client_token_name);
+                            }
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
                                 if route_action(
                                     action,
@@ -1168,6 +1304,17 @@ pub(crate) fn route_thread_main(
                             should_launch_setup_wizard,
                             is_web_client,
                         ) => {
+                            client_is_read_only = client_attributes.read_only;
+                            client_is_web_client = is_web_client;
+                            client_token_name = client_attributes.token_name.clone();
+                            if client_is_web_client {
+                                audit_web_client(
+                                    WebAuditEventKind::SessionCreated,
+                                    client_id,
+                                    &client_token_name,
+                                    None,
+                                );
+                            }
                             let new_client_instruction = ServerInstruction::NewClient(
                                 client_attributes,
                                 cli_args,
@@ -1191,6 +1338,9 @@ pub(crate) fn route_thread_main(
                             pane_id_to_focus,
                             is_web_client,
                         ) => {
+                            client_is_read_only = client_attributes.read_only;
+                            client_is_web_client = is_web_client;
+                            client_token_name = client_attributes.token_name.clone();
                             let allow_web_connections = rlocked_sessions
                                 .as_ref()
                                 .map(|rlocked_sessions| {
@@ -1227,7 +1377,18 @@ pub(crate) fn route_thread_main(
                             let _ = to_server.send(ServerInstruction::RemoveClient(client_id));
                             return Ok(true);
                         },
+                        ClientToServerMsg::KillSession if client_is_read_only => {
+                            log::warn!("Dropping kill request from read-only client {}", client_id);
+                        },
                         ClientToServerMsg::KillSession => {
+                            if client_is_web_client {
+                                audit_web_client(
+                                    WebAuditEventKind::SessionKilled,
+                                    client_id,
+                                    &client_token_name,
+                                    None,
+                                );
+                            }
                             to_server
                                 .send(ServerInstruction::KillSession)
                                 .with_context(err_context)?;
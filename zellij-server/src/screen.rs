@@ -6,12 +6,13 @@ use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::str;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use log::{debug, warn};
 use zellij_utils::data::{
-    Direction, FloatingPaneCoordinates, KeyWithModifier, PaneManifest, PluginPermission, Resize,
-    ResizeStrategy, SessionInfo, Styling, WebSharing,
+    Direction, FloatingPaneCoordinates, InputRestrictionTarget, KeyWithModifier, PaneInfo,
+    PaneManifest, PluginPermission, Resize, ResizeStrategy, SessionEvent, SessionEventType,
+    SessionInfo, Styling, WebSharing,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -29,9 +30,11 @@ use zellij_utils::{
         SwapTiledLayout, TiledPaneLayout,
     },
     position::Position,
+    sessions::{get_session_tags, normalize_session_tags, set_session_tags},
 };
 
 use crate::background_jobs::BackgroundJob;
+use crate::listings::{self, PaneListing};
 use crate::os_input_output::ResizeCache;
 use crate::pane_groups::PaneGroups;
 use crate::panes::alacritty_functions::xparse_color;
@@ -182,8 +185,8 @@ pub enum ScreenInstruction {
     Exit,
     ClearScreen(ClientId),
     DumpScreen(String, ClientId, bool),
-    DumpLayout(Option<PathBuf>, ClientId), // PathBuf is the default configured
-    // shell
+    DumpLayout(Option<PathBuf>, ClientId, bool), // PathBuf is the default configured
+    // shell, bool -> as JSON
     DumpLayoutToPlugin(PluginId),
     EditScrollback(ClientId),
     ScrollUp(ClientId),
@@ -254,6 +257,7 @@ pub enum ScreenInstruction {
     AddClient(
         ClientId,
         bool,                // is_web_client
+        bool,                // is_read_only
         Option<usize>,       // tab position to focus
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane_id to focus
     ),
@@ -272,7 +276,7 @@ pub enum ScreenInstruction {
     ClearPaneFrameColorOverride(Vec<PaneId>),
     PreviousSwapLayout(ClientId),
     NextSwapLayout(ClientId),
-    QueryTabNames(ClientId),
+    QueryTabNames(ClientId, bool), // bool -> as JSON
     NewTiledPluginPane(
         RunPluginOrAlias,
         Option<String>,
@@ -355,7 +359,8 @@ pub enum ScreenInstruction {
     ),
     SerializeLayoutForResurrection,
     RenameSession(String, ClientId), // String -> new name
-    ListClientsMetadata(Option<PathBuf>, ClientId), // Option<PathBuf> - default shell
+    SetSessionTags(Vec<String>, ClientId),
+    ListClientsMetadata(Option<PathBuf>, ClientId, bool), // default shell, bool -> as JSON
     Reconfigure {
         client_id: ClientId,
         keybinds: Keybinds,
@@ -421,6 +426,14 @@ pub enum ScreenInstruction {
     InterceptKeyPresses(PluginId, ClientId),
     ClearKeyPressesIntercepts(ClientId),
     ReplacePaneWithExistingPane(PaneId, PaneId),
+    SnapshotSession(Option<PathBuf>, String, bool, ClientId), // default shell, name, scrollback
+    SetClientIdentity(ClientId, Option<String>, Option<String>), // user name, web login token name
+    RestrictInput(InputRestrictionTarget, Option<Vec<PaneId>>, ClientId), // panes they may type
+    // into, requesting client
+    WaitForPaneExit(PaneId, ClientId),
+    ListPanes(ClientId, bool), // bool -> as JSON
+    ListTabs(ClientId, bool),  // bool -> as JSON
+    SubscribeToSessionEvents(Vec<SessionEventType>, ClientId),
 }
 
 impl From<&ScreenInstruction> for ScreenContext {
@@ -599,6 +612,7 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::SerializeLayoutForResurrection
             },
             ScreenInstruction::RenameSession(..) => ScreenContext::RenameSession,
+            ScreenInstruction::SetSessionTags(..) => ScreenContext::SetSessionTags,
             ScreenInstruction::ListClientsMetadata(..) => ScreenContext::ListClientsMetadata,
             ScreenInstruction::Reconfigure { .. } => ScreenContext::Reconfigure,
             ScreenInstruction::RerunCommandPane { .. } => ScreenContext::RerunCommandPane,
@@ -658,6 +672,15 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ReplacePaneWithExistingPane(..) => {
                 ScreenContext::ReplacePaneWithExistingPane
             },
+            ScreenInstruction::SnapshotSession(..) => ScreenContext::SnapshotSession,
+            ScreenInstruction::SetClientIdentity(..) => ScreenContext::SetClientIdentity,
+            ScreenInstruction::RestrictInput(..) => ScreenContext::RestrictInput,
+            ScreenInstruction::WaitForPaneExit(..) => ScreenContext::WaitForPaneExit,
+            ScreenInstruction::ListPanes(..) => ScreenContext::ListPanes,
+            ScreenInstruction::ListTabs(..) => ScreenContext::ListTabs,
+            ScreenInstruction::SubscribeToSessionEvents(..) => {
+                ScreenContext::SubscribeToSessionEvents
+            },
         }
     }
 }
@@ -712,6 +735,21 @@ pub(crate) struct Screen {
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
     connected_clients: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+    /// Clients that can only watch the session, their input is dropped by the router
+    read_only_clients: HashSet<ClientId>,
+    /// The names users gave themselves (with `--user` or their web login token)
+    client_user_names: Rc<RefCell<HashMap<ClientId, String>>>,
+    /// The login tokens web clients authenticated with, assigned by the web server
+    client_token_names: HashMap<ClientId, String>,
+    /// Clients who may only type into some panes
+    input_restrictions: HashMap<InputRestrictionTarget, HashSet<PaneId>>,
+    /// Clients waiting for a command pane to exit (with `swarm wait`)
+    pane_exit_waiters: HashMap<PaneId, Vec<ClientId>>,
+    /// Clients streaming session events (with `swarm subscribe`), an empty set means all events
+    session_event_subscribers: HashMap<ClientId, HashSet<SessionEventType>>,
+    /// The panes and tabs as last reported to the subscribers, to tell them what changed
+    reported_panes: HashSet<PaneId>,
+    reported_tabs: Vec<TabInfo>,
     /// The indices of this [`Screen`]'s active [`Tab`]s.
     active_tab_indices: BTreeMap<ClientId, usize>,
     tab_history: BTreeMap<ClientId, Vec<usize>>,
@@ -731,6 +769,8 @@ pub(crate) struct Screen {
     // also be this session
     resurrectable_sessions: BTreeMap<String, Duration>, // String is the session name, duration is
     // its creation time
+    session_tags: Vec<String>,
+    last_activity: u64, // seconds since the unix epoch of the last pane output or user input
     default_layout: Box<Layout>,
     default_shell: PathBuf,
     styled_underlines: bool,
@@ -750,6 +790,21 @@ pub(crate) struct Screen {
     web_server_port: u16,
 }
 
+fn unix_timestamp_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn pane_id_of(pane_info: &PaneInfo) -> PaneId {
+    if pane_info.is_plugin {
+        PaneId::Plugin(pane_info.id)
+    } else {
+        PaneId::Terminal(pane_info.id)
+    }
+}
+
 impl Screen {
     /// Creates and returns a new [`Screen`].
     pub fn new(
@@ -784,6 +839,7 @@ impl Screen {
         let session_info = SessionInfo::new(session_name.clone());
         let mut session_infos_on_machine = BTreeMap::new();
         let resurrectable_sessions = BTreeMap::new();
+        let session_tags = get_session_tags(&session_name);
         session_infos_on_machine.insert(session_name.clone(), session_info);
         let current_pane_group = PaneGroups::new(bus.senders.clone());
         Screen {
@@ -796,6 +852,14 @@ impl Screen {
             sixel_image_store: Rc::new(RefCell::new(SixelImageStore::default())),
             style: client_attributes.style,
             connected_clients: Rc::new(RefCell::new(HashMap::new())),
+            read_only_clients: HashSet::new(),
+            client_user_names: Rc::new(RefCell::new(HashMap::new())),
+            client_token_names: HashMap::new(),
+            input_restrictions: HashMap::new(),
+            pane_exit_waiters: HashMap::new(),
+            session_event_subscribers: HashMap::new(),
+            reported_panes: HashSet::new(),
+            reported_tabs: vec![],
             active_tab_indices: BTreeMap::new(),
             tabs: BTreeMap::new(),
             overlay: OverlayWindow::default(),
@@ -820,6 +884,8 @@ impl Screen {
             styled_underlines,
             arrow_fonts,
             resurrectable_sessions,
+            session_tags,
+            last_activity: unix_timestamp_now(),
             layout_dir,
             explicitly_disable_kitty_keyboard_protocol,
             default_editor,
@@ -1441,6 +1507,7 @@ impl Screen {
             self.draw_pane_frames,
             self.auto_layout,
             self.connected_clients.clone(),
+            self.client_user_names.clone(),
             self.session_is_mirrored,
             client_id,
             self.copy_options.clone(),
@@ -1635,13 +1702,287 @@ impl Screen {
             self.tab_history.remove(&client_id);
         }
         self.connected_clients.borrow_mut().remove(&client_id);
+        self.read_only_clients.remove(&client_id);
+        self.client_user_names.borrow_mut().remove(&client_id);
+        self.client_token_names.remove(&client_id);
+        // client ids are reused, the next client with this id is someone else
+        self.input_restrictions
+            .remove(&InputRestrictionTarget::Client(client_id));
+        for waiting_clients in self.pane_exit_waiters.values_mut() {
+            waiting_clients.retain(|c| *c != client_id);
+        }
+        self.pane_exit_waiters
+            .retain(|_, waiting_clients| !waiting_clients.is_empty());
+        self.session_event_subscribers.remove(&client_id);
+        self.update_connected_users().with_context(err_context)?;
         self.log_and_report_session_state()
             .with_context(err_context)
     }
 
-    pub fn generate_and_report_tab_state(&mut self) -> Result<Vec<TabInfo>> {
-        let mut plugin_updates = vec![];
-        let mut tab_infos_for_screen_state = BTreeMap::new();
+    /// Replies to the client once this command pane exits, right away if it already has
+    pub fn wait_for_pane_exit(&mut self, pane_id: PaneId, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to wait for pane {pane_id:?} to exit");
+        let exited_pane = self
+            .tabs
+            .values()
+            .find_map(|tab| tab.get_pane_with_id(pane_id))
+            .map(|pane| pane.exited().then(|| pane.exit_status()));
+        match exited_pane {
+            Some(Some(exit_status)) => self
+                .bus
+                .senders
+                .send_to_server(ServerInstruction::PaneExited(exit_status, client_id))
+                .with_context(err_context),
+            Some(None) => {
+                self.pane_exit_waiters
+                    .entry(pane_id)
+                    .or_default()
+                    .push(client_id);
+                Ok(())
+            },
+            None => {
+                let error = match pane_id {
+                    PaneId::Terminal(id) | PaneId::Plugin(id) => {
+                        format!("No pane with id {} in this session", id)
+                    },
+                };
+                self.bus
+                    .senders
+                    .send_to_server(ServerInstruction::LogError(vec![error], client_id))
+                    .with_context(err_context)
+            },
+        }
+    }
+
+    // panes are closed in many ways (eg. along with their tab), so rather than catching all of
+    // them we check whether a pane someone is waiting for is gone whenever the session changes
+    fn report_exit_of_closed_panes(&mut self) -> Result<()> {
+        let closed_panes: Vec<PaneId> = self
+            .pane_exit_waiters
+            .keys()
+            .filter(|pane_id| {
+                !self
+                    .tabs
+                    .values()
+                    .any(|tab| tab.get_pane_with_id(**pane_id).is_some())
+            })
+            .copied()
+            .collect();
+        for pane_id in closed_panes {
+            self.report_pane_exit(pane_id, None)?;
+        }
+        Ok(())
+    }
+
+    fn report_pane_exit(&mut self, pane_id: PaneId, exit_status: Option<i32>) -> Result<()> {
+        for client_id in self.pane_exit_waiters.remove(&pane_id).unwrap_or_default() {
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::PaneExited(exit_status, client_id))
+                .with_context(|| format!("failed to report the exit of pane {pane_id:?}"))?;
+        }
+        Ok(())
+    }
+
+    /// Streams these kinds of session events to the client from now on, all of them if none are
+    /// given
+    pub fn subscribe_to_session_events(
+        &mut self,
+        event_types: Vec<SessionEventType>,
+        client_id: ClientId,
+    ) {
+        if self.session_event_subscribers.is_empty() {
+            // subscribers are only told about changes, not about what was there to begin with
+            self.reported_panes = self
+                .tabs
+                .values()
+                .flat_map(|tab| tab.pane_infos())
+                .map(|pane_info| pane_id_of(&pane_info))
+                .collect();
+            self.reported_tabs = self.tab_infos().into_values().collect();
+        }
+        self.session_event_subscribers
+            .insert(client_id, event_types.into_iter().collect());
+    }
+
+    // this is called with the state we just sent the plugins, so subscribers see the same changes
+    fn report_session_events(
+        &mut self,
+        pane_manifest: &PaneManifest,
+        tab_infos: &[TabInfo],
+    ) -> Result<()> {
+        if self.session_event_subscribers.is_empty() {
+            return Ok(());
+        }
+        let mut opened_panes = vec![];
+        let mut panes_in_session = HashSet::new();
+        let mut tab_positions: Vec<&usize> = pane_manifest.panes.keys().collect();
+        tab_positions.sort();
+        for tab_position in tab_positions {
+            for pane_info in pane_manifest.panes.get(tab_position).into_iter().flatten() {
+                let pane_id = pane_id_of(pane_info);
+                if !self.reported_panes.contains(&pane_id) {
+                    opened_panes.push(SessionEvent::PaneOpened {
+                        tab_position: *tab_position,
+                        pane: pane_info.clone(),
+                    });
+                }
+                panes_in_session.insert(pane_id);
+            }
+        }
+        let mut closed_panes: Vec<PaneId> = self
+            .reported_panes
+            .difference(&panes_in_session)
+            .copied()
+            .collect();
+        closed_panes.sort();
+        let mut session_events: Vec<SessionEvent> = closed_panes
+            .into_iter()
+            .map(|pane_id| SessionEvent::PaneClosed {
+                pane_id: pane_id.into(),
+            })
+            .collect();
+        session_events.append(&mut opened_panes);
+        if self.reported_tabs != tab_infos {
+            self.reported_tabs = tab_infos.to_vec();
+            session_events.push(SessionEvent::TabUpdate {
+                tabs: tab_infos.to_vec(),
+            });
+        }
+        self.reported_panes = panes_in_session;
+        for session_event in session_events {
+            self.send_session_event(session_event)?;
+        }
+        Ok(())
+    }
+
+    fn send_session_event(&self, session_event: SessionEvent) -> Result<()> {
+        let event_type = session_event.event_type();
+        for (client_id, event_types) in &self.session_event_subscribers {
+            if event_types.is_empty() || event_types.contains(&event_type) {
+                self.bus
+                    .senders
+                    .send_to_server(ServerInstruction::SessionEvent(
+                        session_event.clone(),
+                        *client_id,
+                    ))
+                    .with_context(|| format!("failed to send {event_type} event"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lets the plugins of every client (eg. the status bar) know who is connected
+    pub fn update_connected_users(&mut self) -> Result<()> {
+        for tab in self.tabs.values_mut() {
+            tab.update_input_modes()
+                .context("failed to update connected users")?;
+        }
+        Ok(())
+    }
+
+    pub fn set_client_identity(
+        &mut self,
+        client_id: ClientId,
+        user_name: Option<String>,
+        token_name: Option<String>,
+    ) -> Result<()> {
+        if let Some(user_name) = user_name {
+            self.client_user_names
+                .borrow_mut()
+                .insert(client_id, user_name);
+        }
+        if let Some(token_name) = token_name {
+            self.client_token_names.insert(client_id, token_name);
+        }
+        self.update_connected_users()
+            .and_then(|_| self.render(None))
+            .with_context(|| format!("failed to set user name for client {client_id}"))
+    }
+
+    pub fn restrict_input(
+        &mut self,
+        target: InputRestrictionTarget,
+        pane_ids: Option<Vec<PaneId>>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        if self.input_restriction(client_id).is_some() {
+            let error = format!("Restricted clients cannot restrict the input of {}", target);
+            return self
+                .bus
+                .senders
+                .send_to_server(ServerInstruction::LogError(vec![error], client_id))
+                .context("failed to restrict input");
+        }
+        match pane_ids {
+            Some(pane_ids) => {
+                self.input_restrictions
+                    .insert(target, pane_ids.into_iter().collect());
+            },
+            None => {
+                self.input_restrictions.remove(&target);
+            },
+        }
+        Ok(())
+    }
+
+    /// The panes this client may type into, if it is restricted
+    fn input_restriction(&self, client_id: ClientId) -> Option<&HashSet<PaneId>> {
+        self.input_restrictions
+            .get(&InputRestrictionTarget::Client(client_id))
+            .or_else(|| {
+                self.client_token_names
+                    .get(&client_id)
+                    .and_then(|token_name| {
+                        self.input_restrictions
+                            .get(&InputRestrictionTarget::Token(token_name.clone()))
+                    })
+            })
+    }
+
+    /// Read-only clients may look around, but in a mirrored session every client sees the same
+    /// focus so moving it would change the view of everyone else
+    fn moves_focus_of_read_only_client_in_mirrored_session(
+        &self,
+        instruction: &ScreenInstruction,
+    ) -> bool {
+        if !self.session_is_mirrored {
+            return false;
+        }
+        let client_id = match instruction {
+            ScreenInstruction::FocusNextPane(client_id)
+            | ScreenInstruction::FocusPreviousPane(client_id)
+            | ScreenInstruction::MoveFocusLeft(client_id)
+            | ScreenInstruction::MoveFocusLeftOrPreviousTab(client_id)
+            | ScreenInstruction::MoveFocusDown(client_id)
+            | ScreenInstruction::MoveFocusUp(client_id)
+            | ScreenInstruction::MoveFocusRight(client_id)
+            | ScreenInstruction::MoveFocusRightOrNextTab(client_id)
+            | ScreenInstruction::SwitchTabNext(client_id)
+            | ScreenInstruction::SwitchTabPrev(client_id)
+            | ScreenInstruction::ToggleTab(client_id) => Some(*client_id),
+            ScreenInstruction::GoToTab(_, client_id)
+            | ScreenInstruction::GoToTabName(_, _, _, _, client_id) => *client_id,
+            _ => None,
+        };
+        client_id
+            .map(|client_id| self.read_only_clients.contains(&client_id))
+            .unwrap_or(false)
+    }
+
+    /// The client whose focused pane receives the input of this one, clients without a tab of
+    /// their own (eg. `swarm action write-chars`) type into the pane focused by the first client
+    fn client_receiving_input(&self, client_id: ClientId) -> ClientId {
+        if self.active_tab_indices.contains_key(&client_id) {
+            client_id
+        } else {
+            self.get_first_client_id().unwrap_or(client_id)
+        }
+    }
+
+    /// The tabs of the session as seen by all clients, by tab position
+    pub fn tab_infos(&self) -> BTreeMap<usize, TabInfo> {
+        let mut tab_infos = BTreeMap::new();
         for tab in self.tabs.values() {
             let all_focused_clients: Vec<ClientId> = self
                 .active_tab_indices
@@ -1673,8 +2014,25 @@ impl Screen {
                 selectable_tiled_panes_count,
                 selectable_floating_panes_count,
             };
-            tab_infos_for_screen_state.insert(tab.position, tab_info_for_screen);
+            tab_infos.insert(tab.position, tab_info_for_screen);
         }
+        tab_infos
+    }
+
+    /// Every pane of the session, the pty thread fills in their cwds and running commands
+    pub fn pane_listings(&self) -> Vec<PaneListing> {
+        let mut pane_listings = vec![];
+        for tab in self.tabs.values() {
+            for pane_info in tab.pane_infos() {
+                pane_listings.push(PaneListing::new(tab.position, tab.name.clone(), pane_info));
+            }
+        }
+        pane_listings
+    }
+
+    pub fn generate_and_report_tab_state(&mut self) -> Result<Vec<TabInfo>> {
+        let mut plugin_updates = vec![];
+        let tab_infos_for_screen_state = self.tab_infos();
         for (client_id, active_tab_index) in self.active_tab_indices.iter() {
             let mut plugin_tab_updates = vec![];
             for tab in self.tabs.values() {
@@ -1741,9 +2099,13 @@ impl Screen {
     }
     fn log_and_report_session_state(&mut self) -> Result<()> {
         let err_context = || format!("Failed to log and report session state");
+        self.report_exit_of_closed_panes()
+            .with_context(err_context)?;
         // generate own session info
         let pane_manifest = self.generate_and_report_pane_state()?;
         let tab_infos = self.generate_and_report_tab_state()?;
+        self.report_session_events(&pane_manifest, &tab_infos)
+            .with_context(err_context)?;
         // in the context of unit/integration tests, we don't need to list available layouts
         // because this is mostly about HD access - it does however throw off the timing in the
         // tests and causes them to flake, which is why we skip it here
@@ -1768,6 +2130,8 @@ impl Screen {
                 .count(),
             plugins: Default::default(), // these are filled in by the wasm thread
             tab_history: self.tab_history.clone(),
+            tags: self.session_tags.clone(),
+            last_activity: Some(self.last_activity),
         };
         self.bus
             .senders
@@ -1789,6 +2153,17 @@ impl Screen {
             .with_context(err_context)?;
         Ok(())
     }
+    fn update_last_activity(&mut self) {
+        // this is called for every pty read and keypress, so we only report it once a second
+        let now = unix_timestamp_now();
+        if now > self.last_activity {
+            self.last_activity = now;
+            let _ = self
+                .bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::ReportSessionActivity(now));
+        }
+    }
     fn dump_layout_to_hd(&mut self) -> Result<()> {
         let err_context = || format!("Failed to log and report session state");
         let session_layout_metadata = self.get_layout_metadata(Some(self.default_shell.clone()));
@@ -2920,10 +3295,18 @@ impl Screen {
             .context("failed to unblock input")
     }
     fn get_layout_metadata(&self, default_shell: Option<PathBuf>) -> SessionLayoutMetadata {
+        self.get_layout_metadata_with_viewport(default_shell, self.serialize_pane_viewport)
+    }
+    fn get_layout_metadata_with_viewport(
+        &self,
+        default_shell: Option<PathBuf>,
+        serialize_pane_viewport: bool,
+    ) -> SessionLayoutMetadata {
         let mut session_layout_metadata = SessionLayoutMetadata::new(self.default_layout.clone());
         if let Some(default_shell) = default_shell {
             session_layout_metadata.update_default_shell(default_shell);
         }
+        session_layout_metadata.update_read_only_clients(self.read_only_clients.clone());
         let first_client_id = self.get_first_client_id();
         let active_tab_index =
             first_client_id.and_then(|client_id| self.active_tab_indices.get(&client_id));
@@ -2981,7 +3364,7 @@ impl Screen {
                         p.invoked_with().clone(),
                         p.custom_title(),
                         !focused_clients.is_empty(),
-                        if self.serialize_pane_viewport {
+                        if serialize_pane_viewport {
                             p.serialize(self.scrollback_lines_to_serialize)
                         } else {
                             None
@@ -3019,7 +3402,7 @@ impl Screen {
                         p.invoked_with().clone(),
                         p.custom_title(),
                         !focused_clients.is_empty(),
-                        if self.serialize_pane_viewport {
+                        if serialize_pane_viewport {
                             p.serialize(self.scrollback_lines_to_serialize)
                         } else {
                             None
@@ -3290,6 +3673,14 @@ pub(crate) fn screen_thread_main(
             .recv()
             .context("failed to receive event on channel")?;
         err_ctx.add_call(ContextType::Screen((&event).into()));
+        if screen.moves_focus_of_read_only_client_in_mirrored_session(&event) {
+            log::warn!(
+                "Dropping {:?} from a read-only client, in a mirrored session it would move the focus of every client",
+                ScreenContext::from(&event)
+            );
+            screen.unblock_input()?;
+            continue;
+        }
         // here we start caching resizes, so that we'll send them in bulk at the end of each event
         // when this cache is Dropped, for more information, see the comments in PtyWriter
         let _resize_cache = ResizeCache::new(thread_senders.clone());
@@ -3304,6 +3695,7 @@ pub(crate) fn screen_thread_main(
                         break;
                     }
                 }
+                screen.update_last_activity();
                 let _ = screen
                     .bus
                     .senders
@@ -3546,14 +3938,29 @@ pub(crate) fn screen_thread_main(
                         continue;
                     }
                 }
+                screen.update_last_activity();
                 let mut state_changed = false;
+                // input sent through another client (eg. the cli) is restricted like the client
+                // whose pane it ends up in
+                let input_restriction = screen
+                    .input_restriction(client_id)
+                    .or_else(|| screen.input_restriction(screen.client_receiving_input(client_id)))
+                    .cloned();
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| {
-                        let write_result = match tab.is_sync_panes_active() {
-                            true => tab.write_to_terminals_on_current_tab(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id),
-                            false => tab.write_to_active_terminal(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id),
+                        let write_result = match (&input_restriction, tab.is_sync_panes_active()) {
+                            // restricted users only ever type into the focused pane, and only
+                            // if they're allowed to
+                            (Some(allowed_panes), _) => match tab.get_active_pane_id(client_id) {
+                                Some(pane_id) if allowed_panes.contains(&pane_id) => {
+                                    tab.write_to_active_terminal(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id)
+                                },
+                                _ => Ok(false),
+                            },
+                            (None, true) => tab.write_to_terminals_on_current_tab(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id),
+                            (None, false) => tab.write_to_active_terminal(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id),
                         };
                         if let Ok(true) = write_result {
                             state_changed = true;
@@ -3694,7 +4101,7 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::DumpLayout(default_shell, client_id) => {
+            ScreenInstruction::DumpLayout(default_shell, client_id, json) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata = screen.get_layout_metadata(default_shell);
                 screen
@@ -3703,10 +4110,11 @@ pub(crate) fn screen_thread_main(
                     .send_to_plugin(PluginInstruction::DumpLayout(
                         session_layout_metadata,
                         client_id,
+                        json,
                     ))
                     .with_context(err_context)?;
             },
-            ScreenInstruction::ListClientsMetadata(default_shell, client_id) => {
+            ScreenInstruction::ListClientsMetadata(default_shell, client_id, json) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata = screen.get_layout_metadata(default_shell);
                 screen
@@ -3715,6 +4123,7 @@ pub(crate) fn screen_thread_main(
                     .send_to_plugin(PluginInstruction::ListClientsMetadata(
                         session_layout_metadata,
                         client_id,
+                        json,
                     ))
                     .with_context(err_context)?;
             },
@@ -3998,6 +4407,17 @@ pub(crate) fn screen_thread_main(
                         break;
                     }
                 }
+                screen.report_pane_exit(id, exit_status)?;
+                let pane_is_in_session = screen
+                    .tabs
+                    .values()
+                    .any(|tab| tab.get_pane_with_id(id).is_some());
+                if let (PaneId::Terminal(terminal_id), true) = (id, pane_is_in_session) {
+                    screen.send_session_event(SessionEvent::CommandExited {
+                        pane_id: terminal_id,
+                        exit_status,
+                    })?;
+                }
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
             },
@@ -4119,6 +4539,11 @@ pub(crate) fn screen_thread_main(
                     if should_change_focus_to_new_tab {
                         screen.go_to_tab(tab_index as usize + 1, client_id)?;
                     }
+                    screen
+                        .bus
+                        .senders
+                        .send_to_server(ServerInstruction::LayoutApplied(client_id))
+                        .non_fatal();
                 } else if should_change_focus_to_new_tab {
                     let client_id_to_switch = if screen.active_tab_indices.contains_key(&client_id)
                     {
@@ -4330,10 +4755,15 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::AddClient(
                 client_id,
                 is_web_client,
+                is_read_only,
                 tab_position_to_focus,
                 pane_id_to_focus,
             ) => {
                 screen.add_client(client_id, is_web_client)?;
+                if is_read_only {
+                    screen.read_only_clients.insert(client_id);
+                }
+                screen.update_connected_users()?;
                 let pane_id = pane_id_to_focus.map(|(pane_id, is_plugin)| {
                     if is_plugin {
                         PaneId::Plugin(pane_id)
@@ -4514,12 +4944,18 @@ pub(crate) fn screen_thread_main(
                 screen.log_and_report_session_state()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::QueryTabNames(client_id) => {
+            ScreenInstruction::QueryTabNames(client_id, json) => {
                 let tab_names = screen
                     .get_tabs_mut()
                     .values()
                     .map(|tab| tab.name.clone())
                     .collect::<Vec<String>>();
+                let tab_names = if json {
+                    vec![serde_json::to_string(&tab_names)
+                        .context("failed to serialize tab names")?]
+                } else {
+                    tab_names
+                };
                 screen
                     .bus
                     .senders
@@ -5117,6 +5553,26 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.unblock_input()?;
             },
+            ScreenInstruction::SetSessionTags(tags, client_id) => {
+                let tags = normalize_session_tags(tags.iter());
+                match set_session_tags(&screen.session_name, &tags) {
+                    Ok(()) => {
+                        screen.session_tags = tags;
+                        screen.log_and_report_session_state()?;
+                    },
+                    Err(e) => {
+                        let error_text = format!("Failed to set session tags: {}", e);
+                        log::error!("{}", error_text);
+                        if let Some(os_input) = &mut screen.bus.os_input {
+                            let _ = os_input.send_to_client(
+                                client_id,
+                                ServerToClientMsg::LogError(vec![error_text]),
+                            );
+                        }
+                    },
+                }
+                screen.unblock_input()?;
+            },
             ScreenInstruction::Reconfigure {
                 client_id,
                 keybinds,
@@ -5552,6 +6008,61 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::ReplacePaneWithExistingPane(old_pane_id, new_pane_id) => {
                 screen.replace_pane_with_existing_pane(old_pane_id, new_pane_id)
             },
+            ScreenInstruction::SnapshotSession(
+                default_shell,
+                snapshot_name,
+                with_scrollback,
+                client_id,
+            ) => {
+                let session_layout_metadata = screen.get_layout_metadata_with_viewport(
+                    default_shell,
+                    with_scrollback || screen.serialize_pane_viewport,
+                );
+                screen
+                    .bus
+                    .senders
+                    .send_to_pty(PtyInstruction::SnapshotSession(
+                        session_layout_metadata,
+                        snapshot_name,
+                        client_id,
+                    ))
+                    .with_context(|| format!("Failed to snapshot session"))?;
+            },
+            ScreenInstruction::SetClientIdentity(client_id, user_name, token_name) => {
+                screen.set_client_identity(client_id, user_name, token_name)?;
+            },
+            ScreenInstruction::RestrictInput(target, pane_ids, client_id) => {
+                screen.restrict_input(target, pane_ids, client_id)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::WaitForPaneExit(pane_id, client_id) => {
+                screen.wait_for_pane_exit(pane_id, client_id)?;
+            },
+            ScreenInstruction::ListPanes(client_id, json) => {
+                let pane_listings = screen.pane_listings();
+                // the pty thread knows the cwds and running commands of the terminals
+                screen
+                    .bus
+                    .senders
+                    .send_to_pty(PtyInstruction::ListPanes(pane_listings, client_id, json))
+                    .context("failed to list panes")?;
+            },
+            ScreenInstruction::SubscribeToSessionEvents(event_types, client_id) => {
+                screen.subscribe_to_session_events(event_types, client_id);
+            },
+            ScreenInstruction::ListTabs(client_id, json) => {
+                let tab_infos: Vec<TabInfo> = screen.tab_infos().into_values().collect();
+                let tab_listing = if json {
+                    serde_json::to_string(&tab_infos).context("failed to serialize tabs")?
+                } else {
+                    listings::render_tabs(&tab_infos)
+                };
+                screen
+                    .bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(vec![tab_listing], client_id))
+                    .context("failed to list tabs")?;
+            },
         }
     }
     Ok(())
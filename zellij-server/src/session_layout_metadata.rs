@@ -1,6 +1,6 @@
 use crate::panes::PaneId;
 use crate::ClientId;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use zellij_utils::common_path::common_path_all;
 use zellij_utils::pane_size::PaneGeom;
@@ -21,6 +21,7 @@ pub struct SessionLayoutMetadata {
     pub default_shell: Option<PathBuf>,
     pub default_editor: Option<PathBuf>,
     tabs: Vec<TabLayoutMetadata>,
+    read_only_clients: HashSet<ClientId>,
 }
 
 impl SessionLayoutMetadata {
@@ -42,7 +43,7 @@ impl SessionLayoutMetadata {
                         &run_command.command.display().to_string(),
                         &run_command.args,
                     ) {
-                        tiled_pane.run = None;
+                        tiled_pane.run = Self::default_shell_run(&run_command.env);
                     }
                 }
             }
@@ -53,13 +54,16 @@ impl SessionLayoutMetadata {
                         &run_command.command.display().to_string(),
                         &run_command.args,
                     ) {
-                        floating_pane.run = None;
+                        floating_pane.run = Self::default_shell_run(&run_command.env);
                     }
                 }
             }
         }
     }
-    pub fn list_clients_metadata(&self) -> String {
+    pub fn update_read_only_clients(&mut self, read_only_clients: HashSet<ClientId>) {
+        self.read_only_clients = read_only_clients;
+    }
+    pub fn list_clients_metadata(&self, json: bool) -> String {
         let mut clients_metadata: BTreeMap<ClientId, ClientMetadata> = BTreeMap::new();
         for tab in &self.tabs {
             let panes = if tab.hide_floating_panes {
@@ -74,13 +78,18 @@ impl SessionLayoutMetadata {
                         ClientMetadata {
                             pane_id: pane.id.clone(),
                             command: pane.run.clone(),
+                            is_read_only: self.read_only_clients.contains(focused_client),
                         },
                     );
                 }
             }
         }
 
-        ClientMetadata::render_many(clients_metadata, &self.default_editor)
+        if json {
+            ClientMetadata::render_many_as_json(clients_metadata, &self.default_editor)
+        } else {
+            ClientMetadata::render_many(clients_metadata, &self.default_editor)
+        }
     }
     pub fn all_clients_metadata(&self) -> BTreeMap<ClientId, ClientMetadata> {
         let mut clients_metadata: BTreeMap<ClientId, ClientMetadata> = BTreeMap::new();
@@ -97,6 +106,7 @@ impl SessionLayoutMetadata {
                         ClientMetadata {
                             pane_id: pane.id.clone(),
                             command: pane.run.clone(),
+                            is_read_only: self.read_only_clients.contains(focused_client),
                         },
                     );
                 }
@@ -190,6 +200,13 @@ impl SessionLayoutMetadata {
             == Some(command_name)
             && args.is_empty()
     }
+    fn default_shell_run(env: &BTreeMap<String, String>) -> Option<Run> {
+        if env.is_empty() {
+            None
+        } else {
+            Some(Run::Shell(None, env.clone()))
+        }
+    }
 }
 
 impl SessionLayoutMetadata {
@@ -251,12 +268,20 @@ impl SessionLayoutMetadata {
                     let mut command_line = command.iter();
                     if let Some(command_name) = command_line.next() {
                         let args: Vec<String> = command_line.map(|c| c.to_owned()).collect();
+                        // environment variables from the layout are not visible in the command
+                        // line, so we keep the ones the pane was originally opened with
+                        let env = pane_layout_metadata
+                            .run
+                            .as_ref()
+                            .map(|r| r.get_env())
+                            .unwrap_or_default();
                         if Self::is_default_shell(self.default_shell.as_ref(), &command_name, &args)
                         {
-                            pane_layout_metadata.run = None;
+                            pane_layout_metadata.run = Self::default_shell_run(&env);
                         } else {
                             let mut run_command = RunCommand::new(PathBuf::from(command_name));
                             run_command.args = args;
+                            run_command.env = env;
                             pane_layout_metadata.run = Some(Run::Command(run_command));
                         }
                     }
@@ -422,6 +447,7 @@ impl PaneLayoutMetadata {
 pub struct ClientMetadata {
     pane_id: PaneId,
     command: Option<Run>,
+    is_read_only: bool,
 }
 impl ClientMetadata {
     pub fn stringify_pane_id(&self) -> String {
@@ -431,10 +457,19 @@ impl ClientMetadata {
         }
     }
     pub fn stringify_command(&self, editor: &Option<PathBuf>) -> String {
-        let stringified = match &self.command {
+        self.command_with_args(editor).unwrap_or("N/A".to_owned())
+    }
+    fn command_with_args(&self, editor: &Option<PathBuf>) -> Option<String> {
+        match &self.command {
             Some(Run::Command(..)) => {
                 let (command, args) = extract_command_and_args(&self.command);
-                command.map(|c| format!("{} {}", c, args.join(" ")))
+                command.map(|c| {
+                    if args.is_empty() {
+                        c
+                    } else {
+                        format!("{} {}", c, args.join(" "))
+                    }
+                })
             },
             Some(Run::EditFile(..)) => {
                 let (file_to_edit, _line_number) = extract_edit_and_line_number(&self.command);
@@ -448,25 +483,37 @@ impl ClientMetadata {
                 plugin.map(|p| format!("{}", p))
             },
             _ => None,
-        };
-        stringified.unwrap_or("N/A".to_owned())
+        }
+    }
+    pub fn stringify_access(&self) -> &'static str {
+        if self.is_read_only {
+            "read-only"
+        } else {
+            "interactive"
+        }
     }
     pub fn get_pane_id(&self) -> PaneId {
         self.pane_id
     }
+    pub fn is_read_only(&self) -> bool {
+        self.is_read_only
+    }
     pub fn render_many(
         clients_metadata: BTreeMap<ClientId, ClientMetadata>,
         default_editor: &Option<PathBuf>,
     ) -> String {
         let mut lines = vec![];
-        lines.push(String::from("CLIENT_ID ZELLIJ_PANE_ID RUNNING_COMMAND"));
+        lines.push(String::from(
+            "CLIENT_ID ZELLIJ_PANE_ID ACCESS      RUNNING_COMMAND",
+        ));
 
         for (client_id, client_metadata) in clients_metadata.iter() {
-            // 9 - CLIENT_ID, 14 - ZELLIJ_PANE_ID, 15 - RUNNING_COMMAND
+            // 9 - CLIENT_ID, 14 - ZELLIJ_PANE_ID, 11 - ACCESS, 15 - RUNNING_COMMAND
             lines.push(format!(
-                "{} {} {}",
+                "{} {} {} {}",
                 format!("{0: <9}", client_id),
                 format!("{0: <14}", client_metadata.stringify_pane_id()),
+                format!("{0: <11}", client_metadata.stringify_access()),
                 format!(
                     "{0: <15}",
                     client_metadata.stringify_command(default_editor)
@@ -475,4 +522,83 @@ impl ClientMetadata {
         }
         lines.join("\n")
     }
+    pub fn render_many_as_json(
+        clients_metadata: BTreeMap<ClientId, ClientMetadata>,
+        default_editor: &Option<PathBuf>,
+    ) -> String {
+        let clients: Vec<serde_json::Value> = clients_metadata
+            .iter()
+            .map(|(client_id, client_metadata)| {
+                serde_json::json!({
+                    "client_id": client_id,
+                    "pane_id": client_metadata.stringify_pane_id(),
+                    "access": client_metadata.stringify_access(),
+                    "running_command": client_metadata.command_with_args(default_editor),
+                })
+            })
+            .collect();
+        serde_json::Value::Array(clients).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clients_are_listed_as_json_with_their_focused_pane() {
+        let mut session_layout_metadata = SessionLayoutMetadata::default();
+        let htop = Run::Command(RunCommand {
+            command: PathBuf::from("htop"),
+            ..Default::default()
+        });
+        session_layout_metadata.add_tab(
+            "Tab #1".to_owned(),
+            true,
+            true,
+            vec![
+                PaneLayoutMetadata::new(
+                    PaneId::Terminal(1),
+                    PaneGeom::default(),
+                    false,
+                    Some(htop),
+                    None,
+                    true,
+                    None,
+                    vec![1],
+                ),
+                PaneLayoutMetadata::new(
+                    PaneId::Plugin(2),
+                    PaneGeom::default(),
+                    false,
+                    None,
+                    None,
+                    false,
+                    None,
+                    vec![2],
+                ),
+            ],
+            vec![],
+        );
+        session_layout_metadata.update_read_only_clients(HashSet::from([2]));
+        let clients: serde_json::Value =
+            serde_json::from_str(&session_layout_metadata.list_clients_metadata(true)).unwrap();
+        assert_eq!(
+            clients,
+            serde_json::json!([
+                {
+                    "client_id": 1,
+                    "pane_id": "terminal_1",
+                    "access": "interactive",
+                    "running_command": "htop",
+                },
+                {
+                    "client_id": 2,
+                    "pane_id": "plugin_2",
+                    "access": "read-only",
+                    "running_command": null,
+                },
+            ])
+        );
+    }
 }
@@ -459,12 +459,16 @@ impl<'a> LayoutApplier<'a> {
             self.styled_underlines,
             self.explicitly_disable_kitty_keyboard_protocol,
         );
-        if let Some(pane_initial_contents) = &floating_pane_layout.pane_initial_contents {
-            new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
-            new_pane.handle_pty_bytes("\n\r".as_bytes().into());
-        }
         new_pane.set_borderless(false);
         new_pane.set_content_offset(Offset::frame(1));
+        if let Some(pane_initial_contents) = &floating_pane_layout.pane_initial_contents {
+            if let Some(Run::Command(_)) = &floating_pane_layout.run {
+                new_pane.restore_previous_contents(pane_initial_contents);
+            } else {
+                new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
+                new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+            }
+        }
         if let Some(held_command) = hold_for_command {
             new_pane.hold(None, true, held_command.clone());
         }
@@ -513,8 +517,12 @@ impl<'a> LayoutApplier<'a> {
             self.explicitly_disable_kitty_keyboard_protocol,
         );
         if let Some(pane_initial_contents) = &layout.pane_initial_contents {
-            new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
-            new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+            if let Some(Run::Command(_)) = &layout.run {
+                new_pane.restore_previous_contents(pane_initial_contents);
+            } else {
+                new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
+                new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+            }
         }
         new_pane.set_borderless(layout.borderless);
         if let Some(exclude_from_sync) = layout.exclude_from_sync {
@@ -13,8 +13,8 @@ use std::net::IpAddr;
 use std::path::PathBuf;
 use uuid::Uuid;
 use zellij_utils::data::{
-    Direction, KeyWithModifier, PaneInfo, PermissionStatus, PermissionType, PluginPermission,
-    ResizeStrategy, WebSharing,
+    ConnectedUser, Direction, KeyWithModifier, PaneInfo, PathPermission, PermissionStatus,
+    PermissionType, PluginPermission, ResizeStrategy, WebSharing,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -270,6 +270,7 @@ pub(crate) struct Tab {
     advanced_mouse_actions: bool,
     currently_marking_pane_group: Rc<RefCell<HashMap<ClientId, bool>>>,
     connected_clients_in_app: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+    client_user_names: Rc<RefCell<HashMap<ClientId, String>>>,
     // the below are the configured values - the ones that will be set if and when the web server
     // is brought online
     web_server_ip: IpAddr,
@@ -631,7 +632,7 @@ pub trait Pane {
 pub enum AdjustedInput {
     WriteBytesToTerminal(Vec<u8>),
     ReRunCommandInThisPane(RunCommand),
-    PermissionRequestResult(Vec<PermissionType>, PermissionStatus),
+    PermissionRequestResult(Vec<PermissionType>, Vec<PathPermission>, PermissionStatus),
     CloseThisPane,
     DropToShellInThisPane { working_dir: Option<PathBuf> },
     WriteKeyToPlugin(KeyWithModifier),
@@ -676,6 +677,7 @@ impl Tab {
         draw_pane_frames: bool,
         auto_layout: bool,
         connected_clients_in_app: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+        client_user_names: Rc<RefCell<HashMap<ClientId, String>>>,
         session_is_mirrored: bool,
         client_id: Option<ClientId>,
         copy_options: CopyOptions,
@@ -717,6 +719,7 @@ impl Tab {
             viewport.clone(),
             connected_clients.clone(),
             connected_clients_in_app.clone(),
+            client_user_names.clone(),
             mode_info.clone(),
             character_cell_size.clone(),
             stacked_resize.clone(),
@@ -732,6 +735,7 @@ impl Tab {
             viewport.clone(),
             connected_clients.clone(),
             connected_clients_in_app.clone(),
+            client_user_names.clone(),
             mode_info.clone(),
             character_cell_size.clone(),
             session_is_mirrored,
@@ -796,6 +800,7 @@ impl Tab {
             currently_marking_pane_group,
             advanced_mouse_actions,
             connected_clients_in_app,
+            client_user_names,
             web_server_ip,
             web_server_port,
         }
@@ -1015,6 +1020,14 @@ impl Tab {
         let mode_infos = self.mode_info.borrow();
         let mut plugin_updates = vec![];
         let currently_marking_pane_group = self.currently_marking_pane_group.borrow();
+        let client_user_names = self.client_user_names.borrow();
+        let mut clients_in_app: Vec<ClientId> = self
+            .connected_clients_in_app
+            .borrow()
+            .keys()
+            .copied()
+            .collect();
+        clients_in_app.sort_unstable();
         for client_id in self.connected_clients.borrow().iter() {
             let mut mode_info = mode_infos
                 .get(client_id)
@@ -1028,6 +1041,14 @@ impl Tab {
                 currently_marking_pane_group.get(client_id).copied();
             mode_info.web_server_ip = Some(self.web_server_ip);
             mode_info.web_server_port = Some(self.web_server_port);
+            mode_info.connected_users = clients_in_app
+                .iter()
+                .map(|c_id| ConnectedUser {
+                    client_id: *c_id,
+                    name: client_user_names.get(c_id).cloned(),
+                    is_current_client: c_id == client_id,
+                })
+                .collect();
             mode_info.is_web_client = self
                 .connected_clients_in_app
                 .borrow()
@@ -2530,7 +2551,7 @@ impl Tab {
                         .send_to_plugin(PluginInstruction::Update(plugin_updates))
                         .with_context(err_context)?;
                 },
-                Some(AdjustedInput::PermissionRequestResult(permissions, status)) => {
+                Some(AdjustedInput::PermissionRequestResult(permissions, paths, status)) => {
                     if active_pane.query_should_be_suppressed() {
                         active_pane.set_should_be_suppressed(false);
                         self.suppress_pane(PaneId::Plugin(pid), client_id);
@@ -2541,6 +2562,7 @@ impl Tab {
                             pid,
                             client_id,
                             permissions,
+                            paths,
                             status,
                             None,
                         ))
@@ -254,6 +254,7 @@ fn create_new_tab(size: Size, default_mode: ModeInfo) -> Tab {
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -335,6 +336,7 @@ fn create_new_tab_without_pane_frames(size: Size, default_mode: ModeInfo) -> Tab
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -431,6 +433,7 @@ fn create_new_tab_with_swap_layouts(
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -528,6 +531,7 @@ fn create_new_tab_with_os_api(
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -583,7 +587,15 @@ fn create_new_tab_with_layout(size: Size, default_mode: ModeInfo, layout: &str)
     let copy_options = CopyOptions::default();
     let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
     let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
-    let layout = Layout::from_str(layout, "layout_file_name".into(), None, None).unwrap();
+    let layout = Layout::from_str(
+        layout,
+        "layout_file_name".into(),
+        None,
+        None,
+        BTreeMap::new(),
+        None,
+    )
+    .unwrap();
     let (tab_layout, floating_panes_layout) = layout.new_tab();
     let current_group = Rc::new(RefCell::new(PaneGroups::new(ThreadSenders::default())));
     let currently_marking_pane_group = Rc::new(RefCell::new(HashMap::new()));
@@ -611,6 +623,7 @@ fn create_new_tab_with_layout(size: Size, default_mode: ModeInfo, layout: &str)
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -708,6 +721,7 @@ fn create_new_tab_with_mock_pty_writer(
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -796,6 +810,7 @@ fn create_new_tab_with_sixel_support(
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -194,6 +194,7 @@ fn create_new_tab(size: Size, stacked_resize: bool) -> Tab {
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -274,6 +275,7 @@ fn create_new_tab_with_layout(size: Size, layout: TiledPaneLayout) -> Tab {
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -360,6 +362,7 @@ fn create_new_tab_with_cell_size(
         draw_pane_frames,
         auto_layout,
         connected_clients,
+        Rc::new(RefCell::new(HashMap::new())), // client user names
         session_is_mirrored,
         Some(client_id),
         copy_options,
@@ -2,6 +2,7 @@ use crate::output::CharacterChunk;
 use crate::panes::{AnsiCode, RcCharacterStyles, TerminalCharacter, EMPTY_TERMINAL_CHARACTER};
 use crate::ui::boundaries::boundary_type;
 use crate::ClientId;
+use std::collections::HashMap;
 use zellij_utils::data::{client_id_to_colors, PaletteColor, Style};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::pane_size::{Offset, Viewport};
@@ -55,6 +56,7 @@ pub struct FrameParams {
     pub focused_client: Option<ClientId>,
     pub is_main_client: bool, // more accurately: is_focused_for_main_client
     pub other_focused_clients: Vec<ClientId>,
+    pub client_names: HashMap<ClientId, String>, // the users who gave themselves a name
     pub style: Style,
     pub color: Option<PaletteColor>,
     pub other_cursors_exist_in_session: bool,
@@ -78,6 +80,7 @@ pub struct PaneFrame {
     pub is_main_client: bool,
     pub other_cursors_exist_in_session: bool,
     pub other_focused_clients: Vec<ClientId>,
+    pub client_names: HashMap<ClientId, String>,
     exit_status: Option<ExitStatus>,
     is_first_run: bool,
     pane_is_stacked_over: bool,
@@ -106,6 +109,7 @@ impl PaneFrame {
             focused_client: frame_params.focused_client,
             is_main_client: frame_params.is_main_client,
             other_focused_clients: frame_params.other_focused_clients,
+            client_names: frame_params.client_names,
             other_cursors_exist_in_session: frame_params.other_cursors_exist_in_session,
             exit_status: None,
             is_first_run: false,
@@ -139,6 +143,54 @@ impl PaneFrame {
         let color = client_id_to_colors(client_id, self.style.colors.multiplayer_user_colors);
         background_color(" ", color.map(|c| c.0))
     }
+    fn client_name_label(&self, client_id: ClientId) -> (Vec<TerminalCharacter>, usize) {
+        // named users are shown by name on their color, the rest as a colored block
+        match self.client_names.get(&client_id) {
+            Some(name) => {
+                let color =
+                    client_id_to_colors(client_id, self.style.colors.multiplayer_user_colors);
+                let label = format!(" {} ", name);
+                let label_len = label.width();
+                (background_color(&label, color.map(|c| c.0)), label_len)
+            },
+            None => (self.client_cursor(client_id), 1),
+        }
+    }
+    fn render_named_focus(
+        &self,
+        indication_text: &str,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
+        let has_named_clients = self
+            .other_focused_clients
+            .iter()
+            .any(|c_id| self.client_names.contains_key(c_id));
+        if !has_named_clients {
+            return None;
+        }
+        let mut indication = foreground_color(indication_text, self.color);
+        let mut indication_len = indication_text.width();
+        for client_id in &self.other_focused_clients {
+            let (mut label, label_len) = self.client_name_label(*client_id);
+            indication_len += label_len + 1;
+            indication.push(EMPTY_TERMINAL_CHARACTER);
+            indication.append(&mut label);
+        }
+        if indication_len + 4 <= max_length {
+            // 2 for separators, 2 for padding
+            let mut ret = foreground_color(boundary_type::VERTICAL_LEFT, self.color);
+            ret.push(EMPTY_TERMINAL_CHARACTER);
+            ret.append(&mut indication);
+            ret.push(EMPTY_TERMINAL_CHARACTER);
+            ret.append(&mut foreground_color(
+                boundary_type::VERTICAL_RIGHT,
+                self.color,
+            ));
+            Some((ret, indication_len + 4))
+        } else {
+            None
+        }
+    }
     fn get_corner(&self, corner: &'static str) -> &'static str {
         let corner = if !self.should_draw_pane_frames
             && (corner == boundary_type::TOP_LEFT || corner == boundary_type::TOP_RIGHT)
@@ -282,6 +334,9 @@ impl PaneFrame {
         let mut right_separator = foreground_color(boundary_type::VERTICAL_RIGHT, self.color);
         let full_indication_text = "MY FOCUS AND:";
         let short_indication_text = "+";
+        if let Some(named_indication) = self.render_named_focus(full_indication_text, max_length) {
+            return Some(named_indication);
+        }
         let mut full_indication = foreground_color(full_indication_text, self.color);
         let mut full_indication_len = full_indication_text.width();
         let mut short_indication = foreground_color(short_indication_text, self.color);
@@ -329,6 +384,9 @@ impl PaneFrame {
             "FOCUSED USERS:"
         };
         let middle_indication_text = "U:";
+        if let Some(named_indication) = self.render_named_focus(full_indication_text, max_length) {
+            return Some(named_indication);
+        }
         let mut full_indication = foreground_color(full_indication_text, self.color);
         let mut full_indication_len = full_indication_text.width();
         let mut middle_indication = foreground_color(middle_indication_text, self.color);
@@ -12,6 +12,7 @@ pub struct PaneContentsAndUi<'a> {
     output: &'a mut Output,
     style: Style,
     focused_clients: Vec<ClientId>,
+    focused_client_names: HashMap<ClientId, String>,
     multiple_users_exist_in_session: bool,
     z_index: Option<usize>,
     pane_is_stacked_under: bool,
@@ -28,6 +29,7 @@ impl<'a> PaneContentsAndUi<'a> {
         style: Style,
         active_panes: &HashMap<ClientId, PaneId>,
         multiple_users_exist_in_session: bool,
+        client_user_names: &HashMap<ClientId, String>,
         z_index: Option<usize>,
         pane_is_stacked_under: bool,
         pane_is_stacked_over: bool,
@@ -41,6 +43,10 @@ impl<'a> PaneContentsAndUi<'a> {
             .map(|(c_id, _p_id)| *c_id)
             .collect();
         focused_clients.sort_unstable();
+        let focused_client_names = focused_clients
+            .iter()
+            .filter_map(|c_id| Some((*c_id, client_user_names.get(c_id)?.clone())))
+            .collect();
         let mouse_is_hovering_over_pane_for_clients = mouse_hover_pane_id
             .iter()
             .filter_map(|(client_id, pane_id)| {
@@ -56,6 +62,7 @@ impl<'a> PaneContentsAndUi<'a> {
             output,
             style,
             focused_clients,
+            focused_client_names,
             multiple_users_exist_in_session,
             z_index,
             pane_is_stacked_under,
@@ -233,6 +240,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 focused_client,
                 is_main_client: pane_focused_for_client_id,
                 other_focused_clients: vec![],
+                client_names: HashMap::new(),
                 style: self.style,
                 color: frame_color.map(|c| c.0),
                 other_cursors_exist_in_session: false,
@@ -251,6 +259,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 focused_client,
                 is_main_client: pane_focused_for_client_id,
                 other_focused_clients,
+                client_names: self.focused_client_names.clone(),
                 style: self.style,
                 color: frame_color.map(|c| c.0),
                 other_cursors_exist_in_session: self.multiple_users_exist_in_session,
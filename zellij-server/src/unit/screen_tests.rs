@@ -11,7 +11,7 @@ use insta::assert_snapshot;
 use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
 use zellij_utils::cli::CliAction;
-use zellij_utils::data::{Event, Resize, Style, WebSharing};
+use zellij_utils::data::{Event, Resize, SessionEvent, SessionEventType, Style, WebSharing};
 use zellij_utils::errors::{prelude::*, ErrorContext};
 use zellij_utils::input::actions::Action;
 use zellij_utils::input::command::{RunCommand, TerminalAction};
@@ -1779,6 +1779,101 @@ pub fn send_cli_write_action_to_screen() {
     assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
 }
 
+fn pty_writes_of_restricted_user(
+    allowed_pane_ids: Vec<String>,
+    cli_actions_of_restricted_user: Vec<CliAction>,
+    write_from_cli: bool,
+) -> Vec<PtyWriteInstruction> {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let client_id = mock_screen.main_client_id;
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::SetClientIdentity(
+            client_id,
+            Some("alice".to_owned()),
+            Some("alice-token".to_owned()),
+        ));
+    let restrict_input = CliAction::RestrictInput {
+        client: None,
+        token: Some("alice-token".to_owned()),
+        clear: false,
+        pane_ids: allowed_pane_ids,
+    };
+    let cli_client_id = 10;
+    send_cli_action_to_server(&session_metadata, restrict_input, cli_client_id);
+    for cli_action in cli_actions_of_restricted_user {
+        send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    }
+    let write = CliAction::Write {
+        bytes: vec![102, 111, 111],
+    };
+    let writing_client_id = if write_from_cli {
+        cli_client_id
+    } else {
+        client_id
+    };
+    send_cli_action_to_server(&session_metadata, write, writing_client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let received_pty_instructions = received_pty_instructions.lock().unwrap();
+    received_pty_instructions
+        .iter()
+        .filter(|i| matches!(i, PtyWriteInstruction::Write(..)))
+        .cloned()
+        .collect()
+}
+
+#[test]
+pub fn restricted_user_cannot_type_into_other_panes() {
+    let writes = pty_writes_of_restricted_user(vec!["terminal_1".to_owned()], vec![], false);
+    assert!(writes.is_empty(), "input was dropped: {:?}", writes);
+}
+
+#[test]
+pub fn cli_cannot_type_into_the_pane_of_a_restricted_user() {
+    let writes = pty_writes_of_restricted_user(vec!["terminal_1".to_owned()], vec![], true);
+    assert!(writes.is_empty(), "input was dropped: {:?}", writes);
+}
+
+#[test]
+pub fn restricted_user_cannot_lift_their_own_restriction() {
+    let clear_restriction = CliAction::RestrictInput {
+        client: None,
+        token: Some("alice-token".to_owned()),
+        clear: true,
+        pane_ids: vec![],
+    };
+    let writes = pty_writes_of_restricted_user(
+        vec!["terminal_1".to_owned()],
+        vec![clear_restriction],
+        false,
+    );
+    assert!(writes.is_empty(), "input was dropped: {:?}", writes);
+}
+
+#[test]
+pub fn restricted_user_can_type_into_allowed_panes() {
+    let writes = pty_writes_of_restricted_user(vec!["terminal_0".to_owned()], vec![], false);
+    assert_eq!(
+        format!("{:?}", writes),
+        "[Write([102, 111, 111], 0)]",
+        "input reached the allowed pane"
+    );
+}
+
 #[test]
 pub fn send_cli_resize_action_to_screen() {
     let size = Size { cols: 80, rows: 20 };
@@ -3033,6 +3128,7 @@ pub fn send_cli_new_tab_action_default_params() {
         name: None,
         layout: None,
         layout_dir: None,
+        layout_args: vec![],
         cwd: None,
     };
     send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
@@ -3073,6 +3169,7 @@ pub fn send_cli_new_tab_action_with_name_and_layout() {
             env!("CARGO_MANIFEST_DIR")
         ))),
         layout_dir: None,
+        layout_args: vec![],
         cwd: None,
     };
     send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
@@ -3367,6 +3464,404 @@ pub fn send_cli_query_tab_names_action() {
     assert_snapshot!(format!("{:#?}", log_tab_names_instruction));
 }
 
+#[test]
+pub fn send_cli_query_tab_names_action_as_json() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::QueryTabNames(client_id, true));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::ListTabs(client_id, true));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let logged_lines: Vec<String> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _) => Some(lines.join("\n")),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(logged_lines.len(), 2, "both queries were answered");
+    let tab_names: Vec<String> = serde_json::from_str(&logged_lines[0]).unwrap();
+    assert_eq!(tab_names, vec!["Tab #1".to_owned(), "Tab #2".to_owned()]);
+    let tabs: serde_json::Value = serde_json::from_str(&logged_lines[1]).unwrap();
+    let tab_names_in_listing: Vec<&str> = tabs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tab| tab["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(tab_names_in_listing, vec!["Tab #1", "Tab #2"]);
+}
+
+#[test]
+pub fn read_only_clients_cannot_move_the_focus_in_a_mirrored_session() {
+    let size = Size { cols: 80, rows: 10 };
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.config.options.mirror_session = Some(true);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let read_only_client_id = 2;
+    let is_web_client = false;
+    let is_read_only = true;
+    let _ = mock_screen.to_screen.send(ScreenInstruction::AddClient(
+        read_only_client_id,
+        is_web_client,
+        is_read_only,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::GoToTab { index: 1 },
+        read_only_client_id,
+    );
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::FocusNextPane,
+        read_only_client_id,
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::ListTabs(10, true));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let tab_listing = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, 10) => Some(lines.join("\n")),
+            _ => None,
+        })
+        .expect("tabs were listed");
+    let tabs: serde_json::Value = serde_json::from_str(&tab_listing).unwrap();
+    let focused_tabs: Vec<bool> = tabs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tab| !tab["other_focused_clients"].as_array().unwrap().is_empty())
+        .collect();
+    assert_eq!(
+        focused_tabs,
+        vec![false, true],
+        "every client stayed on the second tab"
+    );
+}
+
+#[test]
+pub fn clients_waiting_for_a_command_pane_are_told_its_exit_status() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::WaitForPaneExit(
+            PaneId::Terminal(0),
+            client_id,
+        ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::HoldPane(
+        PaneId::Terminal(0),
+        Some(3),
+        RunCommand::default(),
+    ));
+    // the pane already exited, this one is answered right away
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::WaitForPaneExit(
+            PaneId::Terminal(0),
+            client_id,
+        ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let pane_exits: Vec<(Option<i32>, ClientId)> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::PaneExited(exit_status, client_id) => {
+                Some((*exit_status, *client_id))
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(pane_exits, vec![(Some(3), client_id), (Some(3), client_id)]);
+}
+
+#[test]
+pub fn clients_waiting_for_a_pane_are_told_when_it_is_closed() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::WaitForPaneExit(
+            PaneId::Terminal(0),
+            client_id,
+        ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::ClosePane(PaneId::Terminal(0), None));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let pane_exits: Vec<(Option<i32>, ClientId)> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::PaneExited(exit_status, client_id) => {
+                Some((*exit_status, *client_id))
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(pane_exits, vec![(None, client_id)]);
+}
+
+#[test]
+pub fn client_is_told_once_every_tab_of_its_layout_was_applied() {
+    let size = Size { cols: 80, rows: 10 };
+    let mut mock_screen = MockScreen::new(size);
+    let client_id = mock_screen.main_client_id;
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    // like when starting a session, all the tabs are opened before any of them is applied
+    for _ in 0..2 {
+        let _ = mock_screen.to_screen.send(ScreenInstruction::NewTab(
+            None,
+            None,
+            Some(TiledPaneLayout::default()),
+            vec![], // floating_panes_layout
+            None,
+            (vec![], vec![]), // swap layouts
+            false,
+            (client_id, false),
+        ));
+    }
+    for (tab_index, pane_id) in [(1, 1), (2, 2)] {
+        let _ = mock_screen.to_screen.send(ScreenInstruction::ApplyLayout(
+            TiledPaneLayout::default(),
+            vec![], // floating_panes_layout
+            vec![(pane_id, None)],
+            vec![], // floating panes ids
+            HashMap::new(),
+            tab_index,
+            false,
+            (client_id, false),
+        ));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let applied_layouts: Vec<ClientId> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::LayoutApplied(client_id) => Some(*client_id),
+            _ => None,
+        })
+        .collect();
+    // one for the layout the session started with and one for both of the new tabs
+    assert_eq!(applied_layouts, vec![client_id, client_id]);
+}
+
+#[test]
+pub fn subscribers_are_told_about_opened_and_closed_panes_and_exited_commands() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::SubscribeToSessionEvents(vec![], client_id));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::NewPane(
+        PaneId::Terminal(2),
+        None,
+        None,
+        None,
+        NewPanePlacement::default(),
+        false,
+        ClientTabIndexOrPaneId::TabIndex(0),
+    ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::HoldPane(
+        PaneId::Terminal(2),
+        Some(1),
+        RunCommand::default(),
+    ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::ClosePane(PaneId::Terminal(2), None));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let pane_events: Vec<SessionEvent> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::SessionEvent(session_event, c_id) if *c_id == client_id => {
+                Some(session_event.clone())
+            },
+            _ => None,
+        })
+        .filter(|session_event| session_event.event_type() != SessionEventType::TabUpdate)
+        .collect();
+    assert_eq!(pane_events.len(), 3, "{:?}", pane_events);
+    match &pane_events[0] {
+        SessionEvent::PaneOpened { tab_position, pane } => {
+            assert_eq!((*tab_position, pane.id, pane.is_plugin), (0, 2, false));
+        },
+        session_event => panic!("expected the pane to open, got: {:?}", session_event),
+    }
+    assert_eq!(
+        pane_events[1..],
+        [
+            SessionEvent::CommandExited {
+                pane_id: 2,
+                exit_status: Some(1),
+            },
+            SessionEvent::PaneClosed {
+                pane_id: zellij_utils::data::PaneId::Terminal(2),
+            },
+        ]
+    );
+}
+
+#[test]
+pub fn subscribers_only_get_the_events_they_asked_for() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::SubscribeToSessionEvents(
+            vec![SessionEventType::PaneClosed],
+            client_id,
+        ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::HoldPane(
+        PaneId::Terminal(0),
+        Some(0),
+        RunCommand::default(),
+    ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::ClosePane(PaneId::Terminal(0), None));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let session_events: Vec<SessionEvent> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::SessionEvent(session_event, _) => Some(session_event.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        session_events,
+        vec![SessionEvent::PaneClosed {
+            pane_id: zellij_utils::data::PaneId::Terminal(0),
+        }]
+    );
+}
+
+#[test]
+pub fn waiting_for_a_pane_that_does_not_exist_is_an_error() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::WaitForPaneExit(
+            PaneId::Terminal(5),
+            client_id,
+        ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let log_error = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::LogError(lines, client_id) => Some((lines.clone(), *client_id)),
+            _ => None,
+        });
+    assert_eq!(
+        log_error,
+        Some((
+            vec!["No pane with id 5 in this session".to_owned()],
+            client_id
+        ))
+    );
+}
+
 #[test]
 pub fn send_cli_launch_or_focus_plugin_action() {
     let size = Size {
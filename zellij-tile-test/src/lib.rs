@@ -0,0 +1,482 @@
+//! A headless host for testing Swarm plugins.
+//!
+//! This crate loads a compiled plugin (`.wasm`) into an in-process host, without a running Swarm
+//! session. Tests can feed it [`Event`]s and [`PipeMessage`]s, assert on the [`PluginCommand`]s
+//! it emits and snapshot its rendered output exactly as it would appear inside a plugin pane.
+//!
+//! eg.
+//! ```no_run
+//! use std::collections::BTreeMap;
+//! use zellij_tile_test::PluginTestHarness;
+//! use zellij_utils::data::{BareKey, Event, KeyWithModifier, PluginCommand};
+//!
+//! let mut plugin = PluginTestHarness::from_wasm("target/wasm32-wasip1/debug/my-plugin.wasm")
+//!     .with_configuration(BTreeMap::new())
+//!     .with_size(20, 80)
+//!     .load()
+//!     .unwrap();
+//! plugin
+//!     .update(Event::Key(KeyWithModifier::new(BareKey::Char('a'))))
+//!     .unwrap();
+//! assert!(plugin
+//!     .take_commands()
+//!     .iter()
+//!     .any(|c| matches!(c, PluginCommand::SwitchToMode(..))));
+//! let snapshot = plugin.render_snapshot().unwrap();
+//! ```
+
+mod streams;
+
+use prost::Message;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use wasmtime::{Caller, Config, Engine, Instance, Linker, Module, Store};
+use wasmtime_wasi::{preview1::WasiP1Ctx, DirPerms, FilePerms, WasiCtxBuilder};
+use zellij_server::panes::{Grid, LinkHandler, SixelImageStore};
+use zellij_utils::{
+    consts::VERSION,
+    data::{
+        Event, EventType, Palette, PipeMessage, PluginCommand, PluginIds, SessionSnapshot, Style,
+    },
+    errors::prelude::*,
+    input::{config::ConfigError, layout::PluginUserConfiguration, session_template},
+    plugin_api::{
+        action::ProtobufPluginConfiguration,
+        event::ProtobufEvent,
+        pipe_message::ProtobufPipeMessage,
+        plugin_command::{
+            ListSessionSnapshotsResponse, ListSessionTemplatesResponse, ProtobufPluginCommand,
+            ResolveSessionTemplateResponse,
+        },
+        plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
+    },
+};
+
+use streams::{VecDequeInputStream, WriteOutputStream};
+
+const DEFAULT_ROWS: usize = 20;
+const DEFAULT_COLS: usize = 80;
+
+/// The state the harness keeps for the loaded plugin, this is the equivalent of the server's
+/// `PluginEnv`
+pub struct HarnessEnv {
+    wasi_ctx: WasiP1Ctx,
+    plugin_ids: PluginIds,
+    stdin_pipe: Arc<Mutex<VecDeque<u8>>>,
+    stdout_pipe: Arc<Mutex<VecDeque<u8>>>,
+    stderr_pipe: Arc<Mutex<Vec<u8>>>,
+    commands: Vec<PluginCommand>,
+    subscriptions: HashSet<EventType>,
+    session_snapshots: Vec<SessionSnapshot>,
+    template_dir: Option<PathBuf>,
+    layout_dir: Option<PathBuf>,
+}
+
+/// Used to configure a plugin before loading it into a [`PluginTestHarness`]
+pub struct PluginTestHarnessBuilder {
+    wasm_path: PathBuf,
+    configuration: BTreeMap<String, String>,
+    rows: usize,
+    cols: usize,
+    plugin_ids: PluginIds,
+    host_dir: Option<PathBuf>,
+    session_snapshots: Vec<SessionSnapshot>,
+    template_dir: Option<PathBuf>,
+    layout_dir: Option<PathBuf>,
+}
+
+impl PluginTestHarnessBuilder {
+    /// The user configuration passed to the plugin's `load` method
+    pub fn with_configuration(mut self, configuration: BTreeMap<String, String>) -> Self {
+        self.configuration = configuration;
+        self
+    }
+    /// The content size of the plugin pane, passed to its `render` method
+    pub fn with_size(mut self, rows: usize, cols: usize) -> Self {
+        self.rows = rows;
+        self.cols = cols;
+        self
+    }
+    /// The ids returned to the plugin when it calls `get_plugin_ids`
+    pub fn with_plugin_ids(mut self, plugin_ids: PluginIds) -> Self {
+        self.plugin_ids = plugin_ids;
+        self
+    }
+    /// A folder to mount as the plugin's `/host` folder, by default nothing is mounted
+    pub fn with_host_dir(mut self, host_dir: impl AsRef<Path>) -> Self {
+        self.host_dir = Some(host_dir.as_ref().to_path_buf());
+        self
+    }
+    /// The snapshots returned to the plugin when it calls `list_session_snapshots`, by default
+    /// there are none
+    pub fn with_session_snapshots(mut self, session_snapshots: Vec<SessionSnapshot>) -> Self {
+        self.session_snapshots = session_snapshots;
+        self
+    }
+    /// The folder `list_session_templates` and `resolve_session_template` read templates from, by
+    /// default there are no templates
+    pub fn with_template_dir(mut self, template_dir: impl AsRef<Path>) -> Self {
+        self.template_dir = Some(template_dir.as_ref().to_path_buf());
+        self
+    }
+    /// The folder the layouts of session templates are looked up in
+    pub fn with_layout_dir(mut self, layout_dir: impl AsRef<Path>) -> Self {
+        self.layout_dir = Some(layout_dir.as_ref().to_path_buf());
+        self
+    }
+    fn harness_env(&self) -> Result<HarnessEnv> {
+        let stdin_pipe = Arc::new(Mutex::new(VecDeque::new()));
+        let stdout_pipe = Arc::new(Mutex::new(VecDeque::new()));
+        let stderr_pipe = Arc::new(Mutex::new(Vec::new()));
+        let mut wasi_ctx_builder = WasiCtxBuilder::new();
+        wasi_ctx_builder.env("CLICOLOR_FORCE", "1");
+        if let Some(host_dir) = &self.host_dir {
+            wasi_ctx_builder.preopened_dir(host_dir, "/host", DirPerms::all(), FilePerms::all())?;
+        }
+        wasi_ctx_builder
+            .stdin(VecDequeInputStream(stdin_pipe.clone()))
+            .stdout(WriteOutputStream(stdout_pipe.clone()))
+            .stderr(WriteOutputStream(stderr_pipe.clone()));
+        Ok(HarnessEnv {
+            wasi_ctx: wasi_ctx_builder.build_p1(),
+            plugin_ids: self.plugin_ids.clone(),
+            stdin_pipe,
+            stdout_pipe,
+            stderr_pipe,
+            commands: vec![],
+            subscriptions: HashSet::new(),
+            session_snapshots: self.session_snapshots.clone(),
+            template_dir: self.template_dir.clone(),
+            layout_dir: self.layout_dir.clone(),
+        })
+    }
+    /// Compile and instantiate the plugin and call its `load` method
+    pub fn load(self) -> Result<PluginTestHarness> {
+        let err_context = || format!("failed to load plugin {}", self.wasm_path.display());
+
+        let engine = Engine::new(&Config::new()).with_context(err_context)?;
+        let module = Module::from_file(&engine, &self.wasm_path).with_context(err_context)?;
+
+        let harness_env = self.harness_env().with_context(err_context)?;
+
+        let mut store = Store::new(&engine, harness_env);
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::preview1::add_to_linker_sync(&mut linker, |env: &mut HarnessEnv| {
+            &mut env.wasi_ctx
+        })
+        .with_context(err_context)?;
+        linker
+            .func_wrap("swarm", "host_run_plugin_command", host_run_plugin_command)
+            .with_context(err_context)?;
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .with_context(err_context)?;
+        if let Some(func) = instance.get_func(&mut store, "_initialize") {
+            func.typed::<(), ()>(&store)?.call(&mut store, ())?;
+        }
+
+        let mut harness = PluginTestHarness {
+            store,
+            instance,
+            rows: self.rows,
+            cols: self.cols,
+        };
+        harness
+            .start(self.configuration)
+            .with_context(err_context)?;
+        Ok(harness)
+    }
+}
+
+/// A loaded plugin, see the crate documentation for an example
+pub struct PluginTestHarness {
+    store: Store<HarnessEnv>,
+    instance: Instance,
+    rows: usize,
+    cols: usize,
+}
+
+impl PluginTestHarness {
+    /// Start configuring a plugin from a compiled `.wasm` file, call `load` on the result to
+    /// load it
+    pub fn from_wasm(wasm_path: impl AsRef<Path>) -> PluginTestHarnessBuilder {
+        PluginTestHarnessBuilder {
+            wasm_path: wasm_path.as_ref().to_path_buf(),
+            configuration: BTreeMap::new(),
+            rows: DEFAULT_ROWS,
+            cols: DEFAULT_COLS,
+            plugin_ids: PluginIds {
+                plugin_id: 1,
+                zellij_pid: std::process::id(),
+                initial_cwd: std::env::current_dir().unwrap_or_default(),
+                client_id: 1,
+            },
+            host_dir: None,
+            session_snapshots: vec![],
+            template_dir: None,
+            layout_dir: None,
+        }
+    }
+    fn start(&mut self, configuration: BTreeMap<String, String>) -> Result<()> {
+        self.instance
+            .get_typed_func::<(), ()>(&mut self.store, "_start")?
+            .call(&mut self.store, ())?;
+        let protobuf_plugin_configuration: ProtobufPluginConfiguration =
+            PluginUserConfiguration::new(configuration)
+                .try_into()
+                .map_err(|e| anyhow!("Failed to serialize user configuration: {:?}", e))?;
+        wasi_write_object(
+            self.store.data(),
+            &protobuf_plugin_configuration.encode_to_vec(),
+        )?;
+        self.instance
+            .get_typed_func::<(), ()>(&mut self.store, "load")?
+            .call(&mut self.store, ())?;
+        Ok(())
+    }
+    /// Send an [`Event`] to the plugin's `update` method, returning whether the plugin asked to
+    /// be rendered.
+    ///
+    /// Like the real host, the event is only delivered if the plugin subscribed to it
+    /// (otherwise this returns `false` without calling the plugin).
+    pub fn update(&mut self, event: Event) -> Result<bool> {
+        let err_context = || format!("failed to send event {:?} to plugin", event);
+        let event_type = EventType::from_str(&event.to_string()).with_context(err_context)?;
+        if !self.store.data().subscriptions.contains(&event_type) {
+            return Ok(false);
+        }
+        let protobuf_event: ProtobufEvent = event
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow!("Failed to convert to protobuf: {:?}", e))
+            .with_context(err_context)?;
+        wasi_write_object(self.store.data(), &protobuf_event.encode_to_vec())
+            .with_context(err_context)?;
+        let should_render = self
+            .instance
+            .get_typed_func::<(), i32>(&mut self.store, "update")
+            .and_then(|update| update.call(&mut self.store, ()))
+            .with_context(err_context)?;
+        Ok(should_render == 1)
+    }
+    /// Send a [`PipeMessage`] to the plugin's `pipe` method, returning whether the plugin asked
+    /// to be rendered
+    pub fn pipe(&mut self, pipe_message: PipeMessage) -> Result<bool> {
+        let err_context = || format!("failed to pipe message {:?} to plugin", pipe_message);
+        let protobuf_pipe_message: ProtobufPipeMessage = pipe_message
+            .clone()
+            .try_into()
+            .map_err(|e| anyhow!("Failed to convert to protobuf: {:?}", e))
+            .with_context(err_context)?;
+        wasi_write_object(self.store.data(), &protobuf_pipe_message.encode_to_vec())
+            .with_context(err_context)?;
+        let should_render = self
+            .instance
+            .get_typed_func::<(), i32>(&mut self.store, "pipe")
+            .and_then(|pipe| pipe.call(&mut self.store, ()))
+            .with_context(err_context)?;
+        Ok(should_render == 1)
+    }
+    /// Call the plugin's `render` method and return the raw bytes it printed (as a string),
+    /// including any ANSI sequences and serialized UI components
+    pub fn render(&mut self) -> Result<String> {
+        let err_context = || "failed to render plugin";
+        self.instance
+            .get_typed_func::<(i32, i32), ()>(&mut self.store, "render")
+            .and_then(|render| render.call(&mut self.store, (self.rows as i32, self.cols as i32)))
+            .with_context(err_context)?;
+        wasi_read_string(self.store.data()).with_context(err_context)
+    }
+    /// Call the plugin's `render` method and return the text that would appear in its pane,
+    /// one line per row, this is intended to be used with snapshot testing (eg. `insta`)
+    pub fn render_snapshot(&mut self) -> Result<String> {
+        let rendered_bytes = self.render()?;
+        let mut grid = Grid::new(
+            self.rows,
+            self.cols,
+            Rc::new(RefCell::new(Palette::default())),
+            Rc::new(RefCell::new(HashMap::new())),
+            Rc::new(RefCell::new(LinkHandler::new())),
+            Rc::new(RefCell::new(None)),
+            Rc::new(RefCell::new(SixelImageStore::default())),
+            Style::default(),
+            false,
+            true,
+            true,
+            false,
+        );
+        let mut vte_parser = vte::Parser::new();
+        for &byte in rendered_bytes.as_bytes() {
+            vte_parser.advance(&mut grid, byte);
+        }
+        Ok(format!("{:?}", grid))
+    }
+    /// Change the content size of the plugin, used in subsequent renders
+    pub fn resize(&mut self, rows: usize, cols: usize) {
+        self.rows = rows;
+        self.cols = cols;
+    }
+    /// All the commands the plugin sent to the host since the last call to `take_commands`
+    pub fn commands(&self) -> &[PluginCommand] {
+        &self.store.data().commands
+    }
+    /// Remove and return all the commands the plugin sent to the host so far
+    pub fn take_commands(&mut self) -> Vec<PluginCommand> {
+        self.store.data_mut().commands.drain(..).collect()
+    }
+    /// The events the plugin is currently subscribed to
+    pub fn subscriptions(&self) -> &HashSet<EventType> {
+        &self.store.data().subscriptions
+    }
+    /// Everything the plugin printed to STDERR so far (eg. with `eprintln!`)
+    pub fn stderr(&self) -> String {
+        String::from_utf8_lossy(&self.store.data().stderr_pipe.lock().unwrap()).to_string()
+    }
+}
+
+fn host_run_plugin_command(mut caller: Caller<'_, HarnessEnv>) {
+    let env = caller.data_mut();
+    let plugin_command = wasi_read_bytes(env).and_then(|bytes| {
+        let command = ProtobufPluginCommand::decode(bytes.as_slice())?;
+        PluginCommand::try_from(command)
+            .map_err(|e| anyhow!("failed to convert serialized command: {}", e))
+    });
+    match plugin_command {
+        Ok(plugin_command) => {
+            respond_to_plugin_command(env, &plugin_command);
+            env.commands.push(plugin_command);
+        },
+        Err(e) => {
+            let _ = writeln!(
+                env.stderr_pipe.lock().unwrap(),
+                "failed to run plugin command: {}",
+                e
+            );
+        },
+    }
+}
+
+// Some commands block until the host writes a response to the plugin's STDIN, this does the
+// same so that the plugin can continue running
+fn respond_to_plugin_command(env: &mut HarnessEnv, plugin_command: &PluginCommand) {
+    match plugin_command {
+        PluginCommand::Subscribe(event_list) => {
+            env.subscriptions.extend(event_list.iter().copied());
+        },
+        PluginCommand::Unsubscribe(event_list) => {
+            env.subscriptions.retain(|e| !event_list.contains(e));
+        },
+        PluginCommand::GetPluginIds => {
+            if let Ok(plugin_ids) = ProtobufPluginIds::try_from(env.plugin_ids.clone()) {
+                let _ = wasi_write_object(env, &plugin_ids.encode_to_vec());
+            }
+        },
+        PluginCommand::GetZellijVersion => {
+            let zellij_version = ProtobufZellijVersion {
+                version: VERSION.to_owned(),
+            };
+            let _ = wasi_write_object(env, &zellij_version.encode_to_vec());
+        },
+        PluginCommand::ListSessionSnapshots => {
+            let list_session_snapshots_response = ListSessionSnapshotsResponse {
+                snapshots: env
+                    .session_snapshots
+                    .iter()
+                    .cloned()
+                    .map(|s| s.into())
+                    .collect(),
+                error: None,
+            };
+            let _ = wasi_write_object(env, &list_session_snapshots_response.encode_to_vec());
+        },
+        PluginCommand::ListSessionTemplates => {
+            let list_session_templates_response = ListSessionTemplatesResponse {
+                templates: session_template::session_templates(
+                    env.template_dir.clone(),
+                    env.layout_dir.clone(),
+                )
+                .into_iter()
+                .map(|t| t.into())
+                .collect(),
+                error: None,
+            };
+            let _ = wasi_write_object(env, &list_session_templates_response.encode_to_vec());
+        },
+        PluginCommand::ResolveSessionTemplate(template_name, args) => {
+            let resolved = session_template::session_template(
+                template_name,
+                env.template_dir.clone(),
+                env.layout_dir.clone(),
+            )
+            .and_then(|template_file| {
+                session_template::resolve_session_template(
+                    &template_file,
+                    args,
+                    env.layout_dir.clone(),
+                )
+            });
+            let resolve_session_template_response = match resolved {
+                Ok(resolved) => ResolveSessionTemplateResponse {
+                    layout: Some(resolved.layout),
+                    error: None,
+                },
+                Err(ConfigError::KdlError(kdl_error)) => ResolveSessionTemplateResponse {
+                    layout: None,
+                    error: Some(kdl_error.error_message),
+                },
+                Err(e) => ResolveSessionTemplateResponse {
+                    layout: None,
+                    error: Some(e.to_string()),
+                },
+            };
+            let _ = wasi_write_object(env, &resolve_session_template_response.encode_to_vec());
+        },
+        PluginCommand::GenerateWebLoginToken(..)
+        | PluginCommand::RevokeWebLoginToken(..)
+        | PluginCommand::RevokeAllWebLoginTokens
+        | PluginCommand::RenameWebLoginToken(..)
+        | PluginCommand::UpdateWebLoginToken(..)
+        | PluginCommand::SetWebLoginTokenExpiry(..)
+        | PluginCommand::ReadWebAuditLog
+        | PluginCommand::ListWebLoginTokens => {
+            // the harness has no web server, respond like a host compiled without one
+            let empty_vec: Vec<u8> = vec![];
+            let _ = wasi_write_object(env, &empty_vec);
+        },
+        _ => {},
+    }
+}
+
+fn wasi_read_string(env: &HarnessEnv) -> Result<String> {
+    let mut buf = vec![];
+    env.stdout_pipe
+        .lock()
+        .unwrap()
+        .read_to_end(&mut buf)
+        .context("failed to read string from WASI env")?;
+    let buf = String::from_utf8_lossy(&buf);
+    Ok(buf.replace("\n", "\n\r"))
+}
+
+fn wasi_read_bytes(env: &HarnessEnv) -> Result<Vec<u8>> {
+    wasi_read_string(env)
+        .and_then(|string| serde_json::from_str(&string).map_err(anyError::new))
+        .context("failed to deserialize object from WASI env")
+}
+
+fn wasi_write_object(env: &HarnessEnv, object: &[u8]) -> Result<()> {
+    let serialized = serde_json::to_string(object).map_err(anyError::new)?;
+    let mut stdin = env.stdin_pipe.lock().unwrap();
+    writeln!(stdin, "{}\r", serialized).context("failed to write object to WASI env")
+}
+
+#[cfg(test)]
+#[path = "./unit/harness_tests.rs"]
+mod harness_tests;
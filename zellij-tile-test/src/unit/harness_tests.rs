@@ -0,0 +1,208 @@
+use super::{respond_to_plugin_command, PluginTestHarness, PluginTestHarnessBuilder};
+use insta::assert_snapshot;
+use prost::Message;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+use zellij_utils::data::{
+    BareKey, Event, EventType, InputMode, KeyWithModifier, ModeInfo, PipeMessage, PipeSource,
+    PluginCommand, SessionSnapshot,
+};
+use zellij_utils::plugin_api::plugin_command::{
+    ListSessionSnapshotsResponse, ListSessionTemplatesResponse, ResolveSessionTemplateResponse,
+};
+
+fn fixture_plugin_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../zellij-utils/assets/plugins/fixture-plugin-for-tests.wasm")
+}
+
+// the response the harness writes to the plugin's STDIN for a blocking command
+fn response_to_plugin_command(
+    harness_builder: PluginTestHarnessBuilder,
+    plugin_command: PluginCommand,
+) -> Vec<u8> {
+    let mut env = harness_builder
+        .harness_env()
+        .expect("failed to create harness env");
+    respond_to_plugin_command(&mut env, &plugin_command);
+    let mut response = String::new();
+    env.stdin_pipe
+        .lock()
+        .unwrap()
+        .read_to_string(&mut response)
+        .unwrap();
+    serde_json::from_str(response.trim()).expect("no response was written to the plugin")
+}
+
+#[test]
+fn load_plugin_and_record_initial_commands() {
+    let plugin = PluginTestHarness::from_wasm(fixture_plugin_path())
+        .load()
+        .expect("failed to load fixture plugin");
+    let commands = plugin.commands();
+    assert!(commands
+        .iter()
+        .any(|c| matches!(c, PluginCommand::RequestPluginPermissions(..))));
+    assert!(commands
+        .iter()
+        .any(|c| matches!(c, PluginCommand::WatchFilesystem)));
+    assert!(plugin.subscriptions().contains(&EventType::Key));
+}
+
+#[test]
+fn send_subscribed_event_to_plugin() {
+    let mut plugin = PluginTestHarness::from_wasm(fixture_plugin_path())
+        .load()
+        .expect("failed to load fixture plugin");
+    plugin.take_commands();
+    plugin
+        .update(Event::Key(KeyWithModifier::new(BareKey::Char('a'))))
+        .expect("failed to update plugin");
+    let commands = plugin.take_commands();
+    assert_eq!(commands.len(), 1);
+    assert!(matches!(
+        commands[0],
+        PluginCommand::SwitchToMode(InputMode::Tab)
+    ));
+}
+
+#[test]
+fn unsubscribed_event_is_not_sent_to_plugin() {
+    let mut plugin = PluginTestHarness::from_wasm(fixture_plugin_path())
+        .load()
+        .expect("failed to load fixture plugin");
+    let should_render = plugin
+        .update(Event::ModeUpdate(ModeInfo::default()))
+        .expect("failed to update plugin");
+    assert!(!should_render);
+}
+
+#[test]
+fn send_pipe_message_to_plugin() {
+    let mut plugin = PluginTestHarness::from_wasm(fixture_plugin_path())
+        .load()
+        .expect("failed to load fixture plugin");
+    plugin.take_commands();
+    let should_render = plugin
+        .pipe(PipeMessage::new(
+            PipeSource::Cli("my_pipe_id".to_owned()),
+            "message_name",
+            &Some("message_payload".to_owned()),
+            &None,
+            false,
+        ))
+        .expect("failed to pipe message to plugin");
+    assert!(should_render);
+    let commands = plugin.take_commands();
+    assert_eq!(commands.len(), 1);
+    assert!(
+        matches!(&commands[0], PluginCommand::UnblockCliPipeInput(pipe_id) if pipe_id == "my_pipe_id")
+    );
+}
+
+#[test]
+fn snapshot_plugin_render() {
+    let mut plugin = PluginTestHarness::from_wasm(fixture_plugin_path())
+        .with_size(5, 40)
+        .load()
+        .expect("failed to load fixture plugin");
+    plugin
+        .pipe(PipeMessage::new(
+            PipeSource::Keybind,
+            "message_to_plugin",
+            &Some("my_payload".to_owned()),
+            &None,
+            false,
+        ))
+        .expect("failed to pipe message to plugin");
+    let snapshot = plugin.render_snapshot().expect("failed to render plugin");
+    assert_snapshot!(snapshot);
+}
+
+#[test]
+fn respond_to_list_session_snapshots() {
+    let response = response_to_plugin_command(
+        PluginTestHarness::from_wasm(fixture_plugin_path()),
+        PluginCommand::ListSessionSnapshots,
+    );
+    let response = ListSessionSnapshotsResponse::decode(response.as_slice()).unwrap();
+    assert!(response.snapshots.is_empty());
+    assert_eq!(response.error, None);
+
+    let session_snapshot = SessionSnapshot {
+        session_name: "work".to_owned(),
+        name: "before-refactor".to_owned(),
+        created: Duration::from_secs(60),
+        layout_path: "/tmp/before-refactor/session-layout.kdl".to_owned(),
+        layout: "layout {\n    pane\n}\n".to_owned(),
+    };
+    let response = response_to_plugin_command(
+        PluginTestHarness::from_wasm(fixture_plugin_path())
+            .with_session_snapshots(vec![session_snapshot.clone()]),
+        PluginCommand::ListSessionSnapshots,
+    );
+    let response = ListSessionSnapshotsResponse::decode(response.as_slice()).unwrap();
+    let snapshots: Vec<SessionSnapshot> =
+        response.snapshots.into_iter().map(|s| s.into()).collect();
+    assert_eq!(snapshots, vec![session_snapshot]);
+}
+
+#[test]
+fn respond_to_session_template_commands() {
+    let config_dir = tempfile::tempdir().unwrap();
+    let template_dir = config_dir.path().join("templates");
+    let layout_dir = config_dir.path().join("layouts");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::create_dir_all(&layout_dir).unwrap();
+    std::fs::write(
+        template_dir.join("api.kdl"),
+        "description \"The API\"\nlayout \"api\"\nparam \"branch\" default=\"main\"\nlayout_args {\n    branch \"${branch}\"\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        layout_dir.join("api.kdl"),
+        "layout {\n    param \"branch\"\n    pane name=\"${branch}\"\n}\n",
+    )
+    .unwrap();
+    let harness_builder = || {
+        PluginTestHarness::from_wasm(fixture_plugin_path())
+            .with_template_dir(&template_dir)
+            .with_layout_dir(&layout_dir)
+    };
+
+    let response = response_to_plugin_command(
+        PluginTestHarness::from_wasm(fixture_plugin_path()),
+        PluginCommand::ListSessionTemplates,
+    );
+    let response = ListSessionTemplatesResponse::decode(response.as_slice()).unwrap();
+    assert!(response.templates.is_empty(), "no templates by default");
+
+    let response =
+        response_to_plugin_command(harness_builder(), PluginCommand::ListSessionTemplates);
+    let response = ListSessionTemplatesResponse::decode(response.as_slice()).unwrap();
+    let template_names: Vec<String> = response.templates.into_iter().map(|t| t.name).collect();
+    assert_eq!(template_names, vec!["api".to_owned()]);
+
+    let mut args = BTreeMap::new();
+    args.insert("branch".to_owned(), "feature".to_owned());
+    let response = response_to_plugin_command(
+        harness_builder(),
+        PluginCommand::ResolveSessionTemplate("api".to_owned(), args),
+    );
+    let response = ResolveSessionTemplateResponse::decode(response.as_slice()).unwrap();
+    assert_eq!(response.error, None);
+    let layout = response
+        .layout
+        .expect("template should resolve to a layout");
+    assert!(layout.contains("feature"), "{}", layout);
+
+    let response = response_to_plugin_command(
+        harness_builder(),
+        PluginCommand::ResolveSessionTemplate("missing".to_owned(), BTreeMap::new()),
+    );
+    let response = ResolveSessionTemplateResponse::decode(response.as_slice()).unwrap();
+    assert_eq!(response.layout, None);
+    assert!(response.error.is_some());
+}
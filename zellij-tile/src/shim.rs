@@ -3,14 +3,17 @@ use std::collections::{BTreeMap, HashSet};
 use std::{
     io,
     path::{Path, PathBuf},
+    time::Duration,
 };
 use zellij_utils::data::*;
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::actions::Action;
 pub use zellij_utils::plugin_api;
 use zellij_utils::plugin_api::plugin_command::{
-    CreateTokenResponse, ListTokensResponse, ProtobufPluginCommand, RenameWebTokenResponse,
-    RevokeAllWebTokensResponse, RevokeTokenResponse,
+    CreateTokenResponse, ListSessionSnapshotsResponse, ListSessionTemplatesResponse,
+    ListTokensResponse, ProtobufPluginCommand, ProtobufWebTokenScope, ReadWebAuditLogResponse,
+    RenameWebTokenResponse, ResolveSessionTemplateResponse, RevokeAllWebTokensResponse,
+    RevokeTokenResponse, UpdateWebTokenResponse,
 };
 use zellij_utils::plugin_api::plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion};
 
@@ -54,6 +57,21 @@ pub fn request_permission(permissions: &[PermissionType]) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Requests read-only or read-write access to specific folders on the host (eg.
+/// `PathPermission::read_only("~/.config/mcp")`). Once granted, each folder is mounted in the
+/// plugin's filesystem under its absolute path on the host, and the grant is remembered for the
+/// next time the plugin is loaded. The result arrives as an `Event::PermissionRequestResult`.
+/// Relative paths are resolved against the plugin's initial cwd.
+///
+/// Newly granted folders only become visible to a plugin that has not yet accessed the
+/// filesystem, so this is best requested in `load`.
+pub fn request_path_permissions(paths: &[PathPermission]) {
+    let plugin_command = PluginCommand::RequestPathPermissions(paths.into());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Query Functions
 /// Returns the unique Swarm pane ID for the plugin as well as the Swarm process id.
 pub fn get_plugin_ids() -> PluginIds {
@@ -369,6 +387,40 @@ pub fn web_request<S: AsRef<str>>(
     unsafe { host_run_plugin_command() };
 }
 
+/// Open a connection (TCP, Unix socket or WebSocket) to the given address, the connection is
+/// established in the background and reported back through the `ConnectionOpened` Event (or the
+/// `ConnectionClosed` Event if it failed), the context will be returned verbatim in both events
+/// and can be used to associate the assigned connection id with this request
+pub fn open_connection<S: AsRef<str>>(
+    kind: ConnectionKind,
+    address: S,
+    context: BTreeMap<String, String>,
+) where
+    S: ToString,
+{
+    let plugin_command = PluginCommand::OpenConnection(kind, address.to_string(), context);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Send data over a connection previously opened with `open_connection`, data received from the
+/// other side will arrive in the `ConnectionMessage` Event
+pub fn send_to_connection(connection_id: u32, data: Vec<u8>) {
+    let plugin_command = PluginCommand::SendToConnection(connection_id, data);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Close a connection previously opened with `open_connection`
+pub fn close_connection(connection_id: u32) {
+    let plugin_command = PluginCommand::CloseConnection(connection_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Hide the plugin pane (suppress it) from the UI
 pub fn hide_self() {
     let plugin_command = PluginCommand::HideSelf;
@@ -1363,8 +1415,11 @@ pub fn set_self_mouse_selection_support(selection_support: bool) {
     unsafe { host_run_plugin_command() };
 }
 
-pub fn generate_web_login_token(token_label: Option<String>) -> Result<String, String> {
-    let plugin_command = PluginCommand::GenerateWebLoginToken(token_label);
+pub fn generate_web_login_token(
+    token_label: Option<String>,
+    scope: WebTokenScope,
+) -> Result<String, String> {
+    let plugin_command = PluginCommand::GenerateWebLoginToken(token_label, scope);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
@@ -1393,8 +1448,7 @@ pub fn revoke_web_login_token(token_label: &str) -> Result<(), String> {
     }
 }
 
-pub fn list_web_login_tokens() -> Result<Vec<(String, String)>, String> {
-    // (name, created_at)
+pub fn list_web_login_tokens() -> Result<Vec<WebLoginToken>, String> {
     let plugin_command = PluginCommand::ListWebLoginTokens;
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
@@ -1404,12 +1458,108 @@ pub fn list_web_login_tokens() -> Result<Vec<(String, String)>, String> {
     if let Some(error) = list_tokens_response.error {
         Err(error)
     } else {
-        let tokens_and_creation_times = std::iter::zip(
+        let mut scopes = list_tokens_response.scopes.into_iter();
+        let mut expiration_times = list_tokens_response.expiration_times.into_iter();
+        let mut allowed_sessions = list_tokens_response.allowed_sessions.into_iter();
+        let tokens = std::iter::zip(
             list_tokens_response.tokens,
             list_tokens_response.creation_times,
         )
+        .map(|(name, created_at)| WebLoginToken {
+            name,
+            created_at,
+            scope: scopes
+                .next()
+                .and_then(ProtobufWebTokenScope::from_i32)
+                .map(|s| s.into())
+                .unwrap_or_default(),
+            allowed_sessions: allowed_sessions
+                .next()
+                .map(|a| a.session_names)
+                .unwrap_or_default(),
+            expires_at: expiration_times.next().filter(|e| !e.is_empty()),
+        })
         .collect();
-        Ok(tokens_and_creation_times)
+        Ok(tokens)
+    }
+}
+
+/// Returns the events of the web audit log, oldest first
+pub fn read_web_audit_log() -> Result<Vec<WebAuditEvent>, String> {
+    let plugin_command = PluginCommand::ReadWebAuditLog;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let read_web_audit_log_response =
+        ReadWebAuditLogResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    if let Some(error) = read_web_audit_log_response.error {
+        Err(error)
+    } else {
+        Ok(read_web_audit_log_response
+            .events
+            .into_iter()
+            .filter_map(|e| e.try_into().ok())
+            .collect())
+    }
+}
+
+/// Returns the named snapshots of all the sessions on this machine
+pub fn list_session_snapshots() -> Result<Vec<SessionSnapshot>, String> {
+    let plugin_command = PluginCommand::ListSessionSnapshots;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let list_session_snapshots_response =
+        ListSessionSnapshotsResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    if let Some(error) = list_session_snapshots_response.error {
+        Err(error)
+    } else {
+        Ok(list_session_snapshots_response
+            .snapshots
+            .into_iter()
+            .map(|s| s.into())
+            .collect())
+    }
+}
+
+/// Returns the session templates found in the templates folder of the config directory
+pub fn list_session_templates() -> Result<Vec<SessionTemplate>, String> {
+    let plugin_command = PluginCommand::ListSessionTemplates;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let list_session_templates_response =
+        ListSessionTemplatesResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    if let Some(error) = list_session_templates_response.error {
+        Err(error)
+    } else {
+        Ok(list_session_templates_response
+            .templates
+            .into_iter()
+            .map(|t| t.into())
+            .collect())
+    }
+}
+
+/// Fills in the template's params and returns a stringified layout that can be used with
+/// `switch_session_with_layout`
+pub fn resolve_session_template(
+    template_name: &str,
+    args: BTreeMap<String, String>,
+) -> Result<String, String> {
+    let plugin_command = PluginCommand::ResolveSessionTemplate(template_name.to_owned(), args);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let resolve_session_template_response =
+        ResolveSessionTemplateResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    match (
+        resolve_session_template_response.layout,
+        resolve_session_template_response.error,
+    ) {
+        (_, Some(error)) => Err(error),
+        (Some(layout), None) => Ok(layout),
+        (None, None) => Err("Failed to resolve session template".to_owned()),
     }
 }
 
@@ -1442,6 +1592,41 @@ pub fn rename_web_token(old_name: &str, new_name: &str) -> Result<(), String> {
     }
 }
 
+/// Replaces the scope and allowed sessions (empty for all) of a web login token
+pub fn update_web_token(
+    token_name: &str,
+    scope: WebTokenScope,
+    allowed_sessions: Vec<String>,
+) -> Result<(), String> {
+    let plugin_command =
+        PluginCommand::UpdateWebLoginToken(token_name.to_owned(), scope, allowed_sessions);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let update_web_token_response =
+        UpdateWebTokenResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    if let Some(error) = update_web_token_response.error {
+        Err(error)
+    } else {
+        Ok(())
+    }
+}
+
+/// Makes a web login token expire `expires_in` from now, or never if `None`
+pub fn set_web_token_expiry(token_name: &str, expires_in: Option<Duration>) -> Result<(), String> {
+    let plugin_command = PluginCommand::SetWebLoginTokenExpiry(token_name.to_owned(), expires_in);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let set_web_token_expiry_response =
+        UpdateWebTokenResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    if let Some(error) = set_web_token_expiry_response.error {
+        Err(error)
+    } else {
+        Ok(())
+    }
+}
+
 pub fn intercept_key_presses() {
     let plugin_command = PluginCommand::InterceptKeyPresses;
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -9,7 +9,7 @@ pub struct EventNameList {
 pub struct Event {
     #[prost(enumeration="EventType", tag="1")]
     pub name: i32,
-    #[prost(oneof="event::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29")]
+    #[prost(oneof="event::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32")]
     pub payload: ::core::option::Option<event::Payload>,
 }
 /// Nested message and enum types in `Event`.
@@ -73,10 +73,42 @@ pub mod event {
         FailedToStartWebServerPayload(super::FailedToStartWebServerPayload),
         #[prost(message, tag="29")]
         InterceptedKeyPayload(super::super::key::Key),
+        #[prost(message, tag="30")]
+        ConnectionOpenedPayload(super::ConnectionOpenedPayload),
+        #[prost(message, tag="31")]
+        ConnectionMessagePayload(super::ConnectionMessagePayload),
+        #[prost(message, tag="32")]
+        ConnectionClosedPayload(super::ConnectionClosedPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConnectionOpenedPayload {
+    #[prost(uint32, tag="1")]
+    pub connection_id: u32,
+    #[prost(message, repeated, tag="2")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConnectionMessagePayload {
+    #[prost(uint32, tag="1")]
+    pub connection_id: u32,
+    #[prost(bytes="vec", tag="2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConnectionClosedPayload {
+    #[prost(uint32, tag="1")]
+    pub connection_id: u32,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag="3")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FailedToStartWebServerPayload {
     #[prost(string, tag="1")]
     pub error: ::prost::alloc::string::String,
@@ -124,6 +156,8 @@ pub struct ClientInfo {
     pub running_command: ::prost::alloc::string::String,
     #[prost(bool, tag="4")]
     pub is_current_client: bool,
+    #[prost(bool, tag="5")]
+    pub is_read_only: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -337,6 +371,10 @@ pub struct SessionManifest {
     pub web_client_count: u32,
     #[prost(message, repeated, tag="10")]
     pub tab_history: ::prost::alloc::vec::Vec<ClientTabHistory>,
+    #[prost(string, repeated, tag="11")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, optional, tag="12")]
+    pub last_activity: ::core::option::Option<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -499,6 +537,18 @@ pub struct ModeUpdatePayload {
     pub web_server_port: ::core::option::Option<u32>,
     #[prost(bool, optional, tag="15")]
     pub web_server_capability: ::core::option::Option<bool>,
+    #[prost(message, repeated, tag="16")]
+    pub connected_users: ::prost::alloc::vec::Vec<ConnectedUser>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConnectedUser {
+    #[prost(uint32, tag="1")]
+    pub client_id: u32,
+    #[prost(string, optional, tag="2")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag="3")]
+    pub is_current_client: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -569,6 +619,9 @@ pub enum EventType {
     BeforeClose = 32,
     FailedToStartWebServer = 34,
     InterceptedKeyPress = 35,
+    ConnectionOpened = 36,
+    ConnectionMessage = 37,
+    ConnectionClosed = 38,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -612,6 +665,9 @@ impl EventType {
             EventType::BeforeClose => "BeforeClose",
             EventType::FailedToStartWebServer => "FailedToStartWebServer",
             EventType::InterceptedKeyPress => "InterceptedKeyPress",
+            EventType::ConnectionOpened => "ConnectionOpened",
+            EventType::ConnectionMessage => "ConnectionMessage",
+            EventType::ConnectionClosed => "ConnectionClosed",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -652,6 +708,9 @@ impl EventType {
             "BeforeClose" => Some(Self::BeforeClose),
             "FailedToStartWebServer" => Some(Self::FailedToStartWebServer),
             "InterceptedKeyPress" => Some(Self::InterceptedKeyPress),
+            "ConnectionOpened" => Some(Self::ConnectionOpened),
+            "ConnectionMessage" => Some(Self::ConnectionMessage),
+            "ConnectionClosed" => Some(Self::ConnectionClosed),
             _ => None,
         }
     }
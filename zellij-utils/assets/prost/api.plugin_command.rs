@@ -3,7 +3,7 @@
 pub struct PluginCommand {
     #[prost(enumeration="CommandName", tag="1")]
     pub name: i32,
-    #[prost(oneof="plugin_command::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112")]
+    #[prost(oneof="plugin_command::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119")]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
 /// Nested message and enum types in `PluginCommand`.
@@ -215,10 +215,48 @@ pub mod plugin_command {
         ReplacePaneWithExistingPanePayload(super::ReplacePaneWithExistingPanePayload),
         #[prost(message, tag="112")]
         NewTabPayload(super::NewTabPayload),
+        #[prost(message, tag="113")]
+        OpenConnectionPayload(super::OpenConnectionPayload),
+        #[prost(message, tag="114")]
+        SendToConnectionPayload(super::SendToConnectionPayload),
+        #[prost(message, tag="115")]
+        CloseConnectionPayload(super::CloseConnectionPayload),
+        #[prost(message, tag="116")]
+        RequestPathPermissionsPayload(super::RequestPathPermissionsPayload),
+        #[prost(message, tag="117")]
+        ResolveSessionTemplatePayload(super::ResolveSessionTemplatePayload),
+        #[prost(message, tag="118")]
+        UpdateWebLoginTokenPayload(super::UpdateWebLoginTokenPayload),
+        #[prost(message, tag="119")]
+        SetWebLoginTokenExpiryPayload(super::SetWebLoginTokenExpiryPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenConnectionPayload {
+    #[prost(enumeration="ConnectionKind", tag="1")]
+    pub kind: i32,
+    #[prost(string, tag="2")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="3")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendToConnectionPayload {
+    #[prost(uint32, tag="1")]
+    pub connection_id: u32,
+    #[prost(bytes="vec", tag="2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloseConnectionPayload {
+    #[prost(uint32, tag="1")]
+    pub connection_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewTabPayload {
     #[prost(string, optional, tag="1")]
     pub name: ::core::option::Option<::prost::alloc::string::String>,
@@ -252,6 +290,27 @@ pub struct RevokeWebLoginTokenPayload {
 pub struct GenerateWebLoginTokenPayload {
     #[prost(string, optional, tag="1")]
     pub token_label: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration="WebTokenScope", tag="2")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateWebLoginTokenPayload {
+    #[prost(string, tag="1")]
+    pub token_label: ::prost::alloc::string::String,
+    #[prost(enumeration="WebTokenScope", tag="2")]
+    pub scope: i32,
+    #[prost(string, repeated, tag="3")]
+    pub allowed_sessions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetWebLoginTokenExpiryPayload {
+    #[prost(string, tag="1")]
+    pub token_label: ::prost::alloc::string::String,
+    /// never expires if not set
+    #[prost(uint64, optional, tag="2")]
+    pub expires_in_secs: ::core::option::Option<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -693,6 +752,12 @@ pub struct RequestPluginPermissionPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestPathPermissionsPayload {
+    #[prost(message, repeated, tag="1")]
+    pub paths: ::prost::alloc::vec::Vec<super::plugin_permission::PathPermission>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SubscribePayload {
     #[prost(message, optional, tag="1")]
     pub subscriptions: ::core::option::Option<super::event::EventNameList>,
@@ -861,13 +926,101 @@ pub struct RevokeTokenResponse {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListTokensResponse {
-    /// tokens/creation_times should be synchronized
+    /// tokens/creation_times/scopes/expiration_times/allowed_sessions should be synchronized
     #[prost(string, repeated, tag="1")]
     pub tokens: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, repeated, tag="2")]
     pub creation_times: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, optional, tag="3")]
     pub error: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration="WebTokenScope", repeated, tag="4")]
+    pub scopes: ::prost::alloc::vec::Vec<i32>,
+    /// empty if the token never expires
+    #[prost(string, repeated, tag="5")]
+    pub expiration_times: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag="6")]
+    pub allowed_sessions: ::prost::alloc::vec::Vec<AllowedSessions>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AllowedSessions {
+    /// empty if all sessions are allowed
+    #[prost(string, repeated, tag="1")]
+    pub session_names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionSnapshot {
+    #[prost(string, tag="1")]
+    pub session_name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub created_secs: u64,
+    #[prost(string, tag="4")]
+    pub layout_path: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub layout: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSessionSnapshotsResponse {
+    #[prost(message, repeated, tag="1")]
+    pub snapshots: ::prost::alloc::vec::Vec<SessionSnapshot>,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionTemplateParam {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="2")]
+    pub description: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub default: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionTemplate {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="2")]
+    pub description: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub layout: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="4")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag="5")]
+    pub params: ::prost::alloc::vec::Vec<SessionTemplateParam>,
+    #[prost(string, repeated, tag="6")]
+    pub mcp_servers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag="7")]
+    pub agents: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSessionTemplatesResponse {
+    #[prost(message, repeated, tag="1")]
+    pub templates: ::prost::alloc::vec::Vec<SessionTemplate>,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResolveSessionTemplatePayload {
+    #[prost(string, tag="1")]
+    pub template_name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="2")]
+    pub args: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResolveSessionTemplateResponse {
+    #[prost(string, optional, tag="1")]
+    pub layout: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -885,6 +1038,40 @@ pub struct RenameWebTokenResponse {
     #[prost(string, optional, tag="2")]
     pub error: ::core::option::Option<::prost::alloc::string::String>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateWebTokenResponse {
+    #[prost(bool, tag="1")]
+    pub successfully_updated: bool,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebAuditEvent {
+    #[prost(uint64, tag="1")]
+    pub timestamp: u64,
+    #[prost(string, tag="2")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="3")]
+    pub session_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="4")]
+    pub token_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="5")]
+    pub client_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="6")]
+    pub remote_address: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="7")]
+    pub details: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadWebAuditLogResponse {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<WebAuditEvent>,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum CommandName {
@@ -1034,6 +1221,16 @@ pub enum CommandName {
     InterceptKeyPresses = 143,
     ClearKeyPressesIntercepts = 144,
     ReplacePaneWithExistingPane = 155,
+    OpenConnection = 156,
+    SendToConnection = 157,
+    CloseConnection = 158,
+    RequestPathPermissions = 159,
+    ListSessionSnapshots = 160,
+    ListSessionTemplates = 161,
+    ResolveSessionTemplate = 162,
+    UpdateWebLoginToken = 163,
+    SetWebLoginTokenExpiry = 164,
+    ReadWebAuditLog = 165,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1188,6 +1385,16 @@ impl CommandName {
             CommandName::InterceptKeyPresses => "InterceptKeyPresses",
             CommandName::ClearKeyPressesIntercepts => "ClearKeyPressesIntercepts",
             CommandName::ReplacePaneWithExistingPane => "ReplacePaneWithExistingPane",
+            CommandName::OpenConnection => "OpenConnection",
+            CommandName::SendToConnection => "SendToConnection",
+            CommandName::CloseConnection => "CloseConnection",
+            CommandName::RequestPathPermissions => "RequestPathPermissions",
+            CommandName::ListSessionSnapshots => "ListSessionSnapshots",
+            CommandName::ListSessionTemplates => "ListSessionTemplates",
+            CommandName::ResolveSessionTemplate => "ResolveSessionTemplate",
+            CommandName::UpdateWebLoginToken => "UpdateWebLoginToken",
+            CommandName::SetWebLoginTokenExpiry => "SetWebLoginTokenExpiry",
+            CommandName::ReadWebAuditLog => "ReadWebAuditLog",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1339,6 +1546,74 @@ impl CommandName {
             "InterceptKeyPresses" => Some(Self::InterceptKeyPresses),
             "ClearKeyPressesIntercepts" => Some(Self::ClearKeyPressesIntercepts),
             "ReplacePaneWithExistingPane" => Some(Self::ReplacePaneWithExistingPane),
+            "OpenConnection" => Some(Self::OpenConnection),
+            "SendToConnection" => Some(Self::SendToConnection),
+            "CloseConnection" => Some(Self::CloseConnection),
+            "RequestPathPermissions" => Some(Self::RequestPathPermissions),
+            "ListSessionSnapshots" => Some(Self::ListSessionSnapshots),
+            "ListSessionTemplates" => Some(Self::ListSessionTemplates),
+            "ResolveSessionTemplate" => Some(Self::ResolveSessionTemplate),
+            "UpdateWebLoginToken" => Some(Self::UpdateWebLoginToken),
+            "SetWebLoginTokenExpiry" => Some(Self::SetWebLoginTokenExpiry),
+            "ReadWebAuditLog" => Some(Self::ReadWebAuditLog),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ConnectionKind {
+    Tcp = 0,
+    UnixSocket = 1,
+    WebSocket = 2,
+}
+impl ConnectionKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ConnectionKind::Tcp => "Tcp",
+            ConnectionKind::UnixSocket => "UnixSocket",
+            ConnectionKind::WebSocket => "WebSocket",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Tcp" => Some(Self::Tcp),
+            "UnixSocket" => Some(Self::UnixSocket),
+            "WebSocket" => Some(Self::WebSocket),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum WebTokenScope {
+    Admin = 0,
+    ReadOnly = 1,
+    Interactive = 2,
+}
+impl WebTokenScope {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WebTokenScope::Admin => "Admin",
+            WebTokenScope::ReadOnly => "ReadOnly",
+            WebTokenScope::Interactive => "Interactive",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Admin" => Some(Self::Admin),
+            "ReadOnly" => Some(Self::ReadOnly),
+            "Interactive" => Some(Self::Interactive),
             _ => None,
         }
     }
@@ -1,3 +1,11 @@
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PathPermission {
+    #[prost(string, tag="1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(enumeration="PathAccess", tag="2")]
+    pub access: i32,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum PermissionType {
@@ -14,6 +22,8 @@ pub enum PermissionType {
     FullHdAccess = 10,
     StartWebServer = 11,
     InterceptInput = 12,
+    NetworkAccess = 13,
+    LocalSocketAccess = 14,
 }
 impl PermissionType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -35,6 +45,8 @@ impl PermissionType {
             PermissionType::FullHdAccess => "FullHdAccess",
             PermissionType::StartWebServer => "StartWebServer",
             PermissionType::InterceptInput => "InterceptInput",
+            PermissionType::NetworkAccess => "NetworkAccess",
+            PermissionType::LocalSocketAccess => "LocalSocketAccess",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -53,6 +65,34 @@ impl PermissionType {
             "FullHdAccess" => Some(Self::FullHdAccess),
             "StartWebServer" => Some(Self::StartWebServer),
             "InterceptInput" => Some(Self::InterceptInput),
+            "NetworkAccess" => Some(Self::NetworkAccess),
+            "LocalSocketAccess" => Some(Self::LocalSocketAccess),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PathAccess {
+    ReadOnly = 0,
+    ReadWrite = 1,
+}
+impl PathAccess {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PathAccess::ReadOnly => "ReadOnly",
+            PathAccess::ReadWrite => "ReadWrite",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ReadOnly" => Some(Self::ReadOnly),
+            "ReadWrite" => Some(Self::ReadWrite),
             _ => None,
         }
     }
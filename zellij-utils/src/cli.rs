@@ -1,15 +1,16 @@
-use crate::data::{Direction, InputMode, Resize};
+use crate::data::{Direction, InputMode, Resize, SessionEventType, WebTokenScope};
 use crate::setup::Setup;
 use crate::{
     consts::{SWARM_CONFIG_DIR_ENV, SWARM_CONFIG_FILE_ENV},
     input::{layout::PluginUserConfiguration, options::CliOptions},
 };
-use clap::{Args, Parser, Subcommand};
+use clap::{ArgEnum, Args, Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 use url::Url;
 
-fn validate_session(name: &str) -> Result<String, String> {
+pub(crate) fn validate_session(name: &str) -> Result<String, String> {
     #[cfg(unix)]
     {
         use crate::consts::SWARM_SOCK_MAX_LENGTH;
@@ -33,6 +34,32 @@ fn validate_session(name: &str) -> Result<String, String> {
     Ok(name.to_owned())
 }
 
+fn validate_user_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        Err("user name cannot be empty".to_owned())
+    } else if name.chars().any(|c| c.is_control()) {
+        Err("user name cannot contain control characters".to_owned())
+    } else {
+        Ok(name.to_owned())
+    }
+}
+
+fn parse_layout_arg(layout_arg: &str) -> Result<(String, String), String> {
+    match layout_arg.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_owned(), value.to_owned())),
+        _ => Err(format!(
+            "layout arguments should be of the form NAME=VALUE, got: {}",
+            layout_arg
+        )),
+    }
+}
+
+fn parse_token_expiry(expires_in: &str) -> Result<Duration, String> {
+    humantime::parse_duration(expires_in)
+        .map_err(|e| format!("expected a duration such as 12h or 7days: {}", e))
+}
+
 #[derive(Parser, Default, Debug, Clone, Serialize, Deserialize)]
 #[clap(version, name = "swarm")]
 pub struct CliArgs {
@@ -62,11 +89,26 @@ pub struct CliArgs {
     #[clap(short, long, value_parser, overrides_with = "layout")]
     pub layout: Option<PathBuf>,
 
+    /// Set a parameter declared in the layout with `param`, eg. --layout-arg project=~/api
+    /// (can be specified multiple times)
+    #[clap(long = "layout-arg", value_name = "NAME=VALUE", number_of_values = 1, value_parser = parse_layout_arg)]
+    pub layout_args: Vec<(String, String)>,
+
     /// Name of a predefined layout inside the layout directory or the path to a layout file
     /// Will always start a new session, even if inside an existing session
     #[clap(short, long, value_parser, overrides_with = "new_session_with_layout")]
     pub new_session_with_layout: Option<PathBuf>,
 
+    /// Start the session in the background without attaching to it, returning once its layout is
+    /// applied (eg. on CI runners or remote machines)
+    #[clap(long, value_parser)]
+    pub headless: bool,
+
+    /// Do not attach to the session of the project (a folder with a `.swarm/` folder) containing
+    /// the current directory
+    #[clap(long, value_parser)]
+    pub no_project: bool,
+
     /// Change where swarm looks for the configuration file
     #[clap(short, long, overrides_with = "config", env = SWARM_CONFIG_FILE_ENV, value_parser)]
     pub config: Option<PathBuf>,
@@ -81,6 +123,15 @@ pub struct CliArgs {
     /// Specify emitting additional debug information
     #[clap(short, long, value_parser)]
     pub debug: bool,
+
+    /// Print the output of queries (eg. list-sessions or action list-panes) as JSON
+    #[clap(long, value_parser, global = true)]
+    pub json: bool,
+
+    /// The name other users of a shared session know you by, shown in the pane frames of the
+    /// panes you focus and in their status bar
+    #[clap(long, value_parser = validate_user_name)]
+    pub user: Option<String>,
 }
 
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
@@ -127,23 +178,72 @@ pub struct WebCli {
     pub daemonize: bool,
     /// Create a login token for the web interface, will only be displayed once and cannot later be
     /// retrieved. Returns the token name and the token.
-    #[clap(long, value_parser, exclusive(true), display_order = 5)]
+    #[clap(
+        long,
+        value_parser,
+        conflicts_with_all(&["start", "daemonize"]),
+        display_order = 5
+    )]
     pub create_token: bool,
+    /// Make the created login token read-only: clients logged in with it can watch sessions but
+    /// cannot send input or actions to them (same as --scope read-only)
+    #[clap(
+        long,
+        value_parser,
+        requires("create-token"),
+        conflicts_with("scope"),
+        display_order = 6
+    )]
+    pub read_only: bool,
+    /// What the created login token allows: read-only (watch existing sessions), interactive
+    /// (also type into them) or admin (also start new sessions) [default: admin]
+    #[clap(
+        long,
+        arg_enum,
+        value_parser,
+        requires("create-token"),
+        display_order = 7
+    )]
+    pub scope: Option<WebTokenScope>,
+    /// Only allow the created login token to access this session, can be a glob (eg. "work-*")
+    /// and can be given multiple times
+    #[clap(
+        long = "allow-session",
+        value_parser,
+        number_of_values = 1,
+        value_name = "SESSION NAME OR GLOB",
+        requires("create-token"),
+        display_order = 8
+    )]
+    pub allowed_sessions: Vec<String>,
+    /// Make the created login token expire after this long (eg. 12h, 7days)
+    #[clap(
+        long,
+        value_parser = parse_token_expiry,
+        value_name = "DURATION",
+        requires("create-token"),
+        display_order = 9
+    )]
+    pub expires_in: Option<Duration>,
     /// Revoke a login token by its name
     #[clap(
         long,
         value_parser,
         exclusive(true),
         value_name = "TOKEN NAME",
-        display_order = 6
+        display_order = 10
     )]
     pub revoke_token: Option<String>,
     /// Revoke all login tokens
-    #[clap(long, value_parser, exclusive(true), display_order = 7)]
+    #[clap(long, value_parser, exclusive(true), display_order = 11)]
     pub revoke_all_tokens: bool,
-    /// List token names and their creation dates (cannot show actual tokens)
-    #[clap(long, value_parser, exclusive(true), display_order = 8)]
+    /// List token names, their creation dates and their access (cannot show actual tokens)
+    #[clap(long, value_parser, exclusive(true), display_order = 12)]
     pub list_tokens: bool,
+    /// Print the audit log: logins, clients attaching to and detaching from sessions and what
+    /// they did in them
+    #[clap(long, value_parser, exclusive(true), display_order = 13)]
+    pub audit_log: bool,
 }
 
 impl WebCli {
@@ -154,7 +254,15 @@ impl WebCli {
                 || self.create_token
                 || self.revoke_token.is_some()
                 || self.revoke_all_tokens
-                || self.list_tokens)
+                || self.list_tokens
+                || self.audit_log)
+    }
+    pub fn token_scope(&self) -> WebTokenScope {
+        if self.read_only {
+            WebTokenScope::ReadOnly
+        } else {
+            self.scope.unwrap_or_default()
+        }
     }
 }
 
@@ -181,10 +289,50 @@ pub enum Sessions {
         /// List the sessions in reverse order (default is ascending order)
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         reverse: bool,
+
+        /// List the sessions grouped by their tags, or only the sessions tagged with GROUP
+        #[clap(
+            short,
+            long,
+            value_parser,
+            value_name = "GROUP",
+            min_values = 0,
+            max_values = 1
+        )]
+        group: Option<Option<String>>,
     },
     /// List existing plugin aliases
     #[clap(visible_alias = "la")]
     ListAliases,
+    /// List the named snapshots of a session (or of all sessions)
+    ListSnapshots {
+        /// Name of the session whose snapshots to list
+        #[clap(value_parser)]
+        session_name: Option<String>,
+
+        /// Do not add colors and formatting to the list (useful for parsing)
+        #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
+        no_formatting: bool,
+    },
+    /// Start a new session, optionally from a session template
+    New {
+        /// Name of the new session
+        #[clap(value_parser = validate_session)]
+        session_name: Option<String>,
+
+        /// Name of a session template inside the templates folder of the config directory
+        #[clap(short, long, value_parser)]
+        template: Option<String>,
+
+        /// Set a parameter declared in the template, eg. --arg branch=main (can be specified
+        /// multiple times, required parameters that are not set will be prompted for)
+        #[clap(long = "arg", value_name = "NAME=VALUE", number_of_values = 1, value_parser = parse_layout_arg, requires("template"))]
+        args: Vec<(String, String)>,
+
+        /// Tag the session with a group, eg. --tag agents (can be specified multiple times)
+        #[clap(long = "tag", value_name = "GROUP", number_of_values = 1, value_parser)]
+        tags: Vec<String>,
+    },
     /// Attach to a session
     #[clap(visible_alias = "a")]
     Attach {
@@ -211,6 +359,33 @@ pub enum Sessions {
         /// If resurrecting a dead session, immediately run all its commands on startup
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         force_run_commands: bool,
+
+        /// Resurrect the session from one of its named snapshots rather than from its last state
+        #[clap(
+            long,
+            value_parser,
+            value_name = "SNAPSHOT NAME",
+            conflicts_with_all(&["create", "create-background", "index"])
+        )]
+        from_snapshot: Option<String>,
+
+        /// Attach to a session tagged with GROUP, choosing between them if there are several
+        #[clap(
+            short,
+            long,
+            value_parser,
+            value_name = "GROUP",
+            conflicts_with_all(&["session-name", "create", "create-background", "index", "from-snapshot"])
+        )]
+        group: Option<String>,
+
+        /// Attach as a viewer: the session is displayed but input and actions are not sent to it
+        #[clap(
+            long,
+            value_parser,
+            conflicts_with_all(&["create", "create-background", "from-snapshot", "force-run-commands"])
+        )]
+        read_only: bool,
     },
 
     /// Kill a specific session
@@ -219,6 +394,20 @@ pub enum Sessions {
         /// Name of target session
         #[clap(value_parser)]
         target_session: Option<String>,
+
+        /// Kill all the running sessions tagged with GROUP
+        #[clap(
+            short,
+            long,
+            value_parser,
+            value_name = "GROUP",
+            conflicts_with("target-session")
+        )]
+        group: Option<String>,
+
+        /// Automatic yes to prompts
+        #[clap(short, long, value_parser)]
+        yes: bool,
     },
 
     /// Delete a specific session
@@ -251,6 +440,29 @@ pub enum Sessions {
         force: bool,
     },
 
+    /// Export a session (its layout, pane contents and plugin configuration) to a portable bundle
+    ExportSession {
+        /// Name of the session to export
+        #[clap(value_parser)]
+        session_name: String,
+        /// The bundle file to create (eg. workspace.tar.zst)
+        #[clap(short, long, value_parser)]
+        output: PathBuf,
+    },
+
+    /// Import a session bundle created with `export-session` as a resurrectable session
+    ImportSession {
+        /// The bundle file to import
+        #[clap(value_parser)]
+        bundle: PathBuf,
+        /// Rebase all the paths under the root folder of the exported session to this folder
+        #[clap(long, value_parser)]
+        root: Option<PathBuf>,
+        /// Import the session under this name rather than its original one
+        #[clap(short, long, value_parser)]
+        name: Option<String>,
+    },
+
     /// Send actions to a specific session
     #[clap(visible_alias = "ac")]
     #[clap(subcommand)]
@@ -462,6 +674,36 @@ tail -f /tmp/my-live-logfile | swarm pipe --name logs --plugin https://example.c
         #[clap(short('c'), long, value_parser, display_order(4))]
         plugin_configuration: Option<PluginUserConfiguration>,
     },
+    /// Wait for a pane of a session, eg. one started with --headless
+    Wait {
+        /// Name of the session the pane is in
+        #[clap(short, long, value_parser)]
+        session: Option<String>,
+        /// The id of the command pane (as in $ZELLIJ_PANE_ID)
+        #[clap(long, value_parser)]
+        pane_id: u32,
+        /// Wait until the command of the pane exits and exit with its exit status
+        #[clap(long, value_parser, required(true))]
+        until_exit: bool,
+    },
+    /// Stream the events of a session (eg. panes opening or commands exiting) as they happen
+    Subscribe {
+        /// Name of the session to subscribe to
+        #[clap(short, long, value_parser)]
+        session: Option<String>,
+        /// The events to stream, comma separated [default: all of them]
+        #[clap(long, arg_enum, value_parser, value_delimiter(','))]
+        events: Vec<SessionEventType>,
+        /// Print each event as a line of JSON (jsonl) or as text
+        #[clap(long, arg_enum, value_parser, default_value("jsonl"))]
+        format: SessionEventFormat,
+    },
+}
+
+#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionEventFormat {
+    Jsonl,
+    Text,
 }
 
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
@@ -717,6 +959,11 @@ pub enum CliAction {
         #[clap(long, value_parser, requires("layout"))]
         layout_dir: Option<PathBuf>,
 
+        /// Set a parameter declared in the layout with `param`, eg. --layout-arg project=~/api
+        /// (can be specified multiple times)
+        #[clap(long = "layout-arg", value_name = "NAME=VALUE", number_of_values = 1, value_parser = parse_layout_arg, requires("layout"))]
+        layout_args: Vec<(String, String)>,
+
         /// Name of the new tab
         #[clap(short, long, value_parser)]
         name: Option<String>,
@@ -765,6 +1012,11 @@ pub enum CliAction {
     RenameSession {
         name: String,
     },
+    /// Replace the tags (groups) of the current session, omit the tags to clear them
+    SetSessionTags {
+        #[clap(value_parser)]
+        tags: Vec<String>,
+    },
     /// Send data to one or more plugins, launch them if they are not running.
     #[clap(override_usage(
 r#"
@@ -840,7 +1092,12 @@ tail -f /tmp/my-live-logfile | swarm action pipe --name logs --plugin https://ex
         #[clap(short('t'), long, value_parser, display_order(10))]
         plugin_title: Option<String>,
     },
+    /// List the clients attached to the session and the panes they focus
     ListClients,
+    /// List the panes of the session with their titles, commands, cwd and geometry
+    ListPanes,
+    /// List the tabs of the session
+    ListTabs,
     TogglePanePinned,
     /// Stack pane ids
     /// Ids are a space separated list of pane ids.
@@ -874,4 +1131,49 @@ tail -f /tmp/my-live-logfile | swarm action pipe --name logs --plugin https://ex
         #[clap(long)]
         pinned: Option<bool>,
     },
+    /// Save a named snapshot of the current session that can later be restored with `swarm
+    /// attach --from-snapshot <NAME>`, an existing snapshot with the same name is overwritten
+    SnapshotSession {
+        /// The name of the snapshot
+        #[clap(short, long, value_parser)]
+        name: String,
+        /// Also save the scrollback of each pane in the snapshot
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        with_scrollback: bool,
+    },
+    /// Restrict which panes a client (by its id, see `list-clients`) or the web clients of a login
+    /// token may type into, the rest of the session stays usable to them. Without pane ids they may
+    /// not type into any pane. Restricted clients cannot change restrictions.
+    ///
+    /// Example: swarm action restrict-input --token alice -- terminal_1 3
+    RestrictInput {
+        /// The id of the client to restrict, the restriction ends when it detaches
+        #[clap(
+            short,
+            long,
+            value_parser,
+            conflicts_with("token"),
+            required_unless_present("token")
+        )]
+        client: Option<u16>,
+        /// The name of the web login token to restrict, applies to every client that logged in
+        /// with it
+        #[clap(short, long, value_parser)]
+        token: Option<String>,
+        /// Lift the restriction, letting the client type into any pane again
+        #[clap(long, value_parser, takes_value(false), conflicts_with("pane-ids"))]
+        clear: bool,
+        /// The panes the client may type into, eg. terminal_1, plugin_2 or 3 (equivalent to
+        /// terminal_3)
+        #[clap(last(true))]
+        pane_ids: Vec<String>,
+    },
+    /// Send a file to the web clients attached to this session, their browsers download it
+    ///
+    /// Example: swarm action send-file target/release/report.html
+    SendFile {
+        /// The file to send, relative paths are relative to the current directory
+        #[clap(value_parser)]
+        path: PathBuf,
+    },
 }
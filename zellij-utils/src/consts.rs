@@ -25,13 +25,68 @@ pub fn session_info_cache_file_name(session_name: &str) -> PathBuf {
 }
 
 pub fn session_layout_cache_file_name(session_name: &str) -> PathBuf {
-    session_info_folder_for_session(session_name).join("session-layout.kdl")
+    session_info_folder_for_session(session_name).join(SESSION_LAYOUT_FILE_NAME)
+}
+
+pub fn session_tags_file_name(session_name: &str) -> PathBuf {
+    session_info_folder_for_session(session_name).join("session-tags")
 }
 
 pub fn session_info_folder_for_session(session_name: &str) -> PathBuf {
     SWARM_SESSION_INFO_CACHE_DIR.join(session_name)
 }
 
+pub fn session_snapshots_folder_for_session(session_name: &str) -> PathBuf {
+    session_info_folder_for_session(session_name).join(SESSION_SNAPSHOTS_FOLDER_NAME)
+}
+
+pub const SESSION_LAYOUT_FILE_NAME: &str = "session-layout.kdl";
+pub const SESSION_SNAPSHOTS_FOLDER_NAME: &str = "snapshots";
+pub const SESSION_SNAPSHOT_LAYOUT_FILE_NAME: &str = "session-layout.kdl";
+
+pub fn session_snapshot_layout_file_name(
+    session_name: &str,
+    snapshot_name: &str,
+) -> Result<PathBuf, String> {
+    validate_snapshot_name(snapshot_name)?;
+    Ok(session_snapshots_folder_for_session(session_name)
+        .join(snapshot_name)
+        .join(SESSION_SNAPSHOT_LAYOUT_FILE_NAME))
+}
+
+fn is_plain_folder_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains(&['/', '\\'][..])
+        && !name.chars().any(|c| c.is_control())
+}
+
+/// Snapshots are folders named after them, so their names must be plain folder names (eg. `..`
+/// would be the session's own info folder)
+pub fn validate_snapshot_name(snapshot_name: &str) -> Result<(), String> {
+    if is_plain_folder_name(snapshot_name) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid snapshot name: \"{}\", snapshot names cannot be empty, start with a dot or contain slashes",
+            snapshot_name
+        ))
+    }
+}
+
+/// The same goes for the session info folders sessions are written to outside of a running
+/// session (eg. when importing them), `..` would be the session info cache dir's parent
+pub fn validate_session_folder_name(session_name: &str) -> Result<(), String> {
+    if is_plain_folder_name(session_name) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid session name: \"{}\", session names cannot be empty, start with a dot or contain slashes",
+            session_name
+        ))
+    }
+}
+
 pub fn create_config_and_cache_folders() {
     if let Err(e) = std::fs::create_dir_all(&SWARM_CACHE_DIR.as_path()) {
         log::error!("Failed to create cache dir: {:?}", e);
@@ -66,6 +121,7 @@ lazy_static! {
         .join(format!("{}", Uuid::new_v4()));
     pub static ref SWARM_PLUGIN_PERMISSIONS_CACHE: PathBuf =
         SWARM_CACHE_DIR.join("permissions.kdl");
+    pub static ref SWARM_TRUSTED_PROJECTS_CACHE: PathBuf = SWARM_CACHE_DIR.join("trusted_projects");
     pub static ref SWARM_SESSION_INFO_CACHE_DIR: PathBuf =
         SWARM_CACHE_DIR.join(VERSION).join("session_info");
     pub static ref SWARM_STDIN_CACHE_FILE: PathBuf =
@@ -159,6 +215,7 @@ mod unix_only {
         pub static ref SWARM_TMP_DIR: PathBuf = temp_dir().join(format!("swarm-{}", *UID));
         pub static ref SWARM_TMP_LOG_DIR: PathBuf = SWARM_TMP_DIR.join("swarm-log");
         pub static ref SWARM_TMP_LOG_FILE: PathBuf = SWARM_TMP_LOG_DIR.join("swarm.log");
+        pub static ref SWARM_WEB_UPLOADS_DIR: PathBuf = SWARM_TMP_DIR.join("web_uploads");
         pub static ref SWARM_SOCK_DIR: PathBuf = {
             let mut ipc_dir = envs::get_socket_dir().map_or_else(
                 |_| {
@@ -944,6 +944,9 @@ pub enum Event {
     FailedToStartWebServer(String),
     BeforeClose,
     InterceptedKeyPress(KeyWithModifier),
+    ConnectionOpened(u32, Context),  // u32 - connection id
+    ConnectionMessage(u32, Vec<u8>), // u32 - connection id, Vec<u8> - received data
+    ConnectionClosed(u32, Option<String>, Context), // u32 - connection id, Option<String> - error
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, EnumDiscriminants, ToString, Serialize, Deserialize)]
@@ -984,6 +987,8 @@ pub enum Permission {
     FullHdAccess,
     StartWebServer,
     InterceptInput,
+    NetworkAccess,
+    LocalSocketAccess,
 }
 
 impl PermissionType {
@@ -1010,6 +1015,54 @@ impl PermissionType {
                 "Start a local web server to serve Swarm sessions".to_owned()
             },
             PermissionType::InterceptInput => "Intercept Input (keyboard & mouse)".to_owned(),
+            PermissionType::NetworkAccess => {
+                "Open network connections (TCP and WebSocket)".to_owned()
+            },
+            PermissionType::LocalSocketAccess => "Connect to local (Unix) sockets".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum PathAccess {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl PathAccess {
+    pub fn covers(&self, other: &PathAccess) -> bool {
+        match self {
+            PathAccess::ReadWrite => true,
+            PathAccess::ReadOnly => other == &PathAccess::ReadOnly,
+        }
+    }
+}
+
+/// Access to a specific folder on the host, mounted in the plugin's filesystem under the same
+/// (absolute) path
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct PathPermission {
+    pub path: PathBuf,
+    pub access: PathAccess,
+}
+
+impl PathPermission {
+    pub fn read_only(path: impl Into<PathBuf>) -> Self {
+        PathPermission {
+            path: path.into(),
+            access: PathAccess::ReadOnly,
+        }
+    }
+    pub fn read_write(path: impl Into<PathBuf>) -> Self {
+        PathPermission {
+            path: path.into(),
+            access: PathAccess::ReadWrite,
+        }
+    }
+    pub fn display_name(&self) -> String {
+        match self.access {
+            PathAccess::ReadOnly => format!("Read files in {}", self.path.display()),
+            PathAccess::ReadWrite => format!("Read and write files in {}", self.path.display()),
         }
     }
 }
@@ -1018,11 +1071,20 @@ impl PermissionType {
 pub struct PluginPermission {
     pub name: String,
     pub permissions: Vec<PermissionType>,
+    pub paths: Vec<PathPermission>,
 }
 
 impl PluginPermission {
     pub fn new(name: String, permissions: Vec<PermissionType>) -> Self {
-        PluginPermission { name, permissions }
+        PluginPermission {
+            name,
+            permissions,
+            paths: vec![],
+        }
+    }
+    pub fn with_paths(mut self, paths: Vec<PathPermission>) -> Self {
+        self.paths = paths;
+        self
     }
 }
 
@@ -1586,6 +1648,44 @@ pub struct ModeInfo {
     pub web_server_ip: Option<IpAddr>,
     pub web_server_port: Option<u16>,
     pub web_server_capability: Option<bool>,
+    /// every client connected to the session, for showing who is around
+    pub connected_users: Vec<ConnectedUser>,
+}
+
+/// A client connected to the session, named if its user gave a name (with `--user` or their web
+/// login token)
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectedUser {
+    pub client_id: ClientId,
+    pub name: Option<String>,
+    pub is_current_client: bool,
+}
+
+impl ConnectedUser {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("user {}", self.client_id))
+    }
+}
+
+/// Who an input restriction applies to, identified by what the server assigned them rather than by
+/// a name they chose for themselves
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputRestrictionTarget {
+    /// A single connected client
+    Client(ClientId),
+    /// Every web client that logged in with this token, including ones that connect later
+    Token(String),
+}
+
+impl fmt::Display for InputRestrictionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputRestrictionTarget::Client(client_id) => write!(f, "client {}", client_id),
+            InputRestrictionTarget::Token(token_name) => write!(f, "token \"{}\"", token_name),
+        }
+    }
 }
 
 impl ModeInfo {
@@ -1635,6 +1735,43 @@ pub struct SessionInfo {
     pub web_clients_allowed: bool,
     pub web_client_count: usize,
     pub tab_history: BTreeMap<ClientId, Vec<usize>>,
+    pub tags: Vec<String>,
+    pub last_activity: Option<u64>, // seconds since the unix epoch of the last pane output or input
+}
+
+/// A named restore point of a session, taken with `swarm action snapshot-session`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionSnapshot {
+    pub session_name: String,
+    pub name: String,
+    pub created: Duration, // time elapsed since the snapshot was taken
+    pub layout_path: String,
+    pub layout: String, // the serialized session layout, as it would be used for resurrection
+}
+
+/// A recipe for new sessions, read from the `templates` folder of the configuration folder
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    pub layout: Option<String>,
+    pub cwd: Option<String>,
+    pub params: Vec<SessionTemplateParam>, // values to prompt for when starting the session
+    pub mcp_servers: Vec<String>,
+    pub agents: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionTemplateParam {
+    pub name: String,
+    pub description: Option<String>,
+    pub default: Option<String>,
+}
+
+impl SessionTemplateParam {
+    pub fn is_required(&self) -> bool {
+        self.default.is_none()
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -1851,6 +1988,7 @@ pub struct ClientInfo {
     pub pane_id: PaneId,
     pub running_command: String,
     pub is_current_client: bool,
+    pub is_read_only: bool,
 }
 
 impl ClientInfo {
@@ -1859,12 +1997,14 @@ impl ClientInfo {
         pane_id: PaneId,
         running_command: String,
         is_current_client: bool,
+        is_read_only: bool,
     ) -> Self {
         ClientInfo {
             client_id,
             pane_id,
             running_command,
             is_current_client,
+            is_read_only,
         }
     }
 }
@@ -2134,6 +2274,13 @@ pub enum HttpVerb {
     Delete,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionKind {
+    Tcp,        // address in the form of host:port
+    UnixSocket, // address is a path to the socket
+    WebSocket,  // address is a ws:// url
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PipeSource {
     Cli(String), // String is the pipe_id of the CLI pipe (used for blocking/unblocking)
@@ -2351,6 +2498,222 @@ impl FromStr for WebSharing {
     }
 }
 
+/// What the holder of a web login token is allowed to do
+#[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WebTokenScope {
+    /// Watch sessions without sending them any input
+    ReadOnly,
+    /// Type into and navigate existing sessions
+    Interactive,
+    /// Also start new sessions and resurrect exited ones
+    Admin,
+}
+
+impl Default for WebTokenScope {
+    fn default() -> Self {
+        Self::Admin
+    }
+}
+
+impl WebTokenScope {
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, WebTokenScope::ReadOnly)
+    }
+    pub fn can_create_sessions(&self) -> bool {
+        matches!(self, WebTokenScope::Admin)
+    }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebTokenScope::ReadOnly => "read-only",
+            WebTokenScope::Interactive => "interactive",
+            WebTokenScope::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for WebTokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for WebTokenScope {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read-only" | "ReadOnly" => Ok(Self::ReadOnly),
+            "interactive" | "Interactive" => Ok(Self::Interactive),
+            "admin" | "Admin" => Ok(Self::Admin),
+            _ => Err(format!("No such scope: {}", s)),
+        }
+    }
+}
+
+/// A web login token as listed by `list_web_login_tokens`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WebLoginToken {
+    pub name: String,
+    pub created_at: String,
+    pub scope: WebTokenScope,
+    pub allowed_sessions: Vec<String>, // session names or globs, empty if all are allowed
+    pub expires_at: Option<String>,
+}
+
+/// Something that happened to a session or the web server because of a web client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebAuditEventKind {
+    Login,
+    LoginFailed,
+    TokenUsed,
+    ClientAttached,
+    ClientDetached,
+    SessionCreated,
+    SessionKilled,
+    Action,
+    FileUploaded,
+    FileDownloaded,
+}
+
+/// An entry of the web audit log, as read by `read_web_audit_log`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WebAuditEvent {
+    pub timestamp: u64, // seconds since the unix epoch
+    pub kind: WebAuditEventKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>, // the web client id, or the session's client id for actions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+impl WebAuditEvent {
+    pub fn new(timestamp: u64, kind: WebAuditEventKind) -> Self {
+        WebAuditEvent {
+            timestamp,
+            kind,
+            session_name: None,
+            token_name: None,
+            client_id: None,
+            remote_address: None,
+            details: None,
+        }
+    }
+    /// eg. 2025-06-01T12:00:00Z
+    pub fn formatted_timestamp(&self) -> String {
+        humantime::format_rfc3339_seconds(
+            std::time::UNIX_EPOCH + Duration::from_secs(self.timestamp),
+        )
+        .to_string()
+    }
+    /// eg. `client_attached session: work, token: ci, client: 6d1c...`
+    pub fn describe(&self) -> String {
+        let fields: Vec<String> = [
+            ("session", &self.session_name),
+            ("token", &self.token_name),
+            ("client", &self.client_id),
+            ("from", &self.remote_address),
+        ]
+        .iter()
+        .filter_map(|(name, value)| value.as_ref().map(|v| format!("{}: {}", name, v)))
+        .chain(self.details.iter().cloned())
+        .collect();
+        if fields.is_empty() {
+            self.kind.to_string()
+        } else {
+            format!("{} {}", self.kind, fields.join(", "))
+        }
+    }
+}
+
+/// The kinds of session events `swarm subscribe` can stream
+#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum SessionEventType {
+    PaneOpened,
+    PaneClosed,
+    /// A command pane's command exited and the pane is held open
+    CommandExited,
+    /// A tab was opened, closed, renamed or otherwise changed
+    TabUpdate,
+}
+
+/// Something that happened in a session, as streamed to `swarm subscribe` clients - the payloads
+/// are the same ones plugins receive in the `PaneUpdate`, `PaneClosed`, `CommandPaneExited` and
+/// `TabUpdate` events
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum SessionEvent {
+    PaneOpened {
+        tab_position: usize,
+        pane: PaneInfo,
+    },
+    PaneClosed {
+        pane_id: PaneId,
+    },
+    CommandExited {
+        pane_id: u32, // terminal id
+        exit_status: Option<i32>,
+    },
+    TabUpdate {
+        tabs: Vec<TabInfo>,
+    },
+}
+
+impl SessionEvent {
+    pub fn event_type(&self) -> SessionEventType {
+        match self {
+            SessionEvent::PaneOpened { .. } => SessionEventType::PaneOpened,
+            SessionEvent::PaneClosed { .. } => SessionEventType::PaneClosed,
+            SessionEvent::CommandExited { .. } => SessionEventType::CommandExited,
+            SessionEvent::TabUpdate { .. } => SessionEventType::TabUpdate,
+        }
+    }
+    /// eg. `pane-opened terminal_3 tab: 0, title: cargo build`
+    pub fn describe(&self) -> String {
+        let stringify_pane_id = |pane_id: &PaneId| match pane_id {
+            PaneId::Terminal(id) => format!("terminal_{}", id),
+            PaneId::Plugin(id) => format!("plugin_{}", id),
+        };
+        let details = match self {
+            SessionEvent::PaneOpened { tab_position, pane } => {
+                let pane_id = if pane.is_plugin {
+                    PaneId::Plugin(pane.id)
+                } else {
+                    PaneId::Terminal(pane.id)
+                };
+                format!(
+                    "{} tab: {}, title: {}",
+                    stringify_pane_id(&pane_id),
+                    tab_position,
+                    pane.title
+                )
+            },
+            SessionEvent::PaneClosed { pane_id } => stringify_pane_id(pane_id),
+            SessionEvent::CommandExited {
+                pane_id,
+                exit_status,
+            } => match exit_status {
+                Some(exit_status) => format!("terminal_{} exit status: {}", pane_id, exit_status),
+                None => format!("terminal_{}", pane_id),
+            },
+            SessionEvent::TabUpdate { tabs } => tabs
+                .iter()
+                .map(|tab| tab.name.clone())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        format!("{} {}", self.event_type(), details)
+    }
+}
+
 type Context = BTreeMap<String, String>;
 
 #[derive(Debug, Clone, EnumDiscriminants, ToString)]
@@ -2525,12 +2888,24 @@ pub enum PluginCommand {
     EmbedMultiplePanes(Vec<PaneId>),
     QueryWebServerStatus,
     SetSelfMouseSelectionSupport(bool),
-    GenerateWebLoginToken(Option<String>), // String -> optional token label
-    RevokeWebLoginToken(String),           // String -> token id (provided name or generated id)
+    GenerateWebLoginToken(Option<String>, WebTokenScope), // String -> optional token label
+    RevokeWebLoginToken(String), // String -> token id (provided name or generated id)
     ListWebLoginTokens,
     RevokeAllWebLoginTokens,
     RenameWebLoginToken(String, String), // (original_name, new_name)
     InterceptKeyPresses,
     ClearKeyPressesIntercepts,
     ReplacePaneWithExistingPane(PaneId, PaneId), // (pane id to replace, pane id of existing)
+    OpenConnection(ConnectionKind, String, Context), // String -> address
+    SendToConnection(u32, Vec<u8>),              // u32 -> connection id
+    CloseConnection(u32),                        // u32 -> connection id
+    RequestPathPermissions(Vec<PathPermission>),
+    ListSessionSnapshots,
+    ListSessionTemplates,
+    ResolveSessionTemplate(String, BTreeMap<String, String>), // template name, param values
+    UpdateWebLoginToken(String, WebTokenScope, Vec<String>),  // token name, scope, allowed
+    // sessions (empty for all)
+    SetWebLoginTokenExpiry(String, Option<Duration>), // token name, time from now until expiry
+    // (None for never)
+    ReadWebAuditLog,
 }
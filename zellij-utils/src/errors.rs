@@ -350,6 +350,7 @@ pub enum ScreenContext {
     NewInPlacePluginPane,
     SerializeLayoutForResurrection,
     RenameSession,
+    SetSessionTags,
     DumpLayoutToPlugin,
     ListClientsMetadata,
     Reconfigure,
@@ -388,6 +389,13 @@ pub enum ScreenContext {
     InterceptKeyPresses,
     ClearKeyPressesIntercepts,
     ReplacePaneWithExistingPane,
+    SnapshotSession,
+    SetClientIdentity,
+    RestrictInput,
+    WaitForPaneExit,
+    ListPanes,
+    ListTabs,
+    SubscribeToSessionEvents,
 }
 
 /// Stack call representations corresponding to the different types of [`PtyInstruction`]s.
@@ -412,6 +420,9 @@ pub enum PtyContext {
     ListClientsMetadata,
     Reconfigure,
     ListClientsToPlugin,
+    SnapshotSession,
+    ReceiveFile,
+    ListPanes,
     Exit,
 }
 
@@ -453,6 +464,7 @@ pub enum PluginContext {
     ChangePluginHostDir,
     WebServerStarted,
     FailedToStartWebServer,
+    PluginWasmFileChanged,
 }
 
 /// Stack call representations corresponding to the different types of [`ClientInstruction`]s.
@@ -511,6 +523,10 @@ pub enum ServerContext {
     WebServerStarted,
     FailedToStartWebServer,
     SendWebClientsForbidden,
+    SendFileToWebClients,
+    PaneExited,
+    SessionEvent,
+    LayoutApplied,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -529,14 +545,22 @@ pub enum BackgroundJobContext {
     StopPluginLoadingAnimation,
     ReadAllSessionInfosOnMachine,
     ReportSessionInfo,
+    ReportSessionActivity,
     ReportLayoutInfo,
     RunCommand,
     WebRequest,
+    OpenConnection,
+    SendToConnection,
+    CloseConnection,
+    ClosePluginConnections,
+    ConnectionEnded,
     ReportPluginList,
     ListWebSessions,
     RenderToClients,
     HighlightPanesWithMessage,
     QuerySwarmWebServerStatus,
+    WriteSessionSnapshot,
+    MoveUploadedFile,
     Exit,
 }
 
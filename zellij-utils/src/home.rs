@@ -84,6 +84,10 @@ pub fn default_layout_dir() -> Option<PathBuf> {
     find_default_config_dir().map(|dir| dir.join("layouts"))
 }
 
+pub fn get_template_dir(config_dir: Option<PathBuf>) -> Option<PathBuf> {
+    config_dir.map(|dir| dir.join("templates"))
+}
+
 pub fn get_theme_dir(config_dir: Option<PathBuf>) -> Option<PathBuf> {
     config_dir.map(|dir| dir.join("themes"))
 }
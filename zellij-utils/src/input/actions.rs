@@ -6,8 +6,9 @@ use super::layout::{
     SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
 };
 use crate::cli::CliAction;
+use crate::consts::validate_snapshot_name;
 use crate::data::{Direction, KeyWithModifier, PaneId, Resize};
-use crate::data::{FloatingPaneCoordinates, InputMode};
+use crate::data::{FloatingPaneCoordinates, InputMode, InputRestrictionTarget, SessionEventType};
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::mouse::MouseEvent;
@@ -128,7 +129,7 @@ pub enum Action {
     /// Dumps the screen to a file
     DumpScreen(String, bool),
     /// Dumps
-    DumpLayout,
+    DumpLayout(bool), // bool -> as JSON
     /// Scroll up in focus pane.
     EditScrollback,
     ScrollUp,
@@ -241,7 +242,7 @@ pub enum Action {
     PreviousSwapLayout,
     NextSwapLayout,
     /// Query all tab names
-    QueryTabNames,
+    QueryTabNames(bool), // bool -> as JSON
     /// Open a new tiled (embedded, non-floating) plugin pane
     NewTiledPluginPane(RunPluginOrAlias, Option<String>, bool, Option<PathBuf>), // String is an optional name, bool is
     // skip_cache, Option<PathBuf> is cwd
@@ -267,6 +268,8 @@ pub enum Action {
     BreakPaneRight,
     BreakPaneLeft,
     RenameSession(String),
+    /// Replace the tags (groups) of the session
+    SetSessionTags(Vec<String>),
     CliPipe {
         pipe_id: String,
         name: Option<String>,
@@ -295,12 +298,27 @@ pub enum Action {
         cwd: Option<PathBuf>,
         pane_title: Option<String>,
     },
-    ListClients,
+    ListClients(bool), // bool -> as JSON
+    ListPanes(bool),   // bool -> as JSON
+    ListTabs(bool),    // bool -> as JSON
     TogglePanePinned,
     StackPanes(Vec<PaneId>),
     ChangeFloatingPaneCoordinates(PaneId, FloatingPaneCoordinates),
     TogglePaneInGroup,
     ToggleGroupMarking,
+    /// Save a named snapshot of the session, optionally with the pane scrollback
+    SnapshotSession(String, bool),
+    /// Only let the client(s) type into these panes, `None` lifts the restriction
+    RestrictInput(InputRestrictionTarget, Option<Vec<PaneId>>),
+    /// Have the web clients attached to this session download this file
+    SendFile(PathBuf),
+    /// Move a file a web client uploaded (staged at this path) into the cwd of the focused pane,
+    /// under the given file name
+    ReceiveFile(PathBuf, String),
+    /// Reply once the command pane with this terminal id exits, with its exit status
+    WaitForPaneExit(u32),
+    /// Stream these session events to the client until it disconnects, all of them if empty
+    SubscribeToSessionEvents(Vec<SessionEventType>),
 }
 
 impl Action {
@@ -314,6 +332,75 @@ impl Action {
         }
     }
 
+    /// Whether a read-only (view-only) client may perform this action. These only change what
+    /// the client itself is looking at, everything else is dropped by the server. In mirrored
+    /// sessions the screen also drops the ones moving the focus, since it is shared.
+    pub fn is_allowed_for_read_only_clients(&self) -> bool {
+        match self {
+            Action::NoOp
+            | Action::Detach
+            | Action::SwitchToMode(..)
+            | Action::FocusNextPane
+            | Action::FocusPreviousPane
+            | Action::MoveFocus(..)
+            | Action::MoveFocusOrTab(..)
+            | Action::GoToNextTab
+            | Action::GoToPreviousTab
+            | Action::GoToTab(..)
+            | Action::ToggleTab
+            | Action::QueryTabNames(..)
+            | Action::ListClients(..)
+            | Action::ListPanes(..)
+            | Action::ListTabs(..)
+            | Action::WaitForPaneExit(..)
+            | Action::SubscribeToSessionEvents(..)
+            | Action::Copy => true,
+            Action::GoToTabName(_, create) => !create,
+            _ => false,
+        }
+    }
+
+    /// Whether this action is recorded in the web audit log when a web client issues it. Typing,
+    /// mouse input and scrolling are left out, both for their volume and because they can carry
+    /// anything the user types (eg. passwords).
+    pub fn is_audited(&self) -> bool {
+        match self {
+            Action::Write(..)
+            | Action::WriteChars(..)
+            | Action::MouseEvent(..)
+            | Action::ScrollUp
+            | Action::ScrollUpAt(..)
+            | Action::ScrollDown
+            | Action::ScrollDownAt(..)
+            | Action::ScrollToBottom
+            | Action::ScrollToTop
+            | Action::PageScrollUp
+            | Action::PageScrollDown
+            | Action::HalfPageScrollUp
+            | Action::HalfPageScrollDown
+            | Action::PaneNameInput(..)
+            | Action::TabNameInput(..)
+            | Action::SearchInput(..)
+            | Action::Search(..)
+            | Action::SearchToggleOption(..)
+            // the web server records uploads itself, with their size
+            | Action::ReceiveFile(..) => false,
+            _ => !self.is_allowed_for_read_only_clients(),
+        }
+    }
+
+    /// The same action, answering with JSON rather than text if it is a query
+    pub fn with_json_output(self) -> Self {
+        match self {
+            Action::DumpLayout(_) => Action::DumpLayout(true),
+            Action::QueryTabNames(_) => Action::QueryTabNames(true),
+            Action::ListClients(_) => Action::ListClients(true),
+            Action::ListPanes(_) => Action::ListPanes(true),
+            Action::ListTabs(_) => Action::ListTabs(true),
+            action => action,
+        }
+    }
+
     pub fn actions_from_cli(
         cli_action: CliAction,
         get_current_dir: Box<dyn Fn() -> PathBuf>,
@@ -335,7 +422,7 @@ impl Action {
                 path.as_os_str().to_string_lossy().into(),
                 full,
             )]),
-            CliAction::DumpLayout => Ok(vec![Action::DumpLayout]),
+            CliAction::DumpLayout => Ok(vec![Action::DumpLayout(false)]),
             CliAction::EditScrollback => Ok(vec![Action::EditScrollback]),
             CliAction::ScrollUp => Ok(vec![Action::ScrollUp]),
             CliAction::ScrollDown => Ok(vec![Action::ScrollDown]),
@@ -530,6 +617,7 @@ impl Action {
                 name,
                 layout,
                 layout_dir,
+                layout_args,
                 cwd,
             } => {
                 let current_dir = get_current_dir();
@@ -558,10 +646,13 @@ impl Action {
                             None,
                         )
                     } else {
-                        Layout::stringified_from_path_or_default(Some(&layout_path), layout_dir)
-                            .map_err(|e| format!("Failed to load layout: {}", e))?
+                        Layout::stringified_from_path_or_default(
+                            Some(&layout_path),
+                            layout_dir.clone(),
+                        )
+                        .map_err(|e| format!("Failed to load layout: {}", e))?
                     };
-                    let mut layout = Layout::from_str(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd).map_err(|e| {
+                    let mut layout = Layout::from_str(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd, layout_args.into_iter().collect(), layout_dir).map_err(|e| {
                         let stringified_error = match e {
                             ConfigError::KdlError(kdl_error) => {
                                 let error = kdl_error.add_src(layout_path.as_path().as_os_str().to_string_lossy().to_string(), String::from(raw_layout));
@@ -656,7 +747,7 @@ impl Action {
             },
             CliAction::PreviousSwapLayout => Ok(vec![Action::PreviousSwapLayout]),
             CliAction::NextSwapLayout => Ok(vec![Action::NextSwapLayout]),
-            CliAction::QueryTabNames => Ok(vec![Action::QueryTabNames]),
+            CliAction::QueryTabNames => Ok(vec![Action::QueryTabNames(false)]),
             CliAction::StartOrReloadPlugin { url, configuration } => {
                 let current_dir = get_current_dir();
                 let run_plugin_or_alias = RunPluginOrAlias::from_url(
@@ -713,6 +804,7 @@ impl Action {
                 )])
             },
             CliAction::RenameSession { name } => Ok(vec![Action::RenameSession(name)]),
+            CliAction::SetSessionTags { tags } => Ok(vec![Action::SetSessionTags(tags)]),
             CliAction::Pipe {
                 name,
                 payload,
@@ -748,7 +840,9 @@ impl Action {
                     skip_cache,
                 }])
             },
-            CliAction::ListClients => Ok(vec![Action::ListClients]),
+            CliAction::ListClients => Ok(vec![Action::ListClients(false)]),
+            CliAction::ListPanes => Ok(vec![Action::ListPanes(false)]),
+            CliAction::ListTabs => Ok(vec![Action::ListTabs(false)]),
             CliAction::TogglePanePinned => Ok(vec![Action::TogglePanePinned]),
             CliAction::StackPanes { pane_ids } => {
                 let mut malformed_ids = vec![];
@@ -800,6 +894,61 @@ impl Action {
                     }
                 }
             },
+            CliAction::SnapshotSession {
+                name,
+                with_scrollback,
+            } => {
+                validate_snapshot_name(&name)?;
+                Ok(vec![Action::SnapshotSession(name, with_scrollback)])
+            },
+            CliAction::RestrictInput {
+                client,
+                token,
+                clear,
+                pane_ids,
+            } => {
+                let target = match (client, token) {
+                    (Some(client_id), _) => InputRestrictionTarget::Client(client_id),
+                    (None, Some(token_name)) => InputRestrictionTarget::Token(token_name),
+                    (None, None) => {
+                        return Err("Either a client id or a token name is required".to_owned())
+                    },
+                };
+                if clear {
+                    return Ok(vec![Action::RestrictInput(target, None)]);
+                }
+                let mut malformed_ids = vec![];
+                let pane_ids = pane_ids
+                    .iter()
+                    .filter_map(
+                        |stringified_pane_id| match PaneId::from_str(stringified_pane_id) {
+                            Ok(pane_id) => Some(pane_id),
+                            Err(_e) => {
+                                malformed_ids.push(stringified_pane_id.to_owned());
+                                None
+                            },
+                        },
+                    )
+                    .collect();
+                if !malformed_ids.is_empty() {
+                    Err(
+                        format!(
+                            "Malformed pane ids: {}, expecting a space separated list of either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)",
+                            malformed_ids.join(", ")
+                        )
+                    )
+                } else {
+                    Ok(vec![Action::RestrictInput(target, Some(pane_ids))])
+                }
+            },
+            CliAction::SendFile { path } => {
+                let path = get_current_dir().join(path);
+                if path.is_file() {
+                    Ok(vec![Action::SendFile(path)])
+                } else {
+                    Err(format!("No such file: {}", path.display()))
+                }
+            },
         }
     }
     pub fn launches_plugin(&self, plugin_url: &str) -> bool {
@@ -47,7 +47,10 @@ pub struct KdlError {
 
 impl KdlError {
     pub fn add_src(mut self, src_name: String, src_input: String) -> Self {
-        self.src = Some(NamedSource::new(src_name, src_input));
+        // errors coming from imported layout files already carry their own source
+        if self.src.is_none() {
+            self.src = Some(NamedSource::new(src_name, src_input));
+        }
         self
     }
 }
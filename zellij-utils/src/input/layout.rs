@@ -17,7 +17,7 @@ use crate::{
         command::RunCommand,
         config::{Config, ConfigError},
     },
-    pane_size::{Constraint, Dimension, PaneGeom},
+    pane_size::{Constraint, Dimension, PaneGeom, Size},
     setup::{self},
 };
 #[cfg(not(target_family = "wasm"))]
@@ -239,6 +239,8 @@ pub enum Run {
     Command(RunCommand),
     EditFile(PathBuf, Option<usize>, Option<PathBuf>), // TODO: merge this with TerminalAction::OpenFile
     Cwd(PathBuf),
+    Shell(Option<PathBuf>, BTreeMap<String, String>), // the default shell with an optional cwd and
+                                                      // additional environment variables
 }
 
 impl Run {
@@ -247,7 +249,7 @@ impl Run {
         // TODO: reconsider the way we parse command/edit/plugin pane_templates from layouts to prevent this
         // madness
         // TODO: handle Plugin variants once there's a need
-        match (base, other) {
+        let mut merged = match (base, other) {
             (Some(Run::Command(base_run_command)), Some(Run::Command(other_run_command))) => {
                 let mut merged = other_run_command.clone();
                 if merged.cwd.is_none() && base_run_command.cwd.is_some() {
@@ -263,13 +265,26 @@ impl Run {
                 merged.cwd = Some(other_cwd.clone());
                 Some(Run::Command(merged))
             },
-            (Some(Run::Cwd(base_cwd)), Some(Run::Command(other_command))) => {
+            (Some(Run::Command(base_run_command)), Some(Run::Shell(other_cwd, other_env))) => {
+                let mut merged = base_run_command.clone();
+                if other_cwd.is_some() {
+                    merged.cwd = other_cwd.clone();
+                }
+                merged.env.extend(other_env.clone());
+                Some(Run::Command(merged))
+            },
+            (Some(Run::Cwd(base_cwd)), Some(Run::Command(other_command)))
+            | (Some(Run::Shell(Some(base_cwd), _)), Some(Run::Command(other_command))) => {
                 let mut merged = other_command.clone();
                 if merged.cwd.is_none() {
                     merged.cwd = Some(base_cwd.clone());
                 }
                 Some(Run::Command(merged))
             },
+            (Some(Run::Cwd(base_cwd)), Some(Run::Shell(None, other_env)))
+            | (Some(Run::Shell(Some(base_cwd), _)), Some(Run::Shell(None, other_env))) => {
+                Some(Run::Shell(Some(base_cwd.clone()), other_env.clone()))
+            },
             (
                 Some(Run::Command(base_run_command)),
                 Some(Run::EditFile(file_to_edit, line_number, edit_cwd)),
@@ -297,7 +312,12 @@ impl Run {
             (Some(base), _) => Some(base.clone()),
             (None, Some(other)) => Some(other.clone()),
             (None, None) => None,
+        };
+        // environment variables of the base are kept unless the other overrides them
+        if let (Some(base), Some(merged)) = (base, merged.as_mut()) {
+            merged.add_env(&base.get_env());
         }
+        merged
     }
     pub fn add_cwd(&mut self, cwd: &PathBuf) {
         match self {
@@ -323,11 +343,47 @@ impl Run {
             Run::Cwd(path) => {
                 *path = cwd.join(&path);
             },
+            Run::Shell(shell_cwd, _env) => match shell_cwd.as_mut() {
+                Some(shell_cwd) => {
+                    *shell_cwd = cwd.join(&shell_cwd);
+                },
+                None => {
+                    let _ = shell_cwd.insert(cwd.clone());
+                },
+            },
             Run::Plugin(run_plugin_or_alias) => {
                 run_plugin_or_alias.add_initial_cwd(&cwd);
             },
         }
     }
+    pub fn add_env(&mut self, env: &BTreeMap<String, String>) {
+        // adds environment variables to terminal panes (except for editors), variables this
+        // instruction already has take precedence
+        if env.is_empty() {
+            return;
+        }
+        match self {
+            Run::Command(run_command) => {
+                for (key, value) in env {
+                    run_command
+                        .env
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+            },
+            Run::Shell(_cwd, shell_env) => {
+                for (key, value) in env {
+                    shell_env
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+            },
+            Run::Cwd(cwd) => {
+                *self = Run::Shell(Some(cwd.clone()), env.clone());
+            },
+            Run::EditFile(..) | Run::Plugin(..) => {},
+        }
+    }
     pub fn add_args(&mut self, args: Option<Vec<String>>) {
         // overrides the args of a Run::Command if they are Some
         // and not empty
@@ -363,12 +419,19 @@ impl Run {
             (Some(Run::Command(..)), Some(Run::Command(..))) => true,
             (Some(Run::EditFile(..)), Some(Run::EditFile(..))) => true,
             (Some(Run::Cwd(..)), Some(Run::Cwd(..))) => true,
+            (Some(Run::Cwd(..)), Some(Run::Shell(..)))
+            | (Some(Run::Shell(..)), Some(Run::Cwd(..)))
+            | (Some(Run::Shell(..)), Some(Run::Shell(..))) => true,
             _ => false,
         }
     }
     pub fn is_terminal(run: &Option<Run>) -> bool {
         match run {
-            Some(Run::Command(..)) | Some(Run::EditFile(..)) | Some(Run::Cwd(..)) | None => true,
+            Some(Run::Command(..))
+            | Some(Run::EditFile(..))
+            | Some(Run::Cwd(..))
+            | Some(Run::Shell(..))
+            | None => true,
             _ => false,
         }
     }
@@ -378,6 +441,14 @@ impl Run {
             Run::Command(run_command) => run_command.cwd.clone(),
             Run::EditFile(_file, _line_num, cwd) => cwd.clone(),
             Run::Cwd(cwd) => Some(cwd.clone()),
+            Run::Shell(cwd, _env) => cwd.clone(),
+        }
+    }
+    pub fn get_env(&self) -> BTreeMap<String, String> {
+        match self {
+            Run::Command(run_command) => run_command.env.clone(),
+            Run::Shell(_cwd, env) => env.clone(),
+            _ => BTreeMap::new(),
         }
     }
     pub fn get_run_plugin(&self) -> Option<RunPlugin> {
@@ -537,6 +608,14 @@ impl PluginUserConfiguration {
             self.0.insert(key.to_owned(), value.clone());
         }
     }
+    /// Whether the plugin should be reloaded whenever its wasm file changes on disk (eg.
+    /// `_dev_watch true` in its configuration)
+    pub fn dev_watch(&self) -> bool {
+        self.0
+            .get("_dev_watch")
+            .map(|v| v == "true")
+            .unwrap_or(false)
+    }
 }
 
 impl FromStr for PluginUserConfiguration {
@@ -665,6 +744,119 @@ impl Display for LayoutConstraint {
     }
 }
 
+/// A single predicate of a `when` block in a layout, evaluated once when the layout is loaded.
+/// All the predicates of one `when` block must hold for the node containing it to be kept.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutCondition {
+    FileExists(PathBuf), // relative paths are resolved against the layout cwd
+    EnvSet(String),
+    Hostname(String),
+    MinCols(usize),
+    MaxCols(usize),
+    MinRows(usize),
+    MaxRows(usize),
+}
+
+impl LayoutCondition {
+    pub fn is_met(&self, context: &LayoutConditionContext) -> bool {
+        match self {
+            LayoutCondition::FileExists(path) => match &context.cwd {
+                Some(cwd) => cwd.join(path).exists(),
+                None => path.exists(),
+            },
+            LayoutCondition::EnvSet(env_var) => std::env::var_os(env_var).is_some(),
+            LayoutCondition::Hostname(hostname) => context.hostname.as_ref() == Some(hostname),
+            // if we could not find out the terminal size, size conditions are never met
+            LayoutCondition::MinCols(cols) => {
+                context.terminal_size.is_some_and(|s| s.cols >= *cols)
+            },
+            LayoutCondition::MaxCols(cols) => {
+                context.terminal_size.is_some_and(|s| s.cols <= *cols)
+            },
+            LayoutCondition::MinRows(rows) => {
+                context.terminal_size.is_some_and(|s| s.rows >= *rows)
+            },
+            LayoutCondition::MaxRows(rows) => {
+                context.terminal_size.is_some_and(|s| s.rows <= *rows)
+            },
+        }
+    }
+}
+
+impl Display for LayoutCondition {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            LayoutCondition::FileExists(path) => write!(f, "file_exists={:?}", path),
+            LayoutCondition::EnvSet(env_var) => write!(f, "env_set={:?}", env_var),
+            LayoutCondition::Hostname(hostname) => write!(f, "hostname={:?}", hostname),
+            LayoutCondition::MinCols(cols) => write!(f, "min_cols={}", cols),
+            LayoutCondition::MaxCols(cols) => write!(f, "max_cols={}", cols),
+            LayoutCondition::MinRows(rows) => write!(f, "min_rows={}", rows),
+            LayoutCondition::MaxRows(rows) => write!(f, "max_rows={}", rows),
+        }
+    }
+}
+
+/// The state of the world `LayoutCondition`s are evaluated against
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutConditionContext {
+    pub cwd: Option<PathBuf>,
+    pub hostname: Option<String>,
+    pub terminal_size: Option<Size>,
+}
+
+impl LayoutConditionContext {
+    pub fn new(cwd: Option<PathBuf>) -> Self {
+        LayoutConditionContext {
+            cwd: cwd.or_else(|| std::env::current_dir().ok()),
+            hostname: current_hostname(),
+            terminal_size: current_terminal_size(),
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn current_hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    nix::unistd::gethostname(&mut buf)
+        .ok()
+        .and_then(|hostname| hostname.to_str().ok().map(|h| h.to_owned()))
+}
+
+#[cfg(target_family = "wasm")]
+fn current_hostname() -> Option<String> {
+    None
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn current_terminal_size() -> Option<Size> {
+    use nix::libc::{ioctl, winsize, STDIN_FILENO, STDOUT_FILENO, TIOCGWINSZ};
+    // layouts are usually loaded by a client attached to a terminal, the server has none and so
+    // size conditions are never met for layouts it loads (eg. from plugins)
+    for fd in [STDOUT_FILENO, STDIN_FILENO] {
+        let mut size = winsize {
+            ws_row: 0,
+            ws_col: 0,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        #[allow(clippy::useless_conversion)]
+        let res = unsafe { ioctl(fd, TIOCGWINSZ.into(), &mut size) };
+        if res == 0 && size.ws_row > 0 && size.ws_col > 0 {
+            return Some(Size {
+                rows: size.ws_row as usize,
+                cols: size.ws_col as usize,
+            });
+        }
+    }
+    None
+}
+
+#[cfg(target_family = "wasm")]
+fn current_terminal_size() -> Option<Size> {
+    None
+}
+
 pub type SwapTiledLayout = (BTreeMap<LayoutConstraint, TiledPaneLayout>, Option<String>); // Option<String> is the swap layout name
 pub type SwapFloatingLayout = (
     BTreeMap<LayoutConstraint, Vec<FloatingPaneLayout>>,
@@ -779,6 +971,15 @@ impl FloatingPaneLayout {
             },
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        match self.run.as_mut() {
+            Some(run) => run.add_env(env),
+            None if !env.is_empty() => {
+                self.run = Some(Run::Shell(None, env.clone()));
+            },
+            None => {},
+        }
+    }
     pub fn add_start_suspended(&mut self, start_suspended: Option<bool>) {
         if let Some(run) = self.run.as_mut() {
             run.add_start_suspended(start_suspended);
@@ -985,7 +1186,7 @@ impl TiledPaneLayout {
                         // in run_instructions_to_ignore (for cases where the cwd for example comes
                         // from a global layout cwd and the pane is actually just a bare pane that
                         // wants to be overidden)
-                        Some(Run::Cwd(_)) | None => true,
+                        Some(Run::Cwd(_)) | Some(Run::Shell(..)) | None => true,
                         _ => false,
                     }
                 }) {
@@ -1014,6 +1215,22 @@ impl TiledPaneLayout {
             child.add_cwd_to_layout(cwd);
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        // logical containers don't run anything themselves, the env goes to the panes in them
+        let is_container = !self.children.is_empty() || self.external_children_index.is_some();
+        if !is_container {
+            match self.run.as_mut() {
+                Some(run) => run.add_env(env),
+                None if !env.is_empty() => {
+                    self.run = Some(Run::Shell(None, env.clone()));
+                },
+                None => {},
+            }
+        }
+        for child in self.children.iter_mut() {
+            child.add_env_to_layout(env);
+        }
+    }
     pub fn populate_plugin_aliases_in_layout(&mut self, plugin_aliases: &PluginAliases) {
         match self.run.as_mut() {
             Some(run) => run.populate_run_plugin_if_needed(plugin_aliases),
@@ -1212,9 +1429,9 @@ impl Layout {
         layout_info: LayoutInfo,
     ) -> Result<Layout, ConfigError> {
         let mut should_start_layout_commands_suspended = false;
+        let layout_dir = layout_dir.clone().or_else(|| default_layout_dir());
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) = match layout_info {
             LayoutInfo::File(layout_name_without_extension) => {
-                let layout_dir = layout_dir.clone().or_else(|| default_layout_dir());
                 let (path_to_layout, stringified_layout, swap_layouts) =
                     Self::stringified_from_dir(
                         &PathBuf::from(layout_name_without_extension),
@@ -1233,13 +1450,15 @@ impl Layout {
             },
             LayoutInfo::Stringified(stringified_layout) => (None, stringified_layout, None),
         };
-        let mut layout = Layout::from_kdl(
+        let mut layout = Layout::from_kdl_with_args(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            BTreeMap::new(),
+            layout_dir,
         );
         if should_start_layout_commands_suspended {
             layout
@@ -1294,22 +1513,29 @@ impl Layout {
         layout_path: Option<&PathBuf>,
         layout_dir: Option<PathBuf>,
         config: Config,
+        layout_args: BTreeMap<String, String>,
     ) -> Result<(Layout, Config), ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
-            Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+            Layout::stringified_from_path_or_default(layout_path, layout_dir.clone())?;
+        let layout = Layout::from_kdl_with_args(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            layout_args,
+            layout_dir,
         )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     #[cfg(not(target_family = "wasm"))]
-    pub fn from_url(url: &str, config: Config) -> Result<(Layout, Config), ConfigError> {
+    pub fn from_url(
+        url: &str,
+        config: Config,
+        layout_args: BTreeMap<String, String>,
+    ) -> Result<(Layout, Config), ConfigError> {
         let raw_layout = task::block_on(async move {
             let download = Downloader::download_without_cache(url).await;
             match download {
@@ -1317,21 +1543,40 @@ impl Layout {
                 Err(e) => Err(ConfigError::DownloadError(format!("{}", e))),
             }
         })?;
-        let mut layout = Layout::from_kdl(&raw_layout, Some(url.into()), None, None)?;
+        let mut layout = Layout::from_kdl_with_args(
+            &raw_layout,
+            Some(url.into()),
+            None,
+            None,
+            layout_args,
+            None,
+        )?;
         layout.recursively_add_start_suspended_including_template(Some(true));
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     pub fn from_stringified_layout(
         stringified_layout: &str,
+        layout_dir: Option<PathBuf>,
         config: Config,
     ) -> Result<(Layout, Config), ConfigError> {
-        let layout = Layout::from_kdl(&stringified_layout, None, None, None)?;
+        let layout = Layout::from_kdl_with_args(
+            &stringified_layout,
+            None,
+            None,
+            None,
+            BTreeMap::new(),
+            layout_dir,
+        )?;
         let config = Config::from_kdl(&stringified_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     #[cfg(target_family = "wasm")]
-    pub fn from_url(_url: &str, _config: Config) -> Result<(Layout, Config), ConfigError> {
+    pub fn from_url(
+        _url: &str,
+        _config: Config,
+        _layout_args: BTreeMap<String, String>,
+    ) -> Result<(Layout, Config), ConfigError> {
         Err(ConfigError::DownloadError(format!(
             "Unsupported platform, cannot download layout from the web"
         )))
@@ -1341,31 +1586,35 @@ impl Layout {
         layout_dir: Option<PathBuf>,
     ) -> Result<Layout, ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
-            Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+            Layout::stringified_from_path_or_default(layout_path, layout_dir.clone())?;
+        let layout = Layout::from_kdl_with_args(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            BTreeMap::new(),
+            layout_dir,
         )?;
         Ok(layout)
     }
     pub fn from_default_assets(
         layout_name: &Path,
-        _layout_dir: Option<PathBuf>,
+        layout_dir: Option<PathBuf>,
         config: Config,
     ) -> Result<(Layout, Config), ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
             Layout::stringified_from_default_assets(layout_name)?;
-        let layout = Layout::from_kdl(
+        let layout = Layout::from_kdl_with_args(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            BTreeMap::new(),
+            layout_dir,
         )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
@@ -1375,8 +1624,17 @@ impl Layout {
         path_to_raw_layout: String,
         swap_layouts: Option<(&str, &str)>, // Option<path_to_swap_layout, stringified_swap_layout>
         cwd: Option<PathBuf>,
+        layout_args: BTreeMap<String, String>,
+        layout_dir: Option<PathBuf>,
     ) -> Result<Layout, ConfigError> {
-        Layout::from_kdl(raw, Some(path_to_raw_layout), swap_layouts, cwd)
+        Layout::from_kdl_with_args(
+            raw,
+            Some(path_to_raw_layout),
+            swap_layouts,
+            cwd,
+            layout_args,
+            layout_dir,
+        )
     }
     pub fn stringified_from_dir(
         layout: &PathBuf,
@@ -7,6 +7,7 @@ pub mod mouse;
 pub mod options;
 pub mod permission;
 pub mod plugins;
+pub mod session_template;
 pub mod theme;
 pub mod web_client;
 
@@ -53,6 +54,7 @@ mod not_wasm {
             web_server_ip: None,
             web_server_port: None,
             web_server_capability: None,
+            connected_users: vec![],
         }
     }
 
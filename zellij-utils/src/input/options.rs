@@ -227,6 +227,10 @@ pub struct Options {
     pub web_server_cert: Option<PathBuf>,
     pub web_server_key: Option<PathBuf>,
     pub enforce_https_for_localhost: Option<bool>,
+    pub web_server_socket: Option<PathBuf>,
+    pub web_server_base_path: Option<String>,
+    #[clap(skip)]
+    pub web_server_trusted_proxies: Option<Vec<IpAddr>>,
     /// A command to run after the discovery of running commands when serializing, for the purpose
     /// of manipulating the command (eg. with a regex) before it gets serialized
     #[clap(long, value_parser)]
@@ -324,6 +328,15 @@ impl Options {
         let enforce_https_for_localhost = other
             .enforce_https_for_localhost
             .or(self.enforce_https_for_localhost);
+        let web_server_socket = other
+            .web_server_socket
+            .or_else(|| self.web_server_socket.clone());
+        let web_server_base_path = other
+            .web_server_base_path
+            .or_else(|| self.web_server_base_path.clone());
+        let web_server_trusted_proxies = other
+            .web_server_trusted_proxies
+            .or_else(|| self.web_server_trusted_proxies.clone());
         let post_command_discovery_hook = other
             .post_command_discovery_hook
             .or(self.post_command_discovery_hook.clone());
@@ -367,6 +380,9 @@ impl Options {
             web_server_cert,
             web_server_key,
             enforce_https_for_localhost,
+            web_server_socket,
+            web_server_base_path,
+            web_server_trusted_proxies,
             post_command_discovery_hook,
         }
     }
@@ -441,6 +457,15 @@ impl Options {
         let enforce_https_for_localhost = other
             .enforce_https_for_localhost
             .or(self.enforce_https_for_localhost);
+        let web_server_socket = other
+            .web_server_socket
+            .or_else(|| self.web_server_socket.clone());
+        let web_server_base_path = other
+            .web_server_base_path
+            .or_else(|| self.web_server_base_path.clone());
+        let web_server_trusted_proxies = other
+            .web_server_trusted_proxies
+            .or_else(|| self.web_server_trusted_proxies.clone());
         let post_command_discovery_hook = other
             .post_command_discovery_hook
             .or_else(|| self.post_command_discovery_hook.clone());
@@ -484,6 +509,9 @@ impl Options {
             web_server_cert,
             web_server_key,
             enforce_https_for_localhost,
+            web_server_socket,
+            web_server_base_path,
+            web_server_trusted_proxies,
             post_command_discovery_hook,
         }
     }
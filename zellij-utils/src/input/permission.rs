@@ -5,14 +5,19 @@ use std::{
     path::PathBuf,
 };
 
-use crate::{consts::SWARM_PLUGIN_PERMISSIONS_CACHE, data::PermissionType};
+use crate::{
+    consts::SWARM_PLUGIN_PERMISSIONS_CACHE,
+    data::{PathPermission, PermissionType},
+};
 
 pub type GrantedPermission = HashMap<String, Vec<PermissionType>>;
+pub type GrantedPaths = HashMap<String, Vec<PathPermission>>;
 
 #[derive(Default, Debug)]
 pub struct PermissionCache {
     path: PathBuf,
     granted: GrantedPermission,
+    granted_paths: GrantedPaths,
 }
 
 impl PermissionCache {
@@ -20,10 +25,34 @@ impl PermissionCache {
         self.granted.insert(plugin_name, permissions);
     }
 
+    pub fn cache_paths(&mut self, plugin_name: String, paths: Vec<PathPermission>) {
+        let granted_paths = self.granted_paths.entry(plugin_name).or_default();
+        for path in paths {
+            // a new grant for an existing path replaces the old one (eg. read-only -> read-write)
+            granted_paths.retain(|p| p.path != path.path);
+            granted_paths.push(path);
+        }
+    }
+
     pub fn get_permissions(&self, plugin_name: String) -> Option<&Vec<PermissionType>> {
         self.granted.get(&plugin_name)
     }
 
+    pub fn get_paths(&self, plugin_name: String) -> Option<&Vec<PathPermission>> {
+        self.granted_paths.get(&plugin_name)
+    }
+
+    pub fn check_paths(&self, plugin_name: String, paths_to_check: &Vec<PathPermission>) -> bool {
+        match self.granted_paths.get(&plugin_name) {
+            Some(granted_paths) => paths_to_check.iter().all(|to_check| {
+                granted_paths
+                    .iter()
+                    .any(|g| g.path == to_check.path && g.access.covers(&to_check.access))
+            }),
+            None => false,
+        }
+    }
+
     pub fn check_permissions(
         &self,
         plugin_name: String,
@@ -45,23 +74,28 @@ impl PermissionCache {
     pub fn from_path_or_default(cache_path: Option<PathBuf>) -> Self {
         let cache_path = cache_path.unwrap_or(SWARM_PLUGIN_PERMISSIONS_CACHE.to_path_buf());
 
-        let granted = match fs::read_to_string(cache_path.clone()) {
+        let (granted, granted_paths) = match fs::read_to_string(cache_path.clone()) {
             Ok(raw_string) => PermissionCache::from_string(raw_string).unwrap_or_default(),
             Err(e) => {
                 log::error!("Failed to read permission cache file: {}", e);
-                GrantedPermission::default()
+                Default::default()
             },
         };
 
         PermissionCache {
             path: cache_path,
             granted,
+            granted_paths,
         }
     }
 
     pub fn write_to_file(&self) -> std::io::Result<()> {
         let mut f = File::create(&self.path)?;
-        write!(f, "{}", PermissionCache::to_string(&self.granted))?;
+        write!(
+            f,
+            "{}",
+            PermissionCache::to_string(&self.granted, &self.granted_paths)
+        )?;
         Ok(())
     }
 }
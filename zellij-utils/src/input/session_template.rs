@@ -0,0 +1,711 @@
+//! Session templates bundle a layout, values for its parameters, a default cwd, environment
+//! variables and a set of MCP servers and agents into a reusable recipe for new sessions.
+//!
+//! Templates are KDL files in the `templates` folder of the configuration folder, eg.
+//! `templates/api.kdl`:
+//!
+//! ```kdl
+//! description "The API and its agents"
+//! layout "compact"
+//! cwd "~/projects/${project}"
+//! param "project" description="Folder under ~/projects"
+//! param "branch" default="main"
+//! layout_args {
+//!     branch "${branch}"
+//! }
+//! env {
+//!     RUST_LOG "debug"
+//! }
+//! mcp "filesystem" {
+//!     command "npx"
+//!     args "-y" "@modelcontextprotocol/server-filesystem" "."
+//! }
+//! agent "reviewer" {
+//!     command "claude"
+//! }
+//! ```
+//!
+//! Resolving a template produces a self-contained layout that can be used to start the session.
+use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::data::{SessionTemplate, SessionTemplateParam};
+use crate::input::config::ConfigError;
+use crate::input::layout::Layout;
+use crate::{kdl_children_nodes, kdl_first_entry_as_string, kdl_get_child, kdl_name};
+
+const AGENTS_TAB_NAME: &str = "agents";
+
+// layout nodes that stay at the layout level when its bare panes are wrapped in a tab
+const LAYOUT_LEVEL_NODES: &[&str] = &[
+    "pane_template",
+    "tab_template",
+    "default_tab_template",
+    "new_tab_template",
+    "swap_tiled_layout",
+    "swap_floating_layout",
+    "cwd",
+    "param",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+struct TemplateCommand {
+    name: String,
+    command: String,
+    args: Vec<String>,
+    cwd: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionTemplateFile {
+    pub template: SessionTemplate,
+    document: KdlDocument, // before substituting the parameters
+}
+
+/// A resolved template, ready to be parsed with `Layout::from_kdl_with_args` or sent as a
+/// `LayoutInfo::Stringified`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSessionTemplate {
+    pub layout_path: Option<String>,
+    pub layout: String,
+}
+
+impl SessionTemplateFile {
+    pub fn from_kdl(name: &str, raw_template: &str) -> Result<Self, ConfigError> {
+        let document: KdlDocument = raw_template.parse()?;
+        let mut template = SessionTemplate {
+            name: name.to_owned(),
+            ..Default::default()
+        };
+        for node in document.nodes() {
+            match kdl_name!(node) {
+                "description" => {
+                    template.description = Some(string_argument(node)?);
+                },
+                "layout" => {
+                    template.layout = Some(string_argument(node)?);
+                },
+                "cwd" => {
+                    template.cwd = Some(string_argument(node)?);
+                },
+                "param" => {
+                    let param = parse_param(node)?;
+                    if template.params.iter().any(|p| p.name == param.name) {
+                        return Err(node_error(
+                            format!("Duplicate definition of the \"{}\" parameter", param.name),
+                            node,
+                        ));
+                    }
+                    template.params.push(param);
+                },
+                "mcp" => {
+                    template.mcp_servers.push(parse_command(node)?.name);
+                },
+                "agent" => {
+                    template.agents.push(parse_command(node)?.name);
+                },
+                "layout_args" | "env" => {
+                    string_map(node)?;
+                },
+                other => {
+                    return Err(node_error(
+                        format!(
+                            "Unknown session template node \"{}\", expected one of: description, layout, cwd, param, layout_args, env, mcp, agent",
+                            other
+                        ),
+                        node,
+                    ));
+                },
+            }
+        }
+        Ok(SessionTemplateFile { template, document })
+    }
+    /// Adds the parameters of the template's layout that the template does not give a value to,
+    /// so that they can be prompted for as well
+    pub fn add_layout_params(&mut self, raw_layout: &str) {
+        let Ok(layout_document) = raw_layout.parse::<KdlDocument>() else {
+            return; // this will be reported when resolving the template
+        };
+        let layout_args = self.layout_args().unwrap_or_default();
+        for param_node in layout_param_nodes(&layout_document) {
+            let Some(param_name) = kdl_first_entry_as_string!(param_node) else {
+                continue;
+            };
+            let is_known = layout_args.contains_key(param_name)
+                || self.template.params.iter().any(|p| p.name == param_name);
+            if !is_known {
+                self.template.params.push(SessionTemplateParam {
+                    name: param_name.to_owned(),
+                    description: Some(format!(
+                        "parameter of the {} layout",
+                        self.template.layout.as_deref().unwrap_or("default")
+                    )),
+                    default: param_node
+                        .get("default")
+                        .map(|e| value_to_string(e.value())),
+                });
+            }
+        }
+    }
+    /// Returns the template layout name, or None if it depends on the template's parameters
+    pub fn static_layout_name(&self) -> Option<&str> {
+        self.template
+            .layout
+            .as_deref()
+            .filter(|layout| !layout.contains("${"))
+    }
+    pub fn param_values(
+        &self,
+        args: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, ConfigError> {
+        if let Some(unknown_arg) = args
+            .keys()
+            .find(|arg| !self.template.params.iter().any(|p| &&p.name == arg))
+        {
+            let known_params: Vec<&str> = self
+                .template
+                .params
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect();
+            return Err(ConfigError::Std(
+                format!(
+                    "Unknown argument \"{}\" for the \"{}\" template, it accepts: {}",
+                    unknown_arg,
+                    self.template.name,
+                    if known_params.is_empty() {
+                        "no arguments".to_owned()
+                    } else {
+                        known_params.join(", ")
+                    }
+                )
+                .into(),
+            ));
+        }
+        let mut values = BTreeMap::new();
+        for param in &self.template.params {
+            let value = args
+                .get(&param.name)
+                .or(param.default.as_ref())
+                .ok_or_else(|| {
+                    ConfigError::Std(
+                        format!(
+                            "Missing value for the \"{}\" parameter of the \"{}\" template",
+                            param.name, self.template.name
+                        )
+                        .into(),
+                    )
+                })?;
+            values.insert(param.name.clone(), value.clone());
+        }
+        Ok(values)
+    }
+    /// Returns the template's layout name or path with the parameters substituted
+    pub fn layout_name(
+        &self,
+        args: &BTreeMap<String, String>,
+    ) -> Result<Option<String>, ConfigError> {
+        let values = self.param_values(args)?;
+        Ok(self
+            .template
+            .layout
+            .as_ref()
+            .map(|layout| substitute(layout, &values)))
+    }
+    /// Applies the template to its layout (as returned by `Layout::stringified_from_path_or_default`)
+    pub fn resolve_with_layout(
+        &self,
+        args: &BTreeMap<String, String>,
+        layout_path: Option<String>,
+        raw_layout: &str,
+        raw_swap_layouts: Option<&str>,
+    ) -> Result<ResolvedSessionTemplate, ConfigError> {
+        let values = self.param_values(args)?;
+        let mut document = self.document.clone();
+        substitute_in_nodes(document.nodes_mut(), &values);
+        let cwd = document
+            .get("cwd")
+            .map(|n| string_argument(n))
+            .transpose()?;
+        let layout_args = match document.get("layout_args") {
+            Some(node) => string_map(node)?,
+            None => BTreeMap::new(),
+        };
+        let env = match document.get("env") {
+            Some(node) => string_map(node)?,
+            None => BTreeMap::new(),
+        };
+        let mut commands = vec![];
+        for node in document.nodes() {
+            match kdl_name!(node) {
+                "mcp" => {
+                    let mut command = parse_command(node)?;
+                    command.name = format!("mcp: {}", command.name);
+                    commands.push(command);
+                },
+                "agent" => commands.push(parse_command(node)?),
+                _ => {},
+            }
+        }
+
+        let mut layout_document: KdlDocument = raw_layout.parse()?;
+        if let Some(raw_swap_layouts) = raw_swap_layouts {
+            // stringified layouts cannot have a swap layout file, so we bring it in
+            let swap_document: KdlDocument = raw_swap_layouts.parse()?;
+            for node in swap_document.nodes() {
+                if kdl_name!(node) == "import" {
+                    layout_document.nodes_mut().push(node.clone());
+                } else {
+                    layout_children(&mut layout_document)?.push(node.clone());
+                }
+            }
+        }
+        if let Some(layout_dir) = layout_path
+            .as_ref()
+            .and_then(|p| Path::new(p).parent())
+            .filter(|d| d.is_dir())
+        {
+            make_imports_absolute(&mut layout_document, layout_dir);
+        }
+        self.fill_layout_params(&mut layout_document, &layout_args, &values)?;
+        if let Some(cwd) = cwd {
+            set_global_cwd(layout_children(&mut layout_document)?, &cwd);
+        }
+        if !env.is_empty() || !commands.is_empty() {
+            let tab_templates = tab_template_names(&layout_document);
+            let layout_children = layout_children(&mut layout_document)?;
+            wrap_bare_panes_in_tab(layout_children, &tab_templates);
+            if !commands.is_empty() {
+                layout_children.push(commands_tab(&commands));
+            }
+            for node in layout_children.iter_mut() {
+                let name = kdl_name!(node);
+                if name == "tab" || tab_templates.iter().any(|t| t == name) {
+                    add_env(node, &env);
+                }
+            }
+        }
+        layout_document.fmt();
+        Ok(ResolvedSessionTemplate {
+            layout_path,
+            layout: layout_document.to_string(),
+        })
+    }
+    fn layout_args(&self) -> Result<BTreeMap<String, String>, ConfigError> {
+        match self.document.get("layout_args") {
+            Some(node) => string_map(node),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+    fn fill_layout_params(
+        &self,
+        layout_document: &mut KdlDocument,
+        layout_args: &BTreeMap<String, String>,
+        values: &BTreeMap<String, String>,
+    ) -> Result<(), ConfigError> {
+        // the values are given as defaults to the layout's parameters, so that the resolved
+        // layout does not need any arguments
+        let mut declared_params = vec![];
+        for param_node in layout_children(layout_document)?
+            .iter_mut()
+            .filter(|n| kdl_name!(n) == "param")
+        {
+            let Some(param_name) = kdl_first_entry_as_string!(param_node).map(|n| n.to_owned())
+            else {
+                continue;
+            };
+            if let Some(value) = layout_args.get(&param_name).or(values.get(&param_name)) {
+                param_node.insert("default", value.clone());
+            }
+            declared_params.push(param_name);
+        }
+        match layout_args
+            .keys()
+            .find(|arg| !declared_params.contains(arg))
+        {
+            Some(unknown_arg) => Err(ConfigError::Std(
+                format!(
+                    "The \"{}\" template sets \"{}\" in layout_args, but its layout does not declare this parameter",
+                    self.template.name, unknown_arg
+                )
+                .into(),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+pub fn session_templates(
+    template_dir: Option<PathBuf>,
+    layout_dir: Option<PathBuf>,
+) -> Vec<SessionTemplate> {
+    let mut templates = vec![];
+    let Some(entries) = template_dir.and_then(|d| std::fs::read_dir(d).ok()) else {
+        return templates;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().map(|e| e == "kdl").unwrap_or(false) {
+            let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            match read_session_template(&path, &name, layout_dir.clone()) {
+                Ok(template_file) => templates.push(template_file.template),
+                Err(e) => log::error!("Failed to read session template {:?}: {}", path, e),
+            }
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+pub fn session_template(
+    name: &str,
+    template_dir: Option<PathBuf>,
+    layout_dir: Option<PathBuf>,
+) -> Result<SessionTemplateFile, ConfigError> {
+    // templates are files named after them, anything else (eg. `../name`) would be read from
+    // outside of the templates folder
+    let is_plain_file_name = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains(&['/', '\\'][..])
+        && !name.chars().any(|c| c.is_control());
+    if !is_plain_file_name {
+        return Err(ConfigError::Std(
+            format!(
+                "Invalid template name: \"{}\", template names cannot be empty, start with a dot or contain slashes",
+                name
+            )
+            .into(),
+        ));
+    }
+    let template_dir = template_dir
+        .ok_or_else(|| ConfigError::Std("Could not find the templates folder".to_owned().into()))?;
+    let path = template_dir.join(name).with_extension("kdl");
+    if !path.is_file() {
+        return Err(ConfigError::Std(
+            format!(
+                "Could not find a template named \"{}\" in {:?}",
+                name, template_dir
+            )
+            .into(),
+        ));
+    }
+    read_session_template(&path, name, layout_dir)
+}
+
+pub fn resolve_session_template(
+    template_file: &SessionTemplateFile,
+    args: &BTreeMap<String, String>,
+    layout_dir: Option<PathBuf>,
+) -> Result<ResolvedSessionTemplate, ConfigError> {
+    let layout_name = template_file.layout_name(args)?.map(PathBuf::from);
+    let (layout_path, raw_layout, raw_swap_layouts) =
+        Layout::stringified_from_path_or_default(layout_name.as_ref(), layout_dir)?;
+    template_file.resolve_with_layout(
+        args,
+        Some(layout_path),
+        &raw_layout,
+        raw_swap_layouts.as_ref().map(|(raw, _path)| raw.as_str()),
+    )
+}
+
+fn read_session_template(
+    path: &Path,
+    name: &str,
+    layout_dir: Option<PathBuf>,
+) -> Result<SessionTemplateFile, ConfigError> {
+    let raw_template =
+        std::fs::read_to_string(path).map_err(|e| ConfigError::IoPath(e, path.to_path_buf()))?;
+    let mut template_file =
+        SessionTemplateFile::from_kdl(name, &raw_template).map_err(|e| match e {
+            ConfigError::KdlError(kdl_error) => ConfigError::KdlError(
+                kdl_error.add_src(path.to_string_lossy().to_string(), raw_template.clone()),
+            ),
+            e => e,
+        })?;
+    if let Some(layout_name) = template_file.static_layout_name().map(PathBuf::from) {
+        if let Ok((_, raw_layout, _)) =
+            Layout::stringified_from_path_or_default(Some(&layout_name), layout_dir)
+        {
+            template_file.add_layout_params(&raw_layout);
+        }
+    } else if template_file.template.layout.is_none() {
+        if let Ok((_, raw_layout, _)) = Layout::stringified_from_path_or_default(None, layout_dir) {
+            template_file.add_layout_params(&raw_layout);
+        }
+    }
+    Ok(template_file)
+}
+
+fn node_error(error_message: String, node: &KdlNode) -> ConfigError {
+    ConfigError::new_kdl_error(error_message, node.span().offset(), node.span().len())
+}
+
+fn string_argument(node: &KdlNode) -> Result<String, ConfigError> {
+    kdl_first_entry_as_string!(node)
+        .map(|s| s.to_owned())
+        .ok_or_else(|| {
+            node_error(
+                format!(
+                    "\"{}\" expects a string, eg. {} \"...\"",
+                    kdl_name!(node),
+                    kdl_name!(node)
+                ),
+                node,
+            )
+        })
+}
+
+fn value_to_string(value: &KdlValue) -> String {
+    match value {
+        KdlValue::String(s) | KdlValue::RawString(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn string_map(node: &KdlNode) -> Result<BTreeMap<String, String>, ConfigError> {
+    let mut map = BTreeMap::new();
+    for child in kdl_children_nodes!(node).unwrap_or(&[]) {
+        let value = child
+            .entries()
+            .iter()
+            .next()
+            .map(|e| value_to_string(e.value()))
+            .ok_or_else(|| {
+                node_error(format!("Missing value for \"{}\"", kdl_name!(child)), child)
+            })?;
+        map.insert(kdl_name!(child).to_owned(), value);
+    }
+    Ok(map)
+}
+
+fn parse_param(node: &KdlNode) -> Result<SessionTemplateParam, ConfigError> {
+    let name = kdl_first_entry_as_string!(node)
+        .filter(|n| {
+            !n.is_empty()
+                && n
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+        .ok_or_else(|| {
+            node_error(
+                "Template parameters must have a name made of letters, numbers, '_' or '-' (eg. param \"project\")".into(),
+                node,
+            )
+        })?;
+    Ok(SessionTemplateParam {
+        name: name.to_owned(),
+        description: node
+            .get("description")
+            .and_then(|e| e.value().as_string())
+            .map(|d| d.to_owned()),
+        default: node.get("default").map(|e| value_to_string(e.value())),
+    })
+}
+
+fn parse_command(node: &KdlNode) -> Result<TemplateCommand, ConfigError> {
+    let name = string_argument(node)?;
+    let command = kdl_get_child!(node, "command")
+        .and_then(|c| kdl_first_entry_as_string!(c))
+        .map(|c| c.to_owned())
+        .ok_or_else(|| {
+            node_error(
+                format!("\"{}\" needs a command to run, eg. command \"npx\"", name),
+                node,
+            )
+        })?;
+    let args = kdl_get_child!(node, "args")
+        .map(|a| {
+            a.entries()
+                .iter()
+                .map(|e| value_to_string(e.value()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let cwd = kdl_get_child!(node, "cwd")
+        .and_then(|c| kdl_first_entry_as_string!(c))
+        .map(|c| c.to_owned());
+    Ok(TemplateCommand {
+        name,
+        command,
+        args,
+        cwd,
+    })
+}
+
+fn substitute(value: &str, values: &BTreeMap<String, String>) -> String {
+    let mut substituted = value.to_owned();
+    for (param_name, param_value) in values {
+        substituted = substituted.replace(&format!("${{{}}}", param_name), param_value);
+    }
+    substituted
+}
+
+fn substitute_in_nodes(nodes: &mut [KdlNode], values: &BTreeMap<String, String>) {
+    for node in nodes.iter_mut() {
+        for entry in node.entries_mut().iter_mut() {
+            let substituted = match entry.value() {
+                KdlValue::String(s) | KdlValue::RawString(s) if s.contains("${") => {
+                    Some(substitute(s, values))
+                },
+                _ => None,
+            };
+            if let Some(substituted) = substituted {
+                let value = KdlValue::String(substituted);
+                entry.set_value_repr(value.to_string());
+                entry.set_value(value);
+            }
+        }
+        if let Some(children) = node.children_mut().as_mut() {
+            substitute_in_nodes(children.nodes_mut(), values);
+        }
+    }
+}
+
+fn layout_param_nodes(layout_document: &KdlDocument) -> Vec<&KdlNode> {
+    layout_document
+        .get("layout")
+        .and_then(|l| kdl_children_nodes!(l))
+        .map(|c| c.iter().filter(|n| kdl_name!(n) == "param").collect())
+        .unwrap_or_default()
+}
+
+fn layout_children(layout_document: &mut KdlDocument) -> Result<&mut Vec<KdlNode>, ConfigError> {
+    let layout_node = layout_document
+        .get_mut("layout")
+        .ok_or_else(|| ConfigError::Std("The template's layout has no layout node".into()))?;
+    Ok(layout_node.ensure_children().nodes_mut())
+}
+
+fn tab_template_names(layout_document: &KdlDocument) -> Vec<String> {
+    layout_document
+        .get("layout")
+        .and_then(|l| kdl_children_nodes!(l))
+        .map(|c| {
+            c.iter()
+                .filter(|n| kdl_name!(n) == "tab_template")
+                .filter_map(|n| n.get("name").and_then(|e| e.value().as_string()))
+                .map(|n| n.to_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn make_imports_absolute(layout_document: &mut KdlDocument, layout_dir: &Path) {
+    for import_node in layout_document
+        .nodes_mut()
+        .iter_mut()
+        .filter(|n| kdl_name!(n) == "import")
+    {
+        let Some(import_path) = kdl_first_entry_as_string!(import_node).map(PathBuf::from) else {
+            continue;
+        };
+        let next_to_layout = layout_dir.join(&import_path);
+        let exists = next_to_layout.is_file() || next_to_layout.with_extension("kdl").is_file();
+        if import_path.is_relative() && exists {
+            if let Some(entry) = import_node.entries_mut().get_mut(0) {
+                *entry = KdlEntry::new(next_to_layout.to_string_lossy().to_string());
+            }
+        }
+    }
+}
+
+fn set_global_cwd(layout_children: &mut Vec<KdlNode>, cwd: &str) {
+    match layout_children.iter_mut().find(|n| kdl_name!(n) == "cwd") {
+        Some(cwd_node) => {
+            // a relative layout cwd is relative to the template's cwd
+            let layout_cwd = kdl_first_entry_as_string!(cwd_node).map(PathBuf::from);
+            if let Some(layout_cwd) = layout_cwd.filter(|c| c.is_relative()) {
+                let joined = PathBuf::from(cwd).join(layout_cwd);
+                if let Some(entry) = cwd_node.entries_mut().get_mut(0) {
+                    *entry = KdlEntry::new(joined.to_string_lossy().to_string());
+                }
+            }
+        },
+        None => {
+            let mut cwd_node = KdlNode::new("cwd");
+            cwd_node.push(cwd.to_owned());
+            layout_children.insert(0, cwd_node);
+        },
+    }
+}
+
+fn wrap_bare_panes_in_tab(layout_children: &mut Vec<KdlNode>, tab_templates: &[String]) {
+    // a layout without tabs describes a single tab, we make it explicit so that more tabs can be
+    // added next to it
+    let has_tabs = layout_children.iter().any(|n| {
+        let name = kdl_name!(n);
+        name == "tab" || tab_templates.iter().any(|t| t == name)
+    });
+    if has_tabs {
+        return;
+    }
+    let (layout_level, tab_level): (Vec<KdlNode>, Vec<KdlNode>) = layout_children
+        .drain(..)
+        .partition(|n| LAYOUT_LEVEL_NODES.contains(&kdl_name!(n)));
+    *layout_children = layout_level;
+    let mut tab = KdlNode::new("tab");
+    let mut tab_children = KdlDocument::new();
+    tab_children.nodes_mut().extend(tab_level);
+    tab.set_children(tab_children);
+    tab.insert("focus", true);
+    layout_children.push(tab);
+}
+
+fn commands_tab(commands: &[TemplateCommand]) -> KdlNode {
+    let mut stack = KdlNode::new("pane");
+    stack.insert("stacked", true);
+    let mut panes = KdlDocument::new();
+    for command in commands {
+        let mut pane = KdlNode::new("pane");
+        pane.insert("name", command.name.clone());
+        pane.insert("command", command.command.clone());
+        if let Some(cwd) = &command.cwd {
+            pane.insert("cwd", cwd.clone());
+        }
+        if !command.args.is_empty() {
+            let mut args = KdlNode::new("args");
+            for arg in &command.args {
+                args.push(arg.clone());
+            }
+            let mut pane_children = KdlDocument::new();
+            pane_children.nodes_mut().push(args);
+            pane.set_children(pane_children);
+        }
+        panes.nodes_mut().push(pane);
+    }
+    stack.set_children(panes);
+    let mut tab = KdlNode::new("tab");
+    tab.insert("name", AGENTS_TAB_NAME);
+    let mut tab_children = KdlDocument::new();
+    tab_children.nodes_mut().push(stack);
+    tab.set_children(tab_children);
+    tab
+}
+
+fn add_env(tab_node: &mut KdlNode, env: &BTreeMap<String, String>) {
+    // variables the tab sets itself take precedence
+    if env.is_empty() {
+        return;
+    }
+    let children = tab_node.ensure_children();
+    if children.get("env").is_none() {
+        children.nodes_mut().insert(0, KdlNode::new("env"));
+    }
+    if let Some(env_node) = children.get_mut("env") {
+        let env_children = env_node.ensure_children();
+        for (key, value) in env {
+            if env_children.get(key).is_none() {
+                let mut variable = KdlNode::new(key.as_str());
+                variable.push(value.clone());
+                env_children.nodes_mut().push(variable);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/session_template_test.rs"]
+mod session_template_test;
@@ -589,6 +589,688 @@ fn layout_with_plugin_panes() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_dev_watch_plugin_pane() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" {
+                    _dev_watch true
+                }
+            }
+            pane {
+                plugin location="file:/path/to/my/other_plugin.wasm"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_pane_layout, _floating_panes) = layout.template.unwrap();
+    let dev_watch: Vec<bool> = tiled_pane_layout
+        .children
+        .iter()
+        .map(|child| match &child.run {
+            Some(Run::Plugin(RunPluginOrAlias::RunPlugin(run_plugin))) => {
+                run_plugin.configuration.dev_watch()
+            },
+            _ => panic!("expected a plugin pane"),
+        })
+        .collect();
+    assert_eq!(dev_watch, vec![true, false]);
+}
+
+#[test]
+fn layout_with_env_on_tabs_and_panes() {
+    let kdl_layout = r#"
+        layout {
+            tab cwd="/tmp" {
+                env {
+                    TAB_VAR "tab"
+                    OVERRIDDEN "tab"
+                }
+                pane
+                pane command="htop" {
+                    env {
+                        OVERRIDDEN "pane"
+                    }
+                }
+                floating_panes {
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (_tab_name, tiled_pane_layout, floating_panes) = layout.tabs.iter().next().unwrap();
+    let tab_env = BTreeMap::from([
+        ("TAB_VAR".to_owned(), "tab".to_owned()),
+        ("OVERRIDDEN".to_owned(), "tab".to_owned()),
+    ]);
+    let pane_env = BTreeMap::from([
+        ("TAB_VAR".to_owned(), "tab".to_owned()),
+        ("OVERRIDDEN".to_owned(), "pane".to_owned()),
+    ]);
+    assert_eq!(
+        tiled_pane_layout.run,
+        Some(Run::Cwd(PathBuf::from("/tmp"))),
+        "the env is not added to the tab's own container"
+    );
+    assert_eq!(
+        tiled_pane_layout.children[0].run,
+        Some(Run::Shell(Some(PathBuf::from("/tmp")), tab_env.clone()))
+    );
+    match &tiled_pane_layout.children[1].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.env, pane_env);
+            assert_eq!(run_command.cwd, Some(PathBuf::from("/tmp")));
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+    assert_eq!(
+        floating_panes[0].run,
+        Some(Run::Shell(Some(PathBuf::from("/tmp")), tab_env))
+    );
+}
+
+#[test]
+fn layout_with_env_in_pane_template() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="cargo" {
+                command "cargo"
+                env {
+                    RUST_LOG "info"
+                    RUST_BACKTRACE "1"
+                }
+            }
+            cargo {
+                args "test"
+                env {
+                    RUST_LOG "debug"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_pane_layout, _floating_panes) = layout.template.unwrap();
+    match &tiled_pane_layout.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.args, vec!["test".to_owned()]);
+            assert_eq!(
+                run_command.env,
+                BTreeMap::from([
+                    ("RUST_LOG".to_owned(), "debug".to_owned()),
+                    ("RUST_BACKTRACE".to_owned(), "1".to_owned()),
+                ])
+            );
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn layout_with_env_on_logical_container_is_inherited_by_children() {
+    let kdl_layout = r#"
+        layout {
+            pane split_direction="vertical" {
+                env {
+                    PARENT "parent"
+                }
+                pane
+                pane cwd="/tmp"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_pane_layout, _floating_panes) = layout.template.unwrap();
+    let env = BTreeMap::from([("PARENT".to_owned(), "parent".to_owned())]);
+    assert_eq!(
+        tiled_pane_layout.children[0].run, None,
+        "the container itself does not become a shell pane"
+    );
+    let children = &tiled_pane_layout.children[0].children;
+    assert_eq!(children[0].run, Some(Run::Shell(None, env.clone())));
+    assert_eq!(
+        children[1].run,
+        Some(Run::Shell(Some(PathBuf::from("/tmp")), env))
+    );
+}
+
+#[test]
+fn layout_with_empty_env_block_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "empty env block is an error");
+}
+
+#[test]
+fn layout_with_params_uses_defaults_and_layout_args() {
+    let kdl_layout = r#"
+        layout {
+            param "project" default="/tmp/project"
+            param "port" type="int" default=8080
+            param "branch"
+            tab name="${branch}" cwd="${project}" {
+                pane command="cargo" {
+                    args "run" "--" "--port" "${port}"
+                }
+            }
+        }
+    "#;
+    let layout_args = BTreeMap::from([
+        ("branch".to_owned(), "main".to_owned()),
+        ("port".to_owned(), "3000".to_owned()),
+    ]);
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        layout_args,
+        None,
+    )
+    .unwrap();
+    let (tab_name, tiled_pane_layout, _floating_panes) = layout.tabs.iter().next().unwrap();
+    assert_eq!(tab_name, &Some("main".to_owned()));
+    match &tiled_pane_layout.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(
+                run_command.args,
+                vec![
+                    "run".to_owned(),
+                    "--".to_owned(),
+                    "--port".to_owned(),
+                    "3000".to_owned()
+                ]
+            );
+            assert_eq!(run_command.cwd, Some(PathBuf::from("/tmp/project")));
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn layout_params_are_substituted_in_swap_layouts() {
+    let kdl_layout = r#"
+        layout {
+            param "editor" default="vim"
+            pane
+        }
+    "#;
+    let kdl_swap_layout = r#"
+        swap_tiled_layout name="with-editor" {
+            tab {
+                pane command="${editor}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        Some(("swap_layout_file_name", kdl_swap_layout)),
+        None,
+        BTreeMap::from([("editor".to_owned(), "hx".to_owned())]),
+        None,
+    )
+    .unwrap();
+    let swap_layout = layout.swap_tiled_layouts[0].0.values().next().unwrap();
+    match &swap_layout.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.command, PathBuf::from("hx"));
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn layout_param_without_value_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            param "project"
+            pane cwd="${project}"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "param without default or argument is an error"
+    );
+}
+
+#[test]
+fn layout_param_with_wrong_type_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            param "port" type="int" default=8080
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        BTreeMap::from([("port".to_owned(), "not-a-port".to_owned())]),
+        None,
+    );
+    assert!(layout.is_err(), "param with the wrong type is an error");
+}
+
+#[test]
+fn unknown_layout_arg_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            param "project" default="."
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        BTreeMap::from([("projcet".to_owned(), "/tmp".to_owned())]),
+        None,
+    );
+    assert!(layout.is_err(), "unknown layout argument is an error");
+}
+
+#[test]
+fn undeclared_layout_param_references_are_left_as_is() {
+    let kdl_layout = r#"
+        layout {
+            param "project" default="/tmp/project"
+            pane command="bash" cwd="${project}" {
+                args "-c" "cd ${project} && echo ${HOME}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        BTreeMap::new(),
+        None,
+    )
+    .unwrap();
+    let tiled_pane_layout = layout.new_tab().0;
+    match &tiled_pane_layout.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(
+                run_command.args,
+                vec![
+                    "-c".to_owned(),
+                    "cd /tmp/project && echo ${HOME}".to_owned()
+                ]
+            );
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn escaped_layout_param_reference_is_kept_as_a_literal() {
+    let kdl_layout = r#"
+        layout {
+            param "project" default="/tmp/project"
+            pane command="echo" cwd="${project}" {
+                args "$${HOME}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        BTreeMap::new(),
+        None,
+    )
+    .unwrap();
+    let tiled_pane_layout = layout.new_tab().0;
+    match &tiled_pane_layout.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.args, vec!["${HOME}".to_owned()]);
+            assert_eq!(run_command.cwd, Some(PathBuf::from("/tmp/project")));
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn layout_without_params_keeps_references_as_is() {
+    let kdl_layout = r#"
+        layout {
+            pane command="echo" {
+                args "${HOME}" "$${HOME}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let tiled_pane_layout = layout.new_tab().0;
+    match &tiled_pane_layout.children[0].run {
+        Some(Run::Command(run_command)) => {
+            // escaping works the same way as in layouts with params
+            assert_eq!(
+                run_command.args,
+                vec!["${HOME}".to_owned(), "${HOME}".to_owned()]
+            );
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn layout_with_conditional_panes_by_file_existence() {
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(project_dir.path().join("Cargo.toml"), "").unwrap();
+    let kdl_layout = r#"
+        layout {
+            pane command="cargo" {
+                args "watch"
+                when file_exists="Cargo.toml"
+            }
+            pane command="npm" {
+                args "run" "dev"
+                when file_exists="package.json"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        Some(project_dir.path().to_path_buf()),
+    )
+    .unwrap();
+    let tiled_pane_layout = layout.new_tab().0;
+    assert_eq!(
+        tiled_pane_layout.children.len(),
+        1,
+        "only one pane was kept"
+    );
+    match &tiled_pane_layout.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.command, PathBuf::from("cargo"));
+            assert_eq!(run_command.args, vec!["watch".to_owned()]);
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn layout_with_conditional_tabs_and_any_of_multiple_when_blocks() {
+    std::env::set_var("SWARM_TEST_LAYOUT_CONDITION_IS_SET", "1");
+    let kdl_layout = r#"
+        layout {
+            tab name="kept" {
+                when hostname="no-such-host.invalid"
+                when env_set="SWARM_TEST_LAYOUT_CONDITION_IS_SET"
+                pane
+            }
+            tab name="removed" {
+                when env_set="SWARM_TEST_LAYOUT_CONDITION_IS_NOT_SET"
+                pane
+            }
+            tab name="also_removed" {
+                when env_set="SWARM_TEST_LAYOUT_CONDITION_IS_SET" hostname="no-such-host.invalid"
+                pane
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let tab_names: Vec<Option<String>> = layout
+        .tabs
+        .iter()
+        .map(|(name, _, _)| name.clone())
+        .collect();
+    assert_eq!(tab_names, vec![Some("kept".to_owned())]);
+}
+
+#[test]
+fn layout_with_unknown_condition_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                when file_exits="Cargo.toml"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "unknown condition is an error");
+}
+
+#[test]
+fn layout_with_condition_outside_of_pane_or_tab_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            when file_exists="Cargo.toml"
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "condition directly in the layout node is an error"
+    );
+}
+
+#[test]
+fn layout_size_conditions_are_evaluated_against_terminal_size() {
+    let context = LayoutConditionContext {
+        terminal_size: Some(Size {
+            rows: 40,
+            cols: 120,
+        }),
+        ..Default::default()
+    };
+    assert!(LayoutCondition::MinCols(100).is_met(&context));
+    assert!(!LayoutCondition::MinCols(200).is_met(&context));
+    assert!(LayoutCondition::MaxRows(40).is_met(&context));
+    assert!(!LayoutCondition::MinRows(41).is_met(&context));
+    let unknown_size_context = LayoutConditionContext::default();
+    assert!(
+        !LayoutCondition::MaxCols(1000).is_met(&unknown_size_context),
+        "size conditions are not met when the terminal size is unknown"
+    );
+}
+
+#[test]
+fn layout_can_import_templates_and_swap_layouts() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        layout_dir.path().join("common.kdl"),
+        r#"
+            import "editors.kdl"
+            pane_template name="editor_with_shell" {
+                editor
+                pane
+            }
+            swap_tiled_layout name="stacked" {
+                tab {
+                    pane stacked=true { children; }
+                }
+            }
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        layout_dir.path().join("editors.kdl"),
+        r#"
+            pane_template name="editor" command="hx"
+        "#,
+    )
+    .unwrap();
+    let layout_path = layout_dir.path().join("main.kdl");
+    std::fs::write(
+        &layout_path,
+        r#"
+            import "common.kdl"
+            layout {
+                editor_with_shell
+            }
+        "#,
+    )
+    .unwrap();
+    let (layout, _config) =
+        Layout::from_path_or_default(Some(&layout_path), None, Config::default(), BTreeMap::new())
+            .unwrap();
+    let tiled_pane_layout = layout.new_tab().0;
+    assert_eq!(tiled_pane_layout.children.len(), 1);
+    let editor_with_shell = &tiled_pane_layout.children[0];
+    assert_eq!(editor_with_shell.children.len(), 2);
+    match &editor_with_shell.children[0].run {
+        Some(Run::Command(run_command)) => assert_eq!(run_command.command, PathBuf::from("hx")),
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+    assert_eq!(layout.swap_tiled_layouts.len(), 1);
+    assert_eq!(layout.swap_tiled_layouts[0].1, Some("stacked".to_owned()));
+}
+
+#[test]
+fn layout_imports_are_looked_up_in_layout_dir() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        layout_dir.path().join("common.kdl"),
+        r#"
+            pane_template name="editor" command="hx"
+        "#,
+    )
+    .unwrap();
+    let kdl_layout = r#"
+        import "common"
+        layout {
+            editor
+        }
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        BTreeMap::new(),
+        Some(layout_dir.path().to_path_buf()),
+    )
+    .unwrap();
+    match &layout.new_tab().0.children[0].run {
+        Some(Run::Command(run_command)) => assert_eq!(run_command.command, PathBuf::from("hx")),
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn circular_layout_import_is_an_error() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(layout_dir.path().join("a.kdl"), r#"import "b.kdl""#).unwrap();
+    std::fs::write(layout_dir.path().join("b.kdl"), r#"import "a.kdl""#).unwrap();
+    let kdl_layout = r#"
+        import "a.kdl"
+        layout
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        BTreeMap::new(),
+        Some(layout_dir.path().to_path_buf()),
+    );
+    assert!(layout.is_err(), "circular import is an error");
+}
+
+#[test]
+fn imported_file_with_a_layout_node_is_an_error() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(layout_dir.path().join("common.kdl"), "layout { pane; }").unwrap();
+    let kdl_layout = r#"
+        import "common.kdl"
+        layout
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        BTreeMap::new(),
+        Some(layout_dir.path().to_path_buf()),
+    );
+    assert!(layout.is_err(), "imported files cannot contain a layout");
+}
+
+#[test]
+fn errors_in_imported_nodes_are_reported_against_the_imported_file() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        layout_dir.path().join("a.kdl"),
+        r#"pane_template name="editor" command="hx""#,
+    )
+    .unwrap();
+    let raw_b = r#"pane_template name="editor" command="vim""#;
+    std::fs::write(layout_dir.path().join("b.kdl"), raw_b).unwrap();
+    let kdl_layout = r#"
+        import "a.kdl"
+        import "b.kdl"
+        layout {
+            editor
+        }
+    "#;
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        BTreeMap::new(),
+        Some(layout_dir.path().to_path_buf()),
+    );
+    match layout {
+        Err(ConfigError::KdlError(kdl_error)) => {
+            let src = kdl_error.src.expect("error has a source");
+            assert_eq!(
+                src.name(),
+                layout_dir.path().join("b.kdl").to_string_lossy().as_ref()
+            );
+            assert_eq!(kdl_error.offset, Some(0));
+            assert_eq!(kdl_error.len, Some(raw_b.len()));
+        },
+        layout => panic!("expected a layout error, got: {:?}", layout),
+    }
+}
+
+#[test]
+fn layout_from_str_looks_up_imports_in_layout_dir() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        layout_dir.path().join("common.kdl"),
+        r#"pane_template name="editor" command="hx""#,
+    )
+    .unwrap();
+    let kdl_layout = r#"
+        import "common.kdl"
+        layout {
+            editor
+        }
+    "#;
+    let layout = Layout::from_str(
+        kdl_layout,
+        "layout_file_name".into(),
+        None,
+        None,
+        BTreeMap::new(),
+        Some(layout_dir.path().to_path_buf()),
+    )
+    .unwrap();
+    match &layout.new_tab().0.children[0].run {
+        Some(Run::Command(run_command)) => assert_eq!(run_command.command, PathBuf::from("hx")),
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
 #[test]
 fn layout_with_borderless_panes() {
     let kdl_layout = r#"
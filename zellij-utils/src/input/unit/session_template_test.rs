@@ -0,0 +1,177 @@
+use super::super::layout::{Layout, Run};
+use super::super::session_template::*;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const TEMPLATE: &str = r#"
+    description "The API and its agents"
+    layout "api"
+    cwd "/projects/${project}"
+    param "project" description="Folder under /projects"
+    param "branch" default="main"
+    layout_args {
+        branch "${branch}"
+    }
+    env {
+        RUST_LOG "debug"
+    }
+    mcp "filesystem" {
+        command "npx"
+        args "-y" "server-filesystem" "${project}"
+    }
+    agent "reviewer" {
+        command "claude"
+    }
+"#;
+
+const LAYOUT: &str = r#"
+    layout {
+        param "branch"
+        pane name="${branch}"
+        pane
+    }
+"#;
+
+fn resolve(args: &[(&str, &str)]) -> Result<Layout, String> {
+    let template_file = SessionTemplateFile::from_kdl("api", TEMPLATE).unwrap();
+    let args: BTreeMap<String, String> = args
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    let resolved = template_file
+        .resolve_with_layout(&args, None, LAYOUT, None)
+        .map_err(|e| e.to_string())?;
+    Layout::from_kdl(&resolved.layout, None, None, None).map_err(|e| format!("{:?}", e))
+}
+
+#[test]
+fn template_summary_lists_params_and_commands() {
+    let template_file = SessionTemplateFile::from_kdl("api", TEMPLATE).unwrap();
+    let template = template_file.template;
+    assert_eq!(
+        template.description,
+        Some("The API and its agents".to_owned())
+    );
+    assert_eq!(template.layout, Some("api".to_owned()));
+    assert_eq!(template.mcp_servers, vec!["filesystem".to_owned()]);
+    assert_eq!(template.agents, vec!["reviewer".to_owned()]);
+    let required: Vec<&str> = template
+        .params
+        .iter()
+        .filter(|p| p.is_required())
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(required, vec!["project"]);
+}
+
+#[test]
+fn resolved_template_sets_cwd_layout_params_env_and_agents_tab() {
+    let layout = resolve(&[("project", "api")]).unwrap();
+    let tabs = layout.tabs();
+    assert_eq!(
+        tabs.len(),
+        2,
+        "bare panes wrapped in a tab next to the agents tab"
+    );
+    assert_eq!(tabs[1].0, Some("agents".to_owned()));
+    let first_tab_runs = tabs[0].1.extract_run_instructions();
+    let pane_names: Vec<Option<String>> =
+        tabs[0].1.children.iter().map(|c| c.name.clone()).collect();
+    assert_eq!(
+        pane_names[0],
+        Some("main".to_owned()),
+        "layout param filled"
+    );
+    for run in first_tab_runs.iter().flatten() {
+        match run {
+            Run::Shell(cwd, env) => {
+                assert_eq!(cwd.as_ref(), Some(&PathBuf::from("/projects/api")));
+                assert_eq!(env.get("RUST_LOG"), Some(&"debug".to_owned()));
+            },
+            _ => {},
+        }
+    }
+    let agent_commands: Vec<(PathBuf, Vec<String>)> = tabs[1]
+        .1
+        .extract_run_instructions()
+        .into_iter()
+        .flatten()
+        .filter_map(|r| match r {
+            Run::Command(run_command) => Some((run_command.command, run_command.args)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        agent_commands,
+        vec![
+            (
+                PathBuf::from("npx"),
+                vec![
+                    "-y".to_owned(),
+                    "server-filesystem".to_owned(),
+                    "api".to_owned()
+                ]
+            ),
+            (PathBuf::from("claude"), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn missing_required_template_param_is_an_error() {
+    let error = resolve(&[]).unwrap_err();
+    assert!(
+        error.contains("Missing value for the \"project\" parameter"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn unknown_template_arg_is_an_error() {
+    let error = resolve(&[("project", "api"), ("nope", "1")]).unwrap_err();
+    assert!(error.contains("Unknown argument \"nope\""), "{}", error);
+}
+
+#[test]
+fn uncovered_layout_params_are_added_to_template_params() {
+    let template = r#"
+        layout "api"
+        param "project"
+    "#;
+    let layout = r#"
+        layout {
+            param "project"
+            param "port" default="8080"
+            param "host"
+            pane
+        }
+    "#;
+    let mut template_file = SessionTemplateFile::from_kdl("api", template).unwrap();
+    template_file.add_layout_params(layout);
+    let params: Vec<(&str, bool)> = template_file
+        .template
+        .params
+        .iter()
+        .map(|p| (p.name.as_str(), p.is_required()))
+        .collect();
+    assert_eq!(
+        params,
+        vec![("project", true), ("port", false), ("host", true)]
+    );
+}
+
+#[test]
+fn templates_outside_of_the_templates_folder_cannot_be_read() {
+    let config_dir = tempfile::tempdir().unwrap();
+    let template_dir = config_dir.path().join("templates");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(config_dir.path().join("outside.kdl"), TEMPLATE).unwrap();
+    for name in ["../outside", "..", "a/b", "a\\b", ".hidden", ""] {
+        let error = session_template(name, Some(template_dir.clone()), None)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("Invalid template name"), "{}", error);
+    }
+}
@@ -1,7 +1,7 @@
 //! IPC stuff for starting to split things into a client and server model.
 use crate::{
     cli::CliArgs,
-    data::{ClientId, ConnectToSession, KeyWithModifier, Style},
+    data::{ClientId, ConnectToSession, KeyWithModifier, SessionEvent, Style},
     errors::{get_current_ctx, prelude::*, ErrorContext},
     input::config::Config,
     input::{actions::Action, layout::Layout, options::Options, plugins::PluginAliases},
@@ -42,6 +42,16 @@ pub enum ClientType {
 pub struct ClientAttributes {
     pub size: Size,
     pub style: Style,
+    /// read-only clients can watch the session but their input and actions are dropped
+    #[serde(default)]
+    pub read_only: bool,
+    /// the name of the login token a web client authenticated with, its actions are audited
+    #[serde(default)]
+    pub token_name: Option<String>,
+    /// the name other users of the session see for this client (in pane frames and the status
+    /// bar), either given with `--user` or the name of the web login token
+    #[serde(default)]
+    pub user_name: Option<String>,
 }
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -115,6 +125,10 @@ pub enum ServerToClientMsg {
     QueryTerminalSize,
     WriteConfigToDisk { config: String },
     StartWebServer,
+    DownloadFile(PathBuf),      // only sent to web clients
+    PaneExited(Option<i32>),    // exit status, only sent to clients waiting with `swarm wait`
+    SessionEvent(SessionEvent), // only sent to clients streaming them with `swarm subscribe`
+    LayoutApplied,              // every tab of the layout the client started has been applied
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1,10 +1,11 @@
+use crate::envs::EnvironmentVariables;
 use crate::input::{
     command::RunCommand,
     config::ConfigError,
     layout::{
-        FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, PluginUserConfiguration, Run,
-        RunPluginOrAlias, SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout,
-        TiledPaneLayout,
+        FloatingPaneLayout, Layout, LayoutCondition, LayoutConditionContext, LayoutConstraint,
+        PercentOrFixed, PluginUserConfiguration, Run, RunPluginOrAlias, SplitDirection, SplitSize,
+        SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
 };
 
@@ -13,6 +14,8 @@ use kdl::*;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
 
+use super::kdl_layout_error;
+use crate::home::default_layout_dir;
 use crate::{
     kdl_child_with_name, kdl_children_nodes, kdl_first_entry_as_bool, kdl_first_entry_as_i64,
     kdl_first_entry_as_string, kdl_get_bool_property_or_child_value,
@@ -23,7 +26,7 @@ use crate::{
     kdl_string_arguments,
 };
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::vec::Vec;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,6 +36,17 @@ pub enum PaneOrFloatingPane {
     Either(TiledPaneLayout),
 }
 
+// imported nodes are moved past this offset so that errors in them can be told apart from errors
+// in the importing file and reported against the imported file instead
+const IMPORTED_FILES_OFFSET: usize = usize::MAX / 2;
+
+struct ImportedLayoutFile {
+    path: PathBuf, // canonicalized, to import each file only once
+    file_name: String,
+    raw_layout: String,
+    nodes: Vec<KdlNode>,
+}
+
 pub struct KdlLayoutParser<'a> {
     global_cwd: Option<PathBuf>,
     raw_layout: &'a str,
@@ -41,6 +55,12 @@ pub struct KdlLayoutParser<'a> {
     default_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>, KdlNode)>,
     new_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     file_name: Option<PathBuf>,
+    layout_args: BTreeMap<String, String>,
+    layout_params: BTreeMap<String, String>, // param name -> resolved value
+    condition_context: Option<LayoutConditionContext>, // populated the first time we meet a condition
+    layout_dir: Option<PathBuf>, // where to look for imported files not next to the importing file
+    import_stack: Vec<PathBuf>,  // the files currently being imported, to detect cycles
+    imported_sources: Vec<(usize, String, String)>, // (offset of its nodes, file name, contents)
 }
 
 impl<'a> KdlLayoutParser<'a> {
@@ -48,6 +68,8 @@ impl<'a> KdlLayoutParser<'a> {
         raw_layout: &'a str,
         global_cwd: Option<PathBuf>,
         file_name: Option<String>,
+        layout_args: BTreeMap<String, String>,
+        layout_dir: Option<PathBuf>,
     ) -> Self {
         KdlLayoutParser {
             raw_layout,
@@ -57,6 +79,12 @@ impl<'a> KdlLayoutParser<'a> {
             new_tab_template: None,
             global_cwd,
             file_name: file_name.map(|f| PathBuf::from(f)),
+            layout_args,
+            layout_params: BTreeMap::new(),
+            condition_context: None,
+            layout_dir,
+            import_stack: vec![],
+            imported_sources: vec![],
         }
     }
     fn is_a_reserved_word(&self, word: &str) -> bool {
@@ -86,6 +114,8 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "swap_floating_layout"
             || word == "hide_floating_panes"
             || word == "contents_file"
+            || word == "param"
+            || word == "when"
     }
     fn is_a_valid_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -106,6 +136,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
             || property_name == "contents_file"
+            || property_name == "env"
     }
     fn is_a_valid_floating_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -124,6 +155,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "height"
             || property_name == "pinned"
             || property_name == "contents_file"
+            || property_name == "env"
     }
     fn is_a_valid_tab_property(&self, property_name: &str) -> bool {
         property_name == "focus"
@@ -136,6 +168,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "min_panes"
             || property_name == "exact_panes"
             || property_name == "hide_floating_panes"
+            || property_name == "env"
     }
     pub fn is_a_reserved_plugin_property(property_name: &str) -> bool {
         property_name == "location"
@@ -424,6 +457,27 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_env(&self, kdl_node: &KdlNode) -> Result<BTreeMap<String, String>, ConfigError> {
+        match kdl_get_child!(kdl_node, "env") {
+            Some(env_node) => Ok(EnvironmentVariables::from_kdl(env_node)?
+                .inner()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+    fn add_env_to_run(&self, run: &mut Option<Run>, env: &BTreeMap<String, String>) {
+        // the env of a pane consuming a pane_template overrides that of the template, so it is
+        // placed on the consumer's run instruction before the two are merged
+        if env.is_empty() {
+            return;
+        }
+        match run.as_mut() {
+            Some(run) => run.add_env(env),
+            None => *run = Some(Run::Shell(None, env.clone())),
+        }
+    }
     fn parse_pane_command(
         &self,
         pane_node: &KdlNode,
@@ -536,6 +590,7 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
         let split_size = self.parse_split_size(kdl_node)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let (external_children_index, children) = match kdl_children_nodes!(kdl_node) {
             Some(children) => {
@@ -572,7 +627,7 @@ impl<'a> KdlLayoutParser<'a> {
                     std::fs::read_to_string(parent_folder.join(contents_file)).ok()
                 })
         });
-        Ok(TiledPaneLayout {
+        let mut pane_layout = TiledPaneLayout {
             borderless: borderless.unwrap_or_default(),
             focus,
             name,
@@ -586,7 +641,9 @@ impl<'a> KdlLayoutParser<'a> {
             is_expanded_in_stack,
             pane_initial_contents,
             ..Default::default()
-        })
+        };
+        pane_layout.add_env_to_layout(&env);
+        Ok(pane_layout)
     }
     fn parse_floating_pane_node(
         &self,
@@ -599,6 +656,7 @@ impl<'a> KdlLayoutParser<'a> {
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
         let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
             .map(|name| name.to_string());
@@ -613,7 +671,7 @@ impl<'a> KdlLayoutParser<'a> {
                     std::fs::read_to_string(parent_folder.join(contents_file)).ok()
                 })
         });
-        Ok(FloatingPaneLayout {
+        let mut floating_pane_layout = FloatingPaneLayout {
             name,
             height,
             width,
@@ -624,7 +682,9 @@ impl<'a> KdlLayoutParser<'a> {
             pinned,
             pane_initial_contents,
             ..Default::default()
-        })
+        };
+        floating_pane_layout.add_env_to_layout(&env);
+        Ok(floating_pane_layout)
     }
     fn insert_children_to_pane_template(
         &self,
@@ -716,7 +776,8 @@ impl<'a> KdlLayoutParser<'a> {
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let split_size = self.parse_split_size(kdl_node)?;
-                let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let mut run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let exclude_from_sync =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
 
@@ -738,6 +799,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &mut pane_template,
                     pane_template_kdl_node,
                 )?;
+                self.add_env_to_run(&mut run, &env);
                 pane_template.run = Run::merge(&pane_template.run, &run);
                 if let Some(pane_template_run_command) = pane_template.run.as_mut() {
                     // we need to do this because panes consuming a pane_template
@@ -746,6 +808,9 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
+                for child in pane_template.children.iter_mut() {
+                    child.add_env_to_layout(&env);
+                }
                 if let Some(borderless) = borderless {
                     pane_template.borderless = borderless;
                 }
@@ -821,7 +886,8 @@ impl<'a> KdlLayoutParser<'a> {
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
-                let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let mut run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
                     &pane_template.run,
@@ -830,6 +896,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &start_suspended,
                     kdl_node,
                 )?;
+                self.add_env_to_run(&mut run, &env);
                 pane_template.run = Run::merge(&pane_template.run, &run);
                 if let Some(pane_template_run_command) = pane_template.run.as_mut() {
                     // we need to do this because panes consuming a pane_template
@@ -875,7 +942,8 @@ impl<'a> KdlLayoutParser<'a> {
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
-                let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let mut run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
                     &pane_template.run,
@@ -884,6 +952,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &start_suspended,
                     kdl_node,
                 )?;
+                self.add_env_to_run(&mut run, &env);
                 pane_template.run = Run::merge(&pane_template.run, &run);
                 if let Some(pane_template_run_command) = pane_template.run.as_mut() {
                     // we need to do this because panes consuming a pane_template
@@ -1069,7 +1138,9 @@ impl<'a> KdlLayoutParser<'a> {
         self.assert_legal_node_name(&template_name, kdl_node)?;
         self.assert_legal_template_name(&template_name, kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
-        let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let mut run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
+        self.add_env_to_run(&mut run, &env);
 
         let is_floating = self.differentiate_pane_and_floating_pane_template(&kdl_node)?;
         let can_be_either_floating_or_tiled =
@@ -1124,13 +1195,16 @@ impl<'a> KdlLayoutParser<'a> {
                     .unwrap_or(false);
             let split_size = self.parse_split_size(kdl_node)?;
             let children_split_direction = self.parse_split_direction(kdl_node)?;
-            let (external_children_index, pane_parts) = match kdl_children_nodes!(kdl_node) {
+            let (external_children_index, mut pane_parts) = match kdl_children_nodes!(kdl_node) {
                 Some(children) => {
                     self.parse_child_pane_nodes_for_pane(&children, children_are_stacked)?
                 },
                 None => (None, vec![]),
             };
             self.assert_no_mixed_children_and_properties(kdl_node)?;
+            for pane_part in pane_parts.iter_mut() {
+                pane_part.add_env_to_layout(&env);
+            }
             self.pane_templates.insert(
                 template_name,
                 (
@@ -1198,6 +1272,11 @@ impl<'a> KdlLayoutParser<'a> {
                 floating_pane.add_cwd_to_layout(&cwd_prefix);
             }
         }
+        let tab_env = self.parse_env(kdl_node)?;
+        pane_layout.add_env_to_layout(&tab_env);
+        for floating_pane in child_floating_panes.iter_mut() {
+            floating_pane.add_env_to_layout(&tab_env);
+        }
         Ok((is_focused, tab_name, pane_layout, child_floating_panes))
     }
     fn parse_child_pane_nodes_for_tab(
@@ -1222,6 +1301,9 @@ impl<'a> KdlLayoutParser<'a> {
                 )?);
             } else if kdl_name!(child) == "floating_panes" {
                 self.populate_floating_pane_children(child, child_floating_panes)?;
+            } else if kdl_name!(child) == "env" {
+                // parsed separately and applied to all panes of the tab
+                continue;
             } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                 return Err(ConfigError::new_layout_kdl_error(
                     format!("Tab property '{}' must be placed on the tab title line and not in the child braces", kdl_name!(child)),
@@ -1607,6 +1689,11 @@ impl<'a> KdlLayoutParser<'a> {
                 floating_pane.add_cwd_to_layout(&cwd_prefix);
             }
         }
+        let tab_env = self.parse_env(kdl_node)?;
+        tab_layout.add_env_to_layout(&tab_env);
+        for floating_pane in tab_template_floating_panes.iter_mut() {
+            floating_pane.add_env_to_layout(&tab_env);
+        }
         tab_layout.external_children_index = None;
         Ok((
             is_focused,
@@ -1701,6 +1788,9 @@ impl<'a> KdlLayoutParser<'a> {
                 } else if kdl_name!(child) == "floating_panes" {
                     children_index_offset += 1;
                     self.populate_floating_pane_children(child, &mut tab_floating_children)?;
+                } else if kdl_name!(child) == "env" {
+                    // applied to the panes of the template below
+                    children_index_offset += 1;
                 } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                     return Err(ConfigError::new_layout_kdl_error(
                         format!("Tab property '{}' must be placed on the tab_template title line and not in the child braces", kdl_name!(child)),
@@ -1716,6 +1806,13 @@ impl<'a> KdlLayoutParser<'a> {
                 }
             }
         }
+        let tab_env = self.parse_env(kdl_node)?;
+        for tab_child in tab_children.iter_mut() {
+            tab_child.add_env_to_layout(&tab_env);
+        }
+        for floating_child in tab_floating_children.iter_mut() {
+            floating_child.add_env_to_layout(&tab_env);
+        }
         Ok((
             TiledPaneLayout {
                 children_split_direction,
@@ -2320,11 +2417,22 @@ impl<'a> KdlLayoutParser<'a> {
         Ok(())
     }
     pub fn parse_external_swap_layouts(
+        &mut self,
+        raw_swap_layouts: &str,
+        existing_layout: Layout,
+    ) -> Result<Layout, ConfigError> {
+        // the swap layouts might use templates imported from other files
+        self.parse_swap_layouts(raw_swap_layouts, existing_layout)
+            .map_err(|e| self.error_in_imported_file(e))
+    }
+    fn parse_swap_layouts(
         &mut self,
         raw_swap_layouts: &str,
         mut existing_layout: Layout,
     ) -> Result<Layout, ConfigError> {
-        let kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        let mut kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        self.substitute_layout_params(kdl_swap_layout.nodes_mut());
+        self.apply_layout_conditions(kdl_swap_layout.nodes_mut())?;
         let mut swap_tiled_layouts = vec![];
         let mut swap_floating_layouts = vec![];
 
@@ -2367,8 +2475,522 @@ impl<'a> KdlLayoutParser<'a> {
             .append(&mut swap_floating_layouts);
         Ok(existing_layout)
     }
+    fn populate_layout_params(&mut self, kdl_layout: &KdlDocument) -> Result<(), ConfigError> {
+        let layout_node = match kdl_layout.nodes().iter().find(|n| kdl_name!(n) == "layout") {
+            Some(layout_node) => layout_node,
+            None => return Ok(()), // this will be reported when parsing the layout
+        };
+        let mut layout_params = BTreeMap::new();
+        for param_node in kdl_children_nodes!(layout_node)
+            .map(|c| c.iter().filter(|n| kdl_name!(n) == "param").collect())
+            .unwrap_or_else(|| vec![])
+        {
+            let param_name = kdl_first_entry_as_string!(param_node)
+                .filter(|n| {
+                    !n.is_empty()
+                        && n.chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                })
+                .ok_or(ConfigError::new_layout_kdl_error(
+                    "Layout parameters must have a name made of letters, numbers, '_' or '-' (eg. param \"project\")".into(),
+                    param_node.span().offset(),
+                    param_node.span().len(),
+                ))?;
+            if layout_params.contains_key(param_name) {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!(
+                        "Duplicate definition of the \"{}\" layout parameter",
+                        param_name
+                    ),
+                    param_node.span().offset(),
+                    param_node.span().len(),
+                ));
+            }
+            let param_type = kdl_get_string_property_or_child_value_with_error!(param_node, "type")
+                .unwrap_or("string");
+            let default_value = param_node.get("default").map(|e| match e.value() {
+                KdlValue::String(s) | KdlValue::RawString(s) => s.clone(),
+                value => value.to_string(),
+            });
+            let value = self
+                .layout_args
+                .get(param_name)
+                .cloned()
+                .or(default_value)
+                .ok_or(ConfigError::new_layout_kdl_error(
+                    format!(
+                        "Missing value for layout parameter \"{}\", set it with --layout-arg {}=<value> or give it a default",
+                        param_name, param_name
+                    ),
+                    param_node.span().offset(),
+                    param_node.span().len(),
+                ))?;
+            let value_is_valid = match param_type {
+                "string" => true,
+                "int" => value.parse::<i64>().is_ok(),
+                "bool" => value == "true" || value == "false",
+                _ => {
+                    return Err(ConfigError::new_layout_kdl_error(
+                        format!(
+                            "Unknown type \"{}\" for layout parameter \"{}\", expected one of: string, int, bool",
+                            param_type, param_name
+                        ),
+                        param_node.span().offset(),
+                        param_node.span().len(),
+                    ));
+                },
+            };
+            if !value_is_valid {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!(
+                        "Layout parameter \"{}\" must be of type {}, got: \"{}\"",
+                        param_name, param_type, value
+                    ),
+                    param_node.span().offset(),
+                    param_node.span().len(),
+                ));
+            }
+            layout_params.insert(param_name.to_owned(), value);
+        }
+        if let Some(unknown_arg) = self
+            .layout_args
+            .keys()
+            .find(|arg| !layout_params.contains_key(*arg))
+        {
+            let known_params: Vec<&str> = layout_params.keys().map(|p| p.as_str()).collect();
+            return Err(ConfigError::new_layout_kdl_error(
+                if known_params.is_empty() {
+                    format!(
+                        "Unknown layout argument \"{}\", this layout does not declare any parameters",
+                        unknown_arg
+                    )
+                } else {
+                    format!(
+                        "Unknown layout argument \"{}\", this layout accepts: {}",
+                        unknown_arg,
+                        known_params.join(", ")
+                    )
+                },
+                layout_node.span().offset(),
+                layout_node.span().len(),
+            ));
+        }
+        self.layout_params = layout_params;
+        Ok(())
+    }
+    fn substitute_layout_params(&self, nodes: &mut [KdlNode]) {
+        // replaces ${param_name} in all string values with the resolved parameter value, leaving
+        // the spans in place so that errors still point to the right place in the file. This is
+        // done the same way whether or not the layout declares parameters: references to
+        // undeclared names (eg. ${HOME} in a shell command) are left as they are and $${name} is
+        // always unescaped to a literal ${name}
+        for node in nodes.iter_mut() {
+            for entry in node.entries_mut().iter_mut() {
+                let substituted = match entry.value() {
+                    KdlValue::String(s) | KdlValue::RawString(s) if s.contains("${") => {
+                        Some(substitute_params_in_string(s, &self.layout_params))
+                    },
+                    _ => None,
+                };
+                if let Some(substituted) = substituted {
+                    let value = KdlValue::String(substituted);
+                    entry.set_value_repr(value.to_string());
+                    entry.set_value(value);
+                }
+            }
+            if let Some(children) = node.children_mut().as_mut() {
+                self.substitute_layout_params(children.nodes_mut());
+            }
+        }
+    }
+    fn parse_when_node(&self, when_node: &KdlNode) -> Result<Vec<LayoutCondition>, ConfigError> {
+        let mut conditions = vec![];
+        for entry in when_node.entries() {
+            let condition_name =
+                entry
+                    .name()
+                    .map(|n| n.value())
+                    .ok_or(ConfigError::new_layout_kdl_error(
+                        "Conditions should be properties, eg. when file_exists=\"Cargo.toml\""
+                            .into(),
+                        entry.span().offset(),
+                        entry.span().len(),
+                    ))?;
+            let string_value = || {
+                entry.value().as_string().map(|s| s.to_owned()).ok_or(
+                    ConfigError::new_layout_kdl_error(
+                        format!("The \"{}\" condition expects a string", condition_name),
+                        entry.span().offset(),
+                        entry.span().len(),
+                    ),
+                )
+            };
+            let size_value = || {
+                entry
+                    .value()
+                    .as_i64()
+                    .filter(|v| *v >= 0)
+                    .map(|v| v as usize)
+                    .ok_or(ConfigError::new_layout_kdl_error(
+                        format!(
+                            "The \"{}\" condition expects a positive number",
+                            condition_name
+                        ),
+                        entry.span().offset(),
+                        entry.span().len(),
+                    ))
+            };
+            conditions.push(match condition_name {
+                "file_exists" => LayoutCondition::FileExists(PathBuf::from(string_value()?)),
+                "env_set" => LayoutCondition::EnvSet(string_value()?),
+                "hostname" => LayoutCondition::Hostname(string_value()?),
+                "min_cols" => LayoutCondition::MinCols(size_value()?),
+                "max_cols" => LayoutCondition::MaxCols(size_value()?),
+                "min_rows" => LayoutCondition::MinRows(size_value()?),
+                "max_rows" => LayoutCondition::MaxRows(size_value()?),
+                _ => {
+                    return Err(ConfigError::new_layout_kdl_error(
+                        format!(
+                            "Unknown condition \"{}\", expected one of: file_exists, env_set, hostname, min_cols, max_cols, min_rows, max_rows",
+                            condition_name
+                        ),
+                        entry.span().offset(),
+                        entry.span().len(),
+                    ));
+                },
+            });
+        }
+        if conditions.is_empty() {
+            return Err(ConfigError::new_layout_kdl_error(
+                "A \"when\" node must have at least one condition, eg. when file_exists=\"Cargo.toml\"".into(),
+                when_node.span().offset(),
+                when_node.span().len(),
+            ));
+        }
+        Ok(conditions)
+    }
+    fn node_conditions_are_met(&mut self, node: &mut KdlNode) -> Result<bool, ConfigError> {
+        // a node is kept if any of its "when" children has all its conditions met, these children
+        // are then removed so that the rest of the parser never sees them
+        let mut condition_groups = vec![];
+        if let Some(children) = node.children_mut().as_mut() {
+            for when_node in children.nodes().iter().filter(|n| kdl_name!(n) == "when") {
+                condition_groups.push(self.parse_when_node(when_node)?);
+            }
+            if !condition_groups.is_empty() {
+                children.nodes_mut().retain(|n| kdl_name!(n) != "when");
+            }
+        }
+        if condition_groups.is_empty() {
+            return Ok(true);
+        }
+        if node
+            .children()
+            .map(|c| c.nodes().is_empty())
+            .unwrap_or(false)
+        {
+            node.clear_children();
+        }
+        let context = self
+            .condition_context
+            .get_or_insert_with(|| LayoutConditionContext::new(self.global_cwd.clone()));
+        Ok(condition_groups
+            .iter()
+            .any(|conditions| conditions.iter().all(|c| c.is_met(context))))
+    }
+    fn apply_layout_conditions(&mut self, nodes: &mut Vec<KdlNode>) -> Result<(), ConfigError> {
+        let mut index = 0;
+        while index < nodes.len() {
+            let node = &mut nodes[index];
+            let node_name = kdl_name!(node);
+            if node_name == "when" {
+                return Err(ConfigError::new_layout_kdl_error(
+                    "Conditions can only be placed inside panes, floating panes or tabs".into(),
+                    node.span().offset(),
+                    node.span().len(),
+                ));
+            }
+            let is_template_definition = node_name == "pane_template"
+                || node_name == "tab_template"
+                || node_name == "default_tab_template"
+                || node_name == "new_tab_template"
+                || node_name == "swap_tiled_layout"
+                || node_name == "swap_floating_layout";
+            // the children of these nodes are user defined values rather than layout nodes
+            let has_free_form_children = node_name == "plugin"
+                || node_name == "env"
+                || node_name == "args"
+                || node_name == "param";
+            if has_free_form_children {
+                index += 1;
+                continue;
+            }
+            if is_template_definition {
+                if let Some(when_node) = kdl_children_nodes!(node)
+                    .and_then(|c| c.iter().find(|n| kdl_name!(n) == "when"))
+                {
+                    return Err(ConfigError::new_layout_kdl_error(
+                        "Conditions cannot be placed directly in templates or swap layouts, place them in the panes or tabs inside them".into(),
+                        when_node.span().offset(),
+                        when_node.span().len(),
+                    ));
+                }
+            } else if !self.node_conditions_are_met(node)? {
+                nodes.remove(index);
+                continue;
+            }
+            if let Some(children) = nodes[index].children_mut().as_mut() {
+                self.apply_layout_conditions(children.nodes_mut())?;
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+    fn resolve_import_path(&self, import_path: &str) -> Option<PathBuf> {
+        let import_path = PathBuf::from(import_path);
+        let importing_file_dir = self
+            .file_name
+            .as_ref()
+            .and_then(|f| f.parent())
+            .filter(|d| d.is_dir())
+            .map(|d| d.to_path_buf());
+        let search_dirs = if import_path.is_absolute() {
+            vec![PathBuf::new()]
+        } else {
+            importing_file_dir
+                .into_iter()
+                .chain(self.layout_dir.clone().or_else(default_layout_dir))
+                .collect()
+        };
+        search_dirs.iter().find_map(|dir| {
+            let candidate = dir.join(&import_path);
+            if candidate.is_file() {
+                Some(candidate)
+            } else if candidate.extension().is_none() && candidate.with_extension("kdl").is_file() {
+                Some(candidate.with_extension("kdl"))
+            } else {
+                None
+            }
+        })
+    }
+    fn import_layout_files(
+        &mut self,
+        kdl_document: &KdlDocument,
+    ) -> Result<Vec<ImportedLayoutFile>, ConfigError> {
+        // returns the importable nodes of every (transitively) imported file, dependencies first and
+        // each file only once
+        let mut imported_files: Vec<ImportedLayoutFile> = vec![];
+        for import_node in kdl_document
+            .nodes()
+            .iter()
+            .filter(|n| kdl_name!(n) == "import")
+        {
+            let import_path = kdl_first_entry_as_string!(import_node).ok_or(
+                ConfigError::new_layout_kdl_error(
+                    "Imports must have a path, eg. import \"common.kdl\"".into(),
+                    import_node.span().offset(),
+                    import_node.span().len(),
+                ),
+            )?;
+            let resolved_path =
+                self.resolve_import_path(import_path)
+                    .ok_or(ConfigError::new_layout_kdl_error(
+                        format!(
+                            "Could not find \"{}\" next to this layout or in the layout folder",
+                            import_path
+                        ),
+                        import_node.span().offset(),
+                        import_node.span().len(),
+                    ))?;
+            let canonical_path = resolved_path
+                .canonicalize()
+                .unwrap_or_else(|_| resolved_path.clone());
+            if self.import_stack.contains(&canonical_path) {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!("Circular import of \"{}\"", import_path),
+                    import_node.span().offset(),
+                    import_node.span().len(),
+                ));
+            }
+            let raw_import = std::fs::read_to_string(&resolved_path)
+                .map_err(|e| ConfigError::IoPath(e, resolved_path.clone()))?;
+            let import_file_name = resolved_path.to_string_lossy().to_string();
+            let nested_imports = self
+                .parse_imported_layout_file(&raw_import, &import_file_name, &canonical_path)
+                .map_err(|e| match e {
+                    ConfigError::KdlError(kdl_error) => ConfigError::KdlError(
+                        kdl_error.add_src(import_file_name.clone(), raw_import.clone()),
+                    ),
+                    ConfigError::KdlDeserializationError(kdl_error) => {
+                        kdl_layout_error(kdl_error, import_file_name.clone(), &raw_import)
+                    },
+                    e => e,
+                })?;
+            for imported_file in nested_imports {
+                if !imported_files.iter().any(|f| f.path == imported_file.path) {
+                    imported_files.push(imported_file);
+                }
+            }
+        }
+        Ok(imported_files)
+    }
+    fn parse_imported_layout_file(
+        &self,
+        raw_import: &str,
+        import_file_name: &str,
+        canonical_path: &Path,
+    ) -> Result<Vec<ImportedLayoutFile>, ConfigError> {
+        let mut kdl_import: KdlDocument = raw_import.parse()?;
+        for node in kdl_import.nodes() {
+            let node_name = kdl_name!(node);
+            if node_name != "import"
+                && node_name != "pane_template"
+                && node_name != "tab_template"
+                && node_name != "swap_tiled_layout"
+                && node_name != "swap_floating_layout"
+            {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!("Imported files can only contain pane_template, tab_template, swap_tiled_layout, swap_floating_layout or import nodes, found: {}", node_name),
+                    node.span().offset(),
+                    node.span().len(),
+                ));
+            }
+        }
+        // the imported file is parsed on its own first, so that errors in it are reported against
+        // the right file
+        let mut import_parser = KdlLayoutParser::new(
+            raw_import,
+            self.global_cwd.clone(),
+            Some(import_file_name.to_owned()),
+            BTreeMap::new(),
+            self.layout_dir.clone(),
+        );
+        import_parser.layout_params = self.layout_params.clone();
+        import_parser.import_stack = self.import_stack.clone();
+        import_parser
+            .import_stack
+            .push(canonical_path.to_path_buf());
+        import_parser.substitute_layout_params(kdl_import.nodes_mut());
+        import_parser.apply_layout_conditions(kdl_import.nodes_mut())?;
+        let mut imported_files = import_parser.import_layout_files(&kdl_import)?;
+        let own_nodes: Vec<KdlNode> = kdl_import
+            .nodes()
+            .iter()
+            .filter(|n| kdl_name!(n) != "import")
+            .cloned()
+            .collect();
+        let all_nodes: Vec<KdlNode> = import_parser
+            .move_imported_nodes(&imported_files)
+            .into_iter()
+            .chain(own_nodes.iter().cloned())
+            .collect();
+        import_parser
+            .populate_imported_nodes(&all_nodes, &kdl_import)
+            .map_err(|e| import_parser.error_in_imported_file(e))?;
+        imported_files.push(ImportedLayoutFile {
+            path: canonical_path.to_path_buf(),
+            file_name: import_file_name.to_owned(),
+            raw_layout: raw_import.to_owned(),
+            nodes: own_nodes,
+        });
+        Ok(imported_files)
+    }
+    fn populate_imported_nodes(
+        &mut self,
+        nodes: &[KdlNode],
+        kdl_import: &KdlDocument,
+    ) -> Result<(), ConfigError> {
+        self.populate_pane_templates(nodes, kdl_import)?;
+        self.populate_tab_templates(nodes)?;
+        self.populate_swap_tiled_layouts(nodes, &mut vec![])?;
+        self.populate_swap_floating_layouts(nodes, &mut vec![])?;
+        Ok(())
+    }
+    fn move_imported_nodes(&mut self, imported_files: &[ImportedLayoutFile]) -> Vec<KdlNode> {
+        // returns the nodes of the imported files with their spans moved past
+        // IMPORTED_FILES_OFFSET, remembering where each file went so that errors can be reported
+        // against it
+        let mut moved_nodes = vec![];
+        for imported_file in imported_files {
+            let offset = self
+                .imported_sources
+                .last()
+                .map(|(offset, _, raw_layout)| offset + raw_layout.len() + 1)
+                .unwrap_or(IMPORTED_FILES_OFFSET);
+            self.imported_sources.push((
+                offset,
+                imported_file.file_name.clone(),
+                imported_file.raw_layout.clone(),
+            ));
+            for node in &imported_file.nodes {
+                let mut node = node.clone();
+                move_node_spans(&mut node, offset);
+                moved_nodes.push(node);
+            }
+        }
+        moved_nodes
+    }
+    fn error_in_imported_file(&self, error: ConfigError) -> ConfigError {
+        match error {
+            ConfigError::KdlError(mut kdl_error) if kdl_error.src.is_none() => {
+                let imported_source = kdl_error.offset.and_then(|error_offset| {
+                    self.imported_sources
+                        .iter()
+                        .find(|(offset, _, raw_layout)| {
+                            error_offset >= *offset && error_offset <= offset + raw_layout.len()
+                        })
+                });
+                match imported_source {
+                    Some((offset, file_name, raw_layout)) => {
+                        kdl_error.offset = kdl_error.offset.map(|o| o - offset);
+                        ConfigError::KdlError(
+                            kdl_error.add_src(file_name.clone(), raw_layout.clone()),
+                        )
+                    },
+                    None => ConfigError::KdlError(kdl_error),
+                }
+            },
+            e => e,
+        }
+    }
     pub fn parse(&mut self) -> Result<Layout, ConfigError> {
-        let kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        self.parse_layout()
+            .map_err(|e| self.error_in_imported_file(e))
+    }
+    fn parse_layout(&mut self) -> Result<Layout, ConfigError> {
+        let mut kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        self.populate_layout_params(&kdl_layout)?;
+        self.substitute_layout_params(kdl_layout.nodes_mut());
+        if let Some(layout_node) = kdl_layout
+            .nodes_mut()
+            .iter_mut()
+            .find(|n| kdl_name!(n) == "layout")
+        {
+            // conditions are evaluated against the layout cwd, so we need it before anything else
+            self.populate_global_cwd(layout_node)?;
+            if let Some(children) = layout_node.children_mut().as_mut() {
+                self.apply_layout_conditions(children.nodes_mut())?;
+            }
+        }
+        if let Some(own_path) = self.file_name.as_ref().and_then(|f| f.canonicalize().ok()) {
+            self.import_stack.push(own_path);
+        }
+        let imported_files = self.import_layout_files(&kdl_layout)?;
+        let imported_nodes = self.move_imported_nodes(&imported_files);
+        if !imported_nodes.is_empty() {
+            if let Some(layout_node) = kdl_layout
+                .nodes_mut()
+                .iter_mut()
+                .find(|n| kdl_name!(n) == "layout")
+            {
+                // imported nodes come first so that duplicate definitions are reported in this file
+                let layout_children = layout_node
+                    .children_mut()
+                    .get_or_insert_with(KdlDocument::new)
+                    .nodes_mut();
+                layout_children.splice(0..0, imported_nodes);
+            }
+        }
         let layout_node = kdl_layout
             .nodes()
             .iter()
@@ -2469,3 +3091,48 @@ impl<'a> KdlLayoutParser<'a> {
         }
     }
 }
+
+// references to undeclared parameters are left as they are, $${name} is left as a literal ${name}
+fn substitute_params_in_string(value: &str, layout_params: &BTreeMap<String, String>) -> String {
+    let mut substituted = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            substituted.push_str(&rest[..start - 1]);
+            substituted.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        substituted.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            substituted.push_str(&rest[start..]);
+            return substituted;
+        };
+        match layout_params.get(&rest[start + 2..end]) {
+            Some(param_value) => substituted.push_str(param_value),
+            None => substituted.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    substituted.push_str(rest);
+    substituted
+}
+
+fn move_node_spans(node: &mut KdlNode, offset: usize) {
+    let span = *node.span();
+    node.set_span((span.offset() + offset, span.len()));
+    let name_span = *node.name().span();
+    node.name_mut()
+        .set_span((name_span.offset() + offset, name_span.len()));
+    for entry in node.entries_mut().iter_mut() {
+        let entry_span = *entry.span();
+        entry.set_span((entry_span.offset() + offset, entry_span.len()));
+    }
+    if let Some(children) = node.children_mut().as_mut() {
+        let children_span = *children.span();
+        children.set_span((children_span.offset() + offset, children_span.len()));
+        for child in children.nodes_mut().iter_mut() {
+            move_node_spans(child, offset);
+        }
+    }
+}
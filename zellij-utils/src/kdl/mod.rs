@@ -1,8 +1,9 @@
 mod kdl_layout_parser;
 use crate::data::{
     BareKey, Direction, FloatingPaneCoordinates, InputMode, KeyWithModifier, LayoutInfo,
-    MultiplayerColors, Palette, PaletteColor, PaneInfo, PaneManifest, PermissionType, Resize,
-    SessionInfo, StyleDeclaration, Styling, TabInfo, WebSharing, DEFAULT_STYLES,
+    MultiplayerColors, Palette, PaletteColor, PaneInfo, PaneManifest, PathAccess, PathPermission,
+    PermissionType, Resize, SessionInfo, StyleDeclaration, Styling, TabInfo, WebSharing,
+    DEFAULT_STYLES,
 };
 use crate::envs::EnvironmentVariables;
 use crate::home::{find_default_config_dir, get_layout_dir};
@@ -12,7 +13,7 @@ use crate::input::layout::{
     Layout, PluginUserConfiguration, RunPlugin, RunPluginOrAlias, SplitSize,
 };
 use crate::input::options::{Clipboard, OnForceClose, Options};
-use crate::input::permission::{GrantedPermission, PermissionCache};
+use crate::input::permission::{GrantedPaths, GrantedPermission, PermissionCache};
 use crate::input::plugins::PluginAliases;
 use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
 use crate::input::web_client::WebClientConfig;
@@ -530,7 +531,7 @@ impl Action {
             },
             "MovePaneBackwards" => Ok(Action::MovePaneBackwards),
             "DumpScreen" => Ok(Action::DumpScreen(string, false)),
-            "DumpLayout" => Ok(Action::DumpLayout),
+            "DumpLayout" => Ok(Action::DumpLayout(false)),
             "NewPane" => {
                 if string.is_empty() {
                     return Ok(Action::NewPane(None, None, false));
@@ -659,7 +660,7 @@ impl Action {
                 node.push(file.clone());
                 Some(node)
             },
-            Action::DumpLayout => Some(KdlNode::new("DumpLayout")),
+            Action::DumpLayout(_) => Some(KdlNode::new("DumpLayout")),
             Action::EditScrollback => Some(KdlNode::new("EditScrollback")),
             Action::ScrollUp => Some(KdlNode::new("ScrollUp")),
             Action::ScrollDown => Some(KdlNode::new("ScrollDown")),
@@ -1500,21 +1501,22 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     .clone()
                     .or_else(|| get_layout_dir(find_default_config_dir()));
                 let (path_to_raw_layout, raw_layout, swap_layouts) =
-                    Layout::stringified_from_path_or_default(layout.as_ref(), layout_dir).map_err(
-                        |e| {
-                            ConfigError::new_kdl_error(
-                                format!("Failed to load layout: {}", e),
-                                kdl_action.span().offset(),
-                                kdl_action.span().len(),
-                            )
-                        },
-                    )?;
+                    Layout::stringified_from_path_or_default(layout.as_ref(), layout_dir.clone())
+                        .map_err(|e| {
+                        ConfigError::new_kdl_error(
+                            format!("Failed to load layout: {}", e),
+                            kdl_action.span().offset(),
+                            kdl_action.span().len(),
+                        )
+                    })?;
 
                 let layout = Layout::from_str(
                     &raw_layout,
                     path_to_raw_layout,
                     swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())),
                     cwd.clone(),
+                    BTreeMap::new(),
+                    layout_dir,
                 )
                 .map_err(|e| {
                     ConfigError::new_kdl_error(
@@ -2399,6 +2401,35 @@ impl Options {
         let enforce_https_for_localhost =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "enforce_https_for_localhost")
                 .map(|(v, _)| v);
+        let web_server_socket =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "web_server_socket")
+                .map(|(string, _entry)| PathBuf::from(string));
+        let web_server_base_path =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "web_server_base_path")
+                .map(|(string, _entry)| string.to_string());
+        let web_server_trusted_proxies = match kdl_options.get("web_server_trusted_proxies") {
+            Some(kdl_node) => {
+                let mut trusted_proxies = vec![];
+                for entry in kdl_node.entries() {
+                    let trusted_proxy = entry
+                        .value()
+                        .as_string()
+                        .and_then(|string| IpAddr::from_str(string).ok())
+                        .ok_or_else(|| {
+                            kdl_parsing_error!(
+                                format!(
+                                    "Invalid value for web_server_trusted_proxies: '{}'",
+                                    entry.value()
+                                ),
+                                entry
+                            )
+                        })?;
+                    trusted_proxies.push(trusted_proxy);
+                }
+                Some(trusted_proxies)
+            },
+            None => None,
+        };
         let post_command_discovery_hook =
             kdl_property_first_arg_as_string_or_error!(kdl_options, "post_command_discovery_hook")
                 .map(|(hook, _entry)| hook.to_string());
@@ -2442,6 +2473,9 @@ impl Options {
             web_server_cert,
             web_server_key,
             enforce_https_for_localhost,
+            web_server_socket,
+            web_server_base_path,
+            web_server_trusted_proxies,
             post_command_discovery_hook,
         })
     }
@@ -3405,6 +3439,94 @@ impl Options {
             None
         }
     }
+    fn web_server_socket_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// A unix socket the web server should listen on instead of web_server_ip and",
+            "// web_server_port, eg. for a reverse proxy on the same machine to forward to",
+            "// (Requires restart)",
+            "// ",
+        );
+        let create_node = |node_value: &str| -> KdlNode {
+            let mut node = KdlNode::new("web_server_socket");
+            node.push(node_value.to_owned());
+            node
+        };
+        if let Some(web_server_socket) = &self.web_server_socket {
+            let mut node = create_node(&web_server_socket.display().to_string());
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node("/path/to/swarm-web.sock");
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn web_server_base_path_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// A path prefix to serve the web client under, eg. when a reverse proxy forwards",
+            "// https://example.com/swarm to it",
+            "// (Requires restart)",
+            "// ",
+        );
+        let create_node = |node_value: &str| -> KdlNode {
+            let mut node = KdlNode::new("web_server_base_path");
+            node.push(node_value.to_owned());
+            node
+        };
+        if let Some(web_server_base_path) = &self.web_server_base_path {
+            let mut node = create_node(web_server_base_path);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node("/swarm");
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn web_server_trusted_proxies_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Reverse proxies whose X-Forwarded-For and X-Forwarded-Proto headers are trusted to",
+            "// tell the address of web clients for the audit log and whether they connected over",
+            "// HTTPS. When set, the web server may be bound without a certificate, plain HTTP",
+            "// requests that did not come through one of these proxies are then refused",
+            "// (Requires restart)",
+            "// ",
+        );
+        let create_node = |node_values: &[IpAddr]| -> KdlNode {
+            let mut node = KdlNode::new("web_server_trusted_proxies");
+            for node_value in node_values {
+                node.push(node_value.to_string());
+            }
+            node
+        };
+        if let Some(web_server_trusted_proxies) = &self.web_server_trusted_proxies {
+            let mut node = create_node(web_server_trusted_proxies);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(&[IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))]);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
     fn stacked_resize_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
         let comment_text = format!(
             "{}\n{}\n{}\n{}",
@@ -3718,6 +3840,17 @@ impl Options {
         if let Some(web_server_port) = self.web_server_port_to_kdl(add_comments) {
             nodes.push(web_server_port);
         }
+        if let Some(web_server_socket) = self.web_server_socket_to_kdl(add_comments) {
+            nodes.push(web_server_socket);
+        }
+        if let Some(web_server_base_path) = self.web_server_base_path_to_kdl(add_comments) {
+            nodes.push(web_server_base_path);
+        }
+        if let Some(web_server_trusted_proxies) =
+            self.web_server_trusted_proxies_to_kdl(add_comments)
+        {
+            nodes.push(web_server_trusted_proxies);
+        }
         if let Some(post_command_discovery_hook) =
             self.post_command_discovery_hook_to_kdl(add_comments)
         {
@@ -3734,7 +3867,25 @@ impl Layout {
         raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
         cwd: Option<PathBuf>,
     ) -> Result<Self, ConfigError> {
-        let mut kdl_layout_parser = KdlLayoutParser::new(raw_layout, cwd, file_name.clone());
+        Layout::from_kdl_with_args(
+            raw_layout,
+            file_name,
+            raw_swap_layouts,
+            cwd,
+            BTreeMap::new(),
+            None,
+        )
+    }
+    pub fn from_kdl_with_args(
+        raw_layout: &str,
+        file_name: Option<String>,
+        raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
+        cwd: Option<PathBuf>,
+        layout_args: BTreeMap<String, String>, // values for the parameters declared in the layout
+        layout_dir: Option<PathBuf>,           // where to look for imported files
+    ) -> Result<Self, ConfigError> {
+        let mut kdl_layout_parser =
+            KdlLayoutParser::new(raw_layout, cwd, file_name.clone(), layout_args, layout_dir);
         let layout = kdl_layout_parser.parse().map_err(|e| match e {
             ConfigError::KdlError(kdl_error) => ConfigError::KdlError(kdl_error.add_src(
                 file_name.unwrap_or_else(|| "N/A".to_owned()),
@@ -4781,10 +4932,13 @@ impl Themes {
 }
 
 impl PermissionCache {
-    pub fn from_string(raw_string: String) -> Result<GrantedPermission, ConfigError> {
+    pub fn from_string(
+        raw_string: String,
+    ) -> Result<(GrantedPermission, GrantedPaths), ConfigError> {
         let kdl_document: KdlDocument = raw_string.parse()?;
 
         let mut granted_permission = GrantedPermission::default();
+        let mut granted_paths = GrantedPaths::default();
 
         for node in kdl_document.nodes() {
             if let Some(children) = node.children() {
@@ -4797,26 +4951,64 @@ impl PermissionCache {
                         PermissionType::from_str(v).ok()
                     })
                     .collect();
+                let paths: Vec<PathPermission> = children
+                    .nodes()
+                    .iter()
+                    .filter_map(|p| {
+                        let path = kdl_get_string_entry!(p, 0)?;
+                        match kdl_name!(p) {
+                            "ReadOnlyPath" => Some(PathPermission::read_only(path)),
+                            "ReadWritePath" => Some(PathPermission::read_write(path)),
+                            _ => None,
+                        }
+                    })
+                    .collect();
 
-                granted_permission.insert(key.into(), permissions);
+                // plugins that were only granted paths have no permissions to restore
+                if !permissions.is_empty() || paths.is_empty() {
+                    granted_permission.insert(key.into(), permissions);
+                }
+                if !paths.is_empty() {
+                    granted_paths.insert(key.into(), paths);
+                }
             }
         }
 
-        Ok(granted_permission)
+        Ok((granted_permission, granted_paths))
     }
 
-    pub fn to_string(granted: &GrantedPermission) -> String {
+    pub fn to_string(granted: &GrantedPermission, granted_paths: &GrantedPaths) -> String {
         let mut kdl_doucment = KdlDocument::new();
 
-        granted.iter().for_each(|(k, v)| {
+        let plugin_names: BTreeSet<&String> = granted.keys().chain(granted_paths.keys()).collect();
+        plugin_names.iter().for_each(|k| {
             let mut node = KdlNode::new(k.as_str());
             let mut children = KdlDocument::new();
 
-            let permissions: HashSet<PermissionType> = v.clone().into_iter().collect();
+            let permissions: HashSet<PermissionType> = granted
+                .get(*k)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
             permissions.iter().for_each(|f| {
                 let n = KdlNode::new(f.to_string().as_str());
                 children.nodes_mut().push(n);
             });
+            let paths: BTreeSet<PathPermission> = granted_paths
+                .get(*k)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            paths.iter().for_each(|p| {
+                let mut n = match p.access {
+                    PathAccess::ReadOnly => KdlNode::new("ReadOnlyPath"),
+                    PathAccess::ReadWrite => KdlNode::new("ReadWritePath"),
+                };
+                n.push(p.path.display().to_string());
+                children.nodes_mut().push(n);
+            });
 
             node.set_children(children);
             kdl_doucment.nodes_mut().push(node);
@@ -4899,6 +5091,20 @@ impl SessionInfo {
             .and_then(|e| e.value().as_bool())
             .unwrap_or(false);
         let is_current_session = name == current_session_name;
+        let tags = kdl_document
+            .get("tags")
+            .map(|t| {
+                t.entries()
+                    .iter()
+                    .filter_map(|e| e.value().as_string().map(|s| s.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let last_activity = kdl_document
+            .get("last_activity")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_i64())
+            .map(|l| l as u64);
         let mut tab_history = BTreeMap::new();
         if let Some(kdl_tab_history) = kdl_document.get("tab_history").and_then(|p| p.children()) {
             for client_node in kdl_tab_history.nodes() {
@@ -4933,6 +5139,8 @@ impl SessionInfo {
             web_clients_allowed,
             plugins: Default::default(), // we do not serialize plugin information
             tab_history,
+            tags,
+            last_activity,
         })
     }
     pub fn to_string(&self) -> String {
@@ -5005,6 +5213,18 @@ impl SessionInfo {
         kdl_document.nodes_mut().push(web_client_count);
         kdl_document.nodes_mut().push(available_layouts);
         kdl_document.nodes_mut().push(tab_history);
+        if !self.tags.is_empty() {
+            let mut tags = KdlNode::new("tags");
+            for tag in &self.tags {
+                tags.push(tag.clone());
+            }
+            kdl_document.nodes_mut().push(tags);
+        }
+        if let Some(last_activity) = self.last_activity {
+            let mut last_activity_node = KdlNode::new("last_activity");
+            last_activity_node.push(last_activity as i64);
+            kdl_document.nodes_mut().push(last_activity_node);
+        }
         kdl_document.fmt();
         kdl_document.to_string()
     }
@@ -5563,6 +5783,8 @@ fn serialize_and_deserialize_session_info_with_data() {
         web_client_count: 2,
         web_clients_allowed: true,
         tab_history: Default::default(),
+        tags: vec!["agents".to_owned(), "backend".to_owned()],
+        last_activity: Some(1760000000),
     };
     let serialized = session_info.to_string();
     let deserealized = SessionInfo::from_string(&serialized, "not this session").unwrap();
@@ -6295,6 +6517,35 @@ fn env_vars_to_string_with_no_env_vars() {
     assert_eq!(EnvironmentVariables::to_kdl(&deserialized), None);
 }
 
+#[test]
+fn permission_cache_with_paths_to_string() {
+    let mut granted = GrantedPermission::new();
+    granted.insert(
+        "file:/path/to/plugin.wasm".to_owned(),
+        vec![PermissionType::ReadApplicationState],
+    );
+    let mut granted_paths = GrantedPaths::new();
+    granted_paths.insert(
+        "file:/path/to/plugin.wasm".to_owned(),
+        vec![
+            PathPermission::read_only("/home/user/.config/mcp"),
+            PathPermission::read_write("/home/user/project"),
+        ],
+    );
+    granted_paths.insert(
+        "swarm:only-paths".to_owned(),
+        vec![PathPermission::read_only("/etc")],
+    );
+    let serialized = PermissionCache::to_string(&granted, &granted_paths);
+    let (deserialized, deserialized_paths) = PermissionCache::from_string(serialized).unwrap();
+    assert_eq!(
+        deserialized.get("file:/path/to/plugin.wasm"),
+        granted.get("file:/path/to/plugin.wasm")
+    );
+    assert_eq!(deserialized.get("swarm:only-paths"), None);
+    assert_eq!(deserialized_paths, granted_paths);
+}
+
 #[test]
 fn config_options_to_string() {
     let fake_config = r##"
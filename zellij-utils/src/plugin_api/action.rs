@@ -543,7 +543,7 @@ impl TryFrom<ProtobufAction> for Action {
             },
             Some(ProtobufActionName::QueryTabNames) => match protobuf_action.optional_payload {
                 Some(_) => Err("QueryTabNames should not have a payload"),
-                None => Ok(Action::QueryTabNames),
+                None => Ok(Action::QueryTabNames(false)),
             },
             Some(ProtobufActionName::NewTiledPluginPane) => {
                 match protobuf_action.optional_payload {
@@ -1137,7 +1137,7 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::NextSwapLayout as i32,
                 optional_payload: None,
             }),
-            Action::QueryTabNames => Ok(ProtobufAction {
+            Action::QueryTabNames(_) => Ok(ProtobufAction {
                 name: ProtobufActionName::QueryTabNames as i32,
                 optional_payload: None,
             }),
@@ -1268,9 +1268,18 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::NewInPlacePluginPane(..)
             | Action::Deny
             | Action::Copy
-            | Action::DumpLayout
+            | Action::DumpLayout(..)
             | Action::CliPipe { .. }
-            | Action::ListClients
+            | Action::ListClients(..)
+            | Action::ListPanes(..)
+            | Action::ListTabs(..)
+            | Action::SnapshotSession(..)
+            | Action::RestrictInput(..)
+            | Action::SendFile(..)
+            | Action::ReceiveFile(..)
+            | Action::WaitForPaneExit(..)
+            | Action::SubscribeToSessionEvents(..)
+            | Action::SetSessionTags(..)
             | Action::StackPanes(..)
             | Action::ChangeFloatingPaneCoordinates(..)
             | Action::SkipConfirm(..) => Err("Unsupported action"),
@@ -2,14 +2,15 @@ pub use super::generated_api::api::{
     action::{Action as ProtobufAction, Position as ProtobufPosition},
     event::{
         event::Payload as ProtobufEventPayload, ClientInfo as ProtobufClientInfo,
-        ClientTabHistory as ProtobufClientTabHistory, CopyDestination as ProtobufCopyDestination,
-        Event as ProtobufEvent, EventNameList as ProtobufEventNameList,
-        EventType as ProtobufEventType, FileMetadata as ProtobufFileMetadata,
-        InputModeKeybinds as ProtobufInputModeKeybinds, KeyBind as ProtobufKeyBind,
-        LayoutInfo as ProtobufLayoutInfo, ModeUpdatePayload as ProtobufModeUpdatePayload,
-        PaneId as ProtobufPaneId, PaneInfo as ProtobufPaneInfo,
-        PaneManifest as ProtobufPaneManifest, PaneType as ProtobufPaneType,
-        PluginInfo as ProtobufPluginInfo, ResurrectableSession as ProtobufResurrectableSession,
+        ClientTabHistory as ProtobufClientTabHistory, ConnectedUser as ProtobufConnectedUser,
+        CopyDestination as ProtobufCopyDestination, Event as ProtobufEvent,
+        EventNameList as ProtobufEventNameList, EventType as ProtobufEventType,
+        FileMetadata as ProtobufFileMetadata, InputModeKeybinds as ProtobufInputModeKeybinds,
+        KeyBind as ProtobufKeyBind, LayoutInfo as ProtobufLayoutInfo,
+        ModeUpdatePayload as ProtobufModeUpdatePayload, PaneId as ProtobufPaneId,
+        PaneInfo as ProtobufPaneInfo, PaneManifest as ProtobufPaneManifest,
+        PaneType as ProtobufPaneType, PluginInfo as ProtobufPluginInfo,
+        ResurrectableSession as ProtobufResurrectableSession,
         SessionManifest as ProtobufSessionManifest, TabInfo as ProtobufTabInfo,
         WebServerStatusPayload as ProtobufWebServerStatusPayload, WebSharing as ProtobufWebSharing,
         *,
@@ -20,8 +21,8 @@ pub use super::generated_api::api::{
 };
 #[allow(hidden_glob_reexports)]
 use crate::data::{
-    ClientInfo, CopyDestination, Event, EventType, FileMetadata, InputMode, KeyWithModifier,
-    LayoutInfo, ModeInfo, Mouse, PaneId, PaneInfo, PaneManifest, PermissionStatus,
+    ClientInfo, ConnectedUser, CopyDestination, Event, EventType, FileMetadata, InputMode,
+    KeyWithModifier, LayoutInfo, ModeInfo, Mouse, PaneId, PaneInfo, PaneManifest, PermissionStatus,
     PluginCapabilities, PluginInfo, SessionInfo, Style, TabInfo, WebServerStatus, WebSharing,
 };
 
@@ -387,6 +388,42 @@ impl TryFrom<ProtobufEvent> for Event {
                 },
                 _ => Err("Malformed payload for the InterceptedKeyPress Event"),
             },
+            Some(ProtobufEventType::ConnectionOpened) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ConnectionOpenedPayload(connection_opened_payload)) => {
+                    Ok(Event::ConnectionOpened(
+                        connection_opened_payload.connection_id,
+                        connection_opened_payload
+                            .context
+                            .into_iter()
+                            .map(|c_i| (c_i.name, c_i.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Malformed payload for the ConnectionOpened Event"),
+            },
+            Some(ProtobufEventType::ConnectionMessage) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ConnectionMessagePayload(
+                    connection_message_payload,
+                )) => Ok(Event::ConnectionMessage(
+                    connection_message_payload.connection_id,
+                    connection_message_payload.data,
+                )),
+                _ => Err("Malformed payload for the ConnectionMessage Event"),
+            },
+            Some(ProtobufEventType::ConnectionClosed) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ConnectionClosedPayload(connection_closed_payload)) => {
+                    Ok(Event::ConnectionClosed(
+                        connection_closed_payload.connection_id,
+                        connection_closed_payload.error,
+                        connection_closed_payload
+                            .context
+                            .into_iter()
+                            .map(|c_i| (c_i.name, c_i.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Malformed payload for the ConnectionClosed Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
@@ -403,6 +440,7 @@ impl TryFrom<ProtobufClientInfo> for ClientInfo {
                 .try_into()?,
             protobuf_client_info.running_command,
             protobuf_client_info.is_current_client,
+            protobuf_client_info.is_read_only,
         ))
     }
 }
@@ -415,6 +453,7 @@ impl TryFrom<ClientInfo> for ProtobufClientInfo {
             pane_id: Some(client_info.pane_id.try_into()?),
             running_command: client_info.running_command,
             is_current_client: client_info.is_current_client,
+            is_read_only: client_info.is_read_only,
         })
     }
 }
@@ -781,6 +820,40 @@ impl TryFrom<Event> for ProtobufEvent {
                 name: ProtobufEventType::InterceptedKeyPress as i32,
                 payload: Some(event::Payload::KeyPayload(key.try_into()?)),
             }),
+            Event::ConnectionOpened(connection_id, context) => Ok(ProtobufEvent {
+                name: ProtobufEventType::ConnectionOpened as i32,
+                payload: Some(event::Payload::ConnectionOpenedPayload(
+                    ConnectionOpenedPayload {
+                        connection_id,
+                        context: context
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
+            Event::ConnectionMessage(connection_id, data) => Ok(ProtobufEvent {
+                name: ProtobufEventType::ConnectionMessage as i32,
+                payload: Some(event::Payload::ConnectionMessagePayload(
+                    ConnectionMessagePayload {
+                        connection_id,
+                        data,
+                    },
+                )),
+            }),
+            Event::ConnectionClosed(connection_id, error, context) => Ok(ProtobufEvent {
+                name: ProtobufEventType::ConnectionClosed as i32,
+                payload: Some(event::Payload::ConnectionClosedPayload(
+                    ConnectionClosedPayload {
+                        connection_id,
+                        error,
+                        context: context
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
         }
     }
 }
@@ -826,6 +899,8 @@ impl TryFrom<SessionInfo> for ProtobufSessionManifest {
                 .into_iter()
                 .map(|t| ProtobufClientTabHistory::from(t))
                 .collect(),
+            tags: session_info.tags,
+            last_activity: session_info.last_activity,
         })
     }
 }
@@ -913,6 +988,8 @@ impl TryFrom<ProtobufSessionManifest> for SessionInfo {
             web_clients_allowed: protobuf_session_manifest.web_clients_allowed,
             web_client_count: protobuf_session_manifest.web_client_count as usize,
             tab_history,
+            tags: protobuf_session_manifest.tags,
+            last_activity: protobuf_session_manifest.last_activity,
         })
     }
 }
@@ -1306,6 +1383,12 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
 
         let web_server_capability = protobuf_mode_update_payload.web_server_capability;
 
+        let connected_users = protobuf_mode_update_payload
+            .connected_users
+            .into_iter()
+            .map(|u| u.into())
+            .collect();
+
         let mode_info = ModeInfo {
             mode: current_mode,
             keybinds,
@@ -1322,6 +1405,7 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
             web_server_ip,
             web_server_port,
             web_server_capability,
+            connected_users,
         };
         Ok(mode_info)
     }
@@ -1346,6 +1430,11 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
         let web_server_ip = mode_info.web_server_ip.map(|i| format!("{}", i));
         let web_server_port = mode_info.web_server_port.map(|p| p as u32);
         let web_server_capability = mode_info.web_server_capability;
+        let connected_users = mode_info
+            .connected_users
+            .into_iter()
+            .map(|u| u.into())
+            .collect();
         let mut protobuf_input_mode_keybinds: Vec<ProtobufInputModeKeybinds> = vec![];
         for (input_mode, input_mode_keybinds) in mode_info.keybinds {
             let mode: ProtobufInputMode = input_mode.try_into()?;
@@ -1386,10 +1475,31 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
             web_server_ip,
             web_server_port,
             web_server_capability,
+            connected_users,
         })
     }
 }
 
+impl From<ProtobufConnectedUser> for ConnectedUser {
+    fn from(protobuf_connected_user: ProtobufConnectedUser) -> Self {
+        ConnectedUser {
+            client_id: protobuf_connected_user.client_id as u16,
+            name: protobuf_connected_user.name,
+            is_current_client: protobuf_connected_user.is_current_client,
+        }
+    }
+}
+
+impl From<ConnectedUser> for ProtobufConnectedUser {
+    fn from(connected_user: ConnectedUser) -> Self {
+        ProtobufConnectedUser {
+            client_id: connected_user.client_id as u32,
+            name: connected_user.name,
+            is_current_client: connected_user.is_current_client,
+        }
+    }
+}
+
 impl TryFrom<ProtobufEventNameList> for HashSet<EventType> {
     type Error = &'static str;
     fn try_from(protobuf_event_name_list: ProtobufEventNameList) -> Result<Self, &'static str> {
@@ -1459,6 +1569,9 @@ impl TryFrom<ProtobufEventType> for EventType {
             ProtobufEventType::BeforeClose => EventType::BeforeClose,
             ProtobufEventType::FailedToStartWebServer => EventType::FailedToStartWebServer,
             ProtobufEventType::InterceptedKeyPress => EventType::InterceptedKeyPress,
+            ProtobufEventType::ConnectionOpened => EventType::ConnectionOpened,
+            ProtobufEventType::ConnectionMessage => EventType::ConnectionMessage,
+            ProtobufEventType::ConnectionClosed => EventType::ConnectionClosed,
         })
     }
 }
@@ -1502,6 +1615,9 @@ impl TryFrom<EventType> for ProtobufEventType {
             EventType::BeforeClose => ProtobufEventType::BeforeClose,
             EventType::FailedToStartWebServer => ProtobufEventType::FailedToStartWebServer,
             EventType::InterceptedKeyPress => ProtobufEventType::InterceptedKeyPress,
+            EventType::ConnectionOpened => ProtobufEventType::ConnectionOpened,
+            EventType::ConnectionMessage => ProtobufEventType::ConnectionMessage,
+            EventType::ConnectionClosed => ProtobufEventType::ConnectionClosed,
         })
     }
 }
@@ -1649,6 +1765,18 @@ fn serialize_mode_update_event_with_non_default_values() {
         web_server_ip: IpAddr::from_str("127.0.0.1").ok(),
         web_server_port: Some(8082),
         web_server_capability: Some(true),
+        connected_users: vec![
+            ConnectedUser {
+                client_id: 1,
+                name: Some("alice".to_owned()),
+                is_current_client: true,
+            },
+            ConnectedUser {
+                client_id: 2,
+                name: None,
+                is_current_client: false,
+            },
+        ],
     });
     let protobuf_event: ProtobufEvent = mode_update_event.clone().try_into().unwrap();
     let serialized_protobuf_event = protobuf_event.encode_to_vec();
@@ -1952,6 +2080,24 @@ fn serialize_file_system_delete_event() {
     );
 }
 
+#[test]
+fn serialize_connection_closed_event() {
+    use prost::Message;
+    let mut context = BTreeMap::new();
+    context.insert("server".to_owned(), "mcp".to_owned());
+    let connection_closed_event =
+        Event::ConnectionClosed(3, Some("connection refused".to_owned()), context);
+    let protobuf_event: ProtobufEvent = connection_closed_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        connection_closed_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
 #[test]
 fn serialize_session_update_event() {
     use prost::Message;
@@ -2099,6 +2245,8 @@ fn serialize_session_update_event_with_non_default_values() {
         web_clients_allowed: false,
         web_client_count: 1,
         tab_history,
+        tags: vec!["agents".to_owned()],
+        last_activity: Some(1760000000),
     };
     let session_info_2 = SessionInfo {
         name: "session 2".to_owned(),
@@ -2117,6 +2265,8 @@ fn serialize_session_update_event_with_non_default_values() {
         web_clients_allowed: false,
         web_client_count: 0,
         tab_history: Default::default(),
+        tags: vec![],
+        last_activity: None,
     };
     let session_infos = vec![session_info_1, session_info_2];
     let resurrectable_sessions = vec![];
@@ -3,10 +3,11 @@ pub use super::generated_api::api::{
     event::{EventNameList as ProtobufEventNameList, Header},
     input_mode::InputMode as ProtobufInputMode,
     plugin_command::{
-        plugin_command::Payload, BreakPanesToNewTabPayload, BreakPanesToTabWithIndexPayload,
-        ChangeFloatingPanesCoordinatesPayload, ChangeHostFolderPayload,
-        ClearScreenForPaneIdPayload, CliPipeOutputPayload, CloseMultiplePanesPayload,
-        CloseTabWithIndexPayload, CommandName, ContextItem,
+        plugin_command::Payload, AllowedSessions, BreakPanesToNewTabPayload,
+        BreakPanesToTabWithIndexPayload, ChangeFloatingPanesCoordinatesPayload,
+        ChangeHostFolderPayload, ClearScreenForPaneIdPayload, CliPipeOutputPayload,
+        CloseConnectionPayload, CloseMultiplePanesPayload, CloseTabWithIndexPayload, CommandName,
+        ConnectionKind as ProtobufConnectionKind, ContextItem,
         CreateTokenResponse as ProtobufCreateTokenResponse, CreateTokenResponse,
         EditScrollbackForPaneWithIdPayload, EmbedMultiplePanesPayload, EnvVariable, ExecCmdPayload,
         FixedOrPercent as ProtobufFixedOrPercent,
@@ -14,35 +15,44 @@ pub use super::generated_api::api::{
         FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates, GenerateWebLoginTokenPayload,
         GroupAndUngroupPanesPayload, HidePaneWithIdPayload, HighlightAndUnhighlightPanesPayload,
         HttpVerb as ProtobufHttpVerb, IdAndNewName, KeyToRebind, KeyToUnbind, KillSessionsPayload,
-        ListTokensResponse, LoadNewPluginPayload, MessageToPluginPayload,
-        MovePaneWithPaneIdInDirectionPayload, MovePaneWithPaneIdPayload, MovePayload,
-        NewPluginArgs as ProtobufNewPluginArgs, NewTabPayload, NewTabsWithLayoutInfoPayload,
-        OpenCommandPaneFloatingNearPluginPayload, OpenCommandPaneInPlaceOfPluginPayload,
-        OpenCommandPaneNearPluginPayload, OpenCommandPanePayload,
-        OpenFileFloatingNearPluginPayload, OpenFileInPlaceOfPluginPayload,
-        OpenFileNearPluginPayload, OpenFilePayload, OpenTerminalFloatingNearPluginPayload,
-        OpenTerminalInPlaceOfPluginPayload, OpenTerminalNearPluginPayload,
-        PageScrollDownInPaneIdPayload, PageScrollUpInPaneIdPayload, PaneId as ProtobufPaneId,
-        PaneIdAndFloatingPaneCoordinates, PaneType as ProtobufPaneType,
-        PluginCommand as ProtobufPluginCommand, PluginMessagePayload, RebindKeysPayload,
-        ReconfigurePayload, ReloadPluginPayload, RenameWebLoginTokenPayload,
-        RenameWebTokenResponse, ReplacePaneWithExistingPanePayload, RequestPluginPermissionPayload,
-        RerunCommandPanePayload, ResizePaneIdWithDirectionPayload, ResizePayload,
+        ListSessionSnapshotsResponse, ListSessionTemplatesResponse, ListTokensResponse,
+        LoadNewPluginPayload, MessageToPluginPayload, MovePaneWithPaneIdInDirectionPayload,
+        MovePaneWithPaneIdPayload, MovePayload, NewPluginArgs as ProtobufNewPluginArgs,
+        NewTabPayload, NewTabsWithLayoutInfoPayload, OpenCommandPaneFloatingNearPluginPayload,
+        OpenCommandPaneInPlaceOfPluginPayload, OpenCommandPaneNearPluginPayload,
+        OpenCommandPanePayload, OpenConnectionPayload, OpenFileFloatingNearPluginPayload,
+        OpenFileInPlaceOfPluginPayload, OpenFileNearPluginPayload, OpenFilePayload,
+        OpenTerminalFloatingNearPluginPayload, OpenTerminalInPlaceOfPluginPayload,
+        OpenTerminalNearPluginPayload, PageScrollDownInPaneIdPayload, PageScrollUpInPaneIdPayload,
+        PaneId as ProtobufPaneId, PaneIdAndFloatingPaneCoordinates, PaneType as ProtobufPaneType,
+        PluginCommand as ProtobufPluginCommand, PluginMessagePayload, ReadWebAuditLogResponse,
+        RebindKeysPayload, ReconfigurePayload, ReloadPluginPayload, RenameWebLoginTokenPayload,
+        RenameWebTokenResponse, ReplacePaneWithExistingPanePayload, RequestPathPermissionsPayload,
+        RequestPluginPermissionPayload, RerunCommandPanePayload, ResizePaneIdWithDirectionPayload,
+        ResizePayload, ResolveSessionTemplatePayload, ResolveSessionTemplateResponse,
         RevokeAllWebTokensResponse, RevokeTokenResponse, RevokeWebLoginTokenPayload,
         RunCommandPayload, ScrollDownInPaneIdPayload, ScrollToBottomInPaneIdPayload,
-        ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload, SetFloatingPanePinnedPayload,
-        SetSelfMouseSelectionSupportPayload, SetTimeoutPayload, ShowPaneWithIdPayload,
-        StackPanesPayload, SubscribePayload, SwitchSessionPayload, SwitchTabToPayload,
-        TogglePaneEmbedOrEjectForPaneIdPayload, TogglePaneIdFullscreenPayload, UnsubscribePayload,
-        WebRequestPayload, WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
+        ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload, SendToConnectionPayload,
+        SessionSnapshot as ProtobufSessionSnapshot, SessionTemplate as ProtobufSessionTemplate,
+        SessionTemplateParam as ProtobufSessionTemplateParam, SetFloatingPanePinnedPayload,
+        SetSelfMouseSelectionSupportPayload, SetTimeoutPayload, SetWebLoginTokenExpiryPayload,
+        ShowPaneWithIdPayload, StackPanesPayload, SubscribePayload, SwitchSessionPayload,
+        SwitchTabToPayload, TogglePaneEmbedOrEjectForPaneIdPayload, TogglePaneIdFullscreenPayload,
+        UnsubscribePayload, UpdateWebLoginTokenPayload, UpdateWebTokenResponse,
+        WebAuditEvent as ProtobufWebAuditEvent, WebRequestPayload,
+        WebTokenScope as ProtobufWebTokenScope, WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
+    },
+    plugin_permission::{
+        PathPermission as ProtobufPathPermission, PermissionType as ProtobufPermissionType,
     },
-    plugin_permission::PermissionType as ProtobufPermissionType,
     resize::ResizeAction as ProtobufResizeAction,
 };
 
 use crate::data::{
-    ConnectToSession, FloatingPaneCoordinates, HttpVerb, InputMode, KeyWithModifier,
-    MessageToPlugin, NewPluginArgs, PaneId, PermissionType, PluginCommand,
+    ConnectToSession, ConnectionKind, FloatingPaneCoordinates, HttpVerb, InputMode,
+    KeyWithModifier, MessageToPlugin, NewPluginArgs, PaneId, PathPermission, PermissionType,
+    PluginCommand, SessionSnapshot, SessionTemplate, SessionTemplateParam, WebAuditEvent,
+    WebAuditEventKind, WebTokenScope,
 };
 use crate::input::actions::Action;
 use crate::input::layout::SplitSize;
@@ -50,6 +60,8 @@ use crate::input::layout::SplitSize;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 impl Into<FloatingPaneCoordinates> for ProtobufFloatingPaneCoordinates {
     fn into(self) -> FloatingPaneCoordinates {
@@ -173,6 +185,46 @@ impl Into<ProtobufHttpVerb> for HttpVerb {
     }
 }
 
+impl Into<ConnectionKind> for ProtobufConnectionKind {
+    fn into(self) -> ConnectionKind {
+        match self {
+            ProtobufConnectionKind::Tcp => ConnectionKind::Tcp,
+            ProtobufConnectionKind::UnixSocket => ConnectionKind::UnixSocket,
+            ProtobufConnectionKind::WebSocket => ConnectionKind::WebSocket,
+        }
+    }
+}
+
+impl Into<ProtobufConnectionKind> for ConnectionKind {
+    fn into(self) -> ProtobufConnectionKind {
+        match self {
+            ConnectionKind::Tcp => ProtobufConnectionKind::Tcp,
+            ConnectionKind::UnixSocket => ProtobufConnectionKind::UnixSocket,
+            ConnectionKind::WebSocket => ProtobufConnectionKind::WebSocket,
+        }
+    }
+}
+
+impl Into<WebTokenScope> for ProtobufWebTokenScope {
+    fn into(self) -> WebTokenScope {
+        match self {
+            ProtobufWebTokenScope::Admin => WebTokenScope::Admin,
+            ProtobufWebTokenScope::ReadOnly => WebTokenScope::ReadOnly,
+            ProtobufWebTokenScope::Interactive => WebTokenScope::Interactive,
+        }
+    }
+}
+
+impl Into<ProtobufWebTokenScope> for WebTokenScope {
+    fn into(self) -> ProtobufWebTokenScope {
+        match self {
+            WebTokenScope::Admin => ProtobufWebTokenScope::Admin,
+            WebTokenScope::ReadOnly => ProtobufWebTokenScope::ReadOnly,
+            WebTokenScope::Interactive => ProtobufWebTokenScope::Interactive,
+        }
+    }
+}
+
 impl TryFrom<ProtobufPaneId> for PaneId {
     type Error = &'static str;
     fn try_from(protobuf_pane_id: ProtobufPaneId) -> Result<Self, &'static str> {
@@ -1675,8 +1727,15 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
             },
             Some(CommandName::GenerateWebLoginToken) => match protobuf_plugin_command.payload {
                 Some(Payload::GenerateWebLoginTokenPayload(generate_web_login_token_payload)) => {
+                    let scope = match ProtobufWebTokenScope::from_i32(
+                        generate_web_login_token_payload.scope,
+                    ) {
+                        Some(scope) => scope.into(),
+                        None => return Err("Unrecognized web token scope"),
+                    };
                     Ok(PluginCommand::GenerateWebLoginToken(
                         generate_web_login_token_payload.token_label,
+                        scope,
                     ))
                 },
                 _ => Err("GenerateWebLoginToken requires a payload"),
@@ -1710,6 +1769,40 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("RenameWebLoginToken requires a payload"),
             },
+            Some(CommandName::UpdateWebLoginToken) => match protobuf_plugin_command.payload {
+                Some(Payload::UpdateWebLoginTokenPayload(update_web_login_token_payload)) => {
+                    let scope =
+                        match ProtobufWebTokenScope::from_i32(update_web_login_token_payload.scope)
+                        {
+                            Some(scope) => scope.into(),
+                            None => return Err("Unrecognized web token scope"),
+                        };
+                    Ok(PluginCommand::UpdateWebLoginToken(
+                        update_web_login_token_payload.token_label,
+                        scope,
+                        update_web_login_token_payload.allowed_sessions,
+                    ))
+                },
+                _ => Err("UpdateWebLoginToken requires a payload"),
+            },
+            Some(CommandName::SetWebLoginTokenExpiry) => match protobuf_plugin_command.payload {
+                Some(Payload::SetWebLoginTokenExpiryPayload(
+                    set_web_login_token_expiry_payload,
+                )) => Ok(PluginCommand::SetWebLoginTokenExpiry(
+                    set_web_login_token_expiry_payload.token_label,
+                    set_web_login_token_expiry_payload
+                        .expires_in_secs
+                        .map(Duration::from_secs),
+                )),
+                _ => Err("SetWebLoginTokenExpiry requires a payload"),
+            },
+            Some(CommandName::ReadWebAuditLog) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("ReadWebAuditLog should not have a payload")
+                } else {
+                    Ok(PluginCommand::ReadWebAuditLog)
+                }
+            },
             Some(CommandName::InterceptKeyPresses) => match protobuf_plugin_command.payload {
                 Some(_) => Err("InterceptKeyPresses should have no payload, found a payload"),
                 None => Ok(PluginCommand::InterceptKeyPresses),
@@ -1734,6 +1827,82 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 )),
                 _ => Err("Mismatched payload for ReplacePaneWithExistingPane"),
             },
+            Some(CommandName::OpenConnection) => match protobuf_plugin_command.payload {
+                Some(Payload::OpenConnectionPayload(open_connection_payload)) => {
+                    let kind = match ProtobufConnectionKind::from_i32(open_connection_payload.kind)
+                    {
+                        Some(kind) => kind.into(),
+                        None => {
+                            return Err("Unrecognized connection kind");
+                        },
+                    };
+                    let context: BTreeMap<String, String> = open_connection_payload
+                        .context
+                        .into_iter()
+                        .map(|e| (e.name, e.value))
+                        .collect();
+                    Ok(PluginCommand::OpenConnection(
+                        kind,
+                        open_connection_payload.address,
+                        context,
+                    ))
+                },
+                _ => Err("Mismatched payload for OpenConnection"),
+            },
+            Some(CommandName::SendToConnection) => match protobuf_plugin_command.payload {
+                Some(Payload::SendToConnectionPayload(send_to_connection_payload)) => {
+                    Ok(PluginCommand::SendToConnection(
+                        send_to_connection_payload.connection_id,
+                        send_to_connection_payload.data,
+                    ))
+                },
+                _ => Err("Mismatched payload for SendToConnection"),
+            },
+            Some(CommandName::CloseConnection) => match protobuf_plugin_command.payload {
+                Some(Payload::CloseConnectionPayload(close_connection_payload)) => Ok(
+                    PluginCommand::CloseConnection(close_connection_payload.connection_id),
+                ),
+                _ => Err("Mismatched payload for CloseConnection"),
+            },
+            Some(CommandName::RequestPathPermissions) => match protobuf_plugin_command.payload {
+                Some(Payload::RequestPathPermissionsPayload(payload)) => {
+                    Ok(PluginCommand::RequestPathPermissions(
+                        payload
+                            .paths
+                            .into_iter()
+                            .filter_map(|p| PathPermission::try_from(p).ok())
+                            .collect(),
+                    ))
+                },
+                _ => Err("Mismatched payload for RequestPathPermissions"),
+            },
+            Some(CommandName::ListSessionSnapshots) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("ListSessionSnapshots should not have a payload")
+                } else {
+                    Ok(PluginCommand::ListSessionSnapshots)
+                }
+            },
+            Some(CommandName::ListSessionTemplates) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("ListSessionTemplates should not have a payload")
+                } else {
+                    Ok(PluginCommand::ListSessionTemplates)
+                }
+            },
+            Some(CommandName::ResolveSessionTemplate) => match protobuf_plugin_command.payload {
+                Some(Payload::ResolveSessionTemplatePayload(payload)) => {
+                    Ok(PluginCommand::ResolveSessionTemplate(
+                        payload.template_name,
+                        payload
+                            .args
+                            .into_iter()
+                            .map(|c| (c.name, c.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Mismatched payload for ResolveSessionTemplate"),
+            },
             None => Err("Unrecognized plugin command"),
         }
     }
@@ -2837,12 +3006,18 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     )),
                 })
             },
-            PluginCommand::GenerateWebLoginToken(token_label) => Ok(ProtobufPluginCommand {
-                name: CommandName::GenerateWebLoginToken as i32,
-                payload: Some(Payload::GenerateWebLoginTokenPayload(
-                    GenerateWebLoginTokenPayload { token_label },
-                )),
-            }),
+            PluginCommand::GenerateWebLoginToken(token_label, scope) => {
+                let scope: ProtobufWebTokenScope = scope.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::GenerateWebLoginToken as i32,
+                    payload: Some(Payload::GenerateWebLoginTokenPayload(
+                        GenerateWebLoginTokenPayload {
+                            token_label,
+                            scope: scope as i32,
+                        },
+                    )),
+                })
+            },
             PluginCommand::RevokeWebLoginToken(token_label) => Ok(ProtobufPluginCommand {
                 name: CommandName::RevokeWebLoginToken as i32,
                 payload: Some(Payload::RevokeWebLoginTokenPayload(
@@ -2863,6 +3038,34 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     RenameWebLoginTokenPayload { old_name, new_name },
                 )),
             }),
+            PluginCommand::UpdateWebLoginToken(token_label, scope, allowed_sessions) => {
+                let scope: ProtobufWebTokenScope = scope.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::UpdateWebLoginToken as i32,
+                    payload: Some(Payload::UpdateWebLoginTokenPayload(
+                        UpdateWebLoginTokenPayload {
+                            token_label,
+                            scope: scope as i32,
+                            allowed_sessions,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::SetWebLoginTokenExpiry(token_label, expires_in) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::SetWebLoginTokenExpiry as i32,
+                    payload: Some(Payload::SetWebLoginTokenExpiryPayload(
+                        SetWebLoginTokenExpiryPayload {
+                            token_label,
+                            expires_in_secs: expires_in.map(|e| e.as_secs()),
+                        },
+                    )),
+                })
+            },
+            PluginCommand::ReadWebAuditLog => Ok(ProtobufPluginCommand {
+                name: CommandName::ReadWebAuditLog as i32,
+                payload: None,
+            }),
             PluginCommand::InterceptKeyPresses => Ok(ProtobufPluginCommand {
                 name: CommandName::InterceptKeyPresses as i32,
                 payload: None,
@@ -2882,6 +3085,165 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     )),
                 })
             },
+            PluginCommand::OpenConnection(kind, address, context) => {
+                let context: Vec<_> = context
+                    .into_iter()
+                    .map(|(name, value)| ContextItem { name, value })
+                    .collect();
+                let kind: ProtobufConnectionKind = kind.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::OpenConnection as i32,
+                    payload: Some(Payload::OpenConnectionPayload(OpenConnectionPayload {
+                        kind: kind as i32,
+                        address,
+                        context,
+                    })),
+                })
+            },
+            PluginCommand::SendToConnection(connection_id, data) => Ok(ProtobufPluginCommand {
+                name: CommandName::SendToConnection as i32,
+                payload: Some(Payload::SendToConnectionPayload(SendToConnectionPayload {
+                    connection_id,
+                    data,
+                })),
+            }),
+            PluginCommand::CloseConnection(connection_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::CloseConnection as i32,
+                payload: Some(Payload::CloseConnectionPayload(CloseConnectionPayload {
+                    connection_id,
+                })),
+            }),
+            PluginCommand::RequestPathPermissions(paths) => Ok(ProtobufPluginCommand {
+                name: CommandName::RequestPathPermissions as i32,
+                payload: Some(Payload::RequestPathPermissionsPayload(
+                    RequestPathPermissionsPayload {
+                        paths: paths
+                            .into_iter()
+                            .filter_map(|p| ProtobufPathPermission::try_from(p).ok())
+                            .collect(),
+                    },
+                )),
+            }),
+            PluginCommand::ListSessionSnapshots => Ok(ProtobufPluginCommand {
+                name: CommandName::ListSessionSnapshots as i32,
+                payload: None,
+            }),
+            PluginCommand::ListSessionTemplates => Ok(ProtobufPluginCommand {
+                name: CommandName::ListSessionTemplates as i32,
+                payload: None,
+            }),
+            PluginCommand::ResolveSessionTemplate(template_name, args) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::ResolveSessionTemplate as i32,
+                    payload: Some(Payload::ResolveSessionTemplatePayload(
+                        ResolveSessionTemplatePayload {
+                            template_name,
+                            args: args
+                                .into_iter()
+                                .map(|(name, value)| ContextItem { name, value })
+                                .collect(),
+                        },
+                    )),
+                })
+            },
+        }
+    }
+}
+
+impl From<SessionSnapshot> for ProtobufSessionSnapshot {
+    fn from(session_snapshot: SessionSnapshot) -> Self {
+        ProtobufSessionSnapshot {
+            session_name: session_snapshot.session_name,
+            name: session_snapshot.name,
+            created_secs: session_snapshot.created.as_secs(),
+            layout_path: session_snapshot.layout_path,
+            layout: session_snapshot.layout,
+        }
+    }
+}
+
+impl From<ProtobufSessionSnapshot> for SessionSnapshot {
+    fn from(protobuf_session_snapshot: ProtobufSessionSnapshot) -> Self {
+        SessionSnapshot {
+            session_name: protobuf_session_snapshot.session_name,
+            name: protobuf_session_snapshot.name,
+            created: Duration::from_secs(protobuf_session_snapshot.created_secs),
+            layout_path: protobuf_session_snapshot.layout_path,
+            layout: protobuf_session_snapshot.layout,
+        }
+    }
+}
+
+impl From<WebAuditEvent> for ProtobufWebAuditEvent {
+    fn from(web_audit_event: WebAuditEvent) -> Self {
+        ProtobufWebAuditEvent {
+            timestamp: web_audit_event.timestamp,
+            kind: web_audit_event.kind.to_string(),
+            session_name: web_audit_event.session_name,
+            token_name: web_audit_event.token_name,
+            client_id: web_audit_event.client_id,
+            remote_address: web_audit_event.remote_address,
+            details: web_audit_event.details,
+        }
+    }
+}
+
+impl TryFrom<ProtobufWebAuditEvent> for WebAuditEvent {
+    type Error = &'static str;
+    fn try_from(protobuf_web_audit_event: ProtobufWebAuditEvent) -> Result<Self, &'static str> {
+        Ok(WebAuditEvent {
+            timestamp: protobuf_web_audit_event.timestamp,
+            kind: WebAuditEventKind::from_str(&protobuf_web_audit_event.kind)
+                .map_err(|_| "Unrecognized web audit event kind")?,
+            session_name: protobuf_web_audit_event.session_name,
+            token_name: protobuf_web_audit_event.token_name,
+            client_id: protobuf_web_audit_event.client_id,
+            remote_address: protobuf_web_audit_event.remote_address,
+            details: protobuf_web_audit_event.details,
+        })
+    }
+}
+
+impl From<SessionTemplate> for ProtobufSessionTemplate {
+    fn from(session_template: SessionTemplate) -> Self {
+        ProtobufSessionTemplate {
+            name: session_template.name,
+            description: session_template.description,
+            layout: session_template.layout,
+            cwd: session_template.cwd,
+            params: session_template
+                .params
+                .into_iter()
+                .map(|p| ProtobufSessionTemplateParam {
+                    name: p.name,
+                    description: p.description,
+                    default: p.default,
+                })
+                .collect(),
+            mcp_servers: session_template.mcp_servers,
+            agents: session_template.agents,
+        }
+    }
+}
+
+impl From<ProtobufSessionTemplate> for SessionTemplate {
+    fn from(protobuf_session_template: ProtobufSessionTemplate) -> Self {
+        SessionTemplate {
+            name: protobuf_session_template.name,
+            description: protobuf_session_template.description,
+            layout: protobuf_session_template.layout,
+            cwd: protobuf_session_template.cwd,
+            params: protobuf_session_template
+                .params
+                .into_iter()
+                .map(|p| SessionTemplateParam {
+                    name: p.name,
+                    description: p.description,
+                    default: p.default,
+                })
+                .collect(),
+            mcp_servers: protobuf_session_template.mcp_servers,
+            agents: protobuf_session_template.agents,
         }
     }
 }
@@ -1,7 +1,11 @@
-pub use super::generated_api::api::plugin_permission::PermissionType as ProtobufPermissionType;
-use crate::data::PermissionType;
+pub use super::generated_api::api::plugin_permission::{
+    PathAccess as ProtobufPathAccess, PathPermission as ProtobufPathPermission,
+    PermissionType as ProtobufPermissionType,
+};
+use crate::data::{PathAccess, PathPermission, PermissionType};
 
 use std::convert::TryFrom;
+use std::path::PathBuf;
 
 impl TryFrom<ProtobufPermissionType> for PermissionType {
     type Error = &'static str;
@@ -28,6 +32,8 @@ impl TryFrom<ProtobufPermissionType> for PermissionType {
             ProtobufPermissionType::FullHdAccess => Ok(PermissionType::FullHdAccess),
             ProtobufPermissionType::StartWebServer => Ok(PermissionType::StartWebServer),
             ProtobufPermissionType::InterceptInput => Ok(PermissionType::InterceptInput),
+            ProtobufPermissionType::NetworkAccess => Ok(PermissionType::NetworkAccess),
+            ProtobufPermissionType::LocalSocketAccess => Ok(PermissionType::LocalSocketAccess),
         }
     }
 }
@@ -57,6 +63,37 @@ impl TryFrom<PermissionType> for ProtobufPermissionType {
             PermissionType::FullHdAccess => Ok(ProtobufPermissionType::FullHdAccess),
             PermissionType::StartWebServer => Ok(ProtobufPermissionType::StartWebServer),
             PermissionType::InterceptInput => Ok(ProtobufPermissionType::InterceptInput),
+            PermissionType::NetworkAccess => Ok(ProtobufPermissionType::NetworkAccess),
+            PermissionType::LocalSocketAccess => Ok(ProtobufPermissionType::LocalSocketAccess),
         }
     }
 }
+
+impl TryFrom<ProtobufPathPermission> for PathPermission {
+    type Error = &'static str;
+    fn try_from(protobuf_path_permission: ProtobufPathPermission) -> Result<Self, &'static str> {
+        let access = match ProtobufPathAccess::from_i32(protobuf_path_permission.access) {
+            Some(ProtobufPathAccess::ReadOnly) => PathAccess::ReadOnly,
+            Some(ProtobufPathAccess::ReadWrite) => PathAccess::ReadWrite,
+            None => return Err("Unrecognized path access"),
+        };
+        Ok(PathPermission {
+            path: PathBuf::from(protobuf_path_permission.path),
+            access,
+        })
+    }
+}
+
+impl TryFrom<PathPermission> for ProtobufPathPermission {
+    type Error = &'static str;
+    fn try_from(path_permission: PathPermission) -> Result<Self, &'static str> {
+        let access = match path_permission.access {
+            PathAccess::ReadOnly => ProtobufPathAccess::ReadOnly,
+            PathAccess::ReadWrite => ProtobufPathAccess::ReadWrite,
+        };
+        Ok(ProtobufPathPermission {
+            path: path_permission.path.display().to_string(),
+            access: access as i32,
+        })
+    }
+}
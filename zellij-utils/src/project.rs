@@ -0,0 +1,392 @@
+//! Project folders
+//!
+//! A project marks itself with a `.swarm/` folder, either at its git root or anywhere between the
+//! git root and the current directory:
+//!
+//! ```text
+//! .swarm/
+//!   layout.kdl   # the layout the project session is created with
+//!   env          # KEY=VALUE lines added to the environment of the session
+//!   session      # the session name (defaults to the project folder name and a hash of its path)
+//! ```
+//!
+//! Starting `swarm` inside a project attaches to the project session, creating it if needed.
+//! Since the layout and env of a project can run arbitrary commands, they are only used once the
+//! user trusted the project. Trust is tied to the contents of every file in the project folder and
+//! of the files its layouts import, so changing any of them prompts again.
+
+use kdl::KdlDocument;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cli::validate_session;
+use crate::sessions::session_name_from_directory_name;
+
+pub const PROJECT_FOLDER: &str = ".swarm";
+const LAYOUT_FILE: &str = "layout.kdl";
+const ENV_FILE: &str = "env";
+const SESSION_NAME_FILE: &str = "session";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Project {
+    pub root: PathBuf,
+    pub session_name: String,
+    pub layout: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
+    fingerprint: String,
+}
+
+impl Project {
+    /// Look for a project folder in `cwd` and its parents up to the git root of `cwd`. Outside of
+    /// a git repository, only `cwd` itself is considered.
+    pub fn find(cwd: &Path) -> Option<Project> {
+        let git_root = git_root(cwd);
+        for dir in cwd.ancestors() {
+            if dir.join(PROJECT_FOLDER).is_dir() {
+                return match Project::from_root(dir) {
+                    Ok(project) => Some(project),
+                    Err(e) => {
+                        log::error!("Failed to read project folder in {:?}: {}", dir, e);
+                        None
+                    },
+                };
+            }
+            if git_root.as_deref().map(|g| g == dir).unwrap_or(true) {
+                break;
+            }
+        }
+        None
+    }
+    pub fn from_root(root: &Path) -> io::Result<Project> {
+        let project_folder = root.join(PROJECT_FOLDER);
+        let layout = Some(project_folder.join(LAYOUT_FILE)).filter(|l| l.is_file());
+        let raw_env = read_optional(&project_folder.join(ENV_FILE))?;
+        let session_name = read_optional(&project_folder.join(SESSION_NAME_FILE))?
+            .trim()
+            .to_owned();
+        let session_name = if session_name.is_empty() {
+            default_session_name(root)
+        } else {
+            validate_project_session_name(&session_name)?;
+            session_name
+        };
+        let mut hasher = Sha256::new();
+        for file in files_to_fingerprint(&project_folder)? {
+            hasher.update(file.to_string_lossy().as_bytes());
+            hasher.update([0]);
+            hasher.update(fs::read(&file)?);
+            hasher.update([0]);
+        }
+        hasher.update(session_name.as_bytes());
+        Ok(Project {
+            root: root.to_path_buf(),
+            session_name,
+            layout,
+            env: parse_env_file(&raw_env),
+            fingerprint: format!("{:x}", hasher.finalize()),
+        })
+    }
+    /// Whether starting the project session would run anything defined by the project
+    pub fn runs_commands(&self) -> bool {
+        self.layout.is_some() || !self.env.is_empty()
+    }
+    pub fn is_trusted(&self, trusted_projects_file: &Path) -> bool {
+        let Ok(trusted_projects) = fs::read_to_string(trusted_projects_file) else {
+            return false;
+        };
+        let root = self.root.to_string_lossy();
+        trusted_projects.lines().any(|line| {
+            line.split_once('\t')
+                .map(|(fingerprint, path)| fingerprint == self.fingerprint && path == root)
+                .unwrap_or(false)
+        })
+    }
+    /// Trust the current contents of the project folder, replacing previous trust for this root
+    pub fn trust(&self, trusted_projects_file: &Path) -> io::Result<()> {
+        let root = self.root.to_string_lossy();
+        let mut trusted_projects: Vec<String> = read_optional(trusted_projects_file)?
+            .lines()
+            .filter(|line| {
+                line.split_once('\t')
+                    .map(|(_, path)| path != root)
+                    .unwrap_or(false)
+            })
+            .map(|line| line.to_owned())
+            .collect();
+        trusted_projects.push(format!("{}\t{}", self.fingerprint, root));
+        if let Some(parent) = trusted_projects_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(trusted_projects_file, trusted_projects.join("\n") + "\n")
+    }
+}
+
+/// The closest folder containing a `.git` entry (folder or file, for worktrees and submodules)
+pub fn git_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(|dir| dir.to_path_buf())
+}
+
+fn default_session_name(root: &Path) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(root.to_string_lossy().as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+    let folder_name = root
+        .file_name()
+        .and_then(|f| session_name_from_directory_name(&f.to_string_lossy()))
+        .unwrap_or_else(|| "project".to_owned());
+    // the hash keeps projects with the same folder name from sharing a session
+    format!("{}-{}", folder_name, &hash[..6])
+}
+
+// the session name comes from whoever can commit to the project, and it becomes the path of the
+// session's socket and cache folder
+fn validate_project_session_name(session_name: &str) -> io::Result<()> {
+    let is_plain_name = session_name_from_directory_name(session_name).as_deref()
+        == Some(session_name)
+        && validate_session(session_name).is_ok();
+    if is_plain_name {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Invalid session name in {}/{}: \"{}\", it can only contain letters, numbers, - and _",
+                PROJECT_FOLDER, SESSION_NAME_FILE, session_name
+            ),
+        ))
+    }
+}
+
+// every file in the project folder, and the files its layouts import from outside of it (imports
+// only found in the user's own layout dir are left out, they don't come with the project)
+fn files_to_fingerprint(project_folder: &Path) -> io::Result<BTreeSet<PathBuf>> {
+    let mut files = BTreeSet::new();
+    let mut folders = vec![fs::canonicalize(project_folder)?];
+    while let Some(folder) = folders.pop() {
+        for entry in fs::read_dir(&folder)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                folders.push(entry.path());
+            } else if entry.path().is_file() {
+                files.insert(entry.path());
+            }
+        }
+    }
+    let mut layouts: Vec<PathBuf> = files
+        .iter()
+        .filter(|file| file.extension().map(|e| e == "kdl").unwrap_or(false))
+        .cloned()
+        .collect();
+    while let Some(layout) = layouts.pop() {
+        for imported_file in imports_of_layout(&layout) {
+            if files.insert(imported_file.clone()) {
+                layouts.push(imported_file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+// resolved the way the layout parser resolves them next to the importing file
+fn imports_of_layout(layout: &Path) -> Vec<PathBuf> {
+    let Some(layout_document) = fs::read_to_string(layout)
+        .ok()
+        .and_then(|raw_layout| raw_layout.parse::<KdlDocument>().ok())
+    else {
+        return vec![];
+    };
+    let layout_folder = layout.parent().unwrap_or(layout);
+    layout_document
+        .nodes()
+        .iter()
+        .filter(|node| node.name().value() == "import")
+        .filter_map(|node| node.entries().first()?.value().as_string())
+        .filter_map(|import_path| {
+            let candidate = layout_folder.join(import_path);
+            let imported_file = if candidate.extension().is_none() && !candidate.is_file() {
+                candidate.with_extension("kdl")
+            } else {
+                candidate
+            };
+            // canonicalized so that eg. `../.swarm/common.kdl` is only followed once
+            fs::canonicalize(imported_file).ok().filter(|f| f.is_file())
+        })
+        .collect()
+}
+
+fn parse_env_file(raw_env: &str) -> BTreeMap<String, String> {
+    raw_env
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            Some((key.trim().to_owned(), value.to_owned()))
+        })
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+fn read_optional(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_project(root: &Path, layout: Option<&str>, env: Option<&str>) {
+        let project_folder = root.join(PROJECT_FOLDER);
+        fs::create_dir_all(&project_folder).unwrap();
+        if let Some(layout) = layout {
+            fs::write(project_folder.join(LAYOUT_FILE), layout).unwrap();
+        }
+        if let Some(env) = env {
+            fs::write(project_folder.join(ENV_FILE), env).unwrap();
+        }
+    }
+
+    #[test]
+    fn project_is_found_up_to_the_git_root() {
+        let repo = tempfile::tempdir().unwrap();
+        let nested = repo.path().join("crates").join("api");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir_all(repo.path().join(".git")).unwrap();
+        create_project(repo.path(), Some("layout {}"), None);
+        let project = Project::find(&nested).unwrap();
+        assert_eq!(project.root, repo.path());
+        assert_eq!(project.layout, Some(repo.path().join(".swarm/layout.kdl")));
+        assert_eq!(
+            Project::find(&nested).unwrap().session_name,
+            project.session_name
+        );
+    }
+
+    #[test]
+    fn project_is_not_looked_for_above_the_current_folder_outside_of_git() {
+        let parent = tempfile::tempdir().unwrap();
+        let child = parent.path().join("child");
+        fs::create_dir_all(&child).unwrap();
+        create_project(parent.path(), None, None);
+        assert!(Project::find(&child).is_none());
+        assert!(Project::find(parent.path()).is_some());
+    }
+
+    #[test]
+    fn env_file_and_session_name_are_read() {
+        let root = tempfile::tempdir().unwrap();
+        create_project(
+            root.path(),
+            None,
+            Some("# comment\nexport DATABASE_URL=\"postgres://localhost\"\nRUST_LOG=debug\n\n"),
+        );
+        fs::write(root.path().join(".swarm/session"), "backend\n").unwrap();
+        let project = Project::from_root(root.path()).unwrap();
+        assert_eq!(project.session_name, "backend");
+        assert_eq!(
+            project.env.get("DATABASE_URL").map(|v| v.as_str()),
+            Some("postgres://localhost")
+        );
+        assert_eq!(
+            project.env.get("RUST_LOG").map(|v| v.as_str()),
+            Some("debug")
+        );
+        assert!(project.runs_commands());
+    }
+
+    #[test]
+    fn session_names_that_are_not_plain_names_are_rejected() {
+        let root = tempfile::tempdir().unwrap();
+        create_project(root.path(), Some("layout {}"), None);
+        for session_name in ["../foo", "a/b", "..", ".hidden", "a\\b"] {
+            fs::write(root.path().join(".swarm/session"), session_name).unwrap();
+            let error = Project::from_root(root.path()).unwrap_err();
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn trust_is_revoked_when_an_imported_file_changes() {
+        let root = tempfile::tempdir().unwrap();
+        let trusted_projects_file = root.path().join("cache").join("trusted_projects");
+        create_project(
+            root.path(),
+            Some("import \"common\"\nimport \"../scripts/tabs.kdl\"\nlayout {}"),
+            None,
+        );
+        fs::create_dir_all(root.path().join("scripts")).unwrap();
+        fs::write(
+            root.path().join(".swarm/common.kdl"),
+            "pane_template name=\"a\"",
+        )
+        .unwrap();
+        fs::write(
+            root.path().join("scripts/tabs.kdl"),
+            "tab_template name=\"b\"",
+        )
+        .unwrap();
+        let project = Project::from_root(root.path()).unwrap();
+        project.trust(&trusted_projects_file).unwrap();
+        assert!(Project::from_root(root.path())
+            .unwrap()
+            .is_trusted(&trusted_projects_file));
+
+        fs::write(
+            root.path().join(".swarm/common.kdl"),
+            "pane_template name=\"a\" command=\"rm\"",
+        )
+        .unwrap();
+        let changed_project = Project::from_root(root.path()).unwrap();
+        assert!(!changed_project.is_trusted(&trusted_projects_file));
+        changed_project.trust(&trusted_projects_file).unwrap();
+
+        // imported from outside of the project folder
+        fs::write(
+            root.path().join("scripts/tabs.kdl"),
+            "tab_template name=\"b\" { pane command=\"rm\"; }",
+        )
+        .unwrap();
+        assert!(!Project::from_root(root.path())
+            .unwrap()
+            .is_trusted(&trusted_projects_file));
+    }
+
+    #[test]
+    fn trust_is_revoked_when_the_project_changes() {
+        let root = tempfile::tempdir().unwrap();
+        let trusted_projects_file = root.path().join("cache").join("trusted_projects");
+        create_project(root.path(), Some("layout {}"), None);
+        let project = Project::from_root(root.path()).unwrap();
+        assert!(!project.is_trusted(&trusted_projects_file));
+        project.trust(&trusted_projects_file).unwrap();
+        assert!(project.is_trusted(&trusted_projects_file));
+        create_project(root.path(), Some("layout { pane command=\"rm\"; }"), None);
+        let changed_project = Project::from_root(root.path()).unwrap();
+        assert!(!changed_project.is_trusted(&trusted_projects_file));
+        changed_project.trust(&trusted_projects_file).unwrap();
+        assert!(changed_project.is_trusted(&trusted_projects_file));
+        assert_eq!(
+            fs::read_to_string(&trusted_projects_file)
+                .unwrap()
+                .lines()
+                .count(),
+            1
+        );
+    }
+}
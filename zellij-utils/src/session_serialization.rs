@@ -182,14 +182,17 @@ fn serialize_tiled_pane(
     );
 
     serialize_tiled_layout_attributes(&layout, ignore_size, &mut tiled_pane_node);
+    let env = extract_env(&layout.run);
     let has_child_attributes = !layout.children.is_empty()
         || layout.external_children_index.is_some()
         || !args.is_empty()
+        || !env.is_empty()
         || plugin.is_some()
         || command.is_some();
     if has_child_attributes {
         let mut tiled_pane_node_children = KdlDocument::new();
         serialize_args(args, &mut tiled_pane_node_children);
+        serialize_env(env, &mut tiled_pane_node_children);
         serialize_start_suspended(&command, &mut tiled_pane_node_children);
         serialize_plugin(plugin, plugin_config, &mut tiled_pane_node_children);
         if layout.children.is_empty() && layout.external_children_index.is_some() {
@@ -222,6 +225,12 @@ pub fn extract_command_and_args(layout_run: &Option<Run>) -> (Option<String>, Ve
         _ => (None, vec![]),
     }
 }
+pub fn extract_env(layout_run: &Option<Run>) -> BTreeMap<String, String> {
+    layout_run
+        .as_ref()
+        .map(|run| run.get_env())
+        .unwrap_or_default()
+}
 pub fn extract_plugin_and_config(
     layout_run: &Option<Run>,
 ) -> (Option<String>, Option<PluginUserConfiguration>) {
@@ -320,6 +329,20 @@ fn serialize_args(args: Vec<String>, pane_node_children: &mut KdlDocument) {
     }
 }
 
+fn serialize_env(env: BTreeMap<String, String>, pane_node_children: &mut KdlDocument) {
+    if !env.is_empty() {
+        let mut env_node = KdlNode::new("env");
+        let mut env_node_children = KdlDocument::new();
+        for (key, value) in env {
+            let mut env_var_node = KdlNode::new(key);
+            env_var_node.entries_mut().push(KdlEntry::new(value));
+            env_node_children.nodes_mut().push(env_var_node);
+        }
+        env_node.set_children(env_node_children);
+        pane_node_children.nodes_mut().push(env_node);
+    }
+}
+
 fn serialize_plugin(
     plugin: Option<String>,
     plugin_config: Option<PluginUserConfiguration>,
@@ -662,6 +685,7 @@ fn serialize_floating_pane(
     serialize_start_suspended(&command, &mut floating_pane_node_children);
     serialize_floating_layout_attributes(&layout, &mut floating_pane_node_children);
     serialize_args(args, &mut floating_pane_node_children);
+    serialize_env(extract_env(&layout.run), &mut floating_pane_node_children);
     serialize_plugin(plugin, plugin_config, &mut floating_pane_node_children);
     floating_pane_node.set_children(floating_pane_node_children);
     floating_pane_node
@@ -1676,6 +1700,69 @@ mod tests {
         assert_snapshot!(kdl.0);
     }
     #[test]
+    fn can_serialize_tab_with_pane_env() {
+        use crate::input::command::RunCommand;
+        let mut env = BTreeMap::new();
+        env.insert("RUST_LOG".to_owned(), "debug".to_owned());
+        env.insert("MY_\"VAR\"".to_owned(), "my \"value\"".to_owned());
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    run: Some(Run::Shell(None, env.clone())),
+                    cwd: Some(PathBuf::from("/tmp")),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    run: Some(Run::Command(RunCommand {
+                        command: PathBuf::from("cargo"),
+                        args: vec!["test".to_owned()],
+                        env: env.clone(),
+                        ..Default::default()
+                    })),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 10,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    ..Default::default()
+                },
+            ],
+            floating_panes: vec![PaneLayoutManifest {
+                run: Some(Run::Shell(None, env.clone())),
+                geom: PaneGeom {
+                    x: 5,
+                    y: 5,
+                    rows: Dimension::fixed(10),
+                    cols: Dimension::fixed(10),
+                    stacked: None,
+                    is_pinned: false,
+                    logical_position: None,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab with env".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
     fn can_serialize_tab_with_stacked_panes() {
         let tab_layout_manifest = TabLayoutManifest {
             tiled_panes: vec![
@@ -1,8 +1,11 @@
 use crate::{
     consts::{
-        session_info_folder_for_session, session_layout_cache_file_name,
-        SWARM_SESSION_INFO_CACHE_DIR, SWARM_SOCK_DIR,
+        session_info_folder_for_session, session_layout_cache_file_name, session_tags_file_name,
+        validate_session_folder_name, validate_snapshot_name, SESSION_LAYOUT_FILE_NAME,
+        SESSION_SNAPSHOTS_FOLDER_NAME, SESSION_SNAPSHOT_LAYOUT_FILE_NAME,
+        SWARM_SESSION_INFO_CACHE_DIR, SWARM_SOCK_DIR, VERSION,
     },
+    data::SessionSnapshot,
     envs,
     input::layout::Layout,
     ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
@@ -10,8 +13,12 @@ use crate::{
 use anyhow;
 use humantime::format_duration;
 use interprocess::local_socket::LocalSocketStream;
-use std::collections::HashMap;
+use kdl::{KdlDocument, KdlNode, KdlValue};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
 use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 use std::{fs, io, process};
 use suggest::Suggest;
@@ -170,6 +177,58 @@ fn assert_socket(name: &str) -> bool {
     }
 }
 
+/// The tags (groups) of a session, these are kept next to its resurrection data so that they
+/// survive the session exiting
+pub fn get_session_tags(session_name: &str) -> Vec<String> {
+    fs::read_to_string(session_tags_file_name(session_name))
+        .map(|tags| normalize_session_tags(tags.lines()))
+        .unwrap_or_default()
+}
+
+pub fn set_session_tags(session_name: &str, tags: &[String]) -> io::Result<()> {
+    let tags = normalize_session_tags(tags.iter());
+    let tags_file = session_tags_file_name(session_name);
+    if tags.is_empty() {
+        return match fs::remove_file(&tags_file) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    fs::create_dir_all(session_info_folder_for_session(session_name))?;
+    fs::write(tags_file, tags.join("\n") + "\n")
+}
+
+pub fn normalize_session_tags<S: AsRef<str>>(tags: impl Iterator<Item = S>) -> Vec<String> {
+    let mut normalized: Vec<String> = vec![];
+    for tag in tags {
+        let tag = tag.as_ref().trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_owned());
+        }
+    }
+    normalized
+}
+
+/// Running and resurrectable sessions tagged with `group`, running sessions first
+pub fn get_sessions_in_group(group: &str) -> Vec<(String, bool)> {
+    // (session_name, is_dead)
+    let running_sessions: Vec<String> = get_sessions()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    let dead_sessions: Vec<String> = get_resurrectable_session_names()
+        .into_iter()
+        .filter(|name| !running_sessions.contains(name))
+        .collect();
+    running_sessions
+        .into_iter()
+        .map(|name| (name, false))
+        .chain(dead_sessions.into_iter().map(|name| (name, true)))
+        .filter(|(name, _)| get_session_tags(name).iter().any(|t| t == group))
+        .collect()
+}
+
 pub fn print_sessions(
     mut sessions: Vec<(String, Duration, bool)>,
     no_formatting: bool,
@@ -202,6 +261,7 @@ pub fn print_sessions(
                     String::new()
                 };
                 let timestamp = format!("[Created {} ago]", format_duration(*timestamp));
+                // tags are left out to keep this output stable for scripts, which can use --json
                 println!("{} {} {}", session_name, timestamp, suffix);
             } else {
                 let formatted_session_name = format!("\u{1b}[32;1m{}\u{1b}[m", session_name);
@@ -216,11 +276,149 @@ pub fn print_sessions(
                     "[Created \u{1b}[35;1m{}\u{1b}[m ago]",
                     format_duration(*timestamp)
                 );
-                println!("{} {} {}", formatted_session_name, timestamp, suffix);
+                let tags = format_session_tags(session_name);
+                println!(
+                    "{} {}{} {}",
+                    formatted_session_name, timestamp, tags, suffix
+                );
             }
         })
 }
 
+fn format_session_tags(session_name: &str) -> String {
+    let tags = get_session_tags(session_name);
+    if tags.is_empty() {
+        String::new()
+    } else {
+        format!(" [tags: {}]", tags.join(", "))
+    }
+}
+
+// all the snapshots of the given session (or of all sessions if none is given), newest first
+pub fn get_session_snapshots(session_name: Option<&str>) -> Vec<SessionSnapshot> {
+    get_session_snapshots_in(&SWARM_SESSION_INFO_CACHE_DIR, session_name)
+}
+
+fn get_session_snapshots_in(
+    session_info_cache_dir: &Path,
+    session_name: Option<&str>,
+) -> Vec<SessionSnapshot> {
+    let session_names: Vec<String> = match session_name {
+        Some(session_name) => vec![session_name.to_owned()],
+        None => fs::read_dir(session_info_cache_dir)
+            .map(|folders| {
+                folders
+                    .filter_map(|f| f.ok().map(|f| f.path()))
+                    .filter(|f| f.is_dir())
+                    .filter_map(|f| f.file_name().map(|f| f.to_string_lossy().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    let mut snapshots = vec![];
+    for session_name in session_names {
+        let snapshots_folder = session_info_cache_dir
+            .join(&session_name)
+            .join(SESSION_SNAPSHOTS_FOLDER_NAME);
+        let snapshot_folders = match fs::read_dir(snapshots_folder) {
+            Ok(snapshot_folders) => snapshot_folders,
+            Err(_) => continue, // this session has no snapshots
+        };
+        for snapshot_folder in snapshot_folders.filter_map(|f| f.ok().map(|f| f.path())) {
+            let snapshot_name = match snapshot_folder.file_name() {
+                Some(snapshot_name) => snapshot_name.to_string_lossy().to_string(),
+                None => continue,
+            };
+            if validate_snapshot_name(&snapshot_name).is_err() {
+                continue; // eg. a snapshot that is being written
+            }
+            let layout_path = snapshot_folder.join(SESSION_SNAPSHOT_LAYOUT_FILE_NAME);
+            let layout = match fs::read_to_string(&layout_path) {
+                Ok(layout) => layout,
+                Err(_) => continue,
+            };
+            let created = fs::metadata(&layout_path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .map(|elapsed| Duration::from_secs(elapsed.as_secs()))
+                .unwrap_or_default();
+            snapshots.push(SessionSnapshot {
+                session_name: session_name.clone(),
+                name: snapshot_name,
+                created,
+                layout_path: layout_path.display().to_string(),
+                layout,
+            });
+        }
+    }
+    snapshots.sort_by(|a, b| a.created.cmp(&b.created));
+    snapshots
+}
+
+// the session name and layout to resurrect a session from one of its snapshots
+pub fn session_snapshot_layout(
+    session_name: Option<&str>,
+    snapshot_name: &str,
+) -> Result<(String, Layout), String> {
+    session_snapshot_layout_in(&SWARM_SESSION_INFO_CACHE_DIR, session_name, snapshot_name)
+}
+
+fn session_snapshot_layout_in(
+    session_info_cache_dir: &Path,
+    session_name: Option<&str>,
+    snapshot_name: &str,
+) -> Result<(String, Layout), String> {
+    validate_snapshot_name(snapshot_name)?;
+    let mut matching_snapshots: Vec<SessionSnapshot> =
+        get_session_snapshots_in(session_info_cache_dir, session_name)
+            .into_iter()
+            .filter(|s| s.name == snapshot_name)
+            .collect();
+    if matching_snapshots.len() > 1 {
+        let session_names: Vec<String> = matching_snapshots
+            .iter()
+            .map(|s| s.session_name.clone())
+            .collect();
+        return Err(format!(
+            "More than one session has a snapshot named \"{}\" ({}), please specify which session to attach to.",
+            snapshot_name,
+            session_names.join(", ")
+        ));
+    }
+    let snapshot = matching_snapshots
+        .pop()
+        .ok_or_else(|| format!("No snapshot named \"{}\" found.", snapshot_name))?;
+    let layout = Layout::from_kdl(
+        &snapshot.layout,
+        Some(snapshot.layout_path.clone()),
+        None,
+        None,
+    )
+    .map_err(|e| format!("Failed to parse snapshot \"{}\": {}", snapshot_name, e))?;
+    Ok((snapshot.session_name, layout))
+}
+
+pub fn print_session_snapshots(snapshots: Vec<SessionSnapshot>, no_formatting: bool) {
+    for snapshot in snapshots {
+        if no_formatting {
+            println!(
+                "{} {} [Created {} ago]",
+                snapshot.session_name,
+                snapshot.name,
+                format_duration(snapshot.created)
+            );
+        } else {
+            println!(
+                "\u{1b}[32;1m{}\u{1b}[m {} [Created \u{1b}[35;1m{}\u{1b}[m ago]",
+                snapshot.session_name,
+                snapshot.name,
+                format_duration(snapshot.created)
+            );
+        }
+    }
+}
+
 pub fn print_sessions_with_index(sessions: Vec<String>) {
     let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
     for (i, session) in sessions.iter().enumerate() {
@@ -285,7 +483,269 @@ pub fn delete_session(name: &str, force: bool) {
     }
 }
 
-pub fn list_sessions(no_formatting: bool, short: bool, reverse: bool) {
+const SESSION_BUNDLE_MANIFEST_FILE_NAME: &str = "manifest.kdl";
+const SESSION_BUNDLE_LAYOUT_FILE_NAME: &str = "session-layout.kdl";
+
+// bundles the last serialized layout of a session together with the pane contents it refers to
+// (these are only present if `serialize_pane_viewport` is on, and are already truncated to
+// `scrollback_lines_to_serialize`) into a zstd compressed tarball
+//
+// plugin configurations are part of the serialized layout, and pane cwds in it are relative to
+// its global cwd which is recorded in the manifest so that it can be rebased when importing
+pub fn export_session(session_name: &str, output: &Path) -> Result<(), String> {
+    export_session_in(&SWARM_SESSION_INFO_CACHE_DIR, session_name, output)
+}
+
+fn export_session_in(
+    session_info_cache_dir: &Path,
+    session_name: &str,
+    output: &Path,
+) -> Result<(), String> {
+    let session_folder = session_info_cache_dir.join(session_name);
+    let layout_file_name = session_folder.join(SESSION_LAYOUT_FILE_NAME);
+    let raw_layout = fs::read_to_string(&layout_file_name).map_err(|e| {
+        format!(
+            "Could not find a serialized layout for session \"{}\" (is session_serialization enabled?): {}",
+            session_name, e
+        )
+    })?;
+    let layout_document: KdlDocument = raw_layout
+        .parse()
+        .map_err(|e| format!("Failed to parse session layout: {}", e))?;
+    let mut files = BTreeMap::new();
+    let mut manifest = KdlDocument::new();
+    let mut manifest_node = |name: &str, value: String| {
+        let mut node = KdlNode::new(name);
+        node.push(value);
+        manifest.nodes_mut().push(node);
+    };
+    manifest_node("session_name", session_name.to_owned());
+    manifest_node("version", VERSION.to_owned());
+    if let Some(root) = global_cwd_of_layout(&layout_document) {
+        manifest_node("root", root.display().to_string());
+    }
+    files.insert(
+        SESSION_BUNDLE_MANIFEST_FILE_NAME.to_owned(),
+        manifest.to_string(),
+    );
+    for contents_file in contents_files_of_layout(&layout_document) {
+        match fs::read_to_string(session_folder.join(&contents_file)) {
+            Ok(contents) => {
+                files.insert(contents_file, contents);
+            },
+            Err(e) => log::error!("Failed to read pane contents {}: {}", contents_file, e),
+        }
+    }
+    files.insert(SESSION_BUNDLE_LAYOUT_FILE_NAME.to_owned(), raw_layout);
+    write_bundle(files, output)
+}
+
+fn write_bundle(files: BTreeMap<String, String>, output: &Path) -> Result<(), String> {
+    let output_file = fs::File::create(output)
+        .map_err(|e| format!("Failed to create {}: {}", output.display(), e))?;
+    let encoder = zstd::Encoder::new(output_file, 0)
+        .map_err(|e| format!("Failed to compress bundle: {}", e))?;
+    let mut archive = tar::Builder::new(encoder);
+    for (file_name, contents) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        );
+        header.set_cksum();
+        archive
+            .append_data(&mut header, &file_name, contents.as_bytes())
+            .map_err(|e| format!("Failed to write {} to bundle: {}", file_name, e))?;
+    }
+    archive
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| format!("Failed to write bundle: {}", e))?;
+    Ok(())
+}
+
+// registers the session in a bundle created with `export_session` as a resurrectable session,
+// rebasing all the paths under its original root to `root`, returns the name of the session
+pub fn import_session(
+    bundle: &Path,
+    root: Option<PathBuf>,
+    session_name: Option<String>,
+) -> Result<String, String> {
+    import_session_in(&SWARM_SESSION_INFO_CACHE_DIR, bundle, root, session_name)
+}
+
+fn import_session_in(
+    session_info_cache_dir: &Path,
+    bundle: &Path,
+    root: Option<PathBuf>,
+    session_name: Option<String>,
+) -> Result<String, String> {
+    let bundle_file = fs::File::open(bundle)
+        .map_err(|e| format!("Failed to open {}: {}", bundle.display(), e))?;
+    let decoder = zstd::Decoder::new(bundle_file)
+        .map_err(|e| format!("Failed to decompress bundle: {}", e))?;
+    let mut archive = tar::Archive::new(decoder);
+    let mut files = BTreeMap::new();
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("Failed to read bundle: {}", e))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Failed to read bundle: {}", e))?;
+        let file_name = entry
+            .path()
+            .map_err(|e| format!("Failed to read bundle: {}", e))?
+            .to_string_lossy()
+            .to_string();
+        // we only ever write flat files to the bundle, so anything else is either corrupt or an
+        // attempt to write outside of the session folder
+        if file_name.contains('/') || file_name.starts_with('.') {
+            return Err(format!("Invalid file in bundle: {}", file_name));
+        }
+        let mut contents = String::new();
+        entry
+            .read_to_string(&mut contents)
+            .map_err(|e| format!("Failed to read {} from bundle: {}", file_name, e))?;
+        files.insert(file_name, contents);
+    }
+    let manifest: KdlDocument = files
+        .remove(SESSION_BUNDLE_MANIFEST_FILE_NAME)
+        .ok_or("Bundle has no manifest, was it created with export-session?")?
+        .parse()
+        .map_err(|e| format!("Failed to parse bundle manifest: {}", e))?;
+    let manifest_value = |name: &str| {
+        manifest
+            .get_arg(name)
+            .and_then(|v| v.as_string())
+            .map(|v| v.to_owned())
+    };
+    let session_name = session_name
+        .or_else(|| manifest_value("session_name"))
+        .ok_or("Bundle manifest has no session name")?;
+    validate_session_folder_name(&session_name)?;
+    let session_folder = session_info_cache_dir.join(&session_name);
+    let layout_file_name = session_folder.join(SESSION_LAYOUT_FILE_NAME);
+    if session_exists(&session_name).unwrap_or(false) || layout_file_name.exists() {
+        return Err(format!(
+            "A session named \"{}\" already exists, use --name to import it under a different name.",
+            session_name
+        ));
+    }
+    let raw_layout = files
+        .remove(SESSION_BUNDLE_LAYOUT_FILE_NAME)
+        .ok_or("Bundle has no session layout")?;
+    let raw_layout = match (manifest_value("root"), root) {
+        (Some(original_root), Some(new_root)) => {
+            rebase_layout_paths(&raw_layout, Path::new(&original_root), &new_root)?
+        },
+        (None, Some(_)) => {
+            return Err(
+                "The exported session has no common root folder, cannot import it under --root."
+                    .to_owned(),
+            );
+        },
+        (_, None) => raw_layout,
+    };
+    // make sure we will be able to resurrect this
+    Layout::from_kdl(&raw_layout, None, None, None)
+        .map_err(|e| format!("Failed to parse session layout in bundle: {}", e))?;
+
+    fs::create_dir_all(&session_folder)
+        .and_then(|_| fs::write(&layout_file_name, raw_layout))
+        .and_then(|_| {
+            for (file_name, contents) in files {
+                fs::write(session_folder.join(file_name), contents)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| format!("Failed to write session {}: {}", session_name, e))?;
+    Ok(session_name)
+}
+
+fn global_cwd_of_layout(layout_document: &KdlDocument) -> Option<PathBuf> {
+    layout_document
+        .get("layout")
+        .and_then(|l| l.children())
+        .and_then(|c| c.get_arg("cwd"))
+        .and_then(|c| c.as_string())
+        .map(|c| PathBuf::from(c))
+}
+
+fn contents_files_of_layout(layout_document: &KdlDocument) -> Vec<String> {
+    let mut contents_files = vec![];
+    for node in layout_document.nodes() {
+        if let Some(contents_file) = node
+            .get("contents_file")
+            .and_then(|c| c.value().as_string())
+        {
+            contents_files.push(contents_file.to_owned());
+        }
+        if let Some(children) = node.children() {
+            contents_files.append(&mut contents_files_of_layout(children));
+        }
+    }
+    contents_files
+}
+
+// replaces the original root with the new one in every string in the layout (cwds, commands,
+// edited files, plugin locations, etc.)
+fn rebase_layout_paths(
+    raw_layout: &str,
+    original_root: &Path,
+    new_root: &Path,
+) -> Result<String, String> {
+    fn rebase(value: &str, original_root: &Path, new_root: &Path) -> Option<String> {
+        let (prefix, path) = match value.strip_prefix("file:") {
+            Some(path) => ("file:", path),
+            None => ("", value),
+        };
+        let relative_path = Path::new(path).strip_prefix(original_root).ok()?;
+        let rebased_path = if relative_path.as_os_str().is_empty() {
+            new_root.to_path_buf()
+        } else {
+            new_root.join(relative_path)
+        };
+        Some(format!("{}{}", prefix, rebased_path.display()))
+    }
+    fn rebase_entries(document: &mut KdlDocument, original_root: &Path, new_root: &Path) {
+        for node in document.nodes_mut() {
+            for entry in node.entries_mut() {
+                let rebased = entry
+                    .value()
+                    .as_string()
+                    .and_then(|v| rebase(v, original_root, new_root));
+                if let Some(rebased) = rebased {
+                    let value = KdlValue::String(rebased);
+                    entry.set_value_repr(value.to_string());
+                    entry.set_value(value);
+                }
+            }
+            if let Some(children) = node.children_mut() {
+                rebase_entries(children, original_root, new_root);
+            }
+        }
+    }
+    if original_root.as_os_str().is_empty() || original_root == Path::new("/") {
+        // everything would be under the new root, this is probably not what the user wanted
+        return Err("The exported session has no common root folder, cannot rebase it.".to_owned());
+    }
+    let mut layout_document: KdlDocument = raw_layout
+        .parse()
+        .map_err(|e| format!("Failed to parse session layout: {}", e))?;
+    rebase_entries(&mut layout_document, original_root, new_root);
+    Ok(layout_document.to_string())
+}
+
+pub fn list_sessions(
+    no_formatting: bool,
+    short: bool,
+    reverse: bool,
+    group: Option<Option<String>>,
+    json: bool,
+) {
     let exit_code = match get_sessions() {
         Ok(running_sessions) => {
             let resurrectable_sessions = get_resurrectable_sessions();
@@ -296,22 +756,51 @@ pub fn list_sessions(no_formatting: bool, short: bool, reverse: bool) {
             for (session_name, duration) in running_sessions {
                 all_sessions.insert(session_name.clone(), (duration, false));
             }
-            if all_sessions.is_empty() {
-                eprintln!("No active swarm sessions found.");
-                1
-            } else {
-                print_sessions(
-                    all_sessions
-                        .iter()
-                        .map(|(name, (timestamp, is_dead))| {
-                            (name.clone(), timestamp.clone(), *is_dead)
-                        })
-                        .collect(),
-                    no_formatting,
-                    short,
-                    reverse,
-                );
-                0
+            let all_sessions: Vec<(String, Duration, bool)> = all_sessions
+                .iter()
+                .map(|(name, (timestamp, is_dead))| (name.clone(), timestamp.clone(), *is_dead))
+                .collect();
+            match group {
+                // every session is listed with its tags, so there is nothing to group by
+                Some(Some(group)) if json => {
+                    print_sessions_as_json(
+                        all_sessions
+                            .into_iter()
+                            .filter(|(name, ..)| get_session_tags(name).contains(&group))
+                            .collect(),
+                        reverse,
+                    );
+                    0
+                },
+                _ if json => {
+                    print_sessions_as_json(all_sessions, reverse);
+                    0
+                },
+                Some(Some(group)) => {
+                    let sessions_in_group: Vec<(String, Duration, bool)> = all_sessions
+                        .into_iter()
+                        .filter(|(name, ..)| get_session_tags(name).contains(&group))
+                        .collect();
+                    if sessions_in_group.is_empty() {
+                        eprintln!("No swarm sessions found in group \"{}\".", group);
+                        1
+                    } else {
+                        print_sessions(sessions_in_group, no_formatting, short, reverse);
+                        0
+                    }
+                },
+                _ if all_sessions.is_empty() => {
+                    eprintln!("No active swarm sessions found.");
+                    1
+                },
+                Some(None) => {
+                    print_sessions_by_group(all_sessions, no_formatting, short, reverse);
+                    0
+                },
+                None => {
+                    print_sessions(all_sessions, no_formatting, short, reverse);
+                    0
+                },
             }
         },
         Err(e) => {
@@ -322,6 +811,133 @@ pub fn list_sessions(no_formatting: bool, short: bool, reverse: bool) {
     process::exit(exit_code);
 }
 
+#[derive(Serialize)]
+struct SessionListing {
+    name: String,
+    created_seconds_ago: u64,
+    is_current: bool,
+    exited: bool,
+    tags: Vec<String>,
+}
+
+fn print_sessions_as_json(sessions: Vec<(String, Duration, bool)>, reverse: bool) {
+    let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
+    match sessions_as_json(sessions, reverse, &curr_session, get_session_tags) {
+        Ok(listings) => println!("{}", listings),
+        Err(e) => eprintln!("Failed to serialize sessions: {}", e),
+    }
+}
+
+fn sessions_as_json(
+    mut sessions: Vec<(String, Duration, bool)>,
+    reverse: bool,
+    curr_session: &str,
+    session_tags: impl Fn(&str) -> Vec<String>,
+) -> Result<String, serde_json::Error> {
+    // in the same order as print_sessions
+    sessions.sort_by(|a, b| {
+        if reverse {
+            a.1.cmp(&b.1)
+        } else {
+            b.1.cmp(&a.1)
+        }
+    });
+    let listings: Vec<SessionListing> = sessions
+        .into_iter()
+        .map(|(session_name, timestamp, is_dead)| SessionListing {
+            created_seconds_ago: timestamp.as_secs(),
+            is_current: session_name == curr_session,
+            exited: is_dead,
+            tags: session_tags(&session_name),
+            name: session_name,
+        })
+        .collect();
+    serde_json::to_string(&listings)
+}
+
+fn print_sessions_by_group(
+    sessions: Vec<(String, Duration, bool)>,
+    no_formatting: bool,
+    short: bool,
+    reverse: bool,
+) {
+    // a session with several tags is listed under each of them
+    let mut groups: BTreeMap<String, Vec<(String, Duration, bool)>> = BTreeMap::new();
+    let mut ungrouped = vec![];
+    for session in sessions {
+        let tags = get_session_tags(&session.0);
+        if tags.is_empty() {
+            ungrouped.push(session);
+            continue;
+        }
+        for tag in tags {
+            groups.entry(tag).or_default().push(session.clone());
+        }
+    }
+    let print_heading = |heading: &str| {
+        if no_formatting || short {
+            println!("{}:", heading);
+        } else {
+            println!("\u{1b}[1m{}:\u{1b}[m", heading);
+        }
+    };
+    for (group, sessions) in groups {
+        print_heading(&group);
+        print_sessions(sessions, no_formatting, short, reverse);
+    }
+    if !ungrouped.is_empty() {
+        print_heading("(ungrouped)");
+        print_sessions(ungrouped, no_formatting, short, reverse);
+    }
+}
+
+pub fn list_session_snapshots(session_name: Option<String>, no_formatting: bool, json: bool) {
+    let snapshots = get_session_snapshots(session_name.as_deref());
+    let exit_code = if json {
+        match session_snapshots_as_json(&snapshots) {
+            Ok(listings) => {
+                println!("{}", listings);
+                0
+            },
+            Err(e) => {
+                eprintln!("Failed to serialize session snapshots: {}", e);
+                1
+            },
+        }
+    } else if snapshots.is_empty() {
+        match session_name {
+            Some(session_name) => eprintln!("No snapshots found for session {}.", session_name),
+            None => eprintln!("No session snapshots found."),
+        }
+        1
+    } else {
+        print_session_snapshots(snapshots, no_formatting);
+        0
+    };
+    process::exit(exit_code);
+}
+
+#[derive(Serialize)]
+struct SessionSnapshotListing<'a> {
+    session_name: &'a str,
+    name: &'a str,
+    created_seconds_ago: u64,
+    layout_path: &'a str,
+}
+
+fn session_snapshots_as_json(snapshots: &[SessionSnapshot]) -> Result<String, serde_json::Error> {
+    let listings: Vec<SessionSnapshotListing> = snapshots
+        .iter()
+        .map(|snapshot| SessionSnapshotListing {
+            session_name: &snapshot.session_name,
+            name: &snapshot.name,
+            created_seconds_ago: snapshot.created.as_secs(),
+            layout_path: &snapshot.layout_path,
+        })
+        .collect();
+    serde_json::to_string(&listings)
+}
+
 #[derive(Debug, Clone)]
 pub enum SessionNameMatch {
     AmbiguousPrefix(Vec<String>),
@@ -500,21 +1116,27 @@ pub fn generate_unique_session_name() -> Option<String> {
 /// Returns None if the directory cannot be determined or is invalid.
 fn get_current_directory_name() -> Option<String> {
     use std::env;
-    
+
     let current_dir = env::current_dir().ok()?;
     let dir_name = current_dir.file_name()?.to_string_lossy();
-    
-    // Sanitize the directory name to be safe for session names
+
+    session_name_from_directory_name(&dir_name)
+}
+
+/// Sanitize a directory name to be safe for use as a session name
+///
+/// Returns None if nothing is left of the name after sanitizing it or if it is too long.
+pub fn session_name_from_directory_name(dir_name: &str) -> Option<String> {
     let sanitized = dir_name
         .chars()
         .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
         .collect::<String>();
-    
+
     // Ensure the name is not empty and not too long
     if sanitized.is_empty() || sanitized.len() > 50 {
         return None;
     }
-    
+
     Some(sanitized)
 }
 
@@ -665,3 +1287,320 @@ const NOUNS: &[&'static str] = &[
     "yak",
     "zebra",
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::session_snapshot_layout_file_name;
+
+    fn create_snapshot(session_info_cache_dir: &Path, session_name: &str, snapshot_name: &str) {
+        let snapshot_folder = session_info_cache_dir
+            .join(session_name)
+            .join(SESSION_SNAPSHOTS_FOLDER_NAME)
+            .join(snapshot_name);
+        fs::create_dir_all(&snapshot_folder).unwrap();
+        fs::write(
+            snapshot_folder.join(SESSION_SNAPSHOT_LAYOUT_FILE_NAME),
+            "layout {\n    pane\n}\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn snapshot_names_must_be_plain_folder_names() {
+        for valid_name in ["before-refactor", "v1.2", "snapshot 1"] {
+            assert!(validate_snapshot_name(valid_name).is_ok(), "{}", valid_name);
+        }
+        for invalid_name in ["", ".", "..", ".hidden", "a/b", "../other-session", "a\\b"] {
+            assert!(
+                validate_snapshot_name(invalid_name).is_err(),
+                "{:?} should be rejected",
+                invalid_name
+            );
+            assert!(session_snapshot_layout_file_name("session", invalid_name).is_err());
+        }
+    }
+
+    #[test]
+    fn snapshots_of_a_session_are_listed() {
+        let session_info_cache_dir = tempfile::tempdir().unwrap();
+        create_snapshot(session_info_cache_dir.path(), "work", "first");
+        create_snapshot(session_info_cache_dir.path(), "work", "second");
+        create_snapshot(session_info_cache_dir.path(), "other", "third");
+        // a snapshot that is being written
+        create_snapshot(session_info_cache_dir.path(), "work", ".second.new-1234");
+        let mut snapshot_names: Vec<String> =
+            get_session_snapshots_in(session_info_cache_dir.path(), Some("work"))
+                .into_iter()
+                .map(|snapshot| snapshot.name)
+                .collect();
+        snapshot_names.sort();
+        assert_eq!(snapshot_names, vec!["first", "second"]);
+        let all_snapshots = get_session_snapshots_in(session_info_cache_dir.path(), None);
+        assert_eq!(all_snapshots.len(), 3);
+    }
+
+    #[test]
+    fn the_layout_of_a_snapshot_is_found_by_its_name() {
+        let session_info_cache_dir = tempfile::tempdir().unwrap();
+        create_snapshot(session_info_cache_dir.path(), "work", "first");
+        let (session_name, _layout) =
+            session_snapshot_layout_in(session_info_cache_dir.path(), None, "first").unwrap();
+        assert_eq!(session_name, "work");
+        assert!(
+            session_snapshot_layout_in(session_info_cache_dir.path(), None, "missing").is_err()
+        );
+        assert!(session_snapshot_layout_in(session_info_cache_dir.path(), None, "..").is_err());
+    }
+
+    #[test]
+    fn snapshots_by_the_same_name_in_different_sessions_need_a_session_name() {
+        let session_info_cache_dir = tempfile::tempdir().unwrap();
+        create_snapshot(session_info_cache_dir.path(), "work", "first");
+        create_snapshot(session_info_cache_dir.path(), "other", "first");
+        assert!(session_snapshot_layout_in(session_info_cache_dir.path(), None, "first").is_err());
+        let (session_name, _layout) =
+            session_snapshot_layout_in(session_info_cache_dir.path(), Some("other"), "first")
+                .unwrap();
+        assert_eq!(session_name, "other");
+    }
+
+    const SESSION_LAYOUT: &str = r#"
+        layout {
+            cwd "/home/user/project"
+            tab name="main" {
+                pane cwd="src" contents_file="initial_contents_1"
+                pane command="/home/user/project/run.sh" cwd="/home/user/project/api" {
+                    args "/home/user/project/config.toml" "/etc/hosts"
+                }
+                pane {
+                    plugin location="file:/home/user/project/plugin.wasm"
+                }
+            }
+            tab {
+                pane contents_file="initial_contents_2"
+            }
+        }
+    "#;
+
+    fn create_session(session_info_cache_dir: &Path, session_name: &str, layout: &str) {
+        let session_folder = session_info_cache_dir.join(session_name);
+        fs::create_dir_all(&session_folder).unwrap();
+        fs::write(session_folder.join(SESSION_LAYOUT_FILE_NAME), layout).unwrap();
+        fs::write(session_folder.join("initial_contents_1"), "$ cargo test\n").unwrap();
+        fs::write(session_folder.join("initial_contents_2"), "$ git status\n").unwrap();
+    }
+
+    #[test]
+    fn contents_files_are_found_in_nested_panes() {
+        let layout_document: KdlDocument = SESSION_LAYOUT.parse().unwrap();
+        assert_eq!(
+            contents_files_of_layout(&layout_document),
+            vec!["initial_contents_1", "initial_contents_2"]
+        );
+    }
+
+    #[test]
+    fn paths_under_the_original_root_are_rebased() {
+        let rebased = rebase_layout_paths(
+            SESSION_LAYOUT,
+            Path::new("/home/user/project"),
+            Path::new("/srv/checkout"),
+        )
+        .unwrap();
+        assert!(!rebased.contains("/home/user/project"), "{}", rebased);
+        for rebased_path in [
+            r#"cwd "/srv/checkout""#,
+            r#"command="/srv/checkout/run.sh""#,
+            r#"cwd="/srv/checkout/api""#,
+            r#""/srv/checkout/config.toml""#,
+            r#"location="file:/srv/checkout/plugin.wasm""#,
+        ] {
+            assert!(rebased.contains(rebased_path), "{}", rebased);
+        }
+        // relative paths and paths outside of the root are left alone
+        assert!(rebased.contains(r#"cwd="src""#), "{}", rebased);
+        assert!(rebased.contains(r#""/etc/hosts""#), "{}", rebased);
+        assert!(
+            rebase_layout_paths(SESSION_LAYOUT, Path::new("/"), Path::new("/srv/checkout"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn exported_sessions_are_imported_under_a_new_root() {
+        let exporting_cache_dir = tempfile::tempdir().unwrap();
+        let importing_cache_dir = tempfile::tempdir().unwrap();
+        let bundle_dir = tempfile::tempdir().unwrap();
+        let bundle = bundle_dir.path().join("work.tar.zst");
+        create_session(exporting_cache_dir.path(), "work", SESSION_LAYOUT);
+        export_session_in(exporting_cache_dir.path(), "work", &bundle).unwrap();
+
+        let session_name = import_session_in(
+            importing_cache_dir.path(),
+            &bundle,
+            Some(PathBuf::from("/srv/checkout")),
+            Some("imported".to_owned()),
+        )
+        .unwrap();
+        assert_eq!(session_name, "imported");
+        let session_folder = importing_cache_dir.path().join("imported");
+        let imported_layout =
+            fs::read_to_string(session_folder.join(SESSION_LAYOUT_FILE_NAME)).unwrap();
+        assert!(
+            imported_layout.contains(r#"command="/srv/checkout/run.sh""#),
+            "{}",
+            imported_layout
+        );
+        assert_eq!(
+            fs::read_to_string(session_folder.join("initial_contents_1")).unwrap(),
+            "$ cargo test\n"
+        );
+        assert_eq!(
+            fs::read_to_string(session_folder.join("initial_contents_2")).unwrap(),
+            "$ git status\n"
+        );
+        // the session now exists, so it cannot be imported under the same name again
+        assert!(import_session_in(
+            importing_cache_dir.path(),
+            &bundle,
+            None,
+            Some("imported".to_owned())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn sessions_without_a_root_cannot_be_imported_under_a_new_one() {
+        let exporting_cache_dir = tempfile::tempdir().unwrap();
+        let importing_cache_dir = tempfile::tempdir().unwrap();
+        let bundle_dir = tempfile::tempdir().unwrap();
+        let bundle = bundle_dir.path().join("work.tar.zst");
+        create_session(
+            exporting_cache_dir.path(),
+            "work",
+            "layout {\n    pane\n}\n",
+        );
+        export_session_in(exporting_cache_dir.path(), "work", &bundle).unwrap();
+        assert!(import_session_in(
+            importing_cache_dir.path(),
+            &bundle,
+            Some(PathBuf::from("/srv/checkout")),
+            None,
+        )
+        .is_err());
+        assert_eq!(
+            import_session_in(importing_cache_dir.path(), &bundle, None, None),
+            Ok("work".to_owned())
+        );
+    }
+
+    #[test]
+    fn bundles_cannot_be_imported_outside_of_the_session_info_cache() {
+        let importing_cache_dir = tempfile::tempdir().unwrap();
+        let session_info_cache_dir = importing_cache_dir.path().join("session_info");
+        let bundle_dir = tempfile::tempdir().unwrap();
+        let bundle = bundle_dir.path().join("escape.tar.zst");
+        let mut files = BTreeMap::new();
+        files.insert(
+            SESSION_BUNDLE_MANIFEST_FILE_NAME.to_owned(),
+            "session_name \"..\"\n".to_owned(),
+        );
+        files.insert(
+            SESSION_BUNDLE_LAYOUT_FILE_NAME.to_owned(),
+            "layout {\n    pane\n}\n".to_owned(),
+        );
+        write_bundle(files, &bundle).unwrap();
+
+        assert!(import_session_in(&session_info_cache_dir, &bundle, None, None).is_err());
+        for session_name in ["..", ".", ".hidden", "tab\tname"] {
+            assert!(
+                import_session_in(
+                    &session_info_cache_dir,
+                    &bundle,
+                    None,
+                    Some(session_name.to_owned())
+                )
+                .is_err(),
+                "{:?}",
+                session_name
+            );
+        }
+        assert!(!importing_cache_dir
+            .path()
+            .join(SESSION_LAYOUT_FILE_NAME)
+            .exists());
+        assert!(!session_info_cache_dir
+            .join(SESSION_LAYOUT_FILE_NAME)
+            .exists());
+    }
+
+    #[test]
+    fn sessions_are_listed_as_json_with_their_tags() {
+        let sessions = vec![
+            ("older".to_owned(), Duration::from_secs(120), false),
+            ("exited".to_owned(), Duration::from_secs(30), true),
+            ("newer".to_owned(), Duration::from_secs(10), false),
+        ];
+        let session_tags = |session_name: &str| {
+            if session_name == "newer" {
+                vec!["work".to_owned()]
+            } else {
+                vec![]
+            }
+        };
+        let listings: serde_json::Value = serde_json::from_str(
+            &sessions_as_json(sessions.clone(), false, "newer", session_tags).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            listings,
+            serde_json::json!([
+                {
+                    "name": "older",
+                    "created_seconds_ago": 120,
+                    "is_current": false,
+                    "exited": false,
+                    "tags": [],
+                },
+                {
+                    "name": "exited",
+                    "created_seconds_ago": 30,
+                    "is_current": false,
+                    "exited": true,
+                    "tags": [],
+                },
+                {
+                    "name": "newer",
+                    "created_seconds_ago": 10,
+                    "is_current": true,
+                    "exited": false,
+                    "tags": ["work"],
+                },
+            ])
+        );
+        let reversed: serde_json::Value =
+            serde_json::from_str(&sessions_as_json(sessions, true, "newer", session_tags).unwrap())
+                .unwrap();
+        assert_eq!(reversed[0]["name"], "newer");
+        assert_eq!(reversed[2]["name"], "older");
+    }
+
+    #[test]
+    fn session_snapshots_are_listed_as_json() {
+        let session_info_cache_dir = tempfile::tempdir().unwrap();
+        create_snapshot(session_info_cache_dir.path(), "work", "first");
+        let snapshots = get_session_snapshots_in(session_info_cache_dir.path(), Some("work"));
+        let listings: serde_json::Value =
+            serde_json::from_str(&session_snapshots_as_json(&snapshots).unwrap()).unwrap();
+        assert_eq!(listings.as_array().map(|l| l.len()), Some(1));
+        assert_eq!(listings[0]["session_name"], "work");
+        assert_eq!(listings[0]["name"], "first");
+        assert_eq!(
+            listings[0]["layout_path"],
+            snapshots[0].layout_path.as_str()
+        );
+        assert!(listings[0]["created_seconds_ago"].is_u64());
+        assert_eq!(session_snapshots_as_json(&[]).unwrap(), "[]");
+    }
+}
@@ -13,6 +13,7 @@ use crate::{
         config::{Config, ConfigError},
         layout::Layout,
         options::Options,
+        session_template::{resolve_session_template, session_template},
     },
 };
 use clap::{Args, IntoApp};
@@ -21,6 +22,7 @@ use directories::BaseDirs;
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     convert::TryFrom,
     fmt::Write as FmtWrite,
     fs,
@@ -687,6 +689,20 @@ impl Setup {
             .or_else(|| {
                 get_layout_dir(cli_args.config_dir.clone().or_else(find_default_config_dir))
             });
+        if let Some(Command::Sessions(Sessions::New {
+            template: Some(template_name),
+            args,
+            ..
+        })) = &cli_args.command
+        {
+            return Setup::parse_session_template(
+                template_name,
+                args,
+                layout_dir,
+                config,
+                cli_args,
+            );
+        }
         // the chosen layout can either be a path relative to the layout_dir or a name of one
         // of our assets, this distinction is made when parsing the layout - TODO: ideally, this
         // logic should not be split up and all the decisions should happen here
@@ -699,6 +715,7 @@ impl Setup {
                     .and_then(|cli_options| cli_options.default_layout.clone())
             })
             .or_else(|| config.options.default_layout.clone());
+        let layout_args = cli_args.layout_args.iter().cloned().collect();
         if let Some(layout_url) = chosen_layout
             .as_ref()
             .and_then(|l| l.to_str())
@@ -710,13 +727,42 @@ impl Setup {
                 }
             })
         {
-            Layout::from_url(layout_url, config)
+            Layout::from_url(layout_url, config, layout_args)
         } else {
             // we merge-override the config here because the layout might contain configuration
             // that needs to take precedence
-            Layout::from_path_or_default(chosen_layout.as_ref(), layout_dir.clone(), config)
+            Layout::from_path_or_default(
+                chosen_layout.as_ref(),
+                layout_dir.clone(),
+                config,
+                layout_args,
+            )
         }
     }
+    fn parse_session_template(
+        template_name: &str,
+        args: &[(String, String)],
+        layout_dir: Option<PathBuf>,
+        config: Config,
+        cli_args: &CliArgs,
+    ) -> Result<(Layout, Config), ConfigError> {
+        let template_dir =
+            get_template_dir(cli_args.config_dir.clone().or_else(find_default_config_dir));
+        let args = args.iter().cloned().collect();
+        let template_file = session_template(template_name, template_dir, layout_dir.clone())?;
+        let resolved = resolve_session_template(&template_file, &args, layout_dir.clone())?;
+        let layout = Layout::from_kdl_with_args(
+            &resolved.layout,
+            resolved.layout_path,
+            None,
+            None,
+            BTreeMap::new(),
+            layout_dir,
+        )?;
+        // like with regular layouts, the configuration in the template's layout takes precedence
+        let config = Config::from_kdl(&resolved.layout, Some(config))?;
+        Ok((layout, config))
+    }
     fn handle_setup_commands(cli_args: &CliArgs) {
         if let Some(Command::Setup(ref setup)) = &cli_args.command {
             setup.from_cli().map_or_else(
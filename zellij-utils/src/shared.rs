@@ -165,6 +165,7 @@ pub fn web_server_base_url(
     web_server_port: u16,
     has_certificate: bool,
     enforce_https_for_localhost: bool,
+    base_path: &str,
 ) -> String {
     let is_loopback = match web_server_ip {
         IpAddr::V4(ipv4) => ipv4.is_loopback(),
@@ -176,7 +177,26 @@ pub fn web_server_base_url(
     } else {
         "https"
     };
-    format!("{}://{}:{}", url_prefix, web_server_ip, web_server_port)
+    format!(
+        "{}://{}:{}{}",
+        url_prefix, web_server_ip, web_server_port, base_path
+    )
+}
+
+/// The path prefix the web server serves all of its routes under, normalized to either be empty or
+/// start with a slash and have no trailing slash (eg. "swarm/" becomes "/swarm")
+pub fn web_server_base_path(config_options: &Options) -> String {
+    let base_path = config_options
+        .web_server_base_path
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .trim_matches('/');
+    if base_path.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", base_path)
+    }
 }
 
 pub fn web_server_base_url_from_config(config_options: Options) -> String {
@@ -187,10 +207,12 @@ pub fn web_server_base_url_from_config(config_options: Options) -> String {
     let has_certificate =
         config_options.web_server_cert.is_some() && config_options.web_server_key.is_some();
     let enforce_https_for_localhost = config_options.enforce_https_for_localhost.unwrap_or(false);
+    let base_path = web_server_base_path(&config_options);
     web_server_base_url(
         web_server_ip,
         web_server_port,
         has_certificate,
         enforce_https_for_localhost,
+        &base_path,
     )
 }
@@ -0,0 +1,291 @@
+//! Web audit log
+//!
+//! Web clients (and the sessions they connect to) record what they do here: logins, the tokens
+//! used to create clients, clients attaching to and detaching from sessions, sessions they create
+//! or kill and the actions they issue. Events are appended as JSON lines to `web_audit.jsonl` in
+//! the data dir, which is rotated into `web_audit.jsonl.1`, `.2`... once it grows too large.
+//!
+//! The web server and every session write to the same file, so appending and rotating happen
+//! under an exclusive lock on `web_audit.jsonl.lock`. Events are written from a dedicated thread
+//! so recording one never blocks the caller (which is often an async handler).
+
+use crate::consts::SWARM_PROJ_DIR;
+use crate::data::{WebAuditEvent, WebAuditEventKind};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use nix::fcntl::{flock, FlockArg};
+
+const AUDIT_LOG_FILE_NAME: &str = "web_audit.jsonl";
+const DEV_AUDIT_LOG_FILE_NAME: &str = "web_audit_for_dev.jsonl";
+const MAX_AUDIT_LOG_SIZE: u64 = 5 * 1024 * 1024;
+const ROTATED_AUDIT_LOGS_TO_KEEP: usize = 3;
+
+pub fn audit_log_path() -> PathBuf {
+    if cfg!(debug_assertions) {
+        // kept apart like the tokens db, so tests don't write to the real audit log
+        SWARM_PROJ_DIR.data_dir().join(DEV_AUDIT_LOG_FILE_NAME)
+    } else {
+        SWARM_PROJ_DIR.data_dir().join(AUDIT_LOG_FILE_NAME)
+    }
+}
+
+/// A new event happening now, fill in the fields relevant to it before recording it
+pub fn new_event(kind: WebAuditEventKind) -> WebAuditEvent {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    WebAuditEvent::new(timestamp, kind)
+}
+
+enum WriterMessage {
+    Record(WebAuditEvent),
+    Flush(Sender<()>),
+}
+
+static AUDIT_LOG_WRITER: OnceLock<Mutex<Option<Sender<WriterMessage>>>> = OnceLock::new();
+
+/// Hands the event to the audit log writer thread and returns right away. Failing to write the
+/// audit log is logged rather than returned, it should not take the web server or a session down
+/// with it
+pub fn record(event: WebAuditEvent) {
+    if !send_to_writer(WriterMessage::Record(event)) {
+        log::error!("Failed to write to the web audit log: the writer thread is not running");
+    }
+}
+
+/// All events in the audit log (including the rotated files), oldest first. Lines that can't be
+/// parsed are skipped. Events recorded by this process are written before reading.
+pub fn read_audit_log() -> io::Result<Vec<WebAuditEvent>> {
+    if AUDIT_LOG_WRITER.get().is_some() {
+        let (flushed_sender, flushed_receiver) = mpsc::channel();
+        if send_to_writer(WriterMessage::Flush(flushed_sender)) {
+            let _ = flushed_receiver.recv();
+        }
+    }
+    read_events(&audit_log_path())
+}
+
+fn send_to_writer(message: WriterMessage) -> bool {
+    let writer = AUDIT_LOG_WRITER.get_or_init(|| Mutex::new(spawn_writer()));
+    match writer.lock() {
+        Ok(writer) => writer
+            .as_ref()
+            .map(|sender| sender.send(message).is_ok())
+            .unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+fn spawn_writer() -> Option<Sender<WriterMessage>> {
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name("web_audit_log".to_owned())
+        .spawn(move || {
+            let path = audit_log_path();
+            for message in receiver {
+                match message {
+                    WriterMessage::Record(event) => {
+                        if let Err(e) = append_event(&path, &event, MAX_AUDIT_LOG_SIZE) {
+                            log::error!("Failed to write to the web audit log: {}", e);
+                        }
+                    },
+                    WriterMessage::Flush(flushed) => {
+                        let _ = flushed.send(());
+                    },
+                }
+            }
+        });
+    match spawned {
+        Ok(_) => Some(sender),
+        Err(e) => {
+            log::error!("Failed to start the web audit log writer: {}", e);
+            None
+        },
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{}", index));
+    path.with_file_name(file_name)
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".lock");
+    path.with_file_name(file_name)
+}
+
+/// Holds the lock (shared for readers, exclusive for writers) until the returned file is dropped
+fn lock_audit_log(path: &Path, lock: FlockArg) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock_file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .open(lock_path(path))?;
+    flock(lock_file.as_raw_fd(), lock).map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+    Ok(lock_file)
+}
+
+fn append_event(path: &Path, event: &WebAuditEvent, max_size: u64) -> io::Result<()> {
+    let mut line = serde_json::to_string(event)?;
+    line.push('\n');
+    // rotating renames the file from under other writers, so it happens under the same lock
+    let _lock = lock_audit_log(path, FlockArg::LockExclusive)?;
+    rotate_if_needed(path, max_size)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
+
+fn rotate_if_needed(path: &Path, max_size: u64) -> io::Result<()> {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.len() >= max_size => {},
+        _ => return Ok(()),
+    }
+    for index in (1..ROTATED_AUDIT_LOGS_TO_KEEP).rev() {
+        let rotated = rotated_path(path, index);
+        if rotated.exists() {
+            fs::rename(&rotated, rotated_path(path, index + 1))?;
+        }
+    }
+    fs::rename(path, rotated_path(path, 1))
+}
+
+fn read_events(path: &Path) -> io::Result<Vec<WebAuditEvent>> {
+    let _lock = lock_audit_log(path, FlockArg::LockShared)?;
+    let mut events = vec![];
+    for index in (1..=ROTATED_AUDIT_LOGS_TO_KEEP).rev() {
+        read_events_from_file(&rotated_path(path, index), &mut events)?;
+    }
+    read_events_from_file(path, &mut events)?;
+    Ok(events)
+}
+
+fn read_events_from_file(path: &Path, events: &mut Vec<WebAuditEvent>) -> io::Result<()> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for line in BufReader::new(file).lines() {
+        if let Ok(event) = serde_json::from_str(&line?) {
+            events.push(event);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(timestamp: u64, kind: WebAuditEventKind) -> WebAuditEvent {
+        WebAuditEvent {
+            session_name: Some("work".to_owned()),
+            token_name: Some("token_1".to_owned()),
+            ..WebAuditEvent::new(timestamp, kind)
+        }
+    }
+
+    #[test]
+    fn events_are_read_back_in_order_across_rotated_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUDIT_LOG_FILE_NAME);
+        let max_size = 100; // two events per file
+        for timestamp in 0..10 {
+            append_event(
+                &path,
+                &event(timestamp, WebAuditEventKind::Action),
+                max_size,
+            )
+            .unwrap();
+        }
+        assert!(rotated_path(&path, 1).exists());
+        assert!(!rotated_path(&path, ROTATED_AUDIT_LOGS_TO_KEEP + 1).exists());
+        let timestamps: Vec<u64> = read_events(&path)
+            .unwrap()
+            .iter()
+            .map(|e| e.timestamp)
+            .collect();
+        let mut sorted = timestamps.clone();
+        sorted.sort();
+        assert_eq!(timestamps, sorted, "events are read oldest first");
+        assert_eq!(timestamps.last(), Some(&9));
+        assert!(timestamps.len() < 10, "the oldest events were rotated away");
+    }
+
+    #[test]
+    fn concurrent_writers_do_not_lose_events_across_rotations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUDIT_LOG_FILE_NAME);
+        let max_size = 1000; // plenty of rotations, but nothing rotated away
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    for index in 0..5 {
+                        let timestamp = writer * 100 + index;
+                        append_event(
+                            &path,
+                            &event(timestamp, WebAuditEventKind::Action),
+                            max_size,
+                        )
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        assert!(rotated_path(&path, 1).exists());
+        let mut timestamps: Vec<u64> = read_events(&path)
+            .unwrap()
+            .iter()
+            .map(|e| e.timestamp)
+            .collect();
+        timestamps.sort();
+        let mut expected: Vec<u64> = (0..4)
+            .flat_map(|writer| (0..5).map(move |index| writer * 100 + index))
+            .collect();
+        expected.sort();
+        assert_eq!(timestamps, expected);
+    }
+
+    #[test]
+    fn unparsable_lines_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUDIT_LOG_FILE_NAME);
+        append_event(&path, &event(1, WebAuditEventKind::Login), u64::MAX).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"not\": \"an event\"}\n").unwrap();
+        append_event(&path, &event(2, WebAuditEventKind::TokenUsed), u64::MAX).unwrap();
+        let events = read_events(&path).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                event(1, WebAuditEventKind::Login),
+                event(2, WebAuditEventKind::TokenUsed)
+            ]
+        );
+    }
+
+    #[test]
+    fn events_are_serialized_as_flat_json() {
+        let line = serde_json::to_string(&event(3, WebAuditEventKind::ClientAttached)).unwrap();
+        assert_eq!(
+            line,
+            r#"{"timestamp":3,"kind":"client_attached","session_name":"work","token_name":"token_1"}"#
+        );
+    }
+}
@@ -1,15 +1,91 @@
 // TODO: GATE THIS WHOLE FILE AND RELEVANT DEPS BEHIND web_server_capability
 use crate::consts::SWARM_PROJ_DIR;
-use rusqlite::Connection;
+use crate::data::WebTokenScope;
+use rusqlite::{params, Connection};
 use sha2::{Digest, Sha256};
 use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 #[derive(Debug)]
 pub struct TokenInfo {
     pub name: String,
     pub created_at: String,
+    pub scope: WebTokenScope,
+    pub allowed_sessions: Vec<String>,
+    pub expires_at: Option<String>,
+}
+
+/// What a token (and the session tokens created with it) may be used for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenAccess {
+    pub scope: WebTokenScope,
+    pub allowed_sessions: Vec<String>, // session names or globs, empty if all are allowed
+}
+
+impl TokenAccess {
+    pub fn read_only() -> Self {
+        TokenAccess {
+            scope: WebTokenScope::ReadOnly,
+            allowed_sessions: vec![],
+        }
+    }
+    pub fn allows_session(&self, session_name: &str) -> bool {
+        self.allowed_sessions.is_empty()
+            || self
+                .allowed_sessions
+                .iter()
+                .any(|pattern| glob_matches(pattern, session_name))
+    }
+}
+
+// supports `*` (any run of characters) and `?` (any single character)
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut last_star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            last_star = Some((p, n));
+            p += 1;
+        } else if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if let Some((star_p, star_n)) = last_star {
+            p = star_p + 1;
+            n = star_n + 1;
+            last_star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn serialize_allowed_sessions(allowed_sessions: &[String]) -> Option<String> {
+    if allowed_sessions.is_empty() {
+        None
+    } else {
+        Some(allowed_sessions.join("\n"))
+    }
+}
+
+fn deserialize_allowed_sessions(allowed_sessions: Option<String>) -> Vec<String> {
+    allowed_sessions
+        .map(|a| a.lines().map(|l| l.to_owned()).collect())
+        .unwrap_or_default()
+}
+
+fn expiry_timestamp(expires_in: Option<Duration>) -> Option<i64> {
+    expires_in.map(|expires_in| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        (now + expires_in.as_secs()) as i64
+    })
 }
 
 #[derive(Debug)]
@@ -78,11 +154,26 @@ fn init_db(conn: &Connection) -> Result<()> {
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             token_hash TEXT UNIQUE NOT NULL,
             name TEXT UNIQUE NOT NULL,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            scope TEXT NOT NULL DEFAULT 'admin',
+            allowed_sessions TEXT,
+            expires_at DATETIME
         )",
         [],
     )?;
 
+    // databases created by older versions lack these columns, tokens in them keep full access
+    if add_column_if_missing(conn, "scope", "TEXT NOT NULL DEFAULT 'admin'")?
+        && column_exists(conn, "read_only")?
+    {
+        conn.execute(
+            "UPDATE tokens SET scope = 'read-only' WHERE read_only = 1",
+            [],
+        )?;
+    }
+    add_column_if_missing(conn, "allowed_sessions", "TEXT")?;
+    add_column_if_missing(conn, "expires_at", "DATETIME")?;
+
     conn.execute(
         "CREATE TABLE IF NOT EXISTS session_tokens (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -99,13 +190,37 @@ fn init_db(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+fn column_exists(conn: &Connection, column: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('tokens') WHERE name = ?1",
+        [column],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+fn add_column_if_missing(conn: &Connection, column: &str, definition: &str) -> Result<bool> {
+    if column_exists(conn, column)? {
+        return Ok(false);
+    }
+    conn.execute(
+        &format!("ALTER TABLE tokens ADD COLUMN {} {}", column, definition),
+        [],
+    )?;
+    Ok(true)
+}
+
 fn hash_token(token: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(token.as_bytes());
     format!("{:x}", hasher.finalize())
 }
 
-pub fn create_token(name: Option<String>) -> Result<(String, String)> {
+pub fn create_token(
+    name: Option<String>,
+    access: &TokenAccess,
+    expires_in: Option<Duration>,
+) -> Result<(String, String)> {
     let db_path = get_db_path()?;
     let conn = Connection::open(db_path)?;
     init_db(&conn)?;
@@ -121,8 +236,15 @@ pub fn create_token(name: Option<String>) -> Result<(String, String)> {
     };
 
     match conn.execute(
-        "INSERT INTO tokens (token_hash, name) VALUES (?1, ?2)",
-        [&token_hash, &token_name],
+        "INSERT INTO tokens (token_hash, name, scope, allowed_sessions, expires_at)
+            VALUES (?1, ?2, ?3, ?4, datetime(?5, 'unixepoch'))",
+        params![
+            token_hash,
+            token_name,
+            access.scope.as_str(),
+            serialize_allowed_sessions(&access.allowed_sessions),
+            expiry_timestamp(expires_in),
+        ],
     ) {
         Err(rusqlite::Error::SqliteFailure(ffi_error, _))
             if ffi_error.code == rusqlite::ErrorCode::ConstraintViolation =>
@@ -144,7 +266,8 @@ pub fn create_session_token(auth_token: &str, remember_me: bool) -> Result<Strin
     let auth_token_hash = hash_token(auth_token);
 
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1",
+        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1
+            AND (expires_at IS NULL OR expires_at > datetime('now'))",
         [&auth_token_hash],
         |row| row.get(0),
     )?;
@@ -190,8 +313,13 @@ pub fn validate_session_token(session_token: &str) -> Result<bool> {
 
     let session_token_hash = hash_token(session_token);
 
+    // a session token is only as good as the token it was created with
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM session_tokens WHERE session_token_hash = ?1 AND expires_at > datetime('now')",
+        "SELECT COUNT(*) FROM session_tokens
+            JOIN tokens ON tokens.token_hash = session_tokens.auth_token_hash
+            WHERE session_tokens.session_token_hash = ?1
+            AND session_tokens.expires_at > datetime('now')
+            AND (tokens.expires_at IS NULL OR tokens.expires_at > datetime('now'))",
         [&session_token_hash],
         |row| row.get(0),
     )?;
@@ -199,6 +327,51 @@ pub fn validate_session_token(session_token: &str) -> Result<bool> {
     Ok(count > 0)
 }
 
+pub fn session_token_access(session_token: &str) -> Result<TokenAccess> {
+    let db_path = get_db_path()?;
+    let conn = Connection::open(db_path)?;
+    init_db(&conn)?;
+
+    let session_token_hash = hash_token(session_token);
+
+    match conn.query_row(
+        "SELECT tokens.scope, tokens.allowed_sessions FROM session_tokens
+            JOIN tokens ON tokens.token_hash = session_tokens.auth_token_hash
+            WHERE session_tokens.session_token_hash = ?1",
+        [&session_token_hash],
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+    ) {
+        Ok((scope, allowed_sessions)) => Ok(TokenAccess {
+            // an unknown scope can only come from a newer version, don't grant it anything
+            scope: WebTokenScope::from_str(&scope).unwrap_or(WebTokenScope::ReadOnly),
+            allowed_sessions: deserialize_allowed_sessions(allowed_sessions),
+        }),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Err(TokenError::InvalidToken),
+        Err(e) => Err(TokenError::Database(e)),
+    }
+}
+
+/// The name of the token a session token was created with
+pub fn session_token_name(session_token: &str) -> Result<String> {
+    let db_path = get_db_path()?;
+    let conn = Connection::open(db_path)?;
+    init_db(&conn)?;
+
+    let session_token_hash = hash_token(session_token);
+
+    match conn.query_row(
+        "SELECT tokens.name FROM session_tokens
+            JOIN tokens ON tokens.token_hash = session_tokens.auth_token_hash
+            WHERE session_tokens.session_token_hash = ?1",
+        [&session_token_hash],
+        |row| row.get::<_, String>(0),
+    ) {
+        Ok(name) => Ok(name),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Err(TokenError::InvalidToken),
+        Err(e) => Err(TokenError::Database(e)),
+    }
+}
+
 pub fn cleanup_expired_sessions() -> Result<usize> {
     let db_path = get_db_path()?;
     let conn = Connection::open(db_path)?;
@@ -305,16 +478,58 @@ pub fn rename_token(old_name: &str, new_name: &str) -> Result<()> {
     }
 }
 
+// already connected clients keep the access they connected with
+pub fn update_token_access(name: &str, access: &TokenAccess) -> Result<()> {
+    let db_path = get_db_path()?;
+    let conn = Connection::open(db_path)?;
+    init_db(&conn)?;
+
+    let rows_affected = conn.execute(
+        "UPDATE tokens SET scope = ?1, allowed_sessions = ?2 WHERE name = ?3",
+        params![
+            access.scope.as_str(),
+            serialize_allowed_sessions(&access.allowed_sessions),
+            name,
+        ],
+    )?;
+    if rows_affected == 0 {
+        return Err(TokenError::TokenNotFound(name.to_string()));
+    }
+    Ok(())
+}
+
+pub fn set_token_expiry(name: &str, expires_in: Option<Duration>) -> Result<()> {
+    let db_path = get_db_path()?;
+    let conn = Connection::open(db_path)?;
+    init_db(&conn)?;
+
+    let rows_affected = conn.execute(
+        "UPDATE tokens SET expires_at = datetime(?1, 'unixepoch') WHERE name = ?2",
+        params![expiry_timestamp(expires_in), name],
+    )?;
+    if rows_affected == 0 {
+        return Err(TokenError::TokenNotFound(name.to_string()));
+    }
+    Ok(())
+}
+
 pub fn list_tokens() -> Result<Vec<TokenInfo>> {
     let db_path = get_db_path()?;
     let conn = Connection::open(db_path)?;
     init_db(&conn)?;
 
-    let mut stmt = conn.prepare("SELECT name, created_at FROM tokens ORDER BY created_at")?;
+    let mut stmt = conn.prepare(
+        "SELECT name, created_at, scope, allowed_sessions, expires_at FROM tokens
+            ORDER BY created_at",
+    )?;
     let rows = stmt.query_map([], |row| {
         Ok(TokenInfo {
             name: row.get::<_, String>(0)?,
             created_at: row.get::<_, String>(1)?,
+            scope: WebTokenScope::from_str(&row.get::<_, String>(2)?)
+                .unwrap_or(WebTokenScope::ReadOnly),
+            allowed_sessions: deserialize_allowed_sessions(row.get(3)?),
+            expires_at: row.get::<_, Option<String>>(4)?,
         })
     })?;
 
@@ -341,7 +556,8 @@ pub fn validate_token(token: &str) -> Result<bool> {
     let token_hash = hash_token(token);
 
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1",
+        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1
+            AND (expires_at IS NULL OR expires_at > datetime('now'))",
         [&token_hash],
         |row| row.get(0),
     )?;